@@ -46,6 +46,7 @@ fn many_backedges() {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::LdTrue, Bytecode::LdU8(0), Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -68,6 +69,7 @@ fn many_backedges() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(1),
                 code: vec![],
+                jump_tables: vec![],
             }),
         });
 
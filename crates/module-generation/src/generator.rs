@@ -337,6 +337,7 @@ impl<'a> ModuleGenerator<'a> {
             imports: Self::imports(callable_modules),
             explicit_dependency_declarations: Vec::new(),
             structs: Vec::new(),
+            enums: Vec::new(),
             functions: Vec::new(),
             constants: Vec::new(),
         };
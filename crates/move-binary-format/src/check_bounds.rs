@@ -8,10 +8,12 @@ use crate::{
         PartialVMError, PartialVMResult,
     },
     file_format::{
-        AbilitySet, Bytecode, CodeOffset, CodeUnit, CompiledModule, Constant, FieldHandle,
-        FieldInstantiation, FunctionDefinition, FunctionDefinitionIndex, FunctionHandle,
-        FunctionInstantiation, LocalIndex, ModuleHandle, Signature, SignatureToken,
-        StructDefInstantiation, StructDefinition, StructFieldInformation, StructHandle, TableIndex,
+        AbilitySet, Bytecode, CodeOffset, CodeUnit, CompiledModule, Constant, EnumDefInstantiation,
+        EnumDefinition, FieldDefinition, FieldHandle, FieldInstantiation, FunctionDefinition,
+        FunctionDefinitionIndex, FunctionHandle, FunctionInstantiation, LocalIndex, ModuleHandle,
+        Signature, SignatureIndex, SignatureToken, StructDefInstantiation, StructDefinition,
+        StructFieldInformation, StructHandle, StructHandleIndex, TableIndex, VariantHandle,
+        VariantInstantiationHandle, VariantJumpTable, VariantTag,
     },
     internals::ModuleIndex,
     IndexKind,
@@ -54,6 +56,10 @@ impl<'a> BoundsChecker<'a> {
         self.check_function_instantiations()?;
         self.check_field_instantiations()?;
         self.check_struct_defs()?;
+        self.check_enum_defs()?;
+        self.check_enum_instantiations()?;
+        self.check_variant_handles()?;
+        self.check_variant_instantiation_handles()?;
         self.check_function_defs()
     }
 
@@ -134,6 +140,34 @@ impl<'a> BoundsChecker<'a> {
         Ok(())
     }
 
+    fn check_enum_defs(&self) -> PartialVMResult<()> {
+        for enum_def in self.module.enum_defs() {
+            self.check_enum_def(enum_def)?
+        }
+        Ok(())
+    }
+
+    fn check_enum_instantiations(&self) -> PartialVMResult<()> {
+        for enum_instantiation in self.module.enum_instantiations() {
+            self.check_enum_instantiation(enum_instantiation)?
+        }
+        Ok(())
+    }
+
+    fn check_variant_handles(&self) -> PartialVMResult<()> {
+        for variant_handle in self.module.variant_handles() {
+            self.check_variant_handle(variant_handle)?
+        }
+        Ok(())
+    }
+
+    fn check_variant_instantiation_handles(&self) -> PartialVMResult<()> {
+        for variant_handle in self.module.variant_instantiation_handles() {
+            self.check_variant_instantiation_handle(variant_handle)?
+        }
+        Ok(())
+    }
+
     fn check_function_defs(&mut self) -> PartialVMResult<()> {
         for (function_def_idx, function_def) in self.module.function_defs().iter().enumerate() {
             self.check_function_def(function_def_idx, function_def)?
@@ -258,21 +292,65 @@ impl<'a> BoundsChecker<'a> {
         check_bounds_impl(self.module.struct_handles(), struct_def.struct_handle)?;
         // check signature (type) and type parameter for the field type
         if let StructFieldInformation::Declared(fields) = &struct_def.field_information {
-            let type_param_count = self
-                .module
-                .struct_handles()
-                .get(struct_def.struct_handle.into_index())
-                .map_or(0, |sh| sh.type_parameters.len());
-            // field signatures are inlined
-            for field in fields {
-                check_bounds_impl(self.module.identifiers(), field.name)?;
-                self.check_type(&field.signature.0)?;
-                self.check_type_parameter(&field.signature.0, type_param_count)?;
-            }
+            self.check_field_defs(struct_def.struct_handle, fields)?;
+        }
+        Ok(())
+    }
+
+    fn check_enum_def(&self, enum_def: &EnumDefinition) -> PartialVMResult<()> {
+        check_bounds_impl(self.module.struct_handles(), enum_def.enum_handle)?;
+        for variant in &enum_def.variants {
+            check_bounds_impl(self.module.identifiers(), variant.variant_name)?;
+            self.check_field_defs(enum_def.enum_handle, &variant.fields)?;
+        }
+        Ok(())
+    }
+
+    fn check_field_defs(
+        &self,
+        handle: StructHandleIndex,
+        fields: &[FieldDefinition],
+    ) -> PartialVMResult<()> {
+        let type_param_count = self
+            .module
+            .struct_handles()
+            .get(handle.into_index())
+            .map_or(0, |sh| sh.type_parameters.len());
+        // field signatures are inlined
+        for field in fields {
+            check_bounds_impl(self.module.identifiers(), field.name)?;
+            self.check_type(&field.signature.0)?;
+            self.check_type_parameter(&field.signature.0, type_param_count)?;
         }
         Ok(())
     }
 
+    fn check_enum_instantiation(
+        &self,
+        enum_instantiation: &EnumDefInstantiation,
+    ) -> PartialVMResult<()> {
+        check_bounds_impl(self.module.enum_defs(), enum_instantiation.def)?;
+        check_bounds_impl(self.module.signatures(), enum_instantiation.type_parameters)
+    }
+
+    fn check_variant_handle(&self, variant_handle: &VariantHandle) -> PartialVMResult<()> {
+        check_bounds_impl(self.module.enum_defs(), variant_handle.enum_def)?;
+        // variant tag must be in bounds, enum def just checked above must exist
+        let enum_def = &self.module.enum_defs()[variant_handle.enum_def.into_index()];
+        check_variant_tag(enum_def, variant_handle.variant)
+    }
+
+    fn check_variant_instantiation_handle(
+        &self,
+        variant_handle: &VariantInstantiationHandle,
+    ) -> PartialVMResult<()> {
+        check_bounds_impl(self.module.enum_instantiations(), variant_handle.enum_def)?;
+        let enum_inst = &self.module.enum_instantiations()[variant_handle.enum_def.into_index()];
+        // enum instantiations are checked before variant handles
+        let enum_def = &self.module.enum_defs()[enum_inst.def.into_index()];
+        check_variant_tag(enum_def, variant_handle.variant)
+    }
+
     fn check_function_def(
         &mut self,
         function_def_idx: usize,
@@ -343,8 +421,13 @@ impl<'a> BoundsChecker<'a> {
             self.check_type_parameter(local, type_param_count)?
         }
 
-        // check bytecodes
+        // check jump tables
         let code_len = code_unit.code.len();
+        for jump_table in &code_unit.jump_tables {
+            self.check_jump_table(jump_table, code_len)?;
+        }
+
+        // check bytecodes
         for (bytecode_offset, bytecode) in code_unit.code.iter().enumerate() {
             use self::Bytecode::*;
 
@@ -444,6 +527,44 @@ impl<'a> BoundsChecker<'a> {
                         }
                     }
                 }
+                PackVariant(idx)
+                | UnpackVariant(idx)
+                | UnpackVariantImmRef(idx)
+                | UnpackVariantMutRef(idx) => self.check_code_unit_bounds_impl(
+                    self.module.variant_handles(),
+                    *idx,
+                    bytecode_offset,
+                )?,
+                PackVariantGeneric(idx)
+                | UnpackVariantGeneric(idx)
+                | UnpackVariantGenericImmRef(idx)
+                | UnpackVariantGenericMutRef(idx) => {
+                    self.check_code_unit_bounds_impl(
+                        self.module.variant_instantiation_handles(),
+                        *idx,
+                        bytecode_offset,
+                    )?;
+                    // check type parameters in variant operations are bound to the function type
+                    // parameters
+                    if let Some(enum_inst) = self
+                        .module
+                        .variant_instantiation_handles()
+                        .get(idx.into_index())
+                        .and_then(|handle| {
+                            self.module
+                                .enum_instantiations()
+                                .get(handle.enum_def.into_index())
+                        })
+                    {
+                        self.check_type_parameters_in_signature(
+                            enum_inst.type_parameters,
+                            type_param_count,
+                        )?;
+                    }
+                }
+                VariantSwitch(idx) => {
+                    self.check_code_unit_bounds_impl(&code_unit.jump_tables, *idx, bytecode_offset)?
+                }
                 // Instructions that refer to this code block.
                 BrTrue(offset) | BrFalse(offset) | Branch(offset) => {
                     let offset = *offset as usize;
@@ -505,6 +626,39 @@ impl<'a> BoundsChecker<'a> {
         Ok(())
     }
 
+    fn check_jump_table(
+        &self,
+        jump_table: &VariantJumpTable,
+        code_len: usize,
+    ) -> PartialVMResult<()> {
+        check_bounds_impl(self.module.enum_defs(), jump_table.head_enum)?;
+        for offset in &jump_table.jump_table {
+            let offset = *offset as usize;
+            if offset >= code_len {
+                return Err(bounds_error(
+                    StatusCode::INDEX_OUT_OF_BOUNDS,
+                    IndexKind::CodeDefinition,
+                    offset as TableIndex,
+                    code_len,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_type_parameters_in_signature(
+        &self,
+        idx: SignatureIndex,
+        type_param_count: usize,
+    ) -> PartialVMResult<()> {
+        if let Some(sig) = self.module.signatures().get(idx.into_index()) {
+            for ty in &sig.0 {
+                self.check_type_parameter(ty, type_param_count)?
+            }
+        }
+        Ok(())
+    }
+
     fn check_type(&self, ty: &SignatureToken) -> PartialVMResult<()> {
         use self::SignatureToken::*;
 
@@ -649,6 +803,19 @@ impl<'a> BoundsChecker<'a> {
     }
 }
 
+fn check_variant_tag(enum_def: &EnumDefinition, tag: VariantTag) -> PartialVMResult<()> {
+    let variant_count = enum_def.variants.len();
+    if tag as usize >= variant_count {
+        return Err(bounds_error(
+            StatusCode::INDEX_OUT_OF_BOUNDS,
+            IndexKind::VariantHandle,
+            tag,
+            variant_count,
+        ));
+    }
+    Ok(())
+}
+
 fn check_bounds_impl<T, I>(pool: &[T], idx: I) -> PartialVMResult<()>
 where
     I: ModuleIndex,
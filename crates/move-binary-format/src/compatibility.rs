@@ -115,6 +115,34 @@ impl Compatibility {
             }
         }
 
+        // old module's enums are a subset of the new module's enums
+        for (name, old_enum) in &old_module.enums {
+            let Some(new_enum) = new_module.enums.get(name) else {
+                // Enum not present in new. Same as for structs, existing modules that depend on
+                // this enum will fail to link, and its layout cannot be guaranteed.
+                struct_and_function_linking = false;
+                struct_layout = false;
+                break;
+            };
+
+            if !struct_abilities_compatible(
+                self.disallowed_new_abilities,
+                old_enum.abilities,
+                new_enum.abilities,
+            ) || !struct_type_parameters_compatible(
+                self.disallow_change_struct_type_params,
+                &old_enum.type_parameters,
+                &new_enum.type_parameters,
+            ) {
+                struct_and_function_linking = false;
+            }
+            if new_enum.variants != old_enum.variants {
+                // Variants changed. As for struct fields, any change to the variants or their
+                // fields could break reading previously published enum values.
+                struct_layout = false
+            }
+        }
+
         // The modules are considered as compatible function-wise when all the conditions are met:
         //
         // - old module's public functions are a subset of the new module's public functions
@@ -333,6 +361,7 @@ impl InclusionCheck {
         // of the tables are the exact same except for constants.
         if (self == &Self::Equal)
             && (old_module.structs.len() != new_module.structs.len()
+                || old_module.enums.len() != new_module.enums.len()
                 || old_module.functions.len() != new_module.functions.len()
                 || old_module.friends.len() != new_module.friends.len())
         {
@@ -349,6 +378,16 @@ impl InclusionCheck {
             };
         }
 
+        // Enum checks
+        for (name, old_enum) in &old_module.enums {
+            match new_module.enums.get(name) {
+                Some(new_enum) if old_enum == new_enum => (),
+                _ => {
+                    return err;
+                }
+            };
+        }
+
         // Function checks
         for (name, old_func) in &old_module.functions {
             match new_module
@@ -159,6 +159,31 @@ define_index! {
     kind: FunctionDefinition,
     doc: "Index into the `FunctionDefinition` table.",
 }
+define_index! {
+    name: EnumDefinitionIndex,
+    kind: EnumDefinition,
+    doc: "Index into the `EnumDefinition` table.",
+}
+define_index! {
+    name: EnumDefInstantiationIndex,
+    kind: EnumDefInstantiation,
+    doc: "Index into the `EnumDefInstantiation` table.",
+}
+define_index! {
+    name: VariantHandleIndex,
+    kind: VariantHandle,
+    doc: "Index into the `VariantHandle` table.",
+}
+define_index! {
+    name: VariantInstantiationHandleIndex,
+    kind: VariantInstantiationHandle,
+    doc: "Index into the `VariantInstantiationHandle` table.",
+}
+define_index! {
+    name: VariantJumpTableIndex,
+    kind: VariantJumpTable,
+    doc: "Index into the `VariantJumpTable`s of a `CodeUnit`.",
+}
 
 /// Index of a local variable in a function.
 ///
@@ -166,6 +191,8 @@ define_index! {
 pub type LocalIndex = u8;
 /// Max number of fields in a `StructDefinition`.
 pub type MemberCount = u16;
+/// Index of a variant in an `EnumDefinition`, also used as the runtime tag of enum values.
+pub type VariantTag = u16;
 /// Index into the code stream for a jump. The offset is relative to the beginning of
 /// the instruction stream.
 pub type CodeOffset = u16;
@@ -226,8 +253,8 @@ pub struct ModuleHandle {
     pub name: IdentifierIndex,
 }
 
-/// A `StructHandle` is a reference to a user defined type. It is composed by a `ModuleHandle`
-/// and the name of the type within that module.
+/// A `StructHandle` is a reference to a user defined type, either a struct or an enum. It is
+/// composed by a `ModuleHandle` and the name of the type within that module.
 ///
 /// A type in a module is uniquely identified by its name and as such the name is enough
 /// to perform resolution.
@@ -311,6 +338,28 @@ pub struct FieldHandle {
     pub field: MemberCount,
 }
 
+/// A variant of an enum defined in this module, identified by its tag
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantHandle {
+    pub enum_def: EnumDefinitionIndex,
+    pub variant: VariantTag,
+}
+
+/// A variant of an instantiation of a generic enum defined in this module
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantInstantiationHandle {
+    pub enum_def: EnumDefInstantiationIndex,
+    pub variant: VariantTag,
+}
+
 // DEFINITIONS:
 // Definitions are the module code. So the set of types and functions in the module.
 
@@ -344,6 +393,17 @@ pub struct StructDefInstantiation {
     pub type_parameters: SignatureIndex,
 }
 
+/// A complete or partial instantiation of a generic enum
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct EnumDefInstantiation {
+    pub def: EnumDefinitionIndex,
+    pub type_parameters: SignatureIndex,
+}
+
 /// A complete or partial instantiation of a function
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
@@ -426,6 +486,40 @@ pub struct FieldDefinition {
     pub signature: TypeSignature,
 }
 
+/// An `EnumDefinition` is a type definition with a list of variants, each declaring its own
+/// fields. The tag of a variant is its position in the list.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct EnumDefinition {
+    /// The `StructHandle` for this `EnumDefinition`. This has the name and the abilities
+    /// for the type.
+    pub enum_handle: StructHandleIndex,
+    /// The variants of the enum, in declaration order.
+    pub variants: Vec<VariantDefinition>,
+}
+
+impl EnumDefinition {
+    pub fn variant(&self, tag: VariantTag) -> Option<&VariantDefinition> {
+        self.variants.get(tag as usize)
+    }
+}
+
+/// A `VariantDefinition` is the definition of a variant of an enum: its name and its fields.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantDefinition {
+    /// The name of the variant.
+    pub variant_name: IdentifierIndex,
+    /// The fields of the variant, possibly empty.
+    pub fields: Vec<FieldDefinition>,
+}
+
 /// `Visibility` restricts the accessibility of the associated entity.
 /// - For function visibility, it restricts who may call into the associated function.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -1162,6 +1256,23 @@ pub struct CodeUnit {
         proptest(strategy = "vec(any::<Bytecode>(), 0..=params)")
     )]
     pub code: Vec<Bytecode>,
+    /// Jump tables used by `VariantSwitch` instructions in the code stream.
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(value = "vec![]"))]
+    pub jump_tables: Vec<VariantJumpTable>,
+}
+
+/// A `VariantJumpTable` maps each variant of an enum, by tag, to the code offset a
+/// `VariantSwitch` on a value of that enum branches to.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+#[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "wasm", derive(Serialize, Deserialize))]
+pub struct VariantJumpTable {
+    /// The enum whose values are switched on.
+    pub head_enum: EnumDefinitionIndex,
+    /// One code offset per variant of `head_enum`, indexed by tag.
+    pub jump_table: Vec<CodeOffset>,
 }
 
 /// `Bytecode` is a VM instruction of variable size. The type of the bytecode (opcode) defines
@@ -1620,6 +1731,64 @@ pub enum Bytecode {
     ///
     /// ```..., integer_value -> ..., u256_value```
     CastU256,
+    /// Create an instance of the enum variant specified via `VariantHandleIndex` and push it on
+    /// the stack. The values of the fields of the variant, in the order they appear in the
+    /// variant declaration, must be pushed on the stack. All fields must be provided.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., field(1)_value, field(2)_value, ..., field(n)_value -> ..., instance_value```
+    //
+    // Enum instructions are not generated by proptest: arbitrary modules have no enum
+    // definitions for them to refer to.
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    PackVariant(VariantHandleIndex),
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    PackVariantGeneric(VariantInstantiationHandleIndex),
+    /// Destroy an instance of an enum variant and push the values bound to each field of the
+    /// variant on the stack. Aborts if the instance is not of the variant specified via
+    /// `VariantHandleIndex`.
+    ///
+    /// The values of the fields of the instance appear on the stack in the order defined
+    /// in the variant definition.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., instance_value -> ..., field(1)_value, field(2)_value, ..., field(n)_value```
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    UnpackVariant(VariantHandleIndex),
+    /// Consume a reference to an instance of an enum variant and push an immutable reference to
+    /// each field of the variant on the stack. Aborts if the instance is not of the variant
+    /// specified via `VariantHandleIndex`.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., instance_ref -> ..., field(1)_ref, field(2)_ref, ..., field(n)_ref```
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    UnpackVariantImmRef(VariantHandleIndex),
+    /// Consume a mutable reference to an instance of an enum variant and push a mutable
+    /// reference to each field of the variant on the stack. Aborts if the instance is not of the
+    /// variant specified via `VariantHandleIndex`.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., instance_mut_ref -> ..., field(1)_mut_ref, field(2)_mut_ref, ..., field(n)_mut_ref```
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    UnpackVariantMutRef(VariantHandleIndex),
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    UnpackVariantGeneric(VariantInstantiationHandleIndex),
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    UnpackVariantGenericImmRef(VariantInstantiationHandleIndex),
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    UnpackVariantGenericMutRef(VariantInstantiationHandleIndex),
+    /// Consume a reference to an enum value and branch to the code offset that the jump table
+    /// specified via `VariantJumpTableIndex` associates with the variant of the value.
+    ///
+    /// Stack transition:
+    ///
+    /// ```..., instance_ref -> ...```
+    #[cfg_attr(any(test, feature = "fuzzing"), proptest(skip))]
+    VariantSwitch(VariantJumpTableIndex),
 
     // ******** DEPRECATED BYTECODES ********
     ExistsDeprecated(StructDefinitionIndex),
@@ -1718,6 +1887,19 @@ impl ::std::fmt::Debug for Bytecode {
             Bytecode::VecPopBack(a) => write!(f, "VecPopBack({})", a),
             Bytecode::VecUnpack(a, n) => write!(f, "VecUnpack({}, {})", a, n),
             Bytecode::VecSwap(a) => write!(f, "VecSwap({})", a),
+            Bytecode::PackVariant(a) => write!(f, "PackVariant({})", a),
+            Bytecode::PackVariantGeneric(a) => write!(f, "PackVariantGeneric({})", a),
+            Bytecode::UnpackVariant(a) => write!(f, "UnpackVariant({})", a),
+            Bytecode::UnpackVariantImmRef(a) => write!(f, "UnpackVariantImmRef({})", a),
+            Bytecode::UnpackVariantMutRef(a) => write!(f, "UnpackVariantMutRef({})", a),
+            Bytecode::UnpackVariantGeneric(a) => write!(f, "UnpackVariantGeneric({})", a),
+            Bytecode::UnpackVariantGenericImmRef(a) => {
+                write!(f, "UnpackVariantGenericImmRef({})", a)
+            }
+            Bytecode::UnpackVariantGenericMutRef(a) => {
+                write!(f, "UnpackVariantGenericMutRef({})", a)
+            }
+            Bytecode::VariantSwitch(a) => write!(f, "VariantSwitch({})", a),
         }
     }
 }
@@ -1725,7 +1907,10 @@ impl ::std::fmt::Debug for Bytecode {
 impl Bytecode {
     /// Return true if this bytecode instruction always branches
     pub fn is_unconditional_branch(&self) -> bool {
-        matches!(self, Bytecode::Ret | Bytecode::Abort | Bytecode::Branch(_))
+        matches!(
+            self,
+            Bytecode::Ret | Bytecode::Abort | Bytecode::Branch(_) | Bytecode::VariantSwitch(_)
+        )
     }
 
     /// Return true if the branching behavior of this bytecode instruction depends on a runtime
@@ -1739,19 +1924,28 @@ impl Bytecode {
         self.is_conditional_branch() || self.is_unconditional_branch()
     }
 
-    /// Returns the offset that this bytecode instruction branches to, if any.
+    /// Returns the offsets that this bytecode instruction branches to, if any. The offsets of a
+    /// `VariantSwitch` are found in the jump tables of the code unit.
     /// Note that return and abort are branch instructions, but have no offset.
-    pub fn offset(&self) -> Option<&CodeOffset> {
+    pub fn offsets(&self, jump_tables: &[VariantJumpTable]) -> Vec<CodeOffset> {
         match self {
             Bytecode::BrFalse(offset) | Bytecode::BrTrue(offset) | Bytecode::Branch(offset) => {
-                Some(offset)
+                vec![*offset]
             }
-            _ => None,
+            Bytecode::VariantSwitch(jt_idx) => jump_tables
+                .get(jt_idx.into_index())
+                .map(|jt| jt.jump_table.clone())
+                .unwrap_or_default(),
+            _ => vec![],
         }
     }
 
     /// Return the successor offsets of this bytecode instruction.
-    pub fn get_successors(pc: CodeOffset, code: &[Bytecode]) -> Vec<CodeOffset> {
+    pub fn get_successors(
+        pc: CodeOffset,
+        code: &[Bytecode],
+        jump_tables: &[VariantJumpTable],
+    ) -> Vec<CodeOffset> {
         assert!(
             // The program counter must remain within the bounds of the code
             pc < u16::MAX && (pc as usize) < code.len(),
@@ -1759,27 +1953,16 @@ impl Bytecode {
         );
 
         let bytecode = &code[pc as usize];
-        let mut v = vec![];
-
-        if let Some(offset) = bytecode.offset() {
-            v.push(*offset);
-        }
+        let mut v = bytecode.offsets(jump_tables);
 
         let next_pc = pc + 1;
-        if next_pc >= code.len() as CodeOffset {
-            return v;
-        }
-
-        if !bytecode.is_unconditional_branch() && !v.contains(&next_pc) {
-            // avoid duplicates
-            v.push(pc + 1);
-        }
-
-        // always give successors in ascending order
-        if v.len() > 1 && v[0] > v[1] {
-            v.swap(0, 1);
+        if next_pc < code.len() as CodeOffset && !bytecode.is_unconditional_branch() {
+            v.push(next_pc);
         }
 
+        // always give successors in ascending order, without duplicates
+        v.sort();
+        v.dedup();
         v
     }
 }
@@ -1837,6 +2020,10 @@ impl Arbitrary for CompiledModule {
                         metadata: vec![],
                         struct_defs,
                         function_defs,
+                        enum_defs: vec![],
+                        enum_def_instantiations: vec![],
+                        variant_handles: vec![],
+                        variant_instantiation_handles: vec![],
                     }
                 },
             )
@@ -1892,6 +2079,15 @@ pub struct CompiledModule {
     pub struct_defs: Vec<StructDefinition>,
     /// Function defined in this module.
     pub function_defs: Vec<FunctionDefinition>,
+
+    /// Enums defined in this module.
+    pub enum_defs: Vec<EnumDefinition>,
+    /// Enum instantiations.
+    pub enum_def_instantiations: Vec<EnumDefInstantiation>,
+    /// Handles to enum variants.
+    pub variant_handles: Vec<VariantHandle>,
+    /// Handles to variants of enum instantiations.
+    pub variant_instantiation_handles: Vec<VariantInstantiationHandle>,
 }
 
 impl CompiledModule {
@@ -1904,6 +2100,7 @@ impl CompiledModule {
                 | IndexKind::FieldDefinition
                 | IndexKind::TypeParameter
                 | IndexKind::MemberCount
+                | IndexKind::VariantJumpTable
         ));
         match kind {
             IndexKind::ModuleHandle => self.module_handles.len(),
@@ -1920,12 +2117,19 @@ impl CompiledModule {
             IndexKind::Identifier => self.identifiers.len(),
             IndexKind::AddressIdentifier => self.address_identifiers.len(),
             IndexKind::ConstantPool => self.constant_pool.len(),
+            IndexKind::EnumDefinition => self.enum_defs.len(),
+            IndexKind::EnumDefInstantiation => self.enum_def_instantiations.len(),
+            IndexKind::VariantHandle => self.variant_handles.len(),
+            IndexKind::VariantInstantiationHandle => self.variant_instantiation_handles.len(),
             // XXX these two don't seem to belong here
             other @ IndexKind::LocalPool
             | other @ IndexKind::CodeDefinition
             | other @ IndexKind::FieldDefinition
             | other @ IndexKind::TypeParameter
-            | other @ IndexKind::MemberCount => unreachable!("invalid kind for count: {:?}", other),
+            | other @ IndexKind::MemberCount
+            | other @ IndexKind::VariantJumpTable => {
+                unreachable!("invalid kind for count: {:?}", other)
+            }
         }
     }
 
@@ -2031,6 +2235,33 @@ impl CompiledModule {
         result
     }
 
+    pub fn enum_name(&self, idx: EnumDefinitionIndex) -> &IdentStr {
+        let enum_def = self.enum_def_at(idx);
+        let handle = self.struct_handle_at(enum_def.enum_handle);
+        self.identifier_at(handle.name)
+    }
+
+    pub fn enum_def_at(&self, idx: EnumDefinitionIndex) -> &EnumDefinition {
+        &self.enum_defs[idx.into_index()]
+    }
+
+    pub fn enum_instantiation_at(&self, idx: EnumDefInstantiationIndex) -> &EnumDefInstantiation {
+        &self.enum_def_instantiations[idx.into_index()]
+    }
+
+    pub fn variant_handle_at(&self, idx: VariantHandleIndex) -> &VariantHandle {
+        let handle = &self.variant_handles[idx.into_index()];
+        debug_assert!(handle.enum_def.into_index() < self.enum_defs.len()); // invariant
+        handle
+    }
+
+    pub fn variant_instantiation_handle_at(
+        &self,
+        idx: VariantInstantiationHandleIndex,
+    ) -> &VariantInstantiationHandle {
+        &self.variant_instantiation_handles[idx.into_index()]
+    }
+
     pub fn module_handles(&self) -> &[ModuleHandle] {
         &self.module_handles
     }
@@ -2083,6 +2314,22 @@ impl CompiledModule {
         &self.function_defs
     }
 
+    pub fn enum_defs(&self) -> &[EnumDefinition] {
+        &self.enum_defs
+    }
+
+    pub fn enum_instantiations(&self) -> &[EnumDefInstantiation] {
+        &self.enum_def_instantiations
+    }
+
+    pub fn variant_handles(&self) -> &[VariantHandle] {
+        &self.variant_handles
+    }
+
+    pub fn variant_instantiation_handles(&self) -> &[VariantInstantiationHandle] {
+        &self.variant_instantiation_handles
+    }
+
     pub fn friend_decls(&self) -> &[ModuleHandle] {
         &self.friend_decls
     }
@@ -2111,6 +2358,10 @@ impl CompiledModule {
         self.struct_defs().iter().find(|d| d.struct_handle == idx)
     }
 
+    pub fn find_enum_def(&self, idx: StructHandleIndex) -> Option<&EnumDefinition> {
+        self.enum_defs().iter().find(|d| d.enum_handle == idx)
+    }
+
     pub fn find_struct_def_by_name(&self, name: &IdentStr) -> Option<&StructDefinition> {
         self.struct_defs().iter().find(|def| {
             let handle = self.struct_handle_at(def.struct_handle);
@@ -2197,6 +2448,10 @@ pub fn empty_module() -> CompiledModule {
         function_instantiations: vec![],
         field_instantiations: vec![],
         signatures: vec![Signature(vec![])],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     }
 }
 
@@ -2228,6 +2483,7 @@ pub fn basic_test_module() -> CompiledModule {
         code: Some(CodeUnit {
             locals: SignatureIndex(0),
             code: vec![Bytecode::Ret],
+            jump_tables: vec![],
         }),
     });
 
@@ -49,6 +49,11 @@ pub enum IndexKind {
     CodeDefinition,
     TypeParameter,
     MemberCount,
+    EnumDefinition,
+    EnumDefInstantiation,
+    VariantHandle,
+    VariantInstantiationHandle,
+    VariantJumpTable,
 }
 
 impl IndexKind {
@@ -75,6 +80,11 @@ impl IndexKind {
             CodeDefinition,
             TypeParameter,
             MemberCount,
+            EnumDefinition,
+            EnumDefInstantiation,
+            VariantHandle,
+            VariantInstantiationHandle,
+            VariantJumpTable,
         ]
     }
 }
@@ -103,6 +113,11 @@ impl fmt::Display for IndexKind {
             CodeDefinition => "code definition pool",
             TypeParameter => "type parameter",
             MemberCount => "field offset",
+            EnumDefinition => "enum definition",
+            EnumDefInstantiation => "enum instantiation",
+            VariantHandle => "variant handle",
+            VariantInstantiationHandle => "variant instantiation handle",
+            VariantJumpTable => "variant jump table",
         };
 
         f.write_str(desc)
@@ -2,13 +2,17 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::file_format::{
-    AbilitySet, Bytecode as FBytecode, CodeOffset, CompiledModule, FieldDefinition, FieldHandle,
-    FieldHandleIndex, FieldInstantiation, FieldInstantiationIndex, FunctionDefinition,
-    FunctionHandle, FunctionHandleIndex, FunctionInstantiation, LocalIndex, SignatureIndex,
-    SignatureToken, StructDefInstantiation, StructDefInstantiationIndex, StructDefinition,
-    StructDefinitionIndex, StructFieldInformation, StructTypeParameter, TypeParameterIndex,
-    Visibility,
+use crate::{
+    file_format::{
+        AbilitySet, Bytecode as FBytecode, CodeOffset, CompiledModule, EnumDefInstantiation,
+        EnumDefinition, FieldDefinition, FieldHandle, FieldHandleIndex, FieldInstantiation,
+        FieldInstantiationIndex, FunctionDefinition, FunctionHandle, FunctionHandleIndex,
+        FunctionInstantiation, LocalIndex, SignatureIndex, SignatureToken, StructDefInstantiation,
+        StructDefInstantiationIndex, StructDefinition, StructDefinitionIndex,
+        StructFieldInformation, StructTypeParameter, TypeParameterIndex, VariantHandleIndex,
+        VariantInstantiationHandleIndex, VariantJumpTable, VariantTag, Visibility,
+    },
+    internals::ModuleIndex,
 };
 use move_core_types::{
     account_address::AccountAddress,
@@ -90,6 +94,23 @@ pub struct Struct {
     pub fields: Vec<Field>,
 }
 
+/// Normalized version of a `VariantDefinition`. As for fields, the `name` is included since
+/// renaming a variant should be marked as incompatible.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Ord, PartialOrd)]
+pub struct Variant {
+    pub name: Identifier,
+    pub fields: Vec<Field>,
+}
+
+/// Normalized version of an `EnumDefinition`. Not safe to compare without an associated
+/// `ModuleId` or `Module`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Ord, PartialOrd)]
+pub struct Enum {
+    pub abilities: AbilitySet,
+    pub type_parameters: Vec<StructTypeParameter>,
+    pub variants: Vec<Variant>,
+}
+
 /// Normalized version of a `FunctionDefinition`. Not safe to compare without an associated
 /// `ModuleId` or `Module`.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
@@ -108,6 +129,12 @@ pub struct FieldRef {
     pub field_index: u16,
 }
 
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+pub struct VariantRef {
+    pub enum_name: Identifier,
+    pub variant_tag: VariantTag,
+}
+
 // Functions can reference external modules. We don't track the exact type parameters and the like
 // since we know they can't change, or don't matter since:
 // * Either we allow compatible upgrades in which case the changing of the call parameters/types
@@ -193,6 +220,15 @@ pub enum Bytecode {
     CastU16,
     CastU32,
     CastU256,
+    PackVariant(VariantRef),
+    PackVariantGeneric((VariantRef, Vec<Type>)),
+    UnpackVariant(VariantRef),
+    UnpackVariantImmRef(VariantRef),
+    UnpackVariantMutRef(VariantRef),
+    UnpackVariantGeneric((VariantRef, Vec<Type>)),
+    UnpackVariantGenericImmRef((VariantRef, Vec<Type>)),
+    UnpackVariantGenericMutRef((VariantRef, Vec<Type>)),
+    VariantSwitch(Vec<CodeOffset>),
     // ******** DEPRECATED BYTECODES ********
     MutBorrowGlobalDeprecated(Identifier),
     MutBorrowGlobalGenericDeprecated((Identifier, Vec<Type>)),
@@ -225,6 +261,7 @@ pub struct Module {
     pub dependencies: Vec<ModuleId>,
     pub friends: Vec<ModuleId>,
     pub structs: BTreeMap<Identifier, Struct>,
+    pub enums: BTreeMap<Identifier, Enum>,
     pub functions: BTreeMap<Identifier, Function>,
     pub constants: Vec<Constant>,
 }
@@ -236,6 +273,7 @@ impl Module {
     pub fn new(m: &CompiledModule) -> Self {
         let friends = m.immediate_friends();
         let structs = m.struct_defs().iter().map(|d| Struct::new(m, d)).collect();
+        let enums = m.enum_defs().iter().map(|d| Enum::new(m, d)).collect();
         let dependencies = m.immediate_dependencies();
         let constants = m
             .constant_pool()
@@ -253,6 +291,7 @@ impl Module {
             name: m.name().to_owned(),
             friends,
             structs,
+            enums,
             functions,
             dependencies,
             constants,
@@ -447,6 +486,32 @@ impl Struct {
     }
 }
 
+impl Enum {
+    /// Create an `Enum` for `EnumDefinition` `def` in module `m`.
+    pub fn new(m: &CompiledModule, def: &EnumDefinition) -> (Identifier, Self) {
+        let handle = m.struct_handle_at(def.enum_handle);
+        let variants = def
+            .variants
+            .iter()
+            .map(|v| Variant {
+                name: m.identifier_at(v.variant_name).to_owned(),
+                fields: v.fields.iter().map(|f| Field::new(m, f)).collect(),
+            })
+            .collect();
+        let name = m.identifier_at(handle.name).to_owned();
+        let e = Enum {
+            abilities: handle.abilities,
+            type_parameters: handle.type_parameters.clone(),
+            variants,
+        };
+        (name, e)
+    }
+
+    pub fn type_param_constraints(&self) -> impl ExactSizeIterator<Item = &AbilitySet> {
+        self.type_parameters.iter().map(|param| &param.constraints)
+    }
+}
+
 impl Function {
     /// Create a `FunctionSignature` for `FunctionHandle` `f` in module `m`.
     pub fn new(m: &CompiledModule, def: &FunctionDefinition) -> (Identifier, Self) {
@@ -458,7 +523,7 @@ impl Function {
             .map(|code| {
                 code.code
                     .iter()
-                    .map(|bytecode| Bytecode::new(m, bytecode))
+                    .map(|bytecode| Bytecode::new(m, bytecode, &code.jump_tables))
                     .collect()
             })
             .unwrap_or_default();
@@ -532,6 +597,16 @@ impl FieldRef {
     }
 }
 
+impl VariantRef {
+    pub fn new(m: &CompiledModule, idx: &VariantHandleIndex) -> Self {
+        let handle = m.variant_handle_at(*idx);
+        Self {
+            enum_name: m.enum_name(handle.enum_def).to_owned(),
+            variant_tag: handle.variant,
+        }
+    }
+}
+
 impl FunctionRef {
     pub fn new(m: &CompiledModule, function_handle: &FunctionHandle) -> Self {
         Self {
@@ -546,7 +621,7 @@ impl FunctionRef {
 }
 
 impl Bytecode {
-    pub fn new(m: &CompiledModule, bytecode: &FBytecode) -> Self {
+    pub fn new(m: &CompiledModule, bytecode: &FBytecode, jump_tables: &[VariantJumpTable]) -> Self {
         use Bytecode as B;
         use FBytecode as FB;
         match bytecode {
@@ -657,6 +732,25 @@ impl Bytecode {
             FB::VecPopBack(sig_idx) => B::VecPopBack(signature_to_single_type(m, sig_idx)),
             FB::VecUnpack(sig_idx, len) => B::VecUnpack(signature_to_single_type(m, sig_idx), *len),
             FB::VecSwap(sig_idx) => B::VecSwap(signature_to_single_type(m, sig_idx)),
+            FB::PackVariant(vh_idx) => B::PackVariant(VariantRef::new(m, vh_idx)),
+            FB::PackVariantGeneric(vi_idx) => {
+                B::PackVariantGeneric(variant_instantiation(m, vi_idx))
+            }
+            FB::UnpackVariant(vh_idx) => B::UnpackVariant(VariantRef::new(m, vh_idx)),
+            FB::UnpackVariantImmRef(vh_idx) => B::UnpackVariantImmRef(VariantRef::new(m, vh_idx)),
+            FB::UnpackVariantMutRef(vh_idx) => B::UnpackVariantMutRef(VariantRef::new(m, vh_idx)),
+            FB::UnpackVariantGeneric(vi_idx) => {
+                B::UnpackVariantGeneric(variant_instantiation(m, vi_idx))
+            }
+            FB::UnpackVariantGenericImmRef(vi_idx) => {
+                B::UnpackVariantGenericImmRef(variant_instantiation(m, vi_idx))
+            }
+            FB::UnpackVariantGenericMutRef(vi_idx) => {
+                B::UnpackVariantGenericMutRef(variant_instantiation(m, vi_idx))
+            }
+            FB::VariantSwitch(jt_idx) => {
+                B::VariantSwitch(jump_tables[jt_idx.into_index()].jump_table.clone())
+            }
         }
     }
 }
@@ -737,6 +831,28 @@ fn field_instantiation(m: &CompiledModule, idx: &FieldInstantiationIndex) -> (Fi
     (field_ref, types)
 }
 
+fn variant_instantiation(
+    m: &CompiledModule,
+    idx: &VariantInstantiationHandleIndex,
+) -> (VariantRef, Vec<Type>) {
+    let handle = m.variant_instantiation_handle_at(*idx);
+    let EnumDefInstantiation {
+        def,
+        type_parameters,
+    } = m.enum_instantiation_at(handle.enum_def);
+    let variant_ref = VariantRef {
+        enum_name: m.enum_name(*def).to_owned(),
+        variant_tag: handle.variant,
+    };
+    let types = m
+        .signature_at(*type_parameters)
+        .0
+        .iter()
+        .map(|tok| Type::new(m, tok))
+        .collect();
+    (variant_ref, types)
+}
+
 fn signature_to_single_type(m: &CompiledModule, sig_idx: &SignatureIndex) -> Type {
     Type::new(m, &m.signature_at(*sig_idx).0[0])
 }
@@ -388,6 +388,10 @@ impl CompiledModuleStrategyGen {
                         address_identifiers,
                         constant_pool,
                         metadata,
+                        enum_defs: vec![],
+                        enum_def_instantiations: vec![],
+                        variant_handles: vec![],
+                        variant_instantiation_handles: vec![],
                     }
                 },
             )
@@ -476,6 +476,7 @@ impl CodeUnitGen {
         CodeUnit {
             locals: state.add_signature(locals_signature),
             code,
+            jump_tables: vec![],
         }
     }
 }
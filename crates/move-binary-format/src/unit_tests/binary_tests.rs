@@ -30,7 +30,7 @@ fn test_max_number_of_bytecode() {
     }
     nops.push(Bytecode::Branch(0));
 
-    let result = Bytecode::get_successors(u16::MAX - 1, &nops);
+    let result = Bytecode::get_successors(u16::MAX - 1, &nops, &[]);
     assert_eq!(result, vec![0]);
 }
 
@@ -96,6 +96,7 @@ fn mk_module_entry(vis: u8, is_entry: bool) -> normalized::Module {
                         Bytecode::LdConst(ConstantPoolIndex(2)),
                         Bytecode::Ret,
                     ],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -125,6 +126,10 @@ fn mk_module_entry(vis: u8, is_entry: bool) -> normalized::Module {
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     normalized::Module::new(&m)
 }
@@ -184,6 +189,7 @@ fn mk_module_plus_code_perm(vis: u8, code: Vec<Bytecode>, p: Permutation) -> nor
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code,
+                    jump_tables: vec![],
                 }),
             },
             // public(script) fun fn() { return; }
@@ -200,6 +206,7 @@ fn mk_module_plus_code_perm(vis: u8, code: Vec<Bytecode>, p: Permutation) -> nor
                         Bytecode::LdConst(ConstantPoolIndex(p.permute(2))),
                         Bytecode::Ret,
                     ],
+                    jump_tables: vec![],
                 }),
             },
         ]),
@@ -229,6 +236,10 @@ fn mk_module_plus_code_perm(vis: u8, code: Vec<Bytecode>, p: Permutation) -> nor
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     normalized::Module::new(&m)
 }
@@ -368,6 +379,7 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             // fun g_fn<T>() { return; }
@@ -379,6 +391,7 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             FunctionDefinition {
@@ -389,6 +402,7 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(p.permute(0)),
                     code: vec![],
+                    jump_tables: vec![],
                 }),
             },
         ]),
@@ -422,6 +436,10 @@ fn make_complex_module_perm(p: Permutation) -> normalized::Module {
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     normalized::Module::new(&m)
 }
@@ -5,9 +5,9 @@
 use anyhow::{format_err, Result};
 use move_binary_format::{
     file_format::{
-        AbilitySet, CodeOffset, CodeUnit, ConstantPoolIndex, FunctionDefinitionIndex, LocalIndex,
-        MemberCount, ModuleHandleIndex, SignatureIndex, StructDefinition, StructDefinitionIndex,
-        TableIndex,
+        AbilitySet, CodeOffset, CodeUnit, ConstantPoolIndex, EnumDefinition, EnumDefinitionIndex,
+        FunctionDefinitionIndex, LocalIndex, MemberCount, ModuleHandleIndex, SignatureIndex,
+        StructDefinition, StructDefinitionIndex, TableIndex, VariantTag,
     },
     CompiledModule,
 };
@@ -40,6 +40,19 @@ pub struct StructSourceMap {
     pub fields: Vec<Loc>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnumSourceMap {
+    /// The source declaration location of the enum
+    pub definition_location: Loc,
+
+    /// Important: type parameters need to be added in the order of their declaration
+    pub type_parameters: Vec<SourceName>,
+
+    /// The name and location of each variant, along with the locations of its fields. Variants
+    /// need to be added in the order of the variants in the enum definition.
+    pub variants: Vec<(SourceName, Vec<Loc>)>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FunctionSourceMap {
     /// The source location for the definition of this entire function. Note that in certain
@@ -80,6 +93,9 @@ pub struct SourceMap {
     // A mapping of `StructDefinitionIndex` to source map for each struct/resource.
     struct_map: BTreeMap<TableIndex, StructSourceMap>,
 
+    // A mapping of `EnumDefinitionIndex` to source map for each enum.
+    enum_map: BTreeMap<TableIndex, EnumSourceMap>,
+
     // A mapping of `FunctionDefinitionIndex` to the soure map for that function.
     // For scripts, this map has a single element that points to a source map corresponding to the
     // script's "main" function.
@@ -136,6 +152,56 @@ impl StructSourceMap {
     }
 }
 
+impl EnumSourceMap {
+    pub fn new(definition_location: Loc) -> Self {
+        Self {
+            definition_location,
+            type_parameters: Vec::new(),
+            variants: Vec::new(),
+        }
+    }
+
+    pub fn add_type_parameter(&mut self, type_name: SourceName) {
+        self.type_parameters.push(type_name)
+    }
+
+    pub fn get_type_parameter_name(&self, type_parameter_idx: usize) -> Option<SourceName> {
+        self.type_parameters.get(type_parameter_idx).cloned()
+    }
+
+    pub fn add_variant(&mut self, variant_name: SourceName, field_locs: Vec<Loc>) {
+        self.variants.push((variant_name, field_locs))
+    }
+
+    pub fn get_variant_location(&self, variant: VariantTag) -> Option<Loc> {
+        self.variants
+            .get(variant as usize)
+            .map(|((_, loc), _)| *loc)
+    }
+
+    pub fn dummy_enum_map(
+        &mut self,
+        module: &CompiledModule,
+        enum_def: &EnumDefinition,
+        default_loc: Loc,
+    ) -> Result<()> {
+        let enum_handle = module.struct_handle_at(enum_def.enum_handle);
+
+        // Add dummy locations for the variants and their fields
+        for variant in &enum_def.variants {
+            let name = module.identifier_at(variant.variant_name).to_string();
+            let field_locs = variant.fields.iter().map(|_| default_loc).collect();
+            self.add_variant((name, default_loc), field_locs)
+        }
+
+        for i in 0..enum_handle.type_parameters.len() {
+            let name = format!("Ty{}", i);
+            self.add_type_parameter((name, default_loc))
+        }
+        Ok(())
+    }
+}
+
 impl FunctionSourceMap {
     pub fn new(definition_location: Loc, is_native: bool) -> Self {
         Self {
@@ -274,6 +340,7 @@ impl SourceMap {
             definition_location,
             module_name,
             struct_map: BTreeMap::new(),
+            enum_map: BTreeMap::new(),
             function_map: BTreeMap::new(),
             constant_map: BTreeMap::new(),
         }
@@ -407,6 +474,16 @@ impl SourceMap {
                 )) })
     }
 
+    pub fn add_top_level_enum_mapping(
+        &mut self,
+        enum_def_idx: EnumDefinitionIndex,
+        location: Loc,
+    ) -> Result<()> {
+        self.enum_map.insert(enum_def_idx.0, EnumSourceMap::new(location)).map_or(Ok(()), |_| { Err(format_err!(
+                "Multiple enums at same enum definition index encountered when constructing source map"
+                )) })
+    }
+
     pub fn add_const_mapping(
         &mut self,
         const_idx: ConstantPoolIndex,
@@ -469,6 +546,42 @@ impl SourceMap {
             .ok_or_else(|| format_err!("Unable to get struct type parameter name"))
     }
 
+    pub fn add_enum_type_parameter_mapping(
+        &mut self,
+        enum_def_idx: EnumDefinitionIndex,
+        name: SourceName,
+    ) -> Result<()> {
+        let enum_entry = self.enum_map.get_mut(&enum_def_idx.0).ok_or_else(|| {
+            format_err!("Tried to add enum type parameter mapping to undefined enum index")
+        })?;
+        enum_entry.add_type_parameter(name);
+        Ok(())
+    }
+
+    pub fn add_enum_variant_mapping(
+        &mut self,
+        enum_def_idx: EnumDefinitionIndex,
+        variant_name: SourceName,
+        field_locs: Vec<Loc>,
+    ) -> Result<()> {
+        let enum_entry = self.enum_map.get_mut(&enum_def_idx.0).ok_or_else(|| {
+            format_err!("Tried to add enum variant mapping to undefined enum index")
+        })?;
+        enum_entry.add_variant(variant_name, field_locs);
+        Ok(())
+    }
+
+    pub fn get_enum_type_parameter_name(
+        &self,
+        enum_def_idx: EnumDefinitionIndex,
+        type_parameter_idx: usize,
+    ) -> Result<SourceName> {
+        self.enum_map
+            .get(&enum_def_idx.0)
+            .and_then(|enum_source_map| enum_source_map.get_type_parameter_name(type_parameter_idx))
+            .ok_or_else(|| format_err!("Unable to get enum type parameter name"))
+    }
+
     pub fn get_function_source_map(
         &self,
         fdef_idx: FunctionDefinitionIndex,
@@ -487,6 +600,12 @@ impl SourceMap {
             .ok_or_else(|| format_err!("Unable to get struct source map"))
     }
 
+    pub fn get_enum_source_map(&self, enum_def_idx: EnumDefinitionIndex) -> Result<&EnumSourceMap> {
+        self.enum_map
+            .get(&enum_def_idx.0)
+            .ok_or_else(|| format_err!("Unable to get enum source map"))
+    }
+
     /// Create a 'dummy' source map for a compiled module or script. This is useful for e.g. disassembling
     /// with generated or real names depending upon if the source map is available or not.
     pub fn dummy_from_view(module: &CompiledModule, default_loc: Loc) -> Result<Self> {
@@ -532,6 +651,18 @@ impl SourceMap {
                 .dummy_struct_map(module, struct_def, default_loc)?;
         }
 
+        for (enum_idx, enum_def) in module.enum_defs().iter().enumerate() {
+            empty_source_map.add_top_level_enum_mapping(
+                EnumDefinitionIndex(enum_idx as TableIndex),
+                default_loc,
+            )?;
+            empty_source_map
+                .enum_map
+                .get_mut(&(enum_idx as TableIndex))
+                .ok_or_else(|| format_err!("Unable to get enum map while generating dummy"))?
+                .dummy_enum_map(module, enum_def, default_loc)?;
+        }
+
         for const_idx in 0..module.constant_pool().len() {
             empty_source_map.add_const_mapping(
                 ConstantPoolIndex(const_idx as TableIndex),
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! This module implements a checker for verifying that all of the struct's fields satisfy the
-//! abilities required by the struct's abilities
+//! abilities required by the struct's abilities, and similarly for the fields of enum variants
 use move_binary_format::{
     errors::{verification_error, Location, PartialVMResult, VMResult},
     file_format::{AbilitySet, CompiledModule, StructFieldInformation, TableIndex},
@@ -46,5 +46,30 @@ fn verify_module_impl(module: &CompiledModule) -> PartialVMResult<()> {
             }
         }
     }
+    for (idx, enum_def) in module.enum_defs().iter().enumerate() {
+        let sh = module.struct_handle_at(enum_def.enum_handle);
+        let required_abilities = sh
+            .abilities
+            .into_iter()
+            .map(|a| a.requires())
+            .fold(AbilitySet::EMPTY, |acc, required| acc | required);
+        // As with structs, assume type parameters have all abilities
+        let type_parameter_abilities = sh
+            .type_parameters
+            .iter()
+            .map(|_| AbilitySet::ALL)
+            .collect::<Vec<_>>();
+        for field in enum_def.variants.iter().flat_map(|v| &v.fields) {
+            let field_abilities =
+                module.abilities(&field.signature.0, &type_parameter_abilities)?;
+            if !required_abilities.is_subset(field_abilities) {
+                return Err(verification_error(
+                    StatusCode::FIELD_MISSING_TYPE_ABILITY,
+                    IndexKind::EnumDefinition,
+                    idx as TableIndex,
+                ));
+            }
+        }
+    }
     Ok(())
 }
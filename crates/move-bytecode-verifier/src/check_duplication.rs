@@ -6,8 +6,8 @@
 //! distinct values. Successful verification implies that an index in vector can be used to
 //! uniquely name the entry at that index. Additionally, the checker also verifies the
 //! following:
-//! - struct and field definitions are consistent
-//! - the handles in struct and function definitions point to the self module index
+//! - struct, enum, variant and field definitions are consistent
+//! - the handles in struct, enum and function definitions point to the self module index
 //! - all struct and function handles pointing to the self module index have a definition
 use move_binary_format::{
     errors::{verification_error, Location, PartialVMResult, VMResult},
@@ -48,7 +48,11 @@ impl<'a> DuplicationChecker<'a> {
         checker.check_field_instantiations()?;
         checker.check_function_defintions()?;
         checker.check_struct_definitions()?;
-        checker.check_struct_instantiations()
+        checker.check_struct_instantiations()?;
+        checker.check_enum_definitions()?;
+        checker.check_enum_instantiations()?;
+        checker.check_variant_handles()?;
+        checker.check_variant_instantiation_handles()
     }
 
     fn check_identifiers(identifiers: &[Identifier]) -> PartialVMResult<()> {
@@ -169,6 +173,39 @@ impl<'a> DuplicationChecker<'a> {
         }
     }
 
+    fn check_enum_instantiations(&self) -> PartialVMResult<()> {
+        match Self::first_duplicate_element(self.module.enum_instantiations()) {
+            Some(idx) => Err(verification_error(
+                StatusCode::DUPLICATE_ELEMENT,
+                IndexKind::EnumDefInstantiation,
+                idx,
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn check_variant_handles(&self) -> PartialVMResult<()> {
+        match Self::first_duplicate_element(self.module.variant_handles()) {
+            Some(idx) => Err(verification_error(
+                StatusCode::DUPLICATE_ELEMENT,
+                IndexKind::VariantHandle,
+                idx,
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn check_variant_instantiation_handles(&self) -> PartialVMResult<()> {
+        match Self::first_duplicate_element(self.module.variant_instantiation_handles()) {
+            Some(idx) => Err(verification_error(
+                StatusCode::DUPLICATE_ELEMENT,
+                IndexKind::VariantInstantiationHandle,
+                idx,
+            )),
+            None => Ok(()),
+        }
+    }
+
     fn check_field_instantiations(&self) -> PartialVMResult<()> {
         if let Some(idx) = Self::first_duplicate_element(self.module.field_instantiations()) {
             return Err(verification_error(
@@ -222,12 +259,14 @@ impl<'a> DuplicationChecker<'a> {
                 idx as TableIndex,
             ));
         }
-        // Check that each struct handle in self module is implemented (has a declaration)
+        // Check that each struct handle in self module is implemented (has a declaration), either
+        // by a struct or by an enum
         let implemented_struct_handles: HashSet<StructHandleIndex> = self
             .module
             .struct_defs()
             .iter()
             .map(|x| x.struct_handle)
+            .chain(self.module.enum_defs().iter().map(|x| x.enum_handle))
             .collect();
         if let Some(idx) = (0..self.module.struct_handles().len()).position(|x| {
             let y = StructHandleIndex::new(x as u16);
@@ -243,6 +282,81 @@ impl<'a> DuplicationChecker<'a> {
         Ok(())
     }
 
+    fn check_enum_definitions(&self) -> PartialVMResult<()> {
+        // EnumDefinition - contained StructHandle defines uniqueness, and is not shared with any
+        // struct definition
+        if let Some(idx) =
+            Self::first_duplicate_element(self.module.enum_defs().iter().map(|x| x.enum_handle))
+        {
+            return Err(verification_error(
+                StatusCode::DUPLICATE_ELEMENT,
+                IndexKind::EnumDefinition,
+                idx,
+            ));
+        }
+        let struct_handles: HashSet<StructHandleIndex> = self
+            .module
+            .struct_defs()
+            .iter()
+            .map(|x| x.struct_handle)
+            .collect();
+        if let Some(idx) = self
+            .module
+            .enum_defs()
+            .iter()
+            .position(|x| struct_handles.contains(&x.enum_handle))
+        {
+            return Err(verification_error(
+                StatusCode::DUPLICATE_ELEMENT,
+                IndexKind::EnumDefinition,
+                idx as TableIndex,
+            ));
+        }
+        for (enum_idx, enum_def) in self.module.enum_defs().iter().enumerate() {
+            // Enums must have at least one variant
+            if enum_def.variants.is_empty() {
+                return Err(verification_error(
+                    StatusCode::ZERO_SIZED_ENUM,
+                    IndexKind::EnumDefinition,
+                    enum_idx as TableIndex,
+                ));
+            }
+            // Variant names in enums must be unique
+            if let Some(idx) =
+                Self::first_duplicate_element(enum_def.variants.iter().map(|x| x.variant_name))
+            {
+                return Err(verification_error(
+                    StatusCode::DUPLICATE_ELEMENT,
+                    IndexKind::VariantHandle,
+                    idx,
+                ));
+            }
+            // Field names in variants must be unique, variants may have no fields
+            for variant in &enum_def.variants {
+                if let Some(idx) =
+                    Self::first_duplicate_element(variant.fields.iter().map(|x| x.name))
+                {
+                    return Err(verification_error(
+                        StatusCode::DUPLICATE_ELEMENT,
+                        IndexKind::FieldDefinition,
+                        idx,
+                    ));
+                }
+            }
+        }
+        // Check that each enum definition is pointing to the self module
+        if let Some(idx) = self.module.enum_defs().iter().position(|x| {
+            self.module.struct_handle_at(x.enum_handle).module != self.module.self_handle_idx()
+        }) {
+            return Err(verification_error(
+                StatusCode::INVALID_MODULE_HANDLE,
+                IndexKind::EnumDefinition,
+                idx as TableIndex,
+            ));
+        }
+        Ok(())
+    }
+
     fn check_function_defintions(&self) -> PartialVMResult<()> {
         // FunctionDefinition - contained FunctionHandle defines uniqueness
         if let Some(idx) =
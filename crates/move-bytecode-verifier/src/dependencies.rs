@@ -80,6 +80,15 @@ impl<'a, 'b> Context<'a, 'b> {
                     struct_def.struct_handle,
                 );
             }
+            // Module::EnumName -> def handle idx, enums share the struct handle table
+            for enum_def in module.enum_defs() {
+                let enum_handle = module.struct_handle_at(enum_def.enum_handle);
+                let enum_name = module.identifier_at(enum_handle.name);
+                context.struct_id_to_handle_map.insert(
+                    (module_id.clone(), enum_name.to_owned()),
+                    enum_def.enum_handle,
+                );
+            }
             // Module::FuncName -> def handle idx
             for func_def in module.function_defs() {
                 let func_handle = module.function_handle_at(func_def.function);
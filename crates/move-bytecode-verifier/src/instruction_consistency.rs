@@ -4,13 +4,14 @@
 
 //! This module defines the transfer functions for verifying consistency of each bytecode
 //! instruction, in particular, for the bytecode instructions that come in both generic and
-//! non-generic flavors. It also checks constraints on instructions like VecPack/VecUnpack.
+//! non-generic flavors. It also checks constraints on instructions like VecPack/VecUnpack, and
+//! that the jump tables used by VariantSwitch have exactly one entry per variant.
 
 use move_binary_format::{
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        Bytecode, CodeOffset, CodeUnit, CompiledModule, FieldHandleIndex, FunctionDefinitionIndex,
-        FunctionHandleIndex, StructDefinitionIndex, TableIndex,
+        Bytecode, CodeOffset, CodeUnit, CompiledModule, EnumDefinitionIndex, FieldHandleIndex,
+        FunctionDefinitionIndex, FunctionHandleIndex, StructDefinitionIndex, TableIndex,
     },
 };
 use move_core_types::vm_status::StatusCode;
@@ -42,6 +43,16 @@ impl<'a> InstructionConsistency<'a> {
     }
 
     fn check_instructions(&self, code: &CodeUnit) -> PartialVMResult<()> {
+        for jump_table in &code.jump_tables {
+            let enum_def = self.module.enum_def_at(jump_table.head_enum);
+            if jump_table.jump_table.len() != enum_def.variants.len() {
+                return Err(PartialVMError::new(StatusCode::INVALID_ENUM_SWITCH)
+                    .at_code_offset(self.current_function(), 0)
+                    .with_message(
+                        "Jump table must have exactly one entry per variant".to_string(),
+                    ));
+            }
+        }
         for (offset, instr) in code.code.iter().enumerate() {
             use Bytecode::*;
 
@@ -116,6 +127,21 @@ impl<'a> InstructionConsistency<'a> {
                     let struct_inst = self.module.struct_instantiation_at(*idx);
                     self.check_type_op(offset, struct_inst.def, /* generic */ true)?;
                 }
+                PackVariant(idx)
+                | UnpackVariant(idx)
+                | UnpackVariantImmRef(idx)
+                | UnpackVariantMutRef(idx) => {
+                    let handle = self.module.variant_handle_at(*idx);
+                    self.check_enum_op(offset, handle.enum_def, /* generic */ false)?;
+                }
+                PackVariantGeneric(idx)
+                | UnpackVariantGeneric(idx)
+                | UnpackVariantGenericImmRef(idx)
+                | UnpackVariantGenericMutRef(idx) => {
+                    let handle = self.module.variant_instantiation_handle_at(*idx);
+                    let enum_inst = self.module.enum_instantiation_at(handle.enum_def);
+                    self.check_enum_op(offset, enum_inst.def, /* generic */ true)?;
+                }
                 VecPack(_, num) | VecUnpack(_, num) => {
                     if *num > u16::MAX as u64 {
                         return Err(PartialVMError::new(StatusCode::CONSTRAINT_NOT_SATISFIED)
@@ -132,7 +158,8 @@ impl<'a> InstructionConsistency<'a> {
                 | WriteRef | Add | Sub | Mul | Mod | Div | BitOr | BitAnd | Xor | Shl | Shr
                 | Or | And | Not | Eq | Neq | Lt | Gt | Le | Ge | CopyLoc(_) | MoveLoc(_)
                 | StLoc(_) | MutBorrowLoc(_) | ImmBorrowLoc(_) | VecLen(_) | VecImmBorrow(_)
-                | VecMutBorrow(_) | VecPushBack(_) | VecPopBack(_) | VecSwap(_) | Abort | Nop => (),
+                | VecMutBorrow(_) | VecPushBack(_) | VecPopBack(_) | VecSwap(_) | Abort | Nop
+                | VariantSwitch(_) => (),
             }
         }
         Ok(())
@@ -175,6 +202,23 @@ impl<'a> InstructionConsistency<'a> {
         Ok(())
     }
 
+    fn check_enum_op(
+        &self,
+        offset: usize,
+        enum_def_index: EnumDefinitionIndex,
+        generic: bool,
+    ) -> PartialVMResult<()> {
+        let enum_def = self.module.enum_def_at(enum_def_index);
+        let enum_handle = self.module.struct_handle_at(enum_def.enum_handle);
+        if enum_handle.type_parameters.is_empty() == generic {
+            return Err(
+                PartialVMError::new(StatusCode::GENERIC_MEMBER_OPCODE_MISMATCH)
+                    .at_code_offset(self.current_function(), offset as CodeOffset),
+            );
+        }
+        Ok(())
+    }
+
     fn check_function_op(
         &self,
         offset: usize,
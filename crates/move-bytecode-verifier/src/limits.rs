@@ -4,6 +4,7 @@
 use move_binary_format::{
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{CompiledModule, SignatureToken, StructFieldInformation, TableIndex},
+    file_format_common::VARIANT_COUNT_MAX,
     IndexKind,
 };
 use move_core_types::{runtime_value::MoveValue, vm_status::StatusCode};
@@ -81,6 +82,13 @@ impl<'a> LimitsVerifier<'a> {
                 }
             }
         }
+        for edef in self.module.enum_defs() {
+            for variant in &edef.variants {
+                for fdef in &variant.fields {
+                    self.verify_type_node(config, &fdef.signature.0)?
+                }
+            }
+        }
         Ok(())
     }
 
@@ -145,6 +153,27 @@ impl<'a> LimitsVerifier<'a> {
                 }
             }
         }
+
+        for (idx, def) in self.module.enum_defs().iter().enumerate() {
+            if def.variants.len() > VARIANT_COUNT_MAX as usize {
+                return Err(verification_error(
+                    StatusCode::MAX_VARIANTS_REACHED,
+                    IndexKind::EnumDefinition,
+                    idx as TableIndex,
+                ));
+            }
+            if let Some(max_fields_in_struct) = config.max_fields_in_struct {
+                if def
+                    .variants
+                    .iter()
+                    .any(|variant| variant.fields.len() > max_fields_in_struct)
+                {
+                    return Err(PartialVMError::new(
+                        StatusCode::MAX_FIELD_DEFINITIONS_REACHED,
+                    ));
+                }
+            }
+        }
         Ok(())
     }
 
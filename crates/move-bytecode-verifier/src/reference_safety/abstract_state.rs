@@ -7,8 +7,8 @@ use crate::absint::{AbstractDomain, FunctionContext, JoinResult};
 use move_binary_format::{
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        CodeOffset, FieldHandleIndex, FunctionDefinitionIndex, LocalIndex, Signature,
-        SignatureToken, StructDefinitionIndex,
+        CodeOffset, EnumDefinitionIndex, FieldHandleIndex, FunctionDefinitionIndex, LocalIndex,
+        Signature, SignatureToken, StructDefinitionIndex, VariantTag,
     },
     safe_unwrap,
 };
@@ -56,6 +56,7 @@ enum Label {
     Local(LocalIndex),
     Global(StructDefinitionIndex),
     Field(FieldHandleIndex),
+    VariantField(EnumDefinitionIndex, VariantTag, usize),
 }
 
 // Needed for debugging with the borrow graph
@@ -65,6 +66,7 @@ impl std::fmt::Display for Label {
             Label::Local(i) => write!(f, "local#{}", i),
             Label::Global(i) => write!(f, "resource@{}", i),
             Label::Field(i) => write!(f, "field#{}", i),
+            Label::VariantField(e, v, i) => write!(f, "variant_field#{}#{}#{}", e, v, i),
         }
     }
 }
@@ -174,6 +176,22 @@ impl AbstractState {
             .add_strong_field_borrow((), parent, Label::Field(field), child)
     }
 
+    fn add_variant_field_borrow(
+        &mut self,
+        parent: RefID,
+        enum_def: EnumDefinitionIndex,
+        variant: VariantTag,
+        field: usize,
+        child: RefID,
+    ) {
+        self.borrow_graph.add_strong_field_borrow(
+            (),
+            parent,
+            Label::VariantField(enum_def, variant, field),
+            child,
+        )
+    }
+
     fn add_local_borrow(&mut self, local: LocalIndex, id: RefID) {
         self.borrow_graph
             .add_strong_field_borrow((), self.frame_root(), Label::Local(local), id)
@@ -449,6 +467,42 @@ impl AbstractState {
         Ok(AbstractValue::Reference(field_borrow_id))
     }
 
+    /// Unpacking a variant by reference borrows all of its fields at once, so the reference must
+    /// not be borrowed already (or mutably borrowed, in the immutable case)
+    pub fn unpack_variant_ref(
+        &mut self,
+        offset: CodeOffset,
+        mut_: bool,
+        id: RefID,
+        enum_def: EnumDefinitionIndex,
+        variant: VariantTag,
+        field_count: usize,
+    ) -> PartialVMResult<Vec<AbstractValue>> {
+        if (mut_ && !self.is_writable(id)) || (!mut_ && !self.is_readable(id, None)) {
+            return Err(self.error(StatusCode::BORROWFIELD_EXISTS_MUTABLE_BORROW_ERROR, offset));
+        }
+
+        let field_borrows = (0..field_count)
+            .map(|field| {
+                let field_borrow_id = self.new_ref(mut_);
+                self.add_variant_field_borrow(id, enum_def, variant, field, field_borrow_id);
+                AbstractValue::Reference(field_borrow_id)
+            })
+            .collect();
+        self.release(id);
+        Ok(field_borrows)
+    }
+
+    /// Switching on a variant reads the tag of the enum behind the reference
+    pub fn variant_switch(&mut self, offset: CodeOffset, id: RefID) -> PartialVMResult<()> {
+        if !self.is_readable(id, None) {
+            return Err(self.error(StatusCode::READREF_EXISTS_MUTABLE_BORROW_ERROR, offset));
+        }
+
+        self.release(id);
+        Ok(())
+    }
+
     pub fn borrow_global(
         &mut self,
         offset: CodeOffset,
@@ -21,8 +21,9 @@ use move_abstract_stack::AbstractStack;
 use move_binary_format::{
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        Bytecode, CodeOffset, FunctionDefinitionIndex, FunctionHandle, IdentifierIndex,
-        SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation,
+        Bytecode, CodeOffset, EnumDefinitionIndex, FunctionDefinitionIndex, FunctionHandle,
+        IdentifierIndex, SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation,
+        VariantTag,
     },
     safe_assert, safe_unwrap, safe_unwrap_err, CompiledModule,
 };
@@ -141,6 +142,54 @@ fn unpack(
     Ok(())
 }
 
+fn num_variant_fields(
+    verifier: &ReferenceSafetyAnalysis,
+    enum_def: EnumDefinitionIndex,
+    variant: VariantTag,
+) -> PartialVMResult<usize> {
+    let enum_def = verifier.module.enum_def_at(enum_def);
+    Ok(safe_unwrap!(enum_def.variant(variant)).fields.len())
+}
+
+fn pack_variant(
+    verifier: &mut ReferenceSafetyAnalysis,
+    enum_def: EnumDefinitionIndex,
+    variant: VariantTag,
+) -> PartialVMResult<()> {
+    for _ in 0..num_variant_fields(verifier, enum_def, variant)? {
+        safe_assert!(safe_unwrap_err!(verifier.stack.pop()).is_value())
+    }
+    verifier.push(AbstractValue::NonReference)?;
+    Ok(())
+}
+
+fn unpack_variant(
+    verifier: &mut ReferenceSafetyAnalysis,
+    enum_def: EnumDefinitionIndex,
+    variant: VariantTag,
+) -> PartialVMResult<()> {
+    safe_assert!(safe_unwrap_err!(verifier.stack.pop()).is_value());
+    let num_fields = num_variant_fields(verifier, enum_def, variant)?;
+    verifier.push_n(AbstractValue::NonReference, num_fields as u64)?;
+    Ok(())
+}
+
+fn unpack_variant_ref(
+    verifier: &mut ReferenceSafetyAnalysis,
+    state: &mut AbstractState,
+    offset: CodeOffset,
+    mut_: bool,
+    enum_def: EnumDefinitionIndex,
+    variant: VariantTag,
+) -> PartialVMResult<()> {
+    let id = safe_unwrap!(safe_unwrap_err!(verifier.stack.pop()).ref_id());
+    let num_fields = num_variant_fields(verifier, enum_def, variant)?;
+    for value in state.unpack_variant_ref(offset, mut_, id, enum_def, variant, num_fields)? {
+        verifier.push(value)?
+    }
+    Ok(())
+}
+
 fn vec_element_type(
     verifier: &mut ReferenceSafetyAnalysis,
     idx: SignatureIndex,
@@ -368,6 +417,47 @@ fn execute_inner(
             unpack(verifier, struct_def)?
         }
 
+        Bytecode::PackVariant(idx) => {
+            let handle = verifier.module.variant_handle_at(*idx);
+            pack_variant(verifier, handle.enum_def, handle.variant)?
+        }
+        Bytecode::PackVariantGeneric(idx) => {
+            let handle = verifier.module.variant_instantiation_handle_at(*idx);
+            let enum_inst = verifier.module.enum_instantiation_at(handle.enum_def);
+            pack_variant(verifier, enum_inst.def, handle.variant)?
+        }
+        Bytecode::UnpackVariant(idx) => {
+            let handle = verifier.module.variant_handle_at(*idx);
+            unpack_variant(verifier, handle.enum_def, handle.variant)?
+        }
+        Bytecode::UnpackVariantGeneric(idx) => {
+            let handle = verifier.module.variant_instantiation_handle_at(*idx);
+            let enum_inst = verifier.module.enum_instantiation_at(handle.enum_def);
+            unpack_variant(verifier, enum_inst.def, handle.variant)?
+        }
+        Bytecode::UnpackVariantImmRef(idx) | Bytecode::UnpackVariantMutRef(idx) => {
+            let mut_ = matches!(bytecode, Bytecode::UnpackVariantMutRef(_));
+            let handle = verifier.module.variant_handle_at(*idx);
+            unpack_variant_ref(
+                verifier,
+                state,
+                offset,
+                mut_,
+                handle.enum_def,
+                handle.variant,
+            )?
+        }
+        Bytecode::UnpackVariantGenericImmRef(idx) | Bytecode::UnpackVariantGenericMutRef(idx) => {
+            let mut_ = matches!(bytecode, Bytecode::UnpackVariantGenericMutRef(_));
+            let handle = verifier.module.variant_instantiation_handle_at(*idx);
+            let enum_inst = verifier.module.enum_instantiation_at(handle.enum_def);
+            unpack_variant_ref(verifier, state, offset, mut_, enum_inst.def, handle.variant)?
+        }
+        Bytecode::VariantSwitch(_) => {
+            let id = safe_unwrap!(safe_unwrap_err!(verifier.stack.pop()).ref_id());
+            state.variant_switch(offset, id)?
+        }
+
         Bytecode::VecPack(idx, num) => {
             if let Some(num_to_pop) = NonZeroU64::new(*num) {
                 let result = verifier.stack.pop_eq_n(num_to_pop);
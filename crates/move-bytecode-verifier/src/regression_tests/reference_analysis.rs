@@ -92,6 +92,7 @@ fn unbalanced_stack_crash() {
             Ret,
         ],
         locals: SignatureIndex::new(2),
+        jump_tables: vec![],
     };
     let fun_def = FunctionDefinition {
         code: Some(code_unit),
@@ -152,8 +153,13 @@ fn too_many_locals() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(0),
                 code: vec![CopyLoc(2), StLoc(33), Branch(0)],
+                jump_tables: vec![],
             }),
         }],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
 
     let res = crate::verify_module_unmetered(&module);
@@ -203,8 +209,13 @@ fn borrow_graph() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(0),
                 code: vec![MoveLoc(0), MoveLoc(1), StLoc(0), StLoc(1), Branch(0)],
+                jump_tables: vec![],
             }),
         }],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
 
     let res = crate::verify_module_unmetered(&module);
@@ -307,8 +318,13 @@ fn indirect_code() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(1),
                 code,
+                jump_tables: vec![],
             }),
         }],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
 
     let res = crate::verify_module_with_config_unmetered(&VerifierConfig::default(), &module)
@@ -4,13 +4,14 @@
 
 //! This module implements a checker for verifying signature tokens used in types of function
 //! parameters, locals, and fields of structs are well-formed. References can only occur at the
-//! top-level in all tokens.  Additionally, references cannot occur at all in field types.
+//! top-level in all tokens.  Additionally, references cannot occur at all in field types, be it
+//! fields of structs or of enum variants.
 use move_binary_format::{
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::{
-        AbilitySet, Bytecode, CodeUnit, CompiledModule, FunctionDefinition, FunctionHandle,
-        Signature, SignatureIndex, SignatureToken, StructDefinition, StructFieldInformation,
-        StructTypeParameter, TableIndex,
+        AbilitySet, Bytecode, CodeUnit, CompiledModule, EnumDefinition, FunctionDefinition,
+        FunctionHandle, Signature, SignatureIndex, SignatureToken, StructDefinition,
+        StructFieldInformation, StructTypeParameter, TableIndex,
     },
     file_format_common::VERSION_6,
     IndexKind,
@@ -36,6 +37,7 @@ impl<'a> SignatureChecker<'a> {
         sig_check.verify_signature_pool(module.signatures())?;
         sig_check.verify_function_signatures(module.function_handles())?;
         sig_check.verify_fields(module.struct_defs())?;
+        sig_check.verify_enum_fields(module.enum_defs())?;
         sig_check.verify_code_units(module.function_handles(), module.function_defs())
     }
 
@@ -95,6 +97,32 @@ impl<'a> SignatureChecker<'a> {
         Ok(())
     }
 
+    fn verify_enum_fields(&self, enum_defs: &[EnumDefinition]) -> PartialVMResult<()> {
+        for (enum_def_idx, enum_def) in enum_defs.iter().enumerate() {
+            let enum_handle = self.module.struct_handle_at(enum_def.enum_handle);
+            let type_param_constraints: Vec<_> = enum_handle.type_param_constraints().collect();
+            let err_handler = |err: PartialVMError, idx| {
+                err.at_index(IndexKind::FieldDefinition, idx as TableIndex)
+                    .at_index(IndexKind::EnumDefinition, enum_def_idx as TableIndex)
+            };
+            for variant in &enum_def.variants {
+                for (field_offset, field_def) in variant.fields.iter().enumerate() {
+                    self.check_signature_token(&field_def.signature.0)
+                        .map_err(|err| err_handler(err, field_offset))?;
+                    self.check_type_instantiation(&field_def.signature.0, &type_param_constraints)
+                        .map_err(|err| err_handler(err, field_offset))?;
+                    self.check_phantom_params(
+                        &field_def.signature.0,
+                        false,
+                        &enum_handle.type_parameters,
+                    )
+                    .map_err(|err| err_handler(err, field_offset))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn verify_code_units(
         &mut self,
         function_handles: &[FunctionHandle],
@@ -169,6 +197,22 @@ impl<'a> SignatureChecker<'a> {
                         type_parameters,
                     )
                 }
+                PackVariantGeneric(idx)
+                | UnpackVariantGeneric(idx)
+                | UnpackVariantGenericImmRef(idx)
+                | UnpackVariantGenericMutRef(idx) => {
+                    let handle = self.module.variant_instantiation_handle_at(*idx);
+                    let enum_inst = self.module.enum_instantiation_at(handle.enum_def);
+                    let enum_def = self.module.enum_def_at(enum_inst.def);
+                    let enum_handle = self.module.struct_handle_at(enum_def.enum_handle);
+                    let type_arguments = &self.module.signature_at(enum_inst.type_parameters).0;
+                    self.check_signature_tokens(type_arguments)?;
+                    self.check_generic_instance(
+                        type_arguments,
+                        enum_handle.type_param_constraints(),
+                        type_parameters,
+                    )
+                }
                 VecPack(idx, _)
                 | VecLen(idx)
                 | VecImmBorrow(idx)
@@ -249,6 +293,11 @@ impl<'a> SignatureChecker<'a> {
                 | ExistsDeprecated(_)
                 | MoveToDeprecated(_)
                 | MoveFromDeprecated(_)
+                | PackVariant(_)
+                | UnpackVariant(_)
+                | UnpackVariantImmRef(_)
+                | UnpackVariantMutRef(_)
+                | VariantSwitch(_)
                 | Abort
                 | Nop => Ok(()),
             };
@@ -13,8 +13,11 @@ use crate::absint::FunctionContext;
 use move_binary_format::{
     control_flow_graph::{BlockId, ControlFlowGraph},
     errors::{PartialVMError, PartialVMResult},
-    file_format::{Bytecode, CodeUnit, FunctionDefinitionIndex, Signature, StructFieldInformation},
-    CompiledModule,
+    file_format::{
+        Bytecode, CodeUnit, EnumDefinitionIndex, FunctionDefinitionIndex, Signature,
+        StructFieldInformation, VariantTag,
+    },
+    safe_unwrap, CompiledModule,
 };
 use move_bytecode_verifier_meter::Meter;
 use move_core_types::vm_status::StatusCode;
@@ -122,7 +125,8 @@ impl<'a> StackUsageVerifier<'a> {
             | Bytecode::BrTrue(_)
             | Bytecode::BrFalse(_)
             | Bytecode::StLoc(_)
-            | Bytecode::Abort => (1, 0),
+            | Bytecode::Abort
+            | Bytecode::VariantSwitch(_) => (1, 0),
 
             // Instructions that push, but don't pop
             Bytecode::LdU8(_)
@@ -265,9 +269,48 @@ impl<'a> StackUsageVerifier<'a> {
                 };
                 (1, field_count as u64)
             }
+
+            // PackVariant performs `num_fields` pops and one push
+            Bytecode::PackVariant(idx) => {
+                let handle = self.module.variant_handle_at(*idx);
+                let field_count = self.variant_field_count(handle.enum_def, handle.variant)?;
+                (field_count, 1)
+            }
+            Bytecode::PackVariantGeneric(idx) => {
+                let handle = self.module.variant_instantiation_handle_at(*idx);
+                let enum_inst = self.module.enum_instantiation_at(handle.enum_def);
+                let field_count = self.variant_field_count(enum_inst.def, handle.variant)?;
+                (field_count, 1)
+            }
+
+            // UnpackVariant, by value or by reference, performs one pop and `num_fields` pushes
+            Bytecode::UnpackVariant(idx)
+            | Bytecode::UnpackVariantImmRef(idx)
+            | Bytecode::UnpackVariantMutRef(idx) => {
+                let handle = self.module.variant_handle_at(*idx);
+                let field_count = self.variant_field_count(handle.enum_def, handle.variant)?;
+                (1, field_count)
+            }
+            Bytecode::UnpackVariantGeneric(idx)
+            | Bytecode::UnpackVariantGenericImmRef(idx)
+            | Bytecode::UnpackVariantGenericMutRef(idx) => {
+                let handle = self.module.variant_instantiation_handle_at(*idx);
+                let enum_inst = self.module.enum_instantiation_at(handle.enum_def);
+                let field_count = self.variant_field_count(enum_inst.def, handle.variant)?;
+                (1, field_count)
+            }
         })
     }
 
+    fn variant_field_count(
+        &self,
+        enum_def: EnumDefinitionIndex,
+        variant: VariantTag,
+    ) -> PartialVMResult<u64> {
+        let enum_def = self.module.enum_def_at(enum_def);
+        Ok(safe_unwrap!(enum_def.variant(variant)).fields.len() as u64)
+    }
+
     fn current_function(&self) -> FunctionDefinitionIndex {
         self.current_function.unwrap_or(FunctionDefinitionIndex(0))
     }
@@ -13,12 +13,12 @@ use move_binary_format::{
     control_flow_graph::ControlFlowGraph,
     errors::{PartialVMError, PartialVMResult},
     file_format::{
-        AbilitySet, Bytecode, CodeOffset, CompiledModule, FieldHandleIndex,
+        AbilitySet, Bytecode, CodeOffset, CompiledModule, EnumDefinition, FieldHandleIndex,
         FunctionDefinitionIndex, FunctionHandle, LocalIndex, Signature, SignatureToken,
         SignatureToken as ST, StructDefinition, StructDefinitionIndex, StructFieldInformation,
-        StructHandleIndex,
+        StructHandleIndex, VariantTag,
     },
-    safe_unwrap_err,
+    safe_unwrap, safe_unwrap_err,
 };
 use move_bytecode_verifier_meter::{Meter, Scope};
 use move_core_types::vm_status::StatusCode;
@@ -350,6 +350,95 @@ fn unpack(
     Ok(())
 }
 
+fn variant_fields_signature(
+    enum_def: &EnumDefinition,
+    variant: VariantTag,
+    type_args: &Signature,
+) -> PartialVMResult<Signature> {
+    let variant_def = safe_unwrap!(enum_def.variant(variant));
+    Ok(Signature(
+        variant_def
+            .fields
+            .iter()
+            .map(|field_def| instantiate(&field_def.signature.0, type_args))
+            .collect(),
+    ))
+}
+
+fn pack_variant(
+    verifier: &mut TypeSafetyChecker,
+    meter: &mut (impl Meter + ?Sized),
+    offset: CodeOffset,
+    enum_def: &EnumDefinition,
+    variant: VariantTag,
+    type_args: &Signature,
+) -> PartialVMResult<()> {
+    let enum_type = materialize_type(enum_def.enum_handle, type_args);
+    let field_sig = variant_fields_signature(enum_def, variant, type_args)?;
+    for sig in field_sig.0.iter().rev() {
+        let arg = safe_unwrap_err!(verifier.stack.pop());
+        if &arg != sig {
+            return Err(verifier.error(StatusCode::PACK_TYPE_MISMATCH_ERROR, offset));
+        }
+    }
+
+    verifier.push(meter, enum_type)?;
+    Ok(())
+}
+
+fn unpack_variant(
+    verifier: &mut TypeSafetyChecker,
+    meter: &mut (impl Meter + ?Sized),
+    offset: CodeOffset,
+    enum_def: &EnumDefinition,
+    variant: VariantTag,
+    type_args: &Signature,
+) -> PartialVMResult<()> {
+    let enum_type = materialize_type(enum_def.enum_handle, type_args);
+
+    let arg = safe_unwrap_err!(verifier.stack.pop());
+    if arg != enum_type {
+        return Err(verifier.error(StatusCode::UNPACK_TYPE_MISMATCH_ERROR, offset));
+    }
+
+    let field_sig = variant_fields_signature(enum_def, variant, type_args)?;
+    for sig in field_sig.0 {
+        verifier.push(meter, sig)?
+    }
+    Ok(())
+}
+
+// helper for both `UnpackVariantImmRef` and `UnpackVariantMutRef`, and their generic versions
+fn unpack_variant_ref(
+    verifier: &mut TypeSafetyChecker,
+    meter: &mut (impl Meter + ?Sized),
+    offset: CodeOffset,
+    mut_: bool,
+    enum_def: &EnumDefinition,
+    variant: VariantTag,
+    type_args: &Signature,
+) -> PartialVMResult<()> {
+    let enum_type = materialize_type(enum_def.enum_handle, type_args);
+
+    let operand = safe_unwrap_err!(verifier.stack.pop());
+    match operand {
+        ST::MutableReference(inner) if *inner == enum_type => (),
+        ST::Reference(inner) if !mut_ && *inner == enum_type => (),
+        _ => return Err(verifier.error(StatusCode::UNPACK_TYPE_MISMATCH_ERROR, offset)),
+    }
+
+    let field_sig = variant_fields_signature(enum_def, variant, type_args)?;
+    for sig in field_sig.0 {
+        let field_ref = if mut_ {
+            ST::MutableReference(Box::new(sig))
+        } else {
+            ST::Reference(Box::new(sig))
+        };
+        verifier.push(meter, field_ref)?
+    }
+    Ok(())
+}
+
 fn exists(
     verifier: &mut TypeSafetyChecker,
     meter: &mut (impl Meter + ?Sized),
@@ -654,6 +743,106 @@ fn verify_instr(
             unpack(verifier, meter, offset, struct_def, type_args)?
         }
 
+        Bytecode::PackVariant(idx) => {
+            let handle = verifier.module.variant_handle_at(*idx);
+            let enum_def = verifier.module.enum_def_at(handle.enum_def);
+            pack_variant(
+                verifier,
+                meter,
+                offset,
+                enum_def,
+                handle.variant,
+                &Signature(vec![]),
+            )?
+        }
+
+        Bytecode::PackVariantGeneric(idx) => {
+            let handle = verifier.module.variant_instantiation_handle_at(*idx);
+            let enum_inst = verifier.module.enum_instantiation_at(handle.enum_def);
+            let enum_def = verifier.module.enum_def_at(enum_inst.def);
+            let type_args = verifier.module.signature_at(enum_inst.type_parameters);
+            verifier.charge_tys(meter, &type_args.0)?;
+            pack_variant(verifier, meter, offset, enum_def, handle.variant, type_args)?
+        }
+
+        Bytecode::UnpackVariant(idx) => {
+            let handle = verifier.module.variant_handle_at(*idx);
+            let enum_def = verifier.module.enum_def_at(handle.enum_def);
+            unpack_variant(
+                verifier,
+                meter,
+                offset,
+                enum_def,
+                handle.variant,
+                &Signature(vec![]),
+            )?
+        }
+
+        Bytecode::UnpackVariantGeneric(idx) => {
+            let handle = verifier.module.variant_instantiation_handle_at(*idx);
+            let enum_inst = verifier.module.enum_instantiation_at(handle.enum_def);
+            let enum_def = verifier.module.enum_def_at(enum_inst.def);
+            let type_args = verifier.module.signature_at(enum_inst.type_parameters);
+            verifier.charge_tys(meter, &type_args.0)?;
+            unpack_variant(verifier, meter, offset, enum_def, handle.variant, type_args)?
+        }
+
+        Bytecode::UnpackVariantImmRef(idx) | Bytecode::UnpackVariantMutRef(idx) => {
+            let mut_ = matches!(bytecode, Bytecode::UnpackVariantMutRef(_));
+            let handle = verifier.module.variant_handle_at(*idx);
+            let enum_def = verifier.module.enum_def_at(handle.enum_def);
+            unpack_variant_ref(
+                verifier,
+                meter,
+                offset,
+                mut_,
+                enum_def,
+                handle.variant,
+                &Signature(vec![]),
+            )?
+        }
+
+        Bytecode::UnpackVariantGenericImmRef(idx) | Bytecode::UnpackVariantGenericMutRef(idx) => {
+            let mut_ = matches!(bytecode, Bytecode::UnpackVariantGenericMutRef(_));
+            let handle = verifier.module.variant_instantiation_handle_at(*idx);
+            let enum_inst = verifier.module.enum_instantiation_at(handle.enum_def);
+            let enum_def = verifier.module.enum_def_at(enum_inst.def);
+            let type_args = verifier.module.signature_at(enum_inst.type_parameters);
+            verifier.charge_tys(meter, &type_args.0)?;
+            unpack_variant_ref(
+                verifier,
+                meter,
+                offset,
+                mut_,
+                enum_def,
+                handle.variant,
+                type_args,
+            )?
+        }
+
+        Bytecode::VariantSwitch(jt_idx) => {
+            let jump_table = safe_unwrap!(verifier
+                .function_context
+                .code()
+                .jump_tables
+                .get(jt_idx.0 as usize));
+            let enum_def = verifier.module.enum_def_at(jump_table.head_enum);
+            let operand = safe_unwrap_err!(verifier.stack.pop());
+            let handle = match &operand {
+                ST::Reference(inner) | ST::MutableReference(inner) => match inner.as_ref() {
+                    ST::Struct(handle) => Some(*handle),
+                    ST::StructInstantiation(inst) => Some(inst.0),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match handle {
+                Some(handle) if handle == enum_def.enum_handle => (),
+                Some(_) => return Err(verifier.error(StatusCode::ENUM_TYPE_MISMATCH, offset)),
+                None => return Err(verifier.error(StatusCode::ENUM_SWITCH_BAD_OPERAND, offset)),
+            }
+        }
+
         Bytecode::ReadRef => {
             let operand = safe_unwrap_err!(verifier.stack.pop());
             match operand {
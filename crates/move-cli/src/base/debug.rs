@@ -0,0 +1,735 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    reroot_path,
+    test::{run_move_unit_tests, UnitTestResult},
+};
+use crate::{
+    sandbox::{self, utils::PackageContext},
+    NativeFunctionRecord, DEFAULT_STORAGE_DIR,
+};
+use anyhow::{anyhow, bail, Result};
+use clap::*;
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_command_line_common::files::FileHash;
+use move_core_types::{
+    annotated_value::MoveValue, errmap::ErrorMapping, language_storage::ModuleId, parser,
+};
+use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig};
+use move_unit_test::UnitTestingConfig;
+use move_vm_runtime::debug::{set_debugger, DebugFrame, Debugger};
+use move_vm_test_utils::gas_schedule::CostTable;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{channel, Receiver},
+        Arc, Mutex,
+    },
+};
+
+/// The only thread reported to the client: Move code is executed on a single thread.
+const THREAD_ID: u64 = 1;
+
+/// Debug unit tests and sandbox runs of this package with a Debug Adapter Protocol client, such
+/// as VS Code.
+#[derive(Parser)]
+#[clap(name = "debug")]
+pub struct Debug {
+    /// Port to serve the Debug Adapter Protocol on.
+    #[clap(long = "port", default_value = "4711")]
+    pub port: u16,
+}
+
+/// Arguments of the `launch` request, selecting what to debug.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchArguments {
+    /// Stop before the first instruction with a source location.
+    #[serde(default)]
+    stop_on_entry: bool,
+    #[serde(flatten)]
+    program: Program,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase")]
+enum Program {
+    /// Run the unit tests of the package, or the ones whose name contains `filter`.
+    Test {
+        #[serde(default)]
+        filter: Option<String>,
+    },
+    /// Run a function of a module in storage, like `move sandbox run`.
+    #[serde(rename_all = "camelCase")]
+    Run {
+        module: PathBuf,
+        function: String,
+        #[serde(default)]
+        signers: Vec<String>,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        type_args: Vec<String>,
+        #[serde(default)]
+        storage_dir: Option<PathBuf>,
+        #[serde(default)]
+        dry_run: bool,
+    },
+}
+
+/// How execution proceeds until it stops next, besides stopping at breakpoints.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StepMode {
+    Continue,
+    /// Stop at the first instruction on another line or in another frame.
+    StepIn {
+        depth: usize,
+        line: Option<Line>,
+    },
+    /// Stop at the first instruction on another line of the frame, or in a caller.
+    Next {
+        depth: usize,
+        line: Option<Line>,
+    },
+    /// Stop at the first instruction in a caller.
+    StepOut {
+        depth: usize,
+    },
+    /// The client disconnected: never stop again.
+    Disconnected,
+}
+
+/// A line of a source file, identified by its index in `SourceIndex::files`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+struct Line {
+    file: usize,
+    line: u64,
+}
+
+struct SourceFile {
+    path: PathBuf,
+    line_starts: Vec<usize>,
+}
+
+/// Maps code offsets of the modules of a package to lines of its source files.
+struct SourceIndex {
+    files: Vec<SourceFile>,
+    file_hashes: BTreeMap<FileHash, usize>,
+    source_maps: BTreeMap<ModuleId, SourceMap>,
+    /// The lines each file has code on, where breakpoints can be set.
+    code_lines: BTreeSet<Line>,
+}
+
+/// The state shared by the thread serving the client and the thread executing Move code.
+struct Control {
+    mode: StepMode,
+    breakpoints: BTreeSet<Line>,
+    /// The frames of the call stack, innermost first, while execution is stopped.
+    frames: Vec<DebugFrame>,
+    /// Values whose fields or elements can be inspected, indexed by their `variablesReference`
+    /// (minus one), while execution is stopped.
+    variables: Vec<Vec<(String, MoveValue)>>,
+}
+
+/// Sends responses and events to the client.
+struct Client {
+    stream: TcpStream,
+    seq: u64,
+}
+
+/// The `Debugger` installed in the VM while a program is debugged.
+struct DapDebugger {
+    sources: Arc<SourceIndex>,
+    control: Arc<Mutex<Control>>,
+    client: Arc<Mutex<Client>>,
+    resume: Receiver<()>,
+    /// The frame depth and line of the previous instruction, to stop only once per line.
+    previous: Option<(usize, Option<Line>)>,
+    reason: &'static str,
+}
+
+impl Debug {
+    pub fn execute(
+        self,
+        path: Option<PathBuf>,
+        config: BuildConfig,
+        natives: Vec<NativeFunctionRecord>,
+        cost_table: &CostTable,
+        error_descriptions: &ErrorMapping,
+    ) -> anyhow::Result<()> {
+        let rerooted_path = reroot_path(path)?;
+        let listener = TcpListener::bind(("127.0.0.1", self.port))?;
+        println!("Serving the Debug Adapter Protocol on port {}", self.port);
+        let (stream, _) = listener.accept()?;
+        let session = Session {
+            path: rerooted_path,
+            config,
+            natives,
+            cost_table,
+            error_descriptions,
+        };
+        session.serve(stream)
+    }
+}
+
+struct Session<'a> {
+    path: PathBuf,
+    config: BuildConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: &'a CostTable,
+    error_descriptions: &'a ErrorMapping,
+}
+
+impl Session<'_> {
+    fn serve(self, stream: TcpStream) -> Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let client = Arc::new(Mutex::new(Client { stream, seq: 0 }));
+        let control = Arc::new(Mutex::new(Control {
+            mode: StepMode::Continue,
+            breakpoints: BTreeSet::new(),
+            frames: vec![],
+            variables: vec![],
+        }));
+        let (resume_sender, resume) = channel();
+        let mut resume = Some(resume);
+        let mut launch = None;
+        let mut sources = None;
+        let mut configuration_done = false;
+
+        std::thread::scope(|scope| -> Result<()> {
+            let mut debuggee = None;
+            while let Some(request) = read_message(&mut reader)? {
+                let command = request["command"].as_str().unwrap_or_default().to_owned();
+                let arguments = &request["arguments"];
+                let mut body = json!({});
+                let result: Result<()> = (|| {
+                    match command.as_str() {
+                        "initialize" => {
+                            body = json!({ "supportsConfigurationDoneRequest": true });
+                        }
+                        "launch" => {
+                            let args: LaunchArguments = serde_json::from_value(arguments.clone())?;
+                            sources = Some(Arc::new(self.source_index(&args.program)?));
+                            launch = Some(args);
+                        }
+                        "configurationDone" => configuration_done = true,
+                        "setBreakpoints" => {
+                            let sources = sources
+                                .as_ref()
+                                .ok_or_else(|| anyhow!("Breakpoints must be set after launch"))?;
+                            body = set_breakpoints(sources, &control, arguments)?;
+                        }
+                        "threads" => {
+                            body = json!({ "threads": [{ "id": THREAD_ID, "name": "main" }] });
+                        }
+                        "stackTrace" => {
+                            let sources = sources.as_ref().unwrap();
+                            body = stack_trace(sources, &control.lock().unwrap());
+                        }
+                        "scopes" => {
+                            let frame = arguments["frameId"].as_u64().unwrap_or_default();
+                            let sources = sources.as_ref().unwrap();
+                            body = scopes(sources, &mut control.lock().unwrap(), frame as usize)?;
+                        }
+                        "variables" => {
+                            let reference =
+                                arguments["variablesReference"].as_u64().unwrap_or_default();
+                            body = variables(&mut control.lock().unwrap(), reference as usize)?;
+                        }
+                        "continue" | "next" | "stepIn" | "stepOut" => {
+                            let mut control = control.lock().unwrap();
+                            let depth = control.frames.len();
+                            let line = control.frames.first().and_then(|frame| {
+                                sources.as_ref().unwrap().line(
+                                    &frame.module,
+                                    frame.function,
+                                    frame.pc,
+                                )
+                            });
+                            control.mode = match command.as_str() {
+                                "next" => StepMode::Next { depth, line },
+                                "stepIn" => StepMode::StepIn { depth, line },
+                                "stepOut" => StepMode::StepOut { depth },
+                                _ => StepMode::Continue,
+                            };
+                            control.frames.clear();
+                            control.variables.clear();
+                            if command == "continue" {
+                                body = json!({ "allThreadsContinued": true });
+                            }
+                            resume_sender.send(()).ok();
+                        }
+                        "disconnect" | "terminate" => {
+                            control.lock().unwrap().mode = StepMode::Disconnected;
+                            resume_sender.send(()).ok();
+                        }
+                        _ => bail!("Unsupported request '{}'", command),
+                    }
+                    Ok(())
+                })();
+                client.lock().unwrap().respond(&request, result, body)?;
+                if command == "initialize" {
+                    client
+                        .lock()
+                        .unwrap()
+                        .send_event("initialized", json!({}))?;
+                }
+                if command == "disconnect" {
+                    break;
+                }
+
+                if debuggee.is_none() && configuration_done {
+                    if let (Some(args), Some(sources)) = (launch.take(), &sources) {
+                        if args.stop_on_entry {
+                            control.lock().unwrap().mode = StepMode::StepIn {
+                                depth: 0,
+                                line: None,
+                            };
+                        }
+                        set_debugger(Some(Box::new(DapDebugger {
+                            sources: sources.clone(),
+                            control: control.clone(),
+                            client: client.clone(),
+                            resume: resume.take().unwrap(),
+                            previous: None,
+                            reason: "step",
+                        })));
+                        let session = &self;
+                        let client = client.clone();
+                        debuggee = Some(scope.spawn(move || {
+                            let result = session.run(args.program);
+                            set_debugger(None);
+                            let mut client = client.lock().unwrap();
+                            let (exit_code, output) = match result {
+                                Ok((success, output)) => (if success { 0 } else { 1 }, output),
+                                Err(e) => (1, format!("{:#}\n", e)),
+                            };
+                            client
+                                .send_event(
+                                    "output",
+                                    json!({ "category": "stdout", "output": output }),
+                                )
+                                .and_then(|_| {
+                                    client.send_event("exited", json!({ "exitCode": exit_code }))
+                                })
+                                .and_then(|_| client.send_event("terminated", json!({})))
+                                .ok();
+                        }));
+                    }
+                }
+            }
+            // Let the program run to completion if the client went away
+            control.lock().unwrap().mode = StepMode::Disconnected;
+            resume_sender.send(()).ok();
+            if let Some(debuggee) = debuggee {
+                debuggee.join().unwrap();
+            }
+            Ok(())
+        })
+    }
+
+    /// Build the package the way `program` is run, and index the source locations of its code.
+    fn source_index(&self, program: &Program) -> Result<SourceIndex> {
+        let mut config = self.config.clone();
+        if let Program::Test { .. } = program {
+            config.test_mode = true;
+            config.dev_mode = true;
+        }
+        let package = config.compile_package(&self.path, &mut Vec::new())?;
+        SourceIndex::new(&package)
+    }
+
+    /// Run `program`, returning whether it succeeded and the output to show to the client.
+    fn run(&self, program: Program) -> Result<(bool, String)> {
+        match program {
+            Program::Test { filter } => {
+                let mut output = vec![];
+                let unit_test_config = UnitTestingConfig {
+                    filter,
+                    num_threads: 1,
+                    ..UnitTestingConfig::default_with_bound(None)
+                };
+                let (result, _) = run_move_unit_tests(
+                    &self.path,
+                    self.config.clone(),
+                    unit_test_config,
+                    self.natives.clone(),
+                    Some(self.cost_table.clone()),
+                    false,
+                    &mut output,
+                )?;
+                Ok((
+                    result == UnitTestResult::Success,
+                    String::from_utf8_lossy(&output).into_owned(),
+                ))
+            }
+            Program::Run {
+                module,
+                function,
+                signers,
+                args,
+                type_args,
+                storage_dir,
+                dry_run,
+            } => {
+                let txn_args = args
+                    .iter()
+                    .map(|arg| parser::parse_transaction_argument(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                let type_args = type_args
+                    .iter()
+                    .map(|ty| parser::parse_type_tag(ty))
+                    .collect::<Result<Vec<_>>>()?;
+                let storage_dir = storage_dir.unwrap_or_else(|| PathBuf::from(DEFAULT_STORAGE_DIR));
+                let context = PackageContext::new(&Some(self.path.clone()), &self.config)?;
+                let state = context.prepare_state(&storage_dir)?;
+                sandbox::commands::run(
+                    self.natives.clone(),
+                    self.cost_table,
+                    self.error_descriptions,
+                    &state,
+                    context.package(),
+                    &module,
+                    &function,
+                    &signers,
+                    &txn_args,
+                    type_args,
+                    None,
+                    dry_run,
+                    None,
+                    false,
+                )?;
+                Ok((true, String::new()))
+            }
+        }
+    }
+}
+
+impl SourceIndex {
+    fn new(package: &CompiledPackage) -> Result<Self> {
+        let mut index = SourceIndex {
+            files: vec![],
+            file_hashes: BTreeMap::new(),
+            source_maps: BTreeMap::new(),
+            code_lines: BTreeSet::new(),
+        };
+        for unit in package.all_modules() {
+            let contents = std::fs::read_to_string(&unit.source_path)?;
+            let file_hash = FileHash::new(&contents);
+            if !index.file_hashes.contains_key(&file_hash) {
+                let line_starts = std::iter::once(0)
+                    .chain(contents.match_indices('\n').map(|(i, _)| i + 1))
+                    .collect();
+                index.file_hashes.insert(file_hash, index.files.len());
+                index.files.push(SourceFile {
+                    path: unit.source_path.canonicalize()?,
+                    line_starts,
+                });
+            }
+            let module = &unit.unit.module;
+            index
+                .source_maps
+                .insert(module.self_id(), unit.unit.source_map.clone());
+        }
+        for unit in package.all_modules() {
+            let module = &unit.unit.module;
+            for (idx, fdef) in module.function_defs().iter().enumerate() {
+                let code_len = fdef.code.as_ref().map_or(0, |code| code.code.len());
+                for pc in 0..code_len {
+                    let function = FunctionDefinitionIndex(idx as u16);
+                    if let Some(line) = index.line(&module.self_id(), function, pc as u16) {
+                        index.code_lines.insert(line);
+                    }
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    /// The source line of the instruction at `pc` in `function` of `module`, if it is known.
+    fn line(&self, module: &ModuleId, function: FunctionDefinitionIndex, pc: u16) -> Option<Line> {
+        let loc = self
+            .source_maps
+            .get(module)?
+            .get_code_location(function, pc)
+            .ok()?;
+        let file = *self.file_hashes.get(&loc.file_hash())?;
+        let line_starts = &self.files[file].line_starts;
+        let line = line_starts.partition_point(|start| *start <= loc.start() as usize);
+        Some(Line {
+            file,
+            line: line as u64,
+        })
+    }
+
+    fn file(&self, path: &Path) -> Option<usize> {
+        let path = path.canonicalize().ok()?;
+        self.files.iter().position(|file| file.path == path)
+    }
+
+    fn source(&self, file: usize) -> Value {
+        let path = &self.files[file].path;
+        json!({
+            "name": path.file_name().map(|name| name.to_string_lossy()),
+            "path": path,
+        })
+    }
+
+    /// The names of the locals of `function` in `module`, as written in the source.
+    fn local_names(&self, module: &ModuleId, function: FunctionDefinitionIndex) -> Vec<String> {
+        let Some(function_map) = self
+            .source_maps
+            .get(module)
+            .and_then(|source_map| source_map.get_function_source_map(function).ok())
+        else {
+            return vec![];
+        };
+        function_map
+            .parameters
+            .iter()
+            .chain(&function_map.locals)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+impl Debugger for DapDebugger {
+    fn should_stop(
+        &mut self,
+        module: &ModuleId,
+        function: FunctionDefinitionIndex,
+        pc: u16,
+        depth: usize,
+    ) -> bool {
+        let line = self.sources.line(module, function, pc);
+        let previous = self.previous.replace((depth, line));
+        if line.is_none() || previous == Some((depth, line)) {
+            return false;
+        }
+        let control = self.control.lock().unwrap();
+        let (stop, reason) = match control.mode {
+            StepMode::Disconnected => return false,
+            _ if control.breakpoints.contains(&line.unwrap()) => (true, "breakpoint"),
+            StepMode::Continue => (false, ""),
+            StepMode::StepIn {
+                depth: from_depth,
+                line: from_line,
+            } => (depth != from_depth || line != from_line, "step"),
+            StepMode::Next {
+                depth: from_depth,
+                line: from_line,
+            } => (
+                depth < from_depth || (depth == from_depth && line != from_line),
+                "step",
+            ),
+            StepMode::StepOut { depth: from_depth } => (depth < from_depth, "step"),
+        };
+        self.reason = reason;
+        stop
+    }
+
+    fn stopped(&mut self, mut frames: Vec<DebugFrame>) {
+        frames.reverse();
+        self.control.lock().unwrap().frames = frames;
+        let sent = self.client.lock().unwrap().send_event(
+            "stopped",
+            json!({ "reason": self.reason, "threadId": THREAD_ID, "allThreadsStopped": true }),
+        );
+        if sent.is_err() || self.resume.recv().is_err() {
+            self.control.lock().unwrap().mode = StepMode::Disconnected;
+        }
+    }
+}
+
+impl Client {
+    fn send(&mut self, mut message: Value) -> Result<()> {
+        self.seq += 1;
+        message["seq"] = json!(self.seq);
+        let content = serde_json::to_string(&message)?;
+        write!(
+            self.stream,
+            "Content-Length: {}\r\n\r\n{}",
+            content.len(),
+            content
+        )?;
+        Ok(self.stream.flush()?)
+    }
+
+    fn send_event(&mut self, event: &str, body: Value) -> Result<()> {
+        self.send(json!({ "type": "event", "event": event, "body": body }))
+    }
+
+    fn respond(&mut self, request: &Value, result: Result<()>, body: Value) -> Result<()> {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": result.is_ok(),
+            "body": body,
+        });
+        if let Err(e) = result {
+            response["message"] = json!(e.to_string());
+        }
+        self.send(response)
+    }
+}
+
+/// Read a message of the Debug Adapter Protocol, or `None` at the end of the stream.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(length) = header.strip_prefix("Content-Length:") {
+            content_length = Some(length.trim().parse::<usize>()?);
+        }
+    }
+    let Some(content_length) = content_length else {
+        bail!("Missing Content-Length header")
+    };
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+fn set_breakpoints(
+    sources: &SourceIndex,
+    control: &Mutex<Control>,
+    arguments: &Value,
+) -> Result<Value> {
+    let path = arguments["source"]["path"]
+        .as_str()
+        .ok_or_else(|| anyhow!("Missing source path"))?;
+    let file = sources.file(Path::new(path));
+    let lines: Vec<u64> = arguments["breakpoints"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|breakpoint| breakpoint["line"].as_u64())
+        .collect();
+
+    let mut control = control.lock().unwrap();
+    if let Some(file) = file {
+        control.breakpoints.retain(|line| line.file != file);
+    }
+    let breakpoints: Vec<_> = lines
+        .into_iter()
+        .map(|line| {
+            let verified = file.is_some_and(|file| {
+                let line = Line { file, line };
+                let verified = sources.code_lines.contains(&line);
+                if verified {
+                    control.breakpoints.insert(line);
+                }
+                verified
+            });
+            json!({ "verified": verified, "line": line })
+        })
+        .collect();
+    Ok(json!({ "breakpoints": breakpoints }))
+}
+
+fn stack_trace(sources: &SourceIndex, control: &Control) -> Value {
+    let frames: Vec<_> = control
+        .frames
+        .iter()
+        .enumerate()
+        .map(|(id, frame)| {
+            let mut stack_frame = json!({
+                "id": id,
+                "name": format!("{}::{}", frame.module, frame.function_name),
+                "line": 0,
+                "column": 0,
+            });
+            if let Some(line) = sources.line(&frame.module, frame.function, frame.pc) {
+                stack_frame["source"] = sources.source(line.file);
+                stack_frame["line"] = json!(line.line);
+                stack_frame["column"] = json!(1);
+            }
+            stack_frame
+        })
+        .collect();
+    json!({ "stackFrames": frames, "totalFrames": control.frames.len() })
+}
+
+fn scopes(sources: &SourceIndex, control: &mut Control, frame: usize) -> Result<Value> {
+    let frame = control
+        .frames
+        .get(frame)
+        .ok_or_else(|| anyhow!("Unknown frame {}", frame))?;
+    let locals = sources
+        .local_names(&frame.module, frame.function)
+        .into_iter()
+        .zip(&frame.locals)
+        // Temporaries introduced by the compiler are not shown
+        .filter(|(name, _)| !name.starts_with(['%', '$']))
+        .filter_map(|(name, value)| {
+            let name = name.split('#').next().unwrap_or_default().to_owned();
+            Some((name, value.clone()?))
+        })
+        .collect();
+    control.variables.push(locals);
+    Ok(json!({
+        "scopes": [{
+            "name": "Locals",
+            "variablesReference": control.variables.len(),
+            "expensive": false,
+        }]
+    }))
+}
+
+fn variables(control: &mut Control, reference: usize) -> Result<Value> {
+    let values = reference
+        .checked_sub(1)
+        .and_then(|idx| control.variables.get(idx))
+        .ok_or_else(|| anyhow!("Unknown variables reference {}", reference))?
+        .clone();
+    let variables: Vec<_> = values
+        .into_iter()
+        .map(|(name, value)| {
+            let children: Vec<(String, MoveValue)> = match &value {
+                MoveValue::Struct(s) => s
+                    .fields
+                    .iter()
+                    .map(|(f, v)| (f.to_string(), v.clone()))
+                    .collect(),
+                MoveValue::Variant(v) => v
+                    .fields
+                    .iter()
+                    .map(|(f, v)| (f.to_string(), v.clone()))
+                    .collect(),
+                MoveValue::Vector(elems) => elems
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (i.to_string(), v.clone()))
+                    .collect(),
+                _ => vec![],
+            };
+            let reference = if children.is_empty() {
+                0
+            } else {
+                control.variables.push(children);
+                control.variables.len()
+            };
+            json!({
+                "name": name,
+                "value": value.to_string(),
+                "variablesReference": reference,
+            })
+        })
+        .collect();
+    Ok(json!({ "variables": variables }))
+}
@@ -0,0 +1,119 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use super::reroot_path;
+use anyhow::bail;
+use clap::*;
+use move_command_line_common::files::{find_move_filenames, FileHash};
+use move_compiler::{
+    diagnostics::{report_diagnostics_to_buffer_with_format, FilesSourceText},
+    formatter::format_string,
+};
+use move_package::{
+    source_package::{layout::SourcePackageLayout, manifest_parser::parse_move_manifest_from_file},
+    BuildConfig,
+};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Format the Move source files of the package at `path`. If no path is provided defaults to
+/// current directory.
+#[derive(Parser)]
+#[clap(name = "fmt")]
+pub struct Fmt {
+    /// Do not write the files, exit with an error if any of them is not formatted
+    #[clap(long = "check")]
+    pub check: bool,
+
+    /// Files to format instead of the sources, tests, examples and scripts of the package
+    #[clap(name = "FILES")]
+    pub files: Vec<PathBuf>,
+}
+
+impl Fmt {
+    pub fn execute(self, path: Option<PathBuf>, config: BuildConfig) -> anyhow::Result<()> {
+        let files = self
+            .files
+            .iter()
+            .map(|file| file.canonicalize())
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let rerooted_path = reroot_path(path)?;
+        let manifest = parse_move_manifest_from_file(&rerooted_path)?;
+        let edition = manifest
+            .package
+            .edition
+            .or(config.default_edition)
+            .unwrap_or_default();
+        let files = if files.is_empty() {
+            let dirs: Vec<_> = [
+                SourcePackageLayout::Sources,
+                SourcePackageLayout::Tests,
+                SourcePackageLayout::Examples,
+                SourcePackageLayout::Scripts,
+            ]
+            .iter()
+            .map(|dir| rerooted_path.join(dir.path()))
+            .filter(|dir| dir.exists())
+            .collect();
+            find_move_filenames(&dirs, false)?
+                .into_iter()
+                .map(PathBuf::from)
+                .collect()
+        } else {
+            files
+        };
+
+        let mut unformatted = vec![];
+        let mut failed = 0;
+        for file in &files {
+            let contents = fs::read_to_string(file)?;
+            let file_hash = FileHash::new(&contents);
+            let formatted = match format_string(file_hash, &contents, edition) {
+                Ok(formatted) => formatted,
+                Err(diags) => {
+                    let sources = FilesSourceText::from([(
+                        file_hash,
+                        (file.to_string_lossy().as_ref().into(), Arc::from(contents)),
+                    )]);
+                    let buf = report_diagnostics_to_buffer_with_format(
+                        &sources,
+                        diags,
+                        config.diagnostics_format,
+                    );
+                    std::io::stderr().write_all(&buf)?;
+                    failed += 1;
+                    continue;
+                }
+            };
+            if formatted == contents {
+                continue;
+            }
+            if self.check {
+                println!("{} is not formatted", display(file));
+            } else {
+                fs::write(file, formatted)?;
+                println!("Formatted {}", display(file));
+            }
+            unformatted.push(file);
+        }
+
+        if failed > 0 {
+            bail!("{failed} file(s) could not be formatted");
+        }
+        if self.check && !unformatted.is_empty() {
+            bail!(
+                "{} file(s) are not formatted, run `move fmt` to format them",
+                unformatted.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn display(file: &Path) -> String {
+    file.strip_prefix("./").unwrap_or(file).display().to_string()
+}
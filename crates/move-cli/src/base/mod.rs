@@ -3,12 +3,15 @@
 
 pub mod build;
 pub mod coverage;
+pub mod debug;
 pub mod disassemble;
 pub mod docgen;
 pub mod errmap;
+pub mod fmt;
 pub mod info;
 pub mod migrate;
 pub mod new;
+pub mod replay;
 pub mod test;
 
 use move_package::source_package::layout::SourcePackageLayout;
@@ -0,0 +1,352 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    sandbox::utils::{get_gas_status, on_disk_state_view::OnDiskStateView},
+    NativeFunctionRecord, DEFAULT_BUILD_DIR, DEFAULT_STORAGE_DIR,
+};
+use anyhow::{bail, Context, Result};
+use clap::*;
+use difference::{Changeset, Difference};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::IdentStr,
+    parser,
+    runtime_value::MoveValue,
+    transaction_argument::{convert_txn_args, TransactionArgument},
+};
+use move_trace_format::{
+    format::{Effect, TraceEvent, TraceReader, TraceValue},
+    tracer::Tracer,
+};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::gas_schedule::CostTable;
+use std::{
+    cell::RefCell, collections::BTreeMap, fmt::Write, fs::File, io::BufReader, path::PathBuf,
+    rc::Rc,
+};
+
+/// Re-execute a run recorded with `move sandbox run --trace` and check it against the trace
+///
+/// Every step of the execution must match the trace, and the first divergence is reported with the
+/// operand stack and locals expected and replayed. The function called and its type arguments are
+/// read from the trace, the other inputs of the run must be the same as when it was recorded.
+#[derive(Parser)]
+#[clap(name = "replay")]
+pub struct Replay {
+    /// Trace file of the run.
+    #[clap(name = "trace")]
+    pub trace_file: PathBuf,
+    /// Index of the run in the trace file, as the traces of successive runs are appended to it.
+    #[clap(long = "index", default_value = "0")]
+    pub index: usize,
+    /// Directory storing the Move resources and module bytecodes as they were before the run.
+    /// Nothing is written to it.
+    #[clap(long = "storage-dir", default_value = DEFAULT_STORAGE_DIR)]
+    pub storage_dir: PathBuf,
+    /// Possibly-empty list of signers of the run.
+    #[clap(
+        long = "signers",
+        num_args(1..),
+        action = clap::ArgAction::Append,
+    )]
+    pub signers: Vec<String>,
+    /// Possibly-empty list of arguments of the run, in the format of `move sandbox run`.
+    #[clap(
+        long = "args",
+        value_parser = parser::parse_transaction_argument,
+        num_args(1..),
+        action = clap::ArgAction::Append,
+    )]
+    pub args: Vec<TransactionArgument>,
+    /// Gas budget of the run, if it was metered.
+    #[clap(long = "gas-budget", short = 'g')]
+    pub gas_budget: Option<u64>,
+}
+
+impl Replay {
+    pub fn execute(
+        self,
+        natives: impl IntoIterator<Item = NativeFunctionRecord>,
+        cost_table: &CostTable,
+    ) -> Result<()> {
+        let file = File::open(&self.trace_file)
+            .with_context(|| format!("Unable to open trace file {:?}", self.trace_file))?;
+        let trace = TraceReader::new(BufReader::new(file))
+            .nth(self.index)
+            .with_context(|| {
+                format!("No trace at index {} in {:?}", self.index, self.trace_file)
+            })??;
+        let Some(TraceEvent::OpenFrame {
+            module,
+            function_name,
+            type_args,
+            ..
+        }) = trace
+            .events
+            .iter()
+            .find(|event| matches!(event, TraceEvent::OpenFrame { .. }))
+            .cloned()
+        else {
+            bail!("The traced run did not call any function")
+        };
+        if !self.storage_dir.exists() {
+            bail!("Storage directory {:?} does not exist", self.storage_dir)
+        }
+        let state = OnDiskStateView::create(PathBuf::from(DEFAULT_BUILD_DIR), self.storage_dir)?;
+
+        let signers = self
+            .signers
+            .iter()
+            .map(|s| AccountAddress::from_hex_literal(s))
+            .collect::<Result<Vec<_>, _>>()?;
+        let args: Vec<Vec<u8>> = signers
+            .iter()
+            .map(|a| {
+                MoveValue::Signer(*a)
+                    .simple_serialize()
+                    .expect("transaction arguments must serialize")
+            })
+            .chain(convert_txn_args(&self.args))
+            .collect();
+
+        let event_count = trace.events.len();
+        let checker = Rc::new(RefCell::new(ReplayChecker::new(trace.events)));
+        let vm = MoveVM::new(natives).unwrap();
+        let mut gas_status = get_gas_status(cost_table, self.gas_budget)?;
+        let mut session = vm.new_session(&state);
+        let type_args = type_args
+            .iter()
+            .map(|tag| session.load_type(tag))
+            .collect::<Result<Vec<_>, _>>()?;
+        session.set_tracer(Box::new(ReplayTracer(checker.clone())));
+        // Failures of the run are recorded in the trace, and checked like any other step
+        let _ = session.execute_function_bypass_visibility(
+            &module,
+            IdentStr::new(&function_name)?,
+            type_args,
+            args,
+            &mut gas_status,
+        );
+        drop(session);
+
+        let divergence = checker.borrow_mut().finish();
+        match divergence {
+            None => {
+                println!(
+                    "Replayed {} events: the execution matches the trace",
+                    event_count
+                );
+                Ok(())
+            }
+            Some(divergence) => {
+                print!("{}", divergence);
+                bail!("The execution diverged from the trace")
+            }
+        }
+    }
+}
+
+/// The operand stack and the locals of the open frames, rebuilt from the events of a trace.
+#[derive(Clone, Default)]
+struct TraceState {
+    stack: Vec<TraceValue>,
+    frames: Vec<FrameState>,
+}
+
+#[derive(Clone)]
+struct FrameState {
+    name: String,
+    is_native: bool,
+    locals: BTreeMap<u16, TraceValue>,
+}
+
+impl TraceState {
+    fn apply(&mut self, event: &TraceEvent) {
+        match event {
+            TraceEvent::OpenFrame {
+                module,
+                function_name,
+                args,
+                is_native,
+                ..
+            } => {
+                // The arguments of a call are moved from the operand stack of the caller
+                if !self.frames.is_empty() {
+                    self.stack
+                        .truncate(self.stack.len().saturating_sub(args.len()));
+                }
+                self.frames.push(FrameState {
+                    name: format!("{}::{}", module.short_str_lossless(), function_name),
+                    is_native: *is_native,
+                    locals: (0..).zip(args.iter().cloned()).collect(),
+                });
+            }
+            TraceEvent::Instruction { effects, .. } => {
+                let Some(frame) = self.frames.last_mut() else {
+                    return;
+                };
+                for effect in effects {
+                    match effect {
+                        Effect::Push(value) => self.stack.push(value.clone()),
+                        Effect::Pop(_) => {
+                            self.stack.pop();
+                        }
+                        Effect::ReadLocal {
+                            index, moved: true, ..
+                        } => {
+                            frame.locals.insert(*index, TraceValue::Invalid);
+                        }
+                        Effect::ReadLocal { moved: false, .. } => (),
+                        Effect::WriteLocal { index, value } => {
+                            frame.locals.insert(*index, value.clone());
+                        }
+                    }
+                }
+            }
+            TraceEvent::CloseFrame { return_values, .. } => {
+                // Move functions push their return values themselves, natives do not
+                if let Some(FrameState {
+                    is_native: true, ..
+                }) = self.frames.pop()
+                {
+                    self.stack.extend(return_values.iter().cloned());
+                }
+            }
+            TraceEvent::Error { .. } => self.frames.clear(),
+            TraceEvent::LoadModule { .. } | TraceEvent::LoadResource { .. } => (),
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut lines = vec!["Operand stack:".to_string()];
+        for (idx, value) in self.stack.iter().enumerate() {
+            lines.push(format!("    [{}] {}", idx, value));
+        }
+        for frame in &self.frames {
+            lines.push(format!("Locals of {}:", frame.name));
+            for (idx, value) in &frame.locals {
+                lines.push(format!("    [{}] {}", idx, value));
+            }
+        }
+        lines
+    }
+}
+
+/// The first event of a replay that differs from the trace, or `None` if the trace or the replay
+/// ended first.
+struct Divergence {
+    index: usize,
+    expected: Option<TraceEvent>,
+    replayed: Option<TraceEvent>,
+    /// The state before the event.
+    state: TraceState,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let describe = |event: &Option<TraceEvent>, end: &str| match event {
+            Some(event) => serde_json::to_string(event).expect("trace events must serialize"),
+            None => end.to_string(),
+        };
+        let lines_after = |event: &Option<TraceEvent>| {
+            let mut state = self.state.clone();
+            if let Some(event) = event {
+                state.apply(event)
+            }
+            state.lines().join("\n")
+        };
+
+        writeln!(f, "Divergence at event {} of the trace", self.index)?;
+        writeln!(
+            f,
+            "  expected: {}",
+            describe(&self.expected, "end of the trace")
+        )?;
+        writeln!(
+            f,
+            "  replayed: {}",
+            describe(&self.replayed, "end of the execution")
+        )?;
+        writeln!(f, "State after the event, expected (-) and replayed (+):")?;
+        let changeset = Changeset::new(
+            &lines_after(&self.expected),
+            &lines_after(&self.replayed),
+            "\n",
+        );
+        let mut diff = String::new();
+        for difference in &changeset.diffs {
+            let (prefix, lines) = match difference {
+                Difference::Same(lines) => (' ', lines),
+                Difference::Rem(lines) => ('-', lines),
+                Difference::Add(lines) => ('+', lines),
+            };
+            for line in lines.lines() {
+                writeln!(diff, "{} {}", prefix, line)?;
+            }
+        }
+        write!(f, "{}", diff)
+    }
+}
+
+/// Checks the events of a replay against the trace, up to the first divergence.
+struct ReplayChecker {
+    expected: Vec<TraceEvent>,
+    /// Number of events of the replay that matched the trace.
+    matched: usize,
+    /// The state rebuilt from the matched events.
+    state: TraceState,
+    divergence: Option<Divergence>,
+}
+
+impl ReplayChecker {
+    fn new(expected: Vec<TraceEvent>) -> Self {
+        Self {
+            expected,
+            matched: 0,
+            state: TraceState::default(),
+            divergence: None,
+        }
+    }
+
+    fn check(&mut self, event: &TraceEvent) {
+        if self.divergence.is_some() {
+            return;
+        }
+        match self.expected.get(self.matched) {
+            Some(expected) if expected == event => {
+                self.state.apply(event);
+                self.matched += 1;
+            }
+            expected => {
+                self.divergence = Some(Divergence {
+                    index: self.matched,
+                    expected: expected.cloned(),
+                    replayed: Some(event.clone()),
+                    state: self.state.clone(),
+                })
+            }
+        }
+    }
+
+    /// The first divergence of the replay, once it has ended.
+    fn finish(&mut self) -> Option<Divergence> {
+        if self.divergence.is_none() && self.matched < self.expected.len() {
+            self.divergence = Some(Divergence {
+                index: self.matched,
+                expected: Some(self.expected[self.matched].clone()),
+                replayed: None,
+                state: self.state.clone(),
+            });
+        }
+        self.divergence.take()
+    }
+}
+
+struct ReplayTracer(Rc<RefCell<ReplayChecker>>);
+
+impl Tracer for ReplayTracer {
+    fn notify(&mut self, event: &TraceEvent) {
+        self.0.borrow_mut().check(event)
+    }
+}
@@ -67,7 +67,7 @@ pub struct Test {
     /// Verbose mode
     #[clap(long = "verbose")]
     pub verbose_mode: bool,
-    /// Collect coverage information for later use with the various `move coverage` subcommands. Currently supported only in debug builds.
+    /// Collect coverage information for later use with the various `move coverage` subcommands.
     #[clap(long = "coverage")]
     pub compute_coverage: bool,
 }
@@ -142,6 +142,7 @@ pub fn run_move_unit_tests<W: Write + Send>(
     let mut test_plan = None;
     build_config.test_mode = true;
     build_config.dev_mode = true;
+    let format = build_config.diagnostics_format;
 
     // Build the resolution graph (resolution graph diagnostics are only needed for CLI commands so
     // ignore them by passing a vector as the writer)
@@ -185,16 +186,19 @@ pub fn run_move_unit_tests<W: Write + Send>(
     let mut warning_diags = None;
     build_plan.compile_with_driver(writer, |compiler| {
         let (files, comments_and_compiler_res) = compiler.run::<PASS_CFGIR>().unwrap();
-        let (_, compiler) =
-            diagnostics::unwrap_or_report_pass_diagnostics(&files, comments_and_compiler_res);
+        let compiler = match comments_and_compiler_res {
+            Ok((_, compiler)) => compiler,
+            Err((_, diags)) => diagnostics::report_diagnostics_with_format(&files, diags, format),
+        };
         let (mut compiler, cfgir) = compiler.into_ast();
         let compilation_env = compiler.compilation_env();
         let built_test_plan = construct_test_plan(compilation_env, Some(root_package), &cfgir);
 
-        let compilation_result = compiler.at_cfgir(cfgir).build();
-        let (units, warnings) =
-            diagnostics::unwrap_or_report_pass_diagnostics(&files, compilation_result);
-        diagnostics::report_warnings(&files, warnings.clone());
+        let (units, warnings) = match compiler.at_cfgir(cfgir).build() {
+            Ok(res) => res,
+            Err((_, diags)) => diagnostics::report_diagnostics_with_format(&files, diags, format),
+        };
+        diagnostics::report_warnings_with_format(&files, warnings.clone(), format);
         let named_units: Vec<_> = units
             .clone()
             .into_iter()
@@ -223,10 +227,10 @@ pub fn run_move_unit_tests<W: Write + Send>(
 
     cleanup_trace();
 
-    // If we need to compute test coverage set the VM tracking environment variable since we will
-    // need this trace to construct the coverage information.
+    // If we need to compute test coverage trace the execution of the tests, since we will need
+    // this trace to construct the coverage information.
     if compute_coverage {
-        std::env::set_var("MOVE_VM_TRACE", &trace_path);
+        unit_test_config.trace_path = Some(trace_path.clone());
     }
 
     // Run the tests. If any of the tests fail, then we don't produce a coverage report, so cleanup
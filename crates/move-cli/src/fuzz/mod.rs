@@ -7,6 +7,7 @@
 
 #[macro_use]
 mod templates;
+pub mod package_target;
 pub mod options;
 pub mod project;
 mod utils;
@@ -71,7 +72,8 @@ include:
   * `-only_ascii`: Only provide ASCII input
 
   * `-dict=<file>`: Use a keyword dictionary from specified file. See
-    http://llvm.org/docs/LibFuzzer.html#dictionaries\
+    http://llvm.org/docs/LibFuzzer.html#dictionaries
+    `move fuzz dict <target>` writes one from the constants of the Move code\
 ";
 
 const BUILD_BEFORE_HELP: &str = "\
@@ -117,6 +119,12 @@ pub enum Fuzz {
     /// Print the `std::fmt::Debug` output for an input
     Fmt(options::Fmt),
 
+    /// Write a Move unit test reproducing an input
+    Repro(options::Repro),
+
+    /// Write a libFuzzer dictionary of the constants of the fuzzed Move code
+    Dict(options::Dict),
+
     /// List all the existing fuzz targets
     List(options::List),
 
@@ -134,7 +142,7 @@ pub enum Fuzz {
     /// Minify a test case
     Tmin(options::Tmin),
 
-    /// Run program on the generated corpus and generate coverage information
+    /// Replay the corpus through the Move VM and report the covered lines of the Move sources
     Coverage(options::Coverage),
 }
 
@@ -147,6 +155,8 @@ impl RunCommand for Fuzz {
             Fuzz::Check(x) => x.run_command(path, config),
             Fuzz::List(x) => x.run_command(path, config),
             Fuzz::Fmt(x) => x.run_command(path, config),
+            Fuzz::Repro(x) => x.run_command(path, config),
+            Fuzz::Dict(x) => x.run_command(path, config),
             Fuzz::Run(x) => x.run_command(path, config),
             Fuzz::Cmin(x) => x.run_command(path, config),
             Fuzz::Tmin(x) => x.run_command(path, config),
@@ -168,6 +178,8 @@ impl FromStr for Fuzz {
             "build" => Ok(Fuzz::Build(Build::parse())),
             "check" => Ok(Fuzz::Check(Check::parse())),
             "fmt" => Ok(Fuzz::Fmt(Fmt::parse())),
+            "repro" => Ok(Fuzz::Repro(Repro::parse())),
+            "dict" => Ok(Fuzz::Dict(Dict::parse())),
             "list" => Ok(Fuzz::List(List::parse())),
             "run" => Ok(Fuzz::Run(Run::parse())),
             "cmin" => Ok(Fuzz::Cmin(Cmin::parse())),
@@ -186,6 +198,8 @@ impl Args for Fuzz {
             "build" => Build::augment_args(cmd),
             "check" => Check::augment_args(cmd),
             "fmt" => Fmt::augment_args(cmd),
+            "repro" => Repro::augment_args(cmd),
+            "dict" => Dict::augment_args(cmd),
             "list" => List::augment_args(cmd),
             "run" => Run::augment_args(cmd),
             "cmin" => Cmin::augment_args(cmd),
@@ -202,6 +216,8 @@ impl Args for Fuzz {
             "build" => Build::augment_args_for_update(cmd),
             "check" => Check::augment_args_for_update(cmd),
             "fmt" => Fmt::augment_args_for_update(cmd),
+            "repro" => Repro::augment_args_for_update(cmd),
+            "dict" => Dict::augment_args_for_update(cmd),
             "list" => List::augment_args_for_update(cmd),
             "run" => Run::augment_args_for_update(cmd),
             "cmin" => Cmin::augment_args_for_update(cmd),
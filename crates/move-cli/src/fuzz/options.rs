@@ -3,15 +3,17 @@ pub mod build;
 pub mod check;
 pub mod cmin;
 pub mod coverage;
+pub mod dict;
 pub mod fmt;
 pub mod init;
 pub mod list;
+pub mod repro;
 pub mod run;
 pub mod tmin;
 
 pub use self::{
-    add::Add, build::Build, check::Check, cmin::Cmin, coverage::Coverage, fmt::Fmt, init::Init,
-    list::List, run::Run, tmin::Tmin,
+    add::Add, build::Build, check::Check, cmin::Cmin, coverage::Coverage, dict::Dict, fmt::Fmt, init::Init,
+    list::List, repro::Repro, run::Run, tmin::Tmin,
 };
 
 use clap::*;
@@ -131,8 +133,8 @@ pub struct CargoBuildOptions {
     pub unstable_flags: Vec<String>,
 
     #[clap(skip = false)]
-    /// Instrument program code with source-based code coverage information.
-    /// This build option will be automatically used when running `cargo fuzz coverage`.
+    /// Build the targets to replay their corpus while tracing the Move VM, to compute Move source
+    /// coverage. This build option will be automatically used when running `move fuzz coverage`.
     /// The option will not be shown to the user, which is ensured by the `skip` attribute.
     /// The attribute takes a default value `false`, ensuring that by default,
     /// the coverage option will be disabled).
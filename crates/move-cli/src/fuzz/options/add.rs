@@ -1,3 +1,4 @@
+use crate::fuzz::package_target::PackageTarget;
 use crate::fuzz::project::{FuzzProject, Manifest};
 use crate::fuzz::{options::FuzzDirWrapper, RunCommand};
 use anyhow::Result;
@@ -13,12 +14,44 @@ pub struct Add {
 
     /// Name of the new fuzz target
     pub target: String,
+
+    /// Fuzz a function of the package instead of a new Move fuzz module, e.g. `0x2::pool::swap`.
+    /// The package is added to the dependencies of the fuzz Move package.
+    #[clap(long, conflicts_with_all = &["sequence", "bytecode"])]
+    pub function: Option<String>,
+
+    /// Fuzz sequences of calls to the public functions of a package module, e.g. `0x2::pool`.
+    /// Values returned by a call are passed to the following ones.
+    #[clap(long, conflicts_with = "bytecode")]
+    pub sequence: Option<String>,
+
+    /// Fuzz the deserializer and the bytecode verifier with structural mutations of compiled
+    /// modules. The corpus is seeded with the modules of the fuzz Move package.
+    #[clap(long)]
+    pub bytecode: bool,
 }
 
 impl RunCommand for Add {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig)-> Result<()> {
-        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
         let manifest = Manifest::parse()?;
-        project.add_target(self, &manifest)
+        let package_path = path.as_deref().unwrap_or_else(|| Path::new("."));
+        let package_target = match (&self.function, &self.sequence) {
+            (Some(function), _) => Some(PackageTarget::resolve_function(
+                function,
+                package_path,
+                config,
+            )?),
+            (None, Some(module)) => Some(PackageTarget::resolve_sequence(
+                module,
+                package_path,
+                config,
+            )?),
+            (None, None) => None,
+        };
+        if self.bytecode {
+            return project.add_module_target(self, &manifest, config);
+        }
+        project.add_target(self, &manifest, package_target.as_ref())
     }
 }
@@ -26,7 +26,7 @@ pub struct Build {
 
 impl RunCommand for Build {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig)-> Result<()> {
-        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
-        project.exec_build(BuildMode::Build, &self.build, self.target.as_deref(), path, config)
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
+        project.exec_build(BuildMode::Build, &self.build, self.target.as_deref(), config)
     }
 }
@@ -23,7 +23,7 @@ pub struct Check {
 
 impl RunCommand for Check {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig)-> Result<()> {
-        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
-        project.exec_build(BuildMode::Check, &self.build, self.target.as_deref(), path, config)
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
+        project.exec_build(BuildMode::Check, &self.build, self.target.as_deref(), config)
     }
 }
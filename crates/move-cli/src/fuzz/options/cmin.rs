@@ -31,7 +31,7 @@ pub struct Cmin {
 
 impl RunCommand for Cmin {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig)-> Result<()> {
-        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
-        project.exec_cmin(self, path, config)
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
+        project.exec_cmin(self, config)
     }
 }
@@ -5,7 +5,7 @@ use crate::fuzz::{
     project::FuzzProject,
     RunCommand,
 };
-use anyhow::{bail, Result};
+use anyhow::Result;
 use clap::Parser;
 
 use move_package::BuildConfig;
@@ -18,10 +18,6 @@ pub struct Coverage {
     #[clap(flatten)] 
     pub fuzz_dir_wrapper: FuzzDirWrapper,
 
-    /// Sets the path to the LLVM bin directory. By default, it will use the one installed with rustc
-    #[clap(long)]
-    pub llvm_path: Option<PathBuf>,
-
     /// Name of the fuzz target
     pub target: String,
 
@@ -35,14 +31,8 @@ pub struct Coverage {
 
 impl RunCommand for Coverage {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig)-> Result<()> {
-        if self.build.cargo_options.build_std {
-            bail!(
-                "-Zbuild-std is currently incompatible with -Zinstrument-coverage, \
-                see https://github.com/rust-lang/wg-cargo-std-aware/issues/63"
-            );
-        }
-        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
         self.build.cargo_options.coverage = true;
-        project.exec_coverage(self, path, config)
+        project.exec_coverage(self, config)
     }
 }
@@ -0,0 +1,26 @@
+use crate::fuzz::{options::FuzzDirWrapper, project::FuzzProject, RunCommand};
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+use move_package::BuildConfig;
+
+#[derive(Clone, Debug, Parser)]
+pub struct Dict {
+    #[clap(flatten)]
+    pub fuzz_dir_wrapper: FuzzDirWrapper,
+
+    /// Name of the fuzz target
+    pub target: String,
+
+    /// File the dictionary is written to, `dicts/<target>.dict` in the fuzz directory by default
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+impl RunCommand for Dict {
+    fn run_command(&mut self, path: &Option<PathBuf>, config: &BuildConfig) -> Result<()> {
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
+        project.exec_dict(self, config)
+    }
+}
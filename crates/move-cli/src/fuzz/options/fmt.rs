@@ -26,7 +26,7 @@ pub struct Fmt {
 
 impl RunCommand for Fmt {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig)-> Result<()> {
-        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
-        project.debug_fmt_input(self, path)
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
+        project.debug_fmt_input(self)
     }
 }
@@ -2,6 +2,7 @@ use crate::fuzz::{options::FuzzDirWrapper, project::FuzzProject, RunCommand};
 use anyhow::Result;
 use clap::Parser;
 
+use move_compiler::editions::{Edition, Flavor};
 use move_package::BuildConfig;
 use std::path::{Path, PathBuf};
 
@@ -15,13 +16,28 @@ pub struct Init {
     /// Whether to create a separate workspace for fuzz targets crate
     pub fuzzing_workspace: Option<bool>,
 
+    #[clap(long)]
+    /// Move edition of the fuzz package, `--default-move-edition` if not supplied
+    pub edition: Option<Edition>,
+
+    #[clap(long)]
+    /// Move flavor of the fuzz package, `--default-move-flavor` if not supplied
+    pub flavor: Option<Flavor>,
+
+    #[clap(long)]
+    /// Depend on the standard library sources shipped with this `move` binary instead of copying
+    /// them into the fuzz project
+    pub reference_stdlib: bool,
+
     #[clap(flatten)] 
     pub fuzz_dir_wrapper: FuzzDirWrapper,
 }
 
 impl RunCommand for Init {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig)-> Result<()> {
-        FuzzProject::init(self, self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
+        self.edition = self.edition.or(config.default_edition);
+        self.flavor = self.flavor.or(config.default_flavor);
+        FuzzProject::init(self, self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
         Ok(())
     }
 }
@@ -0,0 +1,37 @@
+use crate::fuzz::{
+    options::{BuildOptions, FuzzDirWrapper},
+    project::FuzzProject,
+    RunCommand,
+};
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+use move_package::BuildConfig;
+
+#[derive(Clone, Debug, Parser)]
+pub struct Repro {
+    #[clap(flatten)]
+    pub build: BuildOptions,
+
+    #[clap(flatten)]
+    pub fuzz_dir_wrapper: FuzzDirWrapper,
+
+    /// Name of fuzz target
+    pub target: String,
+
+    /// Path to the input testcase to reproduce
+    pub input: PathBuf,
+
+    /// File the Move test is written to, e.g. in the `tests` directory of the fuzzed package.
+    /// By default, the test is printed to stdout
+    #[clap(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+impl RunCommand for Repro {
+    fn run_command(&mut self, path: &Option<PathBuf>, _config: &BuildConfig) -> Result<()> {
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
+        project.exec_repro(self)
+    }
+}
@@ -38,7 +38,7 @@ pub struct Run {
 
 impl RunCommand for Run {
     fn run_command(&mut self,  path: &Option<PathBuf>, config: &BuildConfig) -> Result<()> {
-        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned())?;
-        project.exec_fuzz(self, path, config)
+        let project = FuzzProject::new(self.fuzz_dir_wrapper.fuzz_dir.to_owned(), path)?;
+        project.exec_fuzz(self, config)
     }
 }
@@ -0,0 +1,147 @@
+use anyhow::{anyhow, bail, Context, Result};
+use move_binary_format::{file_format::Visibility, CompiledModule};
+use move_command_line_common::types::{ParsedFqName, ParsedModuleId};
+use move_core_types::{
+    account_address::AccountAddress, identifier::IdentStr, language_storage::ModuleId,
+};
+use move_fuzzer::run_move::{generator::ArgumentGenerator, sequence::SequenceGenerator};
+use move_package::{
+    compilation::compiled_package::CompiledPackage, source_package::layout::SourcePackageLayout,
+    BuildConfig,
+};
+use move_vm_test_utils::InMemoryStorage;
+use std::path::{Path, PathBuf};
+
+/// Package code fuzzed by a target created with `move fuzz add --function` or `--sequence`
+#[derive(Debug, Clone)]
+pub struct PackageTarget {
+    /// What the target calls
+    pub kind: PackageTargetKind,
+    /// Fully qualified name of the function or module, with a numerical address
+    pub qualified_name: String,
+    /// Name of the package defining the function or module
+    pub package_name: String,
+    /// Root directory of the package defining the function or module
+    pub package_root: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageTargetKind {
+    /// A single function, called with arguments generated from its signature
+    Function,
+    /// Sequences of calls to the public functions of a module
+    Sequence,
+}
+
+impl PackageTarget {
+    /// Compiles the package at `package_path` and looks up `function` in it. Named addresses in
+    /// `function` are resolved with the package's address assignments.
+    pub fn resolve_function(
+        function: &str,
+        package_path: &Path,
+        config: &BuildConfig,
+    ) -> Result<Self> {
+        let (package, package_root) = compile(package_path, config)?;
+        let (module_id, name) =
+            ParsedFqName::parse(function)?.into_fq_name(&address_mapping(&package))?;
+        let module = find_module(&package, &module_id)?;
+        check_function(module, IdentStr::new(&name)?, &publish(&package))
+            .with_context(|| format!("function {} cannot be fuzzed", function))?;
+
+        Ok(Self {
+            kind: PackageTargetKind::Function,
+            qualified_name: format!(
+                "{}::{}::{}",
+                module_id.address().to_hex_literal(),
+                module_id.name(),
+                name
+            ),
+            package_name: package.compiled_package_info.package_name.to_string(),
+            package_root,
+        })
+    }
+
+    /// Compiles the package at `package_path` and looks up `module` in it. Named addresses in
+    /// `module` are resolved with the package's address assignments.
+    pub fn resolve_sequence(
+        module: &str,
+        package_path: &Path,
+        config: &BuildConfig,
+    ) -> Result<Self> {
+        let (package, package_root) = compile(package_path, config)?;
+        let module_id =
+            ParsedModuleId::parse(module)?.into_module_id(&address_mapping(&package))?;
+        SequenceGenerator::new(find_module(&package, &module_id)?, &&publish(&package))
+            .with_context(|| format!("module {} cannot be fuzzed", module))?;
+
+        Ok(Self {
+            kind: PackageTargetKind::Sequence,
+            qualified_name: format!(
+                "{}::{}",
+                module_id.address().to_hex_literal(),
+                module_id.name()
+            ),
+            package_name: package.compiled_package_info.package_name.to_string(),
+            package_root,
+        })
+    }
+}
+
+fn compile(package_path: &Path, config: &BuildConfig) -> Result<(CompiledPackage, PathBuf)> {
+    let package_root = SourcePackageLayout::try_find_root(&package_path.canonicalize()?)?;
+    let package = config
+        .clone()
+        .compile_package(&package_root, &mut std::io::stdout())?;
+    Ok((package, package_root))
+}
+
+/// Resolves named addresses with the package's address assignments
+fn address_mapping(package: &CompiledPackage) -> impl Fn(&str) -> Option<AccountAddress> + '_ {
+    |name| {
+        package
+            .compiled_package_info
+            .address_alias_instantiation
+            .iter()
+            .find(|(alias, _)| alias.as_str() == name)
+            .map(|(_, address)| *address)
+    }
+}
+
+fn find_module<'a>(
+    package: &'a CompiledPackage,
+    module_id: &ModuleId,
+) -> Result<&'a CompiledModule> {
+    package
+        .all_modules()
+        .map(|unit| &unit.unit.module)
+        .find(|module| &module.self_id() == module_id)
+        .ok_or_else(|| anyhow!("module {} not found in package", module_id))
+}
+
+/// Publishes the package and its dependencies, to resolve the structs in function signatures
+fn publish(package: &CompiledPackage) -> InMemoryStorage {
+    let mut storage = InMemoryStorage::new();
+    for unit in package.all_compiled_units() {
+        storage.publish_or_overwrite_module(unit.module.self_id(), unit.serialize(None));
+    }
+    storage
+}
+
+/// Checks that the fuzzer can call the function and generate all of its arguments
+fn check_function(
+    module: &CompiledModule,
+    name: &IdentStr,
+    storage: &InMemoryStorage,
+) -> Result<()> {
+    let def = module
+        .function_defs()
+        .iter()
+        .find(|def| module.identifier_at(module.function_handle_at(def.function).name) == name)
+        .ok_or_else(|| anyhow!("function not found in module {}", module.self_id()))?;
+
+    if def.visibility != Visibility::Public && !def.is_entry {
+        bail!("only public and entry functions can be fuzzed");
+    }
+    ArgumentGenerator::new(module, name, &storage)?;
+    Ok(())
+}
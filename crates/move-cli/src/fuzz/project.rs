@@ -1,12 +1,24 @@
+use crate::fuzz::package_target::{PackageTarget, PackageTargetKind};
 use crate::fuzz::options::{self, BuildMode, BuildOptions, CargoBuildOptions, Sanitizer};
 use crate::fuzz::utils::default_target;
 use anyhow::{anyhow, bail, Context, Result};
 use cargo_metadata::MetadataCommand;
-use move_package::BuildConfig;
+use move_compiler::compiled_unit::NamedCompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_coverage::{
+    coverage_map::CoverageMap, format_human_summary, source_coverage::SourceCoverageBuilder,
+    summary::summarize_inst_cov,
+};
+use move_fuzzer::run_move::dictionary::Dictionary;
+use move_command_line_common::files::MOVE_EXTENSION;
+use move_package::{
+    compilation::compiled_package::CompiledPackage, source_package::layout::SourcePackageLayout,
+    BuildConfig,
+};
 use std::collections::HashSet;
 use std::io::Read;
 use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::{
     env, ffi, fs,
     process::{Command, Stdio},
@@ -15,6 +27,9 @@ use std::{
 
 const DEFAULT_FUZZ_DIR: &str = "fuzz";
 
+/// Directory of the fuzz project the bundled standard library is copied to by `move fuzz init`
+const STDLIB_VENDOR_DIR: &str = "deps/move-stdlib";
+
 pub struct FuzzProject {
     /// The project with fuzz targets
     fuzz_dir: PathBuf,
@@ -28,9 +43,9 @@ impl FuzzProject {
     /// directory and walking up the filesystem.
     ///
     /// If `fuzz_dir_opt` is `None`, returns a new instance with the default fuzz project
-    /// path.
-    pub fn new(fuzz_dir_opt: Option<PathBuf>) -> Result<Self> {
-        let mut project = Self::manage_initial_instance(fuzz_dir_opt)?;
+    /// path, in the package at `path` if provided.
+    pub fn new(fuzz_dir_opt: Option<PathBuf>, path: &Option<PathBuf>) -> Result<Self> {
+        let mut project = Self::manage_initial_instance(fuzz_dir_opt, path)?;
         let manifest = project.manifest()?;
         if !is_fuzz_manifest(&manifest) {
             bail!(
@@ -49,8 +64,12 @@ impl FuzzProject {
     ///
     /// This will not clone libfuzzer-sys.
     /// Similar to `FuzzProject::new`, the fuzz directory will depend on `fuzz_dir_opt`.
-    pub fn init(init: &options::Init, fuzz_dir_opt: Option<PathBuf>) -> Result<Self> {
-        let project = Self::manage_initial_instance(fuzz_dir_opt)?;
+    pub fn init(
+        init: &options::Init,
+        fuzz_dir_opt: Option<PathBuf>,
+        path: &Option<PathBuf>,
+    ) -> Result<Self> {
+        let project = Self::manage_initial_instance(fuzz_dir_opt, path)?;
         let fuzz_project = project.fuzz_dir();
         let manifest = Manifest::parse()?;
         println!("{:?}", manifest);
@@ -77,13 +96,23 @@ impl FuzzProject {
             ))
             .with_context(|| format!("failed to write to {}", cargo_toml.display()))?;
 
-        let move_toml_path = fuzz_project.join("Move.toml");
+        let stdlib_path = if init.reference_stdlib {
+            move_stdlib::path_in_crate("")
+        } else {
+            let vendor_dir = fuzz_project.join(STDLIB_VENDOR_DIR);
+            vendor_stdlib(&vendor_dir)?;
+            PathBuf::from(STDLIB_VENDOR_DIR)
+        };
+        let move_toml_path = project.move_manifest_path();
         let mut move_toml = fs::File::create(&move_toml_path)
             .with_context(|| format!("failed to create {}", move_toml_path.display()))?;
         move_toml
             .write_fmt(move_toml_template!(
                 manifest.crate_name,
-                manifest.edition
+                init.edition,
+                init.flavor,
+                stdlib_path.display(),
+                stdlib_path.join("nursery").display()
             ))
             .with_context(|| format!("failed to write to {}", move_toml_path.display()))?;
 
@@ -112,13 +141,61 @@ impl FuzzProject {
         Ok(())
     }
 
-    /// Create a new fuzz target.
-    pub fn add_target(&self, add: &options::Add, manifest: &Manifest) -> Result<()> {
+    /// Create a new fuzz target, calling `package_target` if given or a new Move fuzz module
+    /// otherwise.
+    pub fn add_target(
+        &self,
+        add: &options::Add,
+        manifest: &Manifest,
+        package_target: Option<&PackageTarget>,
+    ) -> Result<()> {
         // Create corpus and artifact directories for the newly added target
         self.corpus_for(&add.target)?;
         self.artifacts_for(&add.target)?;
-        self.create_target_template(&add.target, manifest)
-            .with_context(|| format!("could not add target {:?}", add.target))
+        match package_target {
+            Some(package_target) => {
+                self.create_package_target_template(&add.target, package_target, manifest)
+            }
+            None => self.create_target_template(&add.target, manifest),
+        }
+        .with_context(|| format!("could not add target {:?}", add.target))
+    }
+
+    /// Add a new fuzz target for the deserializer and the bytecode verifier, its corpus being
+    /// seeded with the modules of the fuzz Move package
+    pub fn add_module_target(
+        &self,
+        add: &options::Add,
+        manifest: &Manifest,
+        config: &BuildConfig,
+    ) -> Result<()> {
+        let package = self.move_build(config)?;
+        let corpus = self.corpus_for(&add.target)?;
+        for unit in package.all_modules() {
+            let module = &unit.unit.module;
+            let mut bytes = vec![];
+            module.serialize(&mut bytes)?;
+            let id = module.self_id();
+            let seed = corpus.join(format!("{}_{}", id.address().short_str_lossless(), id.name()));
+            fs::write(&seed, bytes)
+                .with_context(|| format!("failed to write {}", seed.display()))?;
+        }
+        self.artifacts_for(&add.target)?;
+
+        let rust_target_path = self.rust_target_path(&add.target);
+        fs::create_dir_all(self.fuzz_targets_dir())
+            .context("ensuring that `fuzz_targets` directory exists failed")?;
+        let mut rust_script = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&rust_target_path)
+            .with_context(|| format!("could not create target script file at {:?}", rust_target_path))?;
+        rust_script.write_fmt(rust_module_target_template!(manifest.edition))?;
+
+        let mut cargo = fs::OpenOptions::new()
+            .append(true)
+            .open(self.manifest_path())?;
+        Ok(cargo.write_fmt(toml_bin_template!(add.target))?)
     }
 
     /// Add a new fuzz target script with a given name
@@ -147,7 +224,7 @@ impl FuzzProject {
             .create_new(true)
             .open(&move_target_path)
             .with_context(|| format!("could not create target script file at {:?}", move_target_path))?;
-        move_script.write_fmt(move_target_template!(manifest.edition))?;
+        move_script.write_fmt(move_target_template!(target))?;
 
         let mut cargo = fs::OpenOptions::new()
             .append(true)
@@ -155,6 +232,81 @@ impl FuzzProject {
         Ok(cargo.write_fmt(toml_bin_template!(target))?)
     }
 
+    /// Add a new fuzz target calling a package function or module, the package becomes a
+    /// dependency of the fuzz Move package
+    fn create_package_target_template(
+        &self,
+        target: &str,
+        package_target: &PackageTarget,
+        manifest: &Manifest,
+    ) -> Result<()> {
+        let rust_target_path = self.rust_target_path(target);
+
+        fs::create_dir_all(self.fuzz_targets_dir())
+            .context("ensuring that `fuzz_targets` directory exists failed")?;
+
+        let mut rust_script = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&rust_target_path)
+            .with_context(|| format!("could not create target script file at {:?}", rust_target_path))?;
+        match package_target.kind {
+            PackageTargetKind::Function => rust_script.write_fmt(rust_function_target_template!(
+                manifest.edition,
+                package_target.qualified_name
+            ))?,
+            PackageTargetKind::Sequence => rust_script.write_fmt(rust_sequence_target_template!(
+                manifest.edition,
+                package_target.qualified_name
+            ))?,
+        }
+
+        self.add_move_dependency(&package_target.package_name, &package_target.package_root)?;
+
+        let mut cargo = fs::OpenOptions::new()
+            .append(true)
+            .open(self.manifest_path())?;
+        cargo.write_fmt(toml_bin_template!(target))?;
+        Ok(match package_target.kind {
+            PackageTargetKind::Function => cargo.write_fmt(toml_function_target_template!(
+                target,
+                package_target.qualified_name
+            ))?,
+            PackageTargetKind::Sequence => cargo.write_fmt(toml_sequence_target_template!(
+                target,
+                package_target.qualified_name
+            ))?,
+        })
+    }
+
+    /// Add the package at `package_root` to the dependencies of the fuzz Move package, unless
+    /// it is already there
+    fn add_move_dependency(&self, package_name: &str, package_root: &Path) -> Result<()> {
+        let move_toml_path = self.move_manifest_path();
+        let contents = fs::read_to_string(&move_toml_path)
+            .with_context(|| format!("could not read {}", move_toml_path.display()))?;
+        let mut move_toml = contents
+            .parse::<toml_edit::Document>()
+            .with_context(|| format!("could not decode {}", move_toml_path.display()))?;
+        let dependencies = move_toml
+            .entry("dependencies")
+            .or_insert(toml_edit::table())
+            .as_table_mut()
+            .ok_or_else(|| anyhow!("`dependencies` is not a table in {}", move_toml_path.display()))?;
+        if dependencies.contains_key(package_name) {
+            return Ok(());
+        }
+
+        let fuzz_dir = self.fuzz_dir().canonicalize()?;
+        let local = relative_path(&fuzz_dir, package_root);
+        let mut dependency = toml_edit::InlineTable::new();
+        dependency.insert("local", local.to_string_lossy().as_ref().into());
+        dependencies[package_name] = toml_edit::value(dependency);
+
+        fs::write(&move_toml_path, move_toml.to_string())
+            .with_context(|| format!("failed to write to {}", move_toml_path.display()))
+    }
+
     fn cargo_build(&self, subcommand: &str, build: &BuildOptions) -> Result<Command> {
         let mut cmd = Command::new("cargo");
         cmd.arg(subcommand)
@@ -207,10 +359,6 @@ impl FuzzProject {
             rustflags.push_str(" -Clink-dead-code");
         }
 
-        if build.cargo_options.coverage {
-            rustflags.push_str(" -Cinstrument-coverage");
-        }
-
         match build.cargo_options.sanitizer {
             Sanitizer::None => {}
             Sanitizer::Memory => {
@@ -290,37 +438,22 @@ impl FuzzProject {
         Ok(cmd)
     }
 
-    fn move_build(&self, subcommand: &str, build: &BuildOptions, path: &Option<PathBuf>,
-        build_config: &BuildConfig) -> Result<Command> {
-        let mut cmd = Command::new("move");
-        cmd.arg(subcommand);
-        
-        if build.verbose {
-            cmd.arg("--verbose");
-        }
-
-        if build_config.fetch_deps_only {
-            cmd.arg("--fetch-deps-only");
-        }
-
-        if build_config.force_recompilation {
-            cmd.arg("--force");
-        }
-
-        if build_config.skip_fetch_latest_git_deps {
-            cmd.arg("--skip-fetch-latest-git-deps");
-        }
-
-        Ok(cmd)
+    /// Compiles the fuzz Move package with the global build flags, the compiler diagnostics being
+    /// reported on stderr
+    fn move_build(&self, config: &BuildConfig) -> Result<CompiledPackage> {
+        let mut config = config.clone();
+        // The fuzz targets load the compiled package from the fuzz directory
+        config.install_dir = None;
+        config
+            .compile_package_no_exit(self.fuzz_dir(), &mut std::io::stderr())
+            .context("failed to build the fuzz Move package")
     }
 
-    fn cargo_run(&self, build: &options::BuildOptions, fuzz_target: &str, path: &Option<PathBuf>) -> Result<Command> {
+    fn cargo_run(&self, build: &options::BuildOptions, fuzz_target: &str) -> Result<Command> {
         let mut cmd = self.cargo_build("run", build)?;
         cmd.arg("--bin").arg(fuzz_target);
-
-        if let Some(target_dir) = &path {
-            cmd.arg("--target-dir").arg(target_dir);
-        }
+        // Tell the target where to find the compiled Move package.
+        cmd.env(move_fuzzer::run_move::FUZZ_DIR_ENV_VAR, self.fuzz_dir());
 
         let mut artifact_arg = ffi::OsString::from("-artifact_prefix=");
         artifact_arg.push(self.artifacts_for(fuzz_target)?);
@@ -330,12 +463,9 @@ impl FuzzProject {
     }
 
     // note: never returns Ok(None) if build.coverage is true
-    fn target_dir(&self, build: &options::BuildOptions, path: &Option<PathBuf>) -> Result<Option<PathBuf>> {
-        // Use the user-provided target directory, if provided. Otherwise if building for coverage,
-        // use the coverage directory
-        if let Some(target_dir) = path.clone() {
-            return Ok(Some(target_dir));
-        } else if build.cargo_options.coverage {
+    fn target_dir(&self, build: &options::BuildOptions) -> Result<Option<PathBuf>> {
+        // If building for coverage, use the coverage directory
+        if build.cargo_options.coverage {
             // To ensure that fuzzing and coverage-output generation can run in parallel, we
             // produce a separate binary for the coverage command.
             let current_dir = env::current_dir()?;
@@ -355,15 +485,18 @@ impl FuzzProject {
         mode: options::BuildMode,
         build: &options::BuildOptions,
         fuzz_target: Option<&str>,
-        path: &Option<PathBuf>,
         config: &BuildConfig
     ) -> Result<()> {
+        if config.fetch_deps_only {
+            return config.download_deps_for_package(self.fuzz_dir(), &mut std::io::stderr());
+        }
+        self.move_build(config)?;
+
         let cargo_subcommand = match mode {
             options::BuildMode::Build => "build",
             options::BuildMode::Check => "check",
         };
         let mut cargo_cmd = self.cargo_build(cargo_subcommand, build)?;
-        let mut move_cmd = self.move_build(cargo_subcommand, build, &path, config)?;
 
         if let Some(fuzz_target) = fuzz_target {
             cargo_cmd.arg("--bin").arg(fuzz_target);
@@ -371,12 +504,8 @@ impl FuzzProject {
             cargo_cmd.arg("--bins");
         }
 
-        if let Some(target_dir) = self.target_dir(&build, &path)? {
+        if let Some(target_dir) = self.target_dir(&build)? {
             cargo_cmd.arg("--target-dir").arg(&target_dir);
-            move_cmd.arg("--path").arg(&target_dir);
-        }
-        else {
-            move_cmd.arg("--path").arg(&self.fuzz_dir());
         }
 
         let status = cargo_cmd
@@ -386,18 +515,6 @@ impl FuzzProject {
             bail!("failed to build fuzz script: {:?}", cargo_cmd);
         }
 
-        let mut move_build = Command::new("move");
-        move_build.arg(cargo_subcommand)
-            .current_dir("fuzz");
-
-        let move_status = move_build
-            .status()
-            .with_context(|| format!("failed to execute: {:?}", move_build))?;
-        if !move_status.success() {
-            //bail!("failed to build fuzz script: {:?}", move_build);
-            bail!("failed to build fuzz script: {:?}", move_build);
-        }
-
         Ok(())
     }
 
@@ -444,11 +561,10 @@ impl FuzzProject {
         build: &BuildOptions,
         target: &str,
         artifact: &Path,
-        path: &Option<PathBuf>,
     ) -> Result<String> {
         let debug_output = tempfile::NamedTempFile::new().context("failed to create temp file")?;
 
-        let mut cmd = self.cargo_run(build, target, path)?;
+        let mut cmd = self.cargo_run(build, target)?;
         cmd.stdin(Stdio::null());
         cmd.env("RUST_LIBFUZZER_DEBUG_PATH", debug_output.path());
         cmd.arg(artifact);
@@ -482,7 +598,7 @@ impl FuzzProject {
     }
 
     /// Prints the debug output of an input test case
-    pub fn debug_fmt_input(&self, debugfmt: &options::Fmt, path: &Option<PathBuf>) -> Result<()> {
+    pub fn debug_fmt_input(&self, debugfmt: &options::Fmt) -> Result<()> {
         if !debugfmt.input.exists() {
             bail!(
                 "Input test case does not exist: {}",
@@ -491,7 +607,7 @@ impl FuzzProject {
         }
 
         let debug = self
-            .run_fuzz_target_debug_formatter(&debugfmt.build, &debugfmt.target, &debugfmt.input, path)
+            .run_fuzz_target_debug_formatter(&debugfmt.build, &debugfmt.target, &debugfmt.input)
             .with_context(|| {
                 format!(
                     "failed to run `cargo fuzz fmt` on input: {}",
@@ -507,11 +623,97 @@ impl FuzzProject {
         Ok(())
     }
 
+    /// Writes a Move unit test reproducing an input test case
+    pub fn exec_repro(&self, repro: &options::Repro) -> Result<()> {
+        if !repro.input.exists() {
+            bail!("Input test case does not exist: {}", repro.input.display());
+        }
+
+        let test_output = tempfile::NamedTempFile::new().context("failed to create temp file")?;
+        let mut cmd = self.cargo_run(&repro.build, &repro.target)?;
+        cmd.stdin(Stdio::null());
+        cmd.env(move_fuzzer::run_move::REPRO_PATH_ENV_VAR, test_output.path());
+        cmd.env(
+            move_fuzzer::run_move::REPRO_NAME_ENV_VAR,
+            repro_test_name(&repro.target, &repro.input),
+        );
+        cmd.arg(&repro.input);
+
+        let output = cmd
+            .output()
+            .with_context(|| format!("failed to run command: {:?}", cmd))?;
+        if !output.status.success() {
+            bail!(
+                "Fuzz target '{target}' exited with failure when writing the Move test \
+                 reproducing an input.\n\n\
+                 Input: {input}\n\n\
+                 Command: {cmd:?}\n\n\
+                 Status: {status}\n\n\
+                 === stderr ===\n\
+                 {stderr}",
+                target = repro.target,
+                input = repro.input.display(),
+                cmd = cmd,
+                status = output.status,
+                stderr = String::from_utf8_lossy(&output.stderr),
+            );
+        }
+
+        let test = fs::read_to_string(&test_output).context("failed to read temp file")?;
+        match &repro.output {
+            Some(output) => {
+                fs::write(output, test)
+                    .with_context(|| format!("failed to write {}", output.display()))?;
+                eprintln!("Move test written to {}", output.display());
+            }
+            None => print!("{}", test),
+        }
+        Ok(())
+    }
+
+    /// Writes a libFuzzer dictionary of the constants of the fuzz Move package and of its
+    /// dependencies, the standard library excluded
+    pub fn exec_dict(&self, dict: &options::Dict, config: &BuildConfig) -> Result<()> {
+        if !self.targets.contains(&dict.target) {
+            bail!("fuzz target `{}` not found", dict.target);
+        }
+        let package = self.move_build(config)?;
+
+        let mut dictionary = Dictionary::new();
+        for unit in package.all_modules() {
+            if *unit.unit.module.self_id().address() != AccountAddress::ONE {
+                dictionary.add_module(&unit.unit.module);
+            }
+        }
+
+        let output = match &dict.output {
+            Some(output) => output.clone(),
+            None => {
+                let dicts = self.fuzz_dir().join("dicts");
+                fs::create_dir_all(&dicts)
+                    .with_context(|| format!("could not make a dict directory at {:?}", dicts))?;
+                dicts.join(format!("{}.dict", dict.target))
+            }
+        };
+        let mut file = fs::File::create(&output)
+            .with_context(|| format!("failed to create {}", output.display()))?;
+        dictionary.write(&mut file)?;
+
+        eprintln!(
+            "Wrote {} entries to {}, use it with:\n\n\tmove fuzz run {} -- -dict={}\n",
+            dictionary.len(),
+            output.display(),
+            dict.target,
+            output.display()
+        );
+        Ok(())
+    }
+
     /// Fuzz a given fuzz target
-    pub fn exec_fuzz(&self, run: &options::Run, path: &Option<PathBuf>, config: &BuildConfig) -> Result<()> {
+    pub fn exec_fuzz(&self, run: &options::Run, config: &BuildConfig) -> Result<()> {
         // todo: gestione parametri build
-        self.exec_build(BuildMode::Build, &run.build, Some(&run.target), path, config)?;
-        let mut cmd = self.cargo_run(&run.build, &run.target, path)?;
+        self.exec_build(BuildMode::Build, &run.build, Some(&run.target), config)?;
+        let mut cmd = self.cargo_run(&run.build, &run.target)?;
 
         for arg in &run.args {
             cmd.arg(arg);
@@ -563,7 +765,7 @@ impl FuzzProject {
             // an older version of the libfuzzer crate, and doesn't support
             // `RUST_LIBFUZZER_DEBUG_PATH`.
             if let Ok(debug) =
-                self.run_fuzz_target_debug_formatter(&run.build, &run.target, artifact, path)
+                self.run_fuzz_target_debug_formatter(&run.build, &run.target, artifact)
             {
                 eprintln!("Output of `std::fmt::Debug`:\n");
                 for l in debug.lines() {
@@ -592,15 +794,22 @@ impl FuzzProject {
                 target = &run.target,
                 artifact = artifact.display()
             );
+            eprintln!(
+                "Write a Move unit test reproducing it with:\n\n\tmove fuzz repro{fuzz_dir}{options} {target} {artifact}\n",
+                fuzz_dir = &fuzz_dir,
+                options = &run.build,
+                target = &run.target,
+                artifact = artifact.display()
+            );
         }
 
         eprintln!("{:─<80}\n", "");
         bail!("Fuzz target exited with {}", status)
     }
 
-    pub fn exec_tmin(&self, tmin: &options::Tmin, path: &Option<PathBuf>, config: &BuildConfig) -> Result<()> {
-        self.exec_build(BuildMode::Build, &tmin.build, Some(&tmin.target), path, config)?;
-        let mut cmd = self.cargo_run(&tmin.build, &tmin.target, path)?;
+    pub fn exec_tmin(&self, tmin: &options::Tmin, config: &BuildConfig) -> Result<()> {
+        self.exec_build(BuildMode::Build, &tmin.build, Some(&tmin.target), config)?;
+        let mut cmd = self.cargo_run(&tmin.build, &tmin.target)?;
         cmd.arg("-minimize_crash=1")
             .arg(format!("-runs={}", tmin.runs))
             .arg(&tmin.test_case);
@@ -654,7 +863,7 @@ impl FuzzProject {
             // an older version of the libfuzzer crate, and doesn't support
             // `RUST_LIBFUZZER_DEBUG_PATH`.
             if let Ok(debug) =
-                self.run_fuzz_target_debug_formatter(&tmin.build, &tmin.target, artifact, path)
+                self.run_fuzz_target_debug_formatter(&tmin.build, &tmin.target, artifact)
             {
                 eprintln!("Output of `std::fmt::Debug`:\n");
                 for l in debug.lines() {
@@ -681,9 +890,9 @@ impl FuzzProject {
         Ok(())
     }
 
-    pub fn exec_cmin(&self, cmin: &options::Cmin, path: &Option<PathBuf>, config: &BuildConfig) -> Result<()> {
-        self.exec_build(BuildMode::Build, &cmin.build, Some(&cmin.target), path, config)?;
-        let mut cmd = self.cargo_run(&cmin.build, &cmin.target, path)?;
+    pub fn exec_cmin(&self, cmin: &options::Cmin, config: &BuildConfig) -> Result<()> {
+        self.exec_build(BuildMode::Build, &cmin.build, Some(&cmin.target), config)?;
+        let mut cmd = self.cargo_run(&cmin.build, &cmin.target)?;
 
         for arg in &cmin.args {
             cmd.arg(arg);
@@ -720,10 +929,12 @@ impl FuzzProject {
         Ok(())
     }
 
-    /// Produce coverage information for a given corpus
-    pub fn exec_coverage(self, coverage: &options::Coverage, path: &Option<PathBuf>, config: &BuildConfig) -> Result<()> {
-        // Build project with source-based coverage generation enabled.
-        self.exec_build(BuildMode::Build, &coverage.build, Some(&coverage.target), path, config)?;
+    /// Produce Move coverage information for a given corpus: the corpus is replayed through the
+    /// Move VM with instruction tracing enabled, and the trace is reported against the sources of
+    /// the fuzz Move package and its dependencies.
+    pub fn exec_coverage(self, coverage: &options::Coverage, config: &BuildConfig) -> Result<()> {
+        // Build project with Move VM tracing enabled.
+        self.exec_build(BuildMode::Build, &coverage.build, Some(&coverage.target), config)?;
 
         // Retrieve corpus directories.
         let corpora = if coverage.corpus.is_empty() {
@@ -744,53 +955,46 @@ impl FuzzProject {
                 _ => false,
             })
             .peekable();
-        if readable_input_files.peek().is_none() {
+        if readable_input_files.peek().is_none() && !corpora.iter().any(|corpus| corpus.is_file()) {
             bail!(
                 "The corpus does not contain program-input files. \
                  Coverage information requires existing input files. \
-                 Try running the fuzzer first (`cargo fuzz run ...`) to generate a corpus, \
+                 Try running the fuzzer first (`move fuzz run ...`) to generate a corpus, \
                  or provide a nonempty corpus directory."
             )
         }
 
-        let (coverage_out_raw_dir, coverage_out_file) = self.coverage_for(&coverage.target)?;
+        let coverage_dir = self.coverage_for(&coverage.target)?;
+        let trace_path = coverage_dir.join("move.trace");
+        if trace_path.exists() {
+            fs::remove_file(&trace_path)
+                .with_context(|| format!("could not remove {:?}", trace_path))?;
+        }
 
         for corpus in corpora.iter() {
-            // _tmp_dir is deleted when it goes of of scope.
-            let (mut cmd, _tmp_dir) =
-                self.create_coverage_cmd(coverage, &coverage_out_raw_dir, &corpus.as_path(), path)?;
-            eprintln!("Generating coverage data for corpus {:?}", corpus);
+            let mut cmd = self.create_coverage_cmd(coverage, &trace_path, corpus)?;
+            eprintln!("Replaying corpus {:?}", corpus);
             let status = cmd
                 .status()
                 .with_context(|| format!("Failed to run command: {:?}", cmd))?;
             if !status.success() {
-                Err(anyhow!(
-                    "Command exited with failure status {}: {:?}",
-                    status,
-                    cmd
-                ))
-                .context("Failed to generage coverage data")?;
+                // Crashing inputs stop the replay, the trace up to the crash is still reported
+                eprintln!(
+                    "warning: replay of {:?} exited with failure status {}, coverage is partial",
+                    corpus, status
+                );
             }
         }
 
-        let mut profdata_bin_path = coverage.llvm_path.clone().unwrap_or(rustlib()?);
-        profdata_bin_path.push(format!("llvm-profdata{}", env::consts::EXE_SUFFIX));
-        self.merge_coverage(
-            &profdata_bin_path,
-            &coverage_out_raw_dir,
-            &coverage_out_file,
-        )?;
-
-        Ok(())
+        self.report_move_coverage(&trace_path, &coverage_dir)
     }
 
     fn create_coverage_cmd(
         &self,
         coverage: &options::Coverage,
-        coverage_dir: &Path,
-        corpus_dir: &Path,
-        path: &Option<PathBuf>
-    ) -> Result<(Command, tempfile::TempDir)> {
+        trace_path: &Path,
+        corpus: &Path,
+    ) -> Result<Command> {
         let bin_path = {
             let profile_subdir = if coverage.build.dev {
                 "debug"
@@ -799,7 +1003,7 @@ impl FuzzProject {
             };
 
             let target_dir = self
-                .target_dir(&coverage.build, path)?
+                .target_dir(&coverage.build)?
                 .expect("target dir for coverage command should never be None");
             target_dir
                 .join(&coverage.build.cargo_options.triple)
@@ -808,62 +1012,64 @@ impl FuzzProject {
         };
 
         let mut cmd = Command::new(bin_path);
+        cmd.env(move_fuzzer::run_move::FUZZ_DIR_ENV_VAR, self.fuzz_dir());
+        cmd.env(move_fuzzer::run_move::TRACE_PATH_ENV_VAR, trace_path);
 
-        // Raw coverage data will be saved in `coverage/<target>` directory.
-        let corpus_dir_name = corpus_dir
-            .file_name()
-            .and_then(|x| x.to_str())
-            .with_context(|| format!("Invalid corpus directory: {:?}", corpus_dir))?;
-        cmd.env(
-            "LLVM_PROFILE_FILE",
-            coverage_dir.join(format!("default-{}.profraw", corpus_dir_name)),
-        );
-        cmd.arg("-merge=1");
-        let dummy_corpus = tempfile::tempdir()?;
-        cmd.arg(dummy_corpus.path());
-        cmd.arg(corpus_dir);
+        // Execute every input once, without fuzzing
+        cmd.arg("-runs=0");
+        cmd.arg(corpus);
 
         for arg in &coverage.args {
             cmd.arg(arg);
         }
 
-        Ok((cmd, dummy_corpus))
+        Ok(cmd)
     }
 
-    fn merge_coverage(
-        &self,
-        profdata_bin_path: &Path,
-        profdata_raw_path: &Path,
-        profdata_out_path: &Path,
-    ) -> Result<()> {
-        let mut merge_cmd = Command::new(profdata_bin_path);
-        merge_cmd.arg("merge").arg("-sparse");
-        merge_cmd.arg(profdata_raw_path);
-        merge_cmd.arg("-o").arg(profdata_out_path);
-
-        eprintln!("Merging raw coverage data...");
-        let status = merge_cmd
-            .status()
-            .with_context(|| format!("Failed to run command: {:?}", merge_cmd))
-            .with_context(|| "Merging raw coverage files failed.\n\
-                              \n\
-                              Do you have LLVM coverage tools installed?\n\
-                              https://doc.rust-lang.org/rustc/instrument-coverage.html#installing-llvm-coverage-tools")?;
-        if !status.success() {
-            Err(anyhow!(
-                "Command exited with failure status {}: {:?}",
-                status,
-                merge_cmd
-            ))
-            .context("Merging raw coverage files failed")?;
+    /// Writes an annotated copy of the source of every module of the fuzz Move package and its
+    /// dependencies, standard library excluded, and a per-function coverage summary.
+    fn report_move_coverage(&self, trace_path: &Path, coverage_dir: &Path) -> Result<()> {
+        if !trace_path.exists() {
+            bail!("No Move code was executed while replaying the corpus");
         }
-
-        if profdata_out_path.exists() {
-            eprintln!("Coverage data merged and saved in {:?}.", profdata_out_path);
-            Ok(())
-        } else {
-            bail!("Coverage data could not be merged.")
+        let coverage_map = CoverageMap::from_trace_file(trace_path);
+        let package = move_fuzzer::run_move::compiled_package(self.fuzz_dir())?;
+        let units: Vec<_> = package
+            .all_compiled_units_with_source()
+            .filter(|unit| *unit.unit.module.self_id().address() != AccountAddress::ONE)
+            .collect();
+
+        for unit in &units {
+            let NamedCompiledModule {
+                name,
+                module,
+                source_map,
+                ..
+            } = &unit.unit;
+            let source_coverage = SourceCoverageBuilder::new(module, &coverage_map, source_map)
+                .compute_source_coverage(&unit.source_path);
+            let source_path = coverage_dir.join(format!("{}.move.cov", name));
+            let mut file = fs::File::create(&source_path)
+                .with_context(|| format!("could not create {:?}", source_path))?;
+            source_coverage.output_source_coverage(&mut file)?;
         }
+
+        let mut summary = Vec::new();
+        format_human_summary(
+            units.iter().map(|unit| &unit.unit.module),
+            &coverage_map.to_unified_exec_map(),
+            summarize_inst_cov,
+            &mut summary,
+            true,
+        );
+        fs::write(coverage_dir.join("summary.txt"), &summary)?;
+        std::io::stdout().write_all(&summary)?;
+
+        eprintln!(
+            "Move coverage saved in {:?}: `summary.txt` and annotated sources `<module>.move.cov`",
+            coverage_dir
+        );
+        Ok(())
     }
 
     pub(crate) fn fuzz_dir(&self) -> &Path {
@@ -874,18 +1080,19 @@ impl FuzzProject {
         self.fuzz_dir().join("Cargo.toml")
     }
 
-    /// Returns paths to the `coverage/<target>/raw` directory and `coverage/<target>/coverage.profdata` file.
-    fn coverage_for(&self, target: &str) -> Result<(PathBuf, PathBuf)> {
+    fn move_manifest_path(&self) -> PathBuf {
+        self.fuzz_dir().join("Move.toml")
+    }
+
+    /// Returns the path to the `coverage/<target>` directory.
+    fn coverage_for(&self, target: &str) -> Result<PathBuf> {
         let mut coverage_data = self.fuzz_dir().to_owned();
         coverage_data.push("coverage");
         coverage_data.push(target);
-        let mut coverage_raw = coverage_data.clone();
-        coverage_data.push("coverage.profdata");
-        coverage_raw.push("raw");
-        fs::create_dir_all(&coverage_raw).with_context(|| {
-            format!("could not make a coverage directory at {:?}", coverage_raw)
+        fs::create_dir_all(&coverage_data).with_context(|| {
+            format!("could not make a coverage directory at {:?}", coverage_data)
         })?;
-        Ok((coverage_raw, coverage_data))
+        Ok(coverage_data)
     }
 
     fn corpus_for(&self, target: &str) -> Result<PathBuf> {
@@ -963,8 +1170,8 @@ impl FuzzProject {
 
     // If `fuzz_dir_opt` is `None`, returns a new instance with the default fuzz project
     // path. Otherwise, returns a new instance with the inner content of `fuzz_dir_opt`.
-    fn manage_initial_instance(fuzz_dir_opt: Option<PathBuf>) -> Result<Self> {
-        let project_dir = find_package()?;
+    fn manage_initial_instance(fuzz_dir_opt: Option<PathBuf>, path: &Option<PathBuf>) -> Result<Self> {
+        let project_dir = find_package(path)?;
         let fuzz_dir = if let Some(el) = fuzz_dir_opt {
             el
         } else {
@@ -981,21 +1188,22 @@ impl FuzzProject {
     }
 }
 
-fn sysroot() -> Result<String> {
-    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
-    let output = Command::new(rustc).arg("--print").arg("sysroot").output()?;
-    // Note: We must trim() to remove the `\n` from the end of stdout
-    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
-}
-
-fn rustlib() -> Result<PathBuf> {
-    let sysroot = sysroot()?;
-    let mut pathbuf = PathBuf::from(sysroot);
-    pathbuf.push("lib");
-    pathbuf.push("rustlib");
-    pathbuf.push(rustc_version::version_meta()?.host);
-    pathbuf.push("bin");
-    Ok(pathbuf)
+/// Name of the Move test reproducing `input`, a valid identifier derived from the target and
+/// input file names, e.g. `swap_crash_da39a3ee`
+fn repro_test_name(target: &str, input: &Path) -> String {
+    let stem = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name: String = format!("{}_{}", target, stem)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("repro_{}", name)
+    }
 }
 
 fn collect_targets(value: &toml::Value) -> Vec<String> {
@@ -1057,9 +1265,15 @@ fn is_fuzz_manifest(value: &toml::Value) -> bool {
     is_fuzz == Some(true)
 }
 
-/// Returns the path for the first found non-fuzz Cargo package
-fn find_package() -> Result<PathBuf> {
-    let mut dir = env::current_dir()?;
+/// Returns the path for the first found non-fuzz Cargo package, starting from `path` if provided
+/// or from the current directory
+fn find_package(path: &Option<PathBuf>) -> Result<PathBuf> {
+    let mut dir = match path {
+        Some(path) => path
+            .canonicalize()
+            .with_context(|| format!("invalid package path {}", path.display()))?,
+        None => env::current_dir()?,
+    };
     let mut data = Vec::new();
     loop {
         let manifest_path = dir.join("Cargo.toml");
@@ -1088,6 +1302,49 @@ fn find_package() -> Result<PathBuf> {
     bail!("could not find a cargo project")
 }
 
+/// Copies the sources of the standard library and of the nursery shipped with this build of Move
+/// to `vendor_dir`, so that the fuzz package builds without fetching them
+fn vendor_stdlib(vendor_dir: &Path) -> Result<()> {
+    for package in ["", "nursery"] {
+        let from = move_stdlib::path_in_crate(package);
+        let to = vendor_dir.join(package);
+        let sources = to.join(SourcePackageLayout::Sources.path());
+        fs::create_dir_all(&sources)
+            .with_context(|| format!("failed to create directory {}", sources.display()))?;
+
+        let manifest = SourcePackageLayout::Manifest.path();
+        fs::copy(from.join(manifest), to.join(manifest))
+            .with_context(|| format!("failed to copy {}", from.join(manifest).display()))?;
+        let entries = fs::read_dir(from.join(SourcePackageLayout::Sources.path()))
+            .with_context(|| format!("failed to read the sources of {}", from.display()))?;
+        for entry in entries {
+            let source = entry?.path();
+            if source.extension().map_or(false, |ext| ext == MOVE_EXTENSION) {
+                let file_name = source.file_name().unwrap();
+                fs::copy(&source, sources.join(file_name))
+                    .with_context(|| format!("failed to copy {}", source.display()))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the path of `to` relative to the directory `from`, both paths being absolute
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    let path: PathBuf = std::iter::repeat(Component::ParentDir)
+        .take(from.len() - common)
+        .chain(to[common..].iter().copied())
+        .collect();
+    if path.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        path
+    }
+}
+
 fn strip_current_dir_prefix(path: &Path) -> &Path {
     env::current_dir()
         .ok()
@@ -17,6 +17,15 @@ publish = false
 [package.metadata]
 cargo-fuzz = true
 
+# Failed Move executions reported as crashes, besides aborts and invariant violations.
+# Use `[package.metadata.move-fuzz.targets.<target>.oracle]` to configure a single target.
+[package.metadata.move-fuzz.oracle]
+report = ["ARITHMETIC_ERROR", "VECTOR_OPERATION_ERROR", "OUT_OF_GAS"]
+ignore_abort_codes = []
+
+# Set `differential = true` for a target to compare its executions across the `move-execution`
+# cuts, and run it with `--features libfuzzer/differential`.
+
 [dependencies]
 libfuzzer = {{ path = "{libfuzzer_path}" }}
 
@@ -44,25 +53,32 @@ members = ["."]
 }
 
 macro_rules! move_toml_template {
-    ($name:expr, $edition:expr) => {
+    ($name:expr, $edition:expr, $flavor:expr, $stdlib_path:expr, $nursery_path:expr) => {
         format_args!(
             r##"[package]
 name = "{name}_target"
 version = "0.0.0"
-{edition}
-
+{edition}{flavor}
 [dependencies]
-MoveStdlib = {{ git = "https://github.com/move-language/move.git", subdir = "language/move-stdlib", rev = "main" }}
-MoveNursery = {{ git = "https://github.com/move-language/move.git", subdir = "language/move-stdlib/nursery", rev = "main" }}
+MoveStdlib = {{ local = "{stdlib_path}" }}
+MoveNursery = {{ local = "{nursery_path}" }}
 
 [addresses]
-std =  "0x1""##,
+std =  "0x1"
+fuzz = "0x0""##,
             name = $name,
             edition = if let Some(edition) = &$edition {
                 format!("edition = \"{}\"\n", edition)
             } else {
                 String::new()
-            }
+            },
+            flavor = if let Some(flavor) = &$flavor {
+                format!("flavor = \"{}\"\n", flavor)
+            } else {
+                String::new()
+            },
+            stdlib_path = $stdlib_path,
+            nursery_path = $nursery_path,
         )
     };
 }
@@ -116,15 +132,96 @@ fuzz_target!(|data: Vec<u8>| {{
     };
 }
 
-macro_rules! move_target_template {
+macro_rules! rust_function_target_template {
+    ($edition:expr, $function:expr) => {
+        format_args!(
+            r##"#![no_main]
+{extern_crate}
+use libfuzzer::fuzz_function_target;
+
+// The arguments of {function} are generated from its signature
+fuzz_function_target!();
+"##,
+            extern_crate = match $edition.as_deref() {
+                None | Some("2015") => "\nextern crate libfuzzer;\n",
+                Some(_) => "",
+            },
+            function = $function,
+        )
+    };
+}
+
+macro_rules! rust_module_target_template {
     ($edition:expr) => {
         format_args!(
-            r##"script {{
-    fun main(data: vector<u8>) {{
+            r##"#![no_main]
+{extern_crate}
+use libfuzzer::fuzz_module_target;
+
+// Compiled modules, mutated structurally before being deserialized and verified
+fuzz_module_target!();
+"##,
+            extern_crate = match $edition.as_deref() {
+                None | Some("2015") => "\nextern crate libfuzzer;\n",
+                Some(_) => "",
+            },
+        )
+    };
+}
+
+macro_rules! toml_function_target_template {
+    ($name:expr, $function:expr) => {
+        format_args!(
+            r#"
+[package.metadata.move-fuzz.targets.{0}]
+function = "{1}"
+"#,
+            $name, $function
+        )
+    };
+}
+
+macro_rules! rust_sequence_target_template {
+    ($edition:expr, $module:expr) => {
+        format_args!(
+            r##"#![no_main]
+{extern_crate}
+use libfuzzer::fuzz_sequence_target;
+
+// Sequences of calls to the public functions of {module}
+fuzz_sequence_target!();
+"##,
+            extern_crate = match $edition.as_deref() {
+                None | Some("2015") => "\nextern crate libfuzzer;\n",
+                Some(_) => "",
+            },
+            module = $module,
+        )
+    };
+}
+
+macro_rules! toml_sequence_target_template {
+    ($name:expr, $module:expr) => {
+        format_args!(
+            r#"
+[package.metadata.move-fuzz.targets.{0}]
+sequence = "{1}"
+"#,
+            $name, $module
+        )
+    };
+}
+
+macro_rules! move_target_template {
+    ($name:expr) => {
+        format_args!(
+            r##"module fuzz::{name} {{
+    public fun main(data: vector<u8>) {{
         // fuzzing code goes here
     }}
 }}
-"##
+"##,
+            name = $name
         )
     };
 }
@@ -3,8 +3,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use base::{
-    build::Build, coverage::Coverage, disassemble::Disassemble, docgen::Docgen, errmap::Errmap,
-    info::Info, migrate::Migrate, new::New, test::Test,
+    build::Build, coverage::Coverage, debug::Debug, disassemble::Disassemble, docgen::Docgen,
+    errmap::Errmap, fmt::Fmt, info::Info, migrate::Migrate, new::New, replay::Replay, test::Test,
 };
 use move_package::BuildConfig;
 
@@ -61,9 +61,11 @@ pub struct MoveCLI {
 pub enum Command {
     Build(Build),
     Coverage(Coverage),
+    Debug(Debug),
     Disassemble(Disassemble),
     Docgen(Docgen),
     Errmap(Errmap),
+    Fmt(Fmt),
     Fuzz {
         #[clap(subcommand)]
         cmd: fuzz::Fuzz
@@ -71,6 +73,7 @@ pub enum Command {
     Info(Info),
     Migrate(Migrate),
     New(New),
+    Replay(Replay),
     Test(Test),
     /// Execute a sandbox command.
     #[clap(name = "sandbox")]
@@ -97,13 +100,22 @@ pub fn run_cli(
     match cmd {
         Command::Build(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Coverage(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Debug(c) => c.execute(
+            move_args.package_path,
+            move_args.build_config,
+            natives,
+            cost_table,
+            error_descriptions,
+        ),
         Command::Disassemble(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Docgen(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Errmap(c) => c.execute(move_args.package_path, move_args.build_config),
+        Command::Fmt(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Fuzz{cmd} => cmd.execute(move_args.package_path, move_args.build_config),
         Command::Info(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::Migrate(c) => c.execute(move_args.package_path, move_args.build_config),
         Command::New(c) => c.execute_with_defaults(move_args.package_path),
+        Command::Replay(c) => c.execute(natives, cost_table),
         Command::Test(c) => c.execute(
             move_args.package_path,
             move_args.build_config,
@@ -95,10 +95,13 @@ pub enum SandboxCommand {
         /// By default, no `gas-budget` is specified and gas metering is disabled.
         #[clap(long = "gas-budget", short = 'g')]
         gas_budget: Option<u64>,
-        /// If set, the effects of executing `script_file` (i.e., published, updated, and
-        /// deleted resources) will NOT be committed to disk.
+        /// If set, the effects of executing `module_file` (i.e., published, updated, and
+        /// deleted resources, and emitted events) will NOT be committed to disk.
         #[clap(long = "dry-run", short = 'n')]
         dry_run: bool,
+        /// If set, append the execution trace of the run to this file, see `move_trace_format`.
+        #[clap(long = "trace")]
+        trace: Option<PathBuf>,
     },
     /// Run expected value tests using the given batch file.
     #[clap(name = "exp-test")]
@@ -118,6 +121,9 @@ pub enum SandboxCommand {
         /// Path to a resource, events file, or module stored on disk.
         #[clap(name = "file")]
         file: PathBuf,
+        /// Print resources and events as JSON.
+        #[clap(long = "json")]
+        json: bool,
     },
     /// Delete all resources, events, and modules stored on disk under `storage-dir`.
     /// Does *not* delete anything in `src`.
@@ -222,6 +228,7 @@ impl SandboxCommand {
                 type_args,
                 gas_budget,
                 dry_run,
+                trace,
             } => {
                 let context =
                     PackageContext::new(&move_args.package_path, &move_args.build_config)?;
@@ -239,6 +246,7 @@ impl SandboxCommand {
                     type_args.to_vec(),
                     *gas_budget,
                     *dry_run,
+                    trace.as_deref(),
                     move_args.verbose,
                 )
             }
@@ -254,10 +262,10 @@ impl SandboxCommand {
                 *use_temp_dir,
                 *track_cov,
             ),
-            SandboxCommand::View { file } => {
+            SandboxCommand::View { file, json } => {
                 let state = PackageContext::new(&move_args.package_path, &move_args.build_config)?
                     .prepare_state(storage_dir)?;
-                sandbox::commands::view(&state, file)
+                sandbox::commands::view(natives, &state, file, *json)
             }
             SandboxCommand::Clean {} => {
                 // delete storage
@@ -4,8 +4,8 @@
 
 use crate::{
     sandbox::utils::{
-        contains_module, explain_execution_error, get_gas_status, is_bytecode_file,
-        on_disk_state_view::OnDiskStateView,
+        contains_module, explain_execution_effects, explain_execution_error, get_gas_status,
+        is_bytecode_file, maybe_commit_effects, on_disk_state_view::OnDiskStateView,
     },
     NativeFunctionRecord,
 };
@@ -21,6 +21,7 @@ use move_core_types::{
     transaction_argument::{convert_txn_args, TransactionArgument},
 };
 use move_package::compilation::compiled_package::CompiledPackage;
+use move_vm_config::runtime::{VMConfig, VMTracerConfig};
 use move_vm_runtime::move_vm::MoveVM;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::{fs, path::Path};
@@ -37,7 +38,8 @@ pub fn run(
     txn_args: &[TransactionArgument],
     vm_type_tags: Vec<TypeTag>,
     gas_budget: Option<u64>,
-    _dry_run: bool,
+    dry_run: bool,
+    trace_path: Option<&Path>,
     _verbose: bool,
 ) -> Result<()> {
     if !try_exists(module_file)? {
@@ -59,7 +61,13 @@ pub fn run(
     // TODO: parse Value's directly instead of going through the indirection of TransactionArgument?
     let vm_args: Vec<Vec<u8>> = convert_txn_args(txn_args);
 
-    let vm = MoveVM::new(natives).unwrap();
+    let vm_config = VMConfig {
+        tracer_config: trace_path.map(|trace_path| VMTracerConfig {
+            trace_path: trace_path.to_path_buf(),
+        }),
+        ..Default::default()
+    };
+    let vm = MoveVM::new_with_config(natives, vm_config).unwrap();
     let mut gas_status = get_gas_status(cost_table, gas_budget)?;
     let mut session = vm.new_session(state);
 
@@ -118,7 +126,8 @@ pub fn run(
             txn_args,
         )
     } else {
-        let (_changeset, _events) = session.finish().0?;
-        Ok(())
+        let (changeset, events) = session.finish().0?;
+        explain_execution_effects(&changeset, &events);
+        maybe_commit_effects(!dry_run, changeset, events, state)
     }
 }
@@ -43,10 +43,6 @@ const NO_MOVE_CLEAN: &str = "NO_MOVE_CLEAN";
 /// The filename that contains the arguments to the Move binary.
 pub const TEST_ARGS_FILENAME: &str = "args.txt";
 
-/// Name of the environment variable we need to set in order to get tracing
-/// enabled in the move VM.
-const MOVE_VM_TRACING_ENV_VAR_NAME: &str = "MOVE_VM_TRACE";
-
 /// The default file name (inside the build output dir) for the runtime to
 /// dump the execution trace to. The trace will be used by the coverage tool
 /// if --track-cov is set. If --track-cov is not set, then no trace file will
@@ -257,20 +253,16 @@ pub fn run_one(
             continue;
         }
 
-        // enable tracing in the VM by setting the env var.
-        match &trace_file {
-            None => {
-                // this check prevents cascading the coverage tracking flag.
-                // in particular, if
-                //   1. we run with move-cli test <path-to-args-A.txt> --track-cov, and
-                //   2. in this <args-A.txt>, there is another command: test <args-B.txt>
-                // then, when running <args-B.txt>, coverage will not be tracked nor printed
-                env::remove_var(MOVE_VM_TRACING_ENV_VAR_NAME);
+        let mut command = cli_command_template();
+        command.args(&args_iter);
+        // trace the execution of the runs to compute their coverage
+        if let Some(path) = &trace_file {
+            if args_iter.starts_with(&["sandbox", "run"]) {
+                command.arg("--trace").arg(path);
             }
-            Some(path) => env::set_var(MOVE_VM_TRACING_ENV_VAR_NAME, path.as_os_str()),
         }
 
-        let cmd_output = cli_command_template().args(args_iter).output()?;
+        let cmd_output = command.output()?;
         writeln!(&mut output, "Command `{}`:", args_line)?;
         output += std::str::from_utf8(&cmd_output.stdout)?;
         output += std::str::from_utf8(&cmd_output.stderr)?;
@@ -2,20 +2,95 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sandbox::utils::{is_bytecode_file, on_disk_state_view::OnDiskStateView};
+use crate::{
+    sandbox::utils::{is_bytecode_file, on_disk_state_view::OnDiskStateView},
+    NativeFunctionRecord,
+};
 
-use anyhow::{bail, Result};
-use std::path::Path;
-/// Print a module or resource stored in `file`
-pub fn view(_state: &OnDiskStateView, path: &Path) -> Result<()> {
-    if is_bytecode_file(path) {
-        let bytecode_opt = OnDiskStateView::view_module(path)?;
-        match bytecode_opt {
-            Some(bytecode) => println!("{}", bytecode),
-            None => println!("Bytecode not found."),
+use anyhow::{anyhow, bail, Result};
+use move_core_types::{annotated_value::MoveValue, language_storage::TypeTag};
+use move_vm_runtime::{move_vm::MoveVM, session::Session};
+use std::{fs, path::Path};
+
+/// Print a module, resource, or event log stored in `path`
+pub fn view(
+    natives: impl IntoIterator<Item = NativeFunctionRecord>,
+    state: &OnDiskStateView,
+    path: &Path,
+    json: bool,
+) -> Result<()> {
+    println!("{}", view_file(natives, state, path, json)?);
+    Ok(())
+}
+
+/// Render the module, resource, or event log stored in `path`. Resources and events are decoded
+/// using the layouts of their types, as loaded from the modules in `state`, and rendered as JSON
+/// if `json` is set.
+pub fn view_file(
+    natives: impl IntoIterator<Item = NativeFunctionRecord>,
+    state: &OnDiskStateView,
+    path: &Path,
+    json: bool,
+) -> Result<String> {
+    if let Some(tag) = state.get_resource_tag(path) {
+        let vm = MoveVM::new(natives).unwrap();
+        let session = vm.new_session(state);
+        let bytes = fs::read(path)?;
+        let resource = annotate(&session, &TypeTag::Struct(Box::new(tag)), &bytes)?;
+        if json {
+            Ok(serde_json::to_string_pretty(&resource)?)
+        } else {
+            Ok(format!("{:#}", resource))
+        }
+    } else if state.is_event_path(path) {
+        let vm = MoveVM::new(natives).unwrap();
+        let session = vm.new_session(state);
+        let events = OnDiskStateView::read_event_log(path)?
+            .into_iter()
+            .map(|(sequence_number, event_type, data)| {
+                let event = annotate(&session, &event_type, &data)?;
+                Ok((sequence_number, event_type, event))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if json {
+            let events: Vec<_> = events
+                .into_iter()
+                .map(|(sequence_number, event_type, event)| {
+                    serde_json::json!({
+                        "sequence_number": sequence_number,
+                        "type": event_type.to_string(),
+                        "data": event,
+                    })
+                })
+                .collect();
+            Ok(serde_json::to_string_pretty(&events)?)
+        } else {
+            Ok(events
+                .into_iter()
+                .map(|(sequence_number, _, event)| format!("[{}] {:#}", sequence_number, event))
+                .collect::<Vec<_>>()
+                .join("\n"))
         }
+    } else if is_bytecode_file(path) {
+        if json {
+            bail!("`--json` is only supported for resources and events")
+        }
+        Ok(match OnDiskStateView::view_module(path)? {
+            Some(bytecode) => bytecode,
+            None => "Bytecode not found.".to_string(),
+        })
     } else {
         bail!("`move view <file>` must point to a valid file under storage")
     }
-    Ok(())
+}
+
+/// Decode `bytes` as a value of type `type_`, annotated with the names of its fields and types
+fn annotate(
+    session: &Session<'_, '_, &OnDiskStateView>,
+    type_: &TypeTag,
+    bytes: &[u8],
+) -> Result<MoveValue> {
+    let layout = session.get_fully_annotated_type_layout(type_)?;
+    MoveValue::simple_deserialize(bytes, &layout)
+        .map_err(|e| anyhow!("Failed to decode value of type {}: {}", type_, e))
 }
@@ -16,7 +16,7 @@ use move_command_line_common::files::{FileHash, MOVE_COMPILED_EXTENSION};
 use move_compiler::diagnostics::{self, report_diagnostics, Diagnostic, Diagnostics, FileName};
 use move_core_types::{
     account_address::AccountAddress,
-    effects::{ChangeSet, Op},
+    effects::{ChangeSet, Event, Op},
     errmap::ErrorMapping,
     language_storage::{ModuleId, TypeTag},
     transaction_argument::TransactionArgument,
@@ -91,6 +91,92 @@ pub(crate) fn explain_publish_changeset(changeset: &ChangeSet) {
     )
 }
 
+pub(crate) fn explain_execution_effects(changeset: &ChangeSet, events: &[Event]) {
+    // execution effects should contain no modules
+    assert!(changeset.modules().next().is_none());
+    if !events.is_empty() {
+        println!("Emitted {:?} events:", events.len());
+        for (guid, sequence_number, event_type, event_data) in events {
+            println!(
+                "  Emitted {} as event {} of stream {} ({:?} bytes)",
+                event_type,
+                sequence_number,
+                hex::encode(guid),
+                event_data.len()
+            )
+        }
+    }
+    if !changeset.accounts().is_empty() {
+        println!(
+            "Changed resource(s) under {:?} address(es):",
+            changeset.accounts().len()
+        );
+    }
+    // total bytes written across all accounts
+    let mut total_bytes_written = 0;
+    for (addr, account) in changeset.accounts() {
+        println!(
+            "  Changed {:?} resource(s) under address {}:",
+            account.resources().len(),
+            addr.to_hex_literal()
+        );
+        for (struct_tag, op) in account.resources() {
+            match op {
+                Op::New(blob) | Op::Modify(blob) => {
+                    let bytes_written = addr.len() + struct_tag.access_vector().len() + blob.len();
+                    total_bytes_written += bytes_written;
+                    let verb = if matches!(op, Op::New(_)) {
+                        "Added"
+                    } else {
+                        "Modified"
+                    };
+                    println!(
+                        "    {} type {} (wrote {:?} bytes)",
+                        verb, struct_tag, bytes_written
+                    )
+                }
+                Op::Delete => println!("    Deleted type {}", struct_tag),
+            }
+        }
+    }
+    if total_bytes_written != 0 {
+        println!(
+            "Wrote {:?} bytes of resource ID's and data",
+            total_bytes_written
+        );
+    }
+}
+
+/// Commit the resources and events produced by an execution to disk, unless `commit` is false
+pub(crate) fn maybe_commit_effects(
+    commit: bool,
+    changeset: ChangeSet,
+    events: Vec<Event>,
+    state: &OnDiskStateView,
+) -> Result<()> {
+    // all module publishing happens via `save_modules`, so effects shouldn't contain modules
+    if !commit {
+        if !(changeset.accounts().is_empty() && events.is_empty()) {
+            println!(
+                "Discarding changes; re-run without `--dry-run` if you would like to keep them."
+            )
+        }
+        return Ok(());
+    }
+    for (addr, account) in changeset.into_inner() {
+        for (struct_tag, op) in account.into_resources() {
+            match op {
+                Op::New(blob) | Op::Modify(blob) => state.save_resource(addr, struct_tag, &blob)?,
+                Op::Delete => state.delete_resource(addr, struct_tag)?,
+            }
+        }
+    }
+    for (guid, sequence_number, event_type, event_data) in events {
+        state.save_event(&guid, sequence_number, event_type, event_data)?
+    }
+    Ok(())
+}
+
 pub(crate) fn explain_type_error(
     script_params: &[SignatureToken],
     signers: &[AccountAddress],
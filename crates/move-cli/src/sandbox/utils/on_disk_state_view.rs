@@ -11,6 +11,7 @@ use move_core_types::{
     account_address::AccountAddress,
     identifier::Identifier,
     language_storage::{ModuleId, StructTag, TypeTag},
+    parser,
     resolver::{LinkageResolver, ModuleResolver, ResourceResolver},
 };
 use move_disassembler::disassembler::Disassembler;
@@ -23,9 +24,21 @@ use std::{
 /// subdirectory of `DEFAULT_STORAGE_DIR`/<addr> where modules are stored
 pub const MODULES_DIR: &str = "modules";
 
+/// subdirectory of `DEFAULT_STORAGE_DIR`/<addr> where resources are stored
+pub const RESOURCES_DIR: &str = "resources";
+
+/// subdirectory of `DEFAULT_STORAGE_DIR` where events are stored
+pub const EVENTS_DIR: &str = "events";
+
+/// extension of files holding BCS encoded resources and event logs
+pub const BCS_EXTENSION: &str = "bcs";
+
 /// file under `DEFAULT_BUILD_DIR` where a registry of generated struct layouts are stored
 pub const STRUCT_LAYOUTS_FILE: &str = "struct_layouts.yaml";
 
+/// An event in the log of its stream: its sequence number, type, and BCS encoded data
+pub type EventLogEntry = (u64, TypeTag, Vec<u8>);
+
 #[derive(Debug)]
 pub struct OnDiskStateView {
     build_dir: PathBuf,
@@ -77,12 +90,35 @@ impl OnDiskStateView {
         self.is_data_path(p, MODULES_DIR)
     }
 
+    pub fn is_resource_path(&self, p: &Path) -> bool {
+        self.is_data_path(p, RESOURCES_DIR)
+    }
+
+    pub fn is_event_path(&self, p: &Path) -> bool {
+        self.is_data_path(p, EVENTS_DIR)
+    }
+
     fn get_addr_path(&self, addr: &AccountAddress) -> PathBuf {
         let mut path = self.storage_dir.clone();
         path.push(format!("0x{}", addr));
         path
     }
 
+    fn get_resource_path(&self, addr: AccountAddress, tag: StructTag) -> PathBuf {
+        let mut path = self.get_addr_path(&addr);
+        path.push(RESOURCES_DIR);
+        path.push(StructID(tag).to_string());
+        path.with_extension(BCS_EXTENSION)
+    }
+
+    // Events are stored under the hex encoding of the GUID of their stream
+    fn get_event_path(&self, guid: &[u8]) -> PathBuf {
+        let mut path = self.storage_dir.clone();
+        path.push(EVENTS_DIR);
+        path.push(hex::encode(guid));
+        path.with_extension(BCS_EXTENSION)
+    }
+
     fn get_module_path(&self, module_id: &ModuleId) -> PathBuf {
         let mut path = self.get_addr_path(module_id.address());
         path.push(MODULES_DIR);
@@ -108,6 +144,36 @@ impl OnDiskStateView {
     }
 
     /// Read the resource bytes stored on-disk at `addr`/`tag`
+    pub fn get_resource_bytes(
+        &self,
+        addr: AccountAddress,
+        tag: StructTag,
+    ) -> Result<Option<Vec<u8>>> {
+        Self::get_bytes(&self.get_resource_path(addr, tag))
+    }
+
+    /// Extract the type of the resource stored at a path
+    pub fn get_resource_tag(&self, p: &Path) -> Option<StructTag> {
+        if !self.is_resource_path(p) {
+            return None;
+        }
+        parser::parse_struct_tag(p.file_stem()?.to_str()?).ok()
+    }
+
+    /// Read the event log stored on-disk for the stream `guid`
+    pub fn get_events(&self, guid: &[u8]) -> Result<Vec<EventLogEntry>> {
+        Self::read_event_log(&self.get_event_path(guid))
+    }
+
+    /// Read the event log stored on-disk at `path`
+    pub fn read_event_log(path: &Path) -> Result<Vec<EventLogEntry>> {
+        Ok(match Self::get_bytes(path)? {
+            Some(bytes) => bcs::from_bytes(&bytes)?,
+            None => vec![],
+        })
+    }
+
+    /// Read the module bytes stored on-disk at `addr`/`module_id`
     fn get_module_bytes(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>> {
         Self::get_bytes(&self.get_module_path(module_id))
     }
@@ -162,6 +228,37 @@ impl OnDiskStateView {
         Self::view_bytecode(module_path)
     }
 
+    /// Save `bcs_bytes` on disk under the path `addr`/`tag`
+    pub fn save_resource(
+        &self,
+        addr: AccountAddress,
+        tag: StructTag,
+        bcs_bytes: &[u8],
+    ) -> Result<()> {
+        let path = self.get_resource_path(addr, tag);
+        if !path.exists() {
+            fs::create_dir_all(path.parent().unwrap())?
+        }
+        Ok(fs::write(path, bcs_bytes)?)
+    }
+
+    /// Append an event to the log of the stream `guid`
+    pub fn save_event(
+        &self,
+        guid: &[u8],
+        sequence_number: u64,
+        event_type: TypeTag,
+        event_data: Vec<u8>,
+    ) -> Result<()> {
+        let path = self.get_event_path(guid);
+        if !path.exists() {
+            fs::create_dir_all(path.parent().unwrap())?
+        }
+        let mut event_log = Self::read_event_log(&path)?;
+        event_log.push((sequence_number, event_type, event_data));
+        Ok(fs::write(path, bcs::to_bytes(&event_log)?)?)
+    }
+
     /// Save `module` on disk under the path `module.address()`/`module.name()`
     pub fn save_module(&self, module_id: &ModuleId, module_bytes: &[u8]) -> Result<()> {
         let path = self.get_module_path(module_id);
@@ -203,6 +300,20 @@ impl OnDiskStateView {
         Ok(())
     }
 
+    pub fn delete_resource(&self, addr: AccountAddress, tag: StructTag) -> Result<()> {
+        let path = self.get_resource_path(addr, tag);
+        fs::remove_file(&path)?;
+
+        // delete the resources and addr directories if they are now empty
+        for dir in path.ancestors().skip(1).take(2) {
+            if dir.read_dir()?.next().is_some() {
+                break;
+            }
+            fs::remove_dir(dir)?
+        }
+        Ok(())
+    }
+
     fn iter_paths<F>(&self, f: F) -> impl Iterator<Item = PathBuf>
     where
         F: FnOnce(&Path) -> bool + Copy,
@@ -219,6 +330,14 @@ impl OnDiskStateView {
         self.iter_paths(move |p| self.is_module_path(p))
     }
 
+    pub fn resource_paths(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.iter_paths(move |p| self.is_resource_path(p))
+    }
+
+    pub fn event_paths(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.iter_paths(move |p| self.is_event_path(p))
+    }
+
     /// Build all modules in the self.storage_dir.
     /// Returns an Err if a module does not deserialize.
     pub fn get_all_modules(&self) -> Result<Vec<CompiledModule>> {
@@ -248,10 +367,10 @@ impl ResourceResolver for OnDiskStateView {
 
     fn get_resource(
         &self,
-        _address: &AccountAddress,
-        _struct_tag: &StructTag,
+        address: &AccountAddress,
+        struct_tag: &StructTag,
     ) -> Result<Option<Vec<u8>>, Self::Error> {
-        unimplemented!()
+        self.get_resource_bytes(*address, struct_tag.clone())
     }
 }
 
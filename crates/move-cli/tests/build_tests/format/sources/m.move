@@ -0,0 +1,20 @@
+module Test::util{
+    public fun double(x:u64):u64{x*2}
+  public fun add(x:u64,y:u64):u64 { x+y }
+}
+
+module Test::m{
+  use Test::util::{double,add};
+
+    /// Doc comments stay attached to their item
+    public struct S has drop{f:u64,g:u64}
+
+
+    public fun new(f:u64):S{ let mut g=double(f); g=g-1; S{f,g} }
+
+    public fun sum(s:&S):u64 {
+        let n = add(s.f, *&s.g); // trailing comment
+        if (n==0) 0
+        else s.f+n
+    }
+}
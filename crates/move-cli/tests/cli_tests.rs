@@ -2,8 +2,27 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use move_cli::sandbox::commands::test;
-use std::{env, path::PathBuf};
+use move_cli::sandbox::{
+    commands::{test, view_file},
+    utils::on_disk_state_view::OnDiskStateView,
+};
+use move_command_line_common::address::NumericalAddress;
+use move_compiler::Compiler;
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+    resolver::ResourceResolver,
+};
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    env,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
 
 pub const CLI_METATEST_PATH: [&str; 3] = ["tests", "metatests", "args.txt"];
 
@@ -33,3 +52,330 @@ fn run_metatest() {
     // temp workspace + without coverage
     assert!(test::run_all(&path_metatest, &path_cli_binary, true, false).is_ok());
 }
+
+#[test]
+fn resources_and_events_persist_on_disk() {
+    let dir = tempfile::tempdir().unwrap();
+    let state =
+        OnDiskStateView::create(dir.path().join("build"), dir.path().join("storage")).unwrap();
+    let addr = AccountAddress::from_hex_literal("0x42").unwrap();
+    let tag = StructTag {
+        address: addr,
+        module: Identifier::new("m").unwrap(),
+        name: Identifier::new("R").unwrap(),
+        type_params: vec![TypeTag::U64],
+    };
+
+    assert_eq!(state.get_resource(&addr, &tag).unwrap(), None);
+    state.save_resource(addr, tag.clone(), &[1, 2, 3]).unwrap();
+    assert_eq!(
+        state.get_resource(&addr, &tag).unwrap(),
+        Some(vec![1, 2, 3])
+    );
+    assert_eq!(state.resource_paths().count(), 1);
+    state.delete_resource(addr, tag.clone()).unwrap();
+    assert_eq!(state.get_resource(&addr, &tag).unwrap(), None);
+    assert_eq!(state.resource_paths().count(), 0);
+
+    let guid = vec![0, 1];
+    state.save_event(&guid, 0, TypeTag::U64, vec![4]).unwrap();
+    state.save_event(&guid, 1, TypeTag::Bool, vec![5]).unwrap();
+    assert_eq!(
+        state.get_events(&guid).unwrap(),
+        vec![(0, TypeTag::U64, vec![4]), (1, TypeTag::Bool, vec![5])]
+    );
+    assert_eq!(state.event_paths().count(), 1);
+}
+
+#[test]
+fn view_annotates_resources_and_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = dir.path().join("m.move");
+    std::fs::write(
+        &source,
+        "module 0x42::m { struct I has store { b: bool } struct R has store { x: u64, v: vector<u8>, i: I } }",
+    )
+    .unwrap();
+    let (_, units) = Compiler::from_files(
+        None,
+        vec![source.to_str().unwrap()],
+        vec![],
+        BTreeMap::<&str, NumericalAddress>::new(),
+    )
+    .build_and_report()
+    .unwrap();
+    let module = units.into_iter().next().unwrap().into_compiled_unit();
+
+    let state =
+        OnDiskStateView::create(dir.path().join("build"), dir.path().join("storage")).unwrap();
+    let addr = AccountAddress::from_hex_literal("0x42").unwrap();
+    state
+        .save_module(&module.module.self_id(), &module.serialize(None))
+        .unwrap();
+    let tag = StructTag {
+        address: addr,
+        module: Identifier::new("m").unwrap(),
+        name: Identifier::new("R").unwrap(),
+        type_params: vec![],
+    };
+    let resource = bcs::to_bytes(&(7u64, vec![1u8, 2], true)).unwrap();
+    state.save_resource(addr, tag.clone(), &resource).unwrap();
+    state
+        .save_event(&[0], 0, TypeTag::Struct(Box::new(tag)), resource)
+        .unwrap();
+
+    let resource_path = state.resource_paths().next().unwrap();
+    let text = view_file(vec![], &state, &resource_path, false).unwrap();
+    for expected in [
+        "0x42::m::R",
+        "x: 7u64",
+        "v: vector[",
+        "i: 0x42::m::I",
+        "b: true",
+    ] {
+        assert!(text.contains(expected), "{expected} not in {text}");
+    }
+    let json: serde_json::Value =
+        serde_json::from_str(&view_file(vec![], &state, &resource_path, true).unwrap()).unwrap();
+    assert_eq!(json["fields"]["x"], 7);
+    assert_eq!(json["fields"]["v"], serde_json::json!([1, 2]));
+    assert_eq!(json["fields"]["i"]["fields"]["b"], true);
+
+    let event_path = state.event_paths().next().unwrap();
+    let text = view_file(vec![], &state, &event_path, false).unwrap();
+    assert!(text.starts_with("[0] 0x42::m::R"), "{text}");
+    let json: serde_json::Value =
+        serde_json::from_str(&view_file(vec![], &state, &event_path, true).unwrap()).unwrap();
+    assert_eq!(json[0]["sequence_number"], 0);
+    assert_eq!(json[0]["data"]["fields"]["x"], 7);
+}
+
+struct DapClient {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    seq: u64,
+}
+
+impl DapClient {
+    fn request(&mut self, command: &str, arguments: Value) -> Value {
+        self.seq += 1;
+        let content = json!({
+            "seq": self.seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        })
+        .to_string();
+        write!(
+            self.writer,
+            "Content-Length: {}\r\n\r\n{}",
+            content.len(),
+            content
+        )
+        .unwrap();
+        let response = self.wait_for(|msg| msg["type"] == "response");
+        assert_eq!(response["command"], command);
+        assert_eq!(response["success"], true, "{response}");
+        response["body"].clone()
+    }
+
+    /// Read messages until one satisfies `pred`, and return it
+    fn wait_for(&mut self, pred: impl Fn(&Value) -> bool) -> Value {
+        loop {
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                assert!(self.reader.read_line(&mut header).unwrap() > 0);
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                if let Some(length) = header.strip_prefix("Content-Length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+            }
+            let mut content = vec![0; content_length];
+            self.reader.read_exact(&mut content).unwrap();
+            let msg: Value = serde_json::from_slice(&content).unwrap();
+            if pred(&msg) {
+                return msg;
+            }
+        }
+    }
+}
+
+#[test]
+fn debug_unit_test_over_dap() {
+    let dir = tempfile::tempdir().unwrap();
+    let stdlib = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../move-stdlib");
+    std::fs::write(
+        dir.path().join("Move.toml"),
+        format!(
+            "[package]\nname = \"Dbg\"\n\n[addresses]\nstd = \"0x1\"\ndbg = \"0x42\"\n\n\
+             [dependencies]\nMoveStdlib = {{ local = {:?} }}\n",
+            stdlib.display().to_string()
+        ),
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join("sources")).unwrap();
+    let source = dir.path().join("sources").join("m.move");
+    std::fs::write(
+        &source,
+        r#"module dbg::m {
+    struct S has drop { x: u64, v: vector<u8> }
+    fun make(x: u64): S {
+        S { x, v: vector[1, 2] }
+    }
+    #[test]
+    fun test_make() {
+        let s = make(7);
+        assert!(s.x == 7, 0);
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let mut server = Command::new(get_cli_binary_path())
+        .args(["debug", "--port", &port.to_string(), "-p"])
+        .arg(dir.path())
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    let stream = loop {
+        if let Ok(stream) = TcpStream::connect(("127.0.0.1", port)) {
+            break stream;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+    let mut client = DapClient {
+        reader: BufReader::new(stream.try_clone().unwrap()),
+        writer: stream,
+        seq: 0,
+    };
+
+    client.request("initialize", json!({ "adapterID": "move" }));
+    client.request("launch", json!({ "mode": "test" }));
+    let breakpoints = client.request(
+        "setBreakpoints",
+        json!({ "source": { "path": source }, "breakpoints": [{ "line": 9 }, { "line": 2 }] }),
+    );
+    assert_eq!(breakpoints["breakpoints"][0]["verified"], true);
+    assert_eq!(breakpoints["breakpoints"][1]["verified"], false);
+    client.request("configurationDone", json!({}));
+
+    let stopped = client.wait_for(|msg| msg["event"] == "stopped");
+    assert_eq!(stopped["body"]["reason"], "breakpoint");
+    let frames = client.request("stackTrace", json!({ "threadId": 1 }));
+    let frame = &frames["stackFrames"][0];
+    assert_eq!(frame["line"], 9);
+    assert!(frame["name"].as_str().unwrap().ends_with("::m::test_make"));
+
+    let scopes = client.request("scopes", json!({ "frameId": frame["id"] }));
+    let locals = client.request(
+        "variables",
+        json!({ "variablesReference": scopes["scopes"][0]["variablesReference"] }),
+    );
+    let s = &locals["variables"][0];
+    assert_eq!(s["name"], "s");
+    let fields = client.request(
+        "variables",
+        json!({ "variablesReference": s["variablesReference"] }),
+    );
+    assert_eq!(fields["variables"][0]["name"], "x");
+    assert_eq!(fields["variables"][0]["value"], "7u64");
+    assert_eq!(fields["variables"][1]["name"], "v");
+
+    client.request("continue", json!({ "threadId": 1 }));
+    let exited = client.wait_for(|msg| msg["event"] == "exited");
+    assert_eq!(exited["body"]["exitCode"], 0);
+    client.request("disconnect", json!({}));
+    assert!(server.wait().unwrap().success());
+}
+
+#[test]
+fn replay_reports_first_divergence() {
+    let dir = tempfile::tempdir().unwrap();
+    let stdlib = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../move-stdlib");
+    std::fs::write(
+        dir.path().join("Move.toml"),
+        format!(
+            "[package]\nname = \"Rp\"\n\n[addresses]\nstd = \"0x1\"\nrp = \"0x42\"\n\n\
+             [dependencies]\nMoveStdlib = {{ local = {:?} }}\n",
+            stdlib.display().to_string()
+        ),
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join("sources")).unwrap();
+    std::fs::write(
+        dir.path().join("sources").join("m.move"),
+        r#"module rp::m {
+    public entry fun f(x: u64) {
+        let y = add(x, 1);
+        assert!(y == x + 1, 0);
+    }
+    fun add(a: u64, b: u64): u64 {
+        a + b
+    }
+}
+"#,
+    )
+    .unwrap();
+    let move_cmd = |args: &[&str]| {
+        Command::new(get_cli_binary_path())
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .unwrap()
+    };
+
+    assert!(move_cmd(&["sandbox", "publish"]).status.success());
+    let module = format!(
+        "storage/0x{}/modules/m.mv",
+        AccountAddress::from_hex_literal("0x42").unwrap().to_hex()
+    );
+    let run = move_cmd(&[
+        "sandbox",
+        "run",
+        &module,
+        "f",
+        "--dry-run",
+        "--trace",
+        "trace",
+        "--args",
+        "5u64",
+    ]);
+    assert!(run.status.success(), "{run:?}");
+
+    let replay = move_cmd(&["replay", "trace", "--args", "5u64"]);
+    assert!(replay.status.success(), "{replay:?}");
+    let stdout = String::from_utf8(replay.stdout).unwrap();
+    assert!(
+        stdout.contains("the execution matches the trace"),
+        "{stdout}"
+    );
+
+    // The replay computes 5 + 1 = 6, where the trace now records 7
+    let trace_path = dir.path().join("trace");
+    let trace = std::fs::read_to_string(&trace_path).unwrap();
+    assert!(trace.contains(r#"{"push":{"u64":6}}"#));
+    std::fs::write(
+        &trace_path,
+        trace.replace(r#"{"push":{"u64":6}}"#, r#"{"push":{"u64":7}}"#),
+    )
+    .unwrap();
+    let replay = move_cmd(&["replay", "trace", "--args", "5u64"]);
+    assert!(!replay.status.success());
+    let stdout = String::from_utf8(replay.stdout).unwrap();
+    assert!(stdout.contains("Divergence at event"), "{stdout}");
+    assert!(stdout.contains(r#""instruction":"Add""#), "{stdout}");
+    assert!(
+        stdout.contains("-     [0] 7u64\n+     [0] 6u64"),
+        "{stdout}"
+    );
+}
@@ -0,0 +1,83 @@
+module 0x42::shapes {
+    public enum Shape has copy, drop {
+        Empty,
+        Circle { radius: u64 },
+        Rect { width: u64, height: u64 },
+    }
+
+    public enum Maybe<T> has drop {
+        Nothing,
+        Just(T),
+    }
+
+    public fun area(s: &Shape): u64 {
+        match (s) {
+            Shape::Empty => 0,
+            Shape::Circle { radius } => 3 * *radius * *radius,
+            Shape::Rect { width, height } => *width * *height,
+        }
+    }
+
+    public fun grow(s: &mut Shape, by: u64) {
+        match (s) {
+            Shape::Empty => (),
+            Shape::Circle { radius } => *radius = *radius + by,
+            Shape::Rect { width, height } => {
+                *width = *width + by;
+                *height = *height + by;
+            },
+        }
+    }
+
+    public fun consume(s: Shape): u64 {
+        match (s) {
+            Shape::Rect { width, height } if (width == height) => width,
+            Shape::Rect { width, height: _ } => width + 1,
+            _ => 0,
+        }
+    }
+
+    public fun unwrap_or<T: drop>(m: Maybe<T>, default: T): T {
+        match (m) {
+            Maybe::Just(x) => x,
+            Maybe::Nothing => default,
+        }
+    }
+
+    #[test]
+    fun test_area() {
+        assert!(area(&Shape::Empty) == 0, 0);
+        assert!(area(&Shape::Circle { radius: 2 }) == 12, 1);
+        assert!(area(&Shape::Rect { width: 2, height: 5 }) == 10, 2);
+    }
+
+    #[test]
+    fun test_grow() {
+        let mut s = Shape::Rect { width: 1, height: 2 };
+        grow(&mut s, 3);
+        assert!(area(&s) == 20, 0);
+        let mut c = Shape::Circle { radius: 1 };
+        grow(&mut c, 1);
+        assert!(area(&c) == 12, 1);
+    }
+
+    #[test]
+    fun test_consume() {
+        assert!(consume(Shape::Rect { width: 4, height: 4 }) == 4, 0);
+        assert!(consume(Shape::Rect { width: 4, height: 5 }) == 5, 1);
+        assert!(consume(Shape::Circle { radius: 9 }) == 0, 2);
+    }
+
+    #[test]
+    fun test_generic() {
+        assert!(unwrap_or(Maybe::Just(7u8), 1) == 7, 0);
+        assert!(unwrap_or(Maybe::Nothing, 1u64) == 1, 1);
+        assert!(unwrap_or(Maybe::Just(Shape::Empty), Shape::Circle { radius: 1 }) == Shape::Empty, 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0, location = Self)]
+    fun test_failure() {
+        assert!(area(&Shape::Circle { radius: 1 }) == 0, 0);
+    }
+}
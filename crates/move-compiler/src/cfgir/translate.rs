@@ -40,44 +40,50 @@ enum NamedBlockType {
     Named,
 }
 
+type DatatypeDeclaredAbilities = UniqueMap<ModuleIdent, UniqueMap<DatatypeName, AbilitySet>>;
+
 struct Context<'env> {
     env: &'env mut CompilationEnv,
     current_package: Option<Symbol>,
-    datatype_declared_abilities: UniqueMap<ModuleIdent, UniqueMap<DatatypeName, AbilitySet>>,
+    datatype_declared_abilities: &'env DatatypeDeclaredAbilities,
     label_count: usize,
     named_blocks: UniqueMap<BlockLabel, (Label, Label)>,
     // Used for populating block_info
     loop_bounds: BTreeMap<Label, G::LoopInfo>,
 }
 
+fn datatype_declared_abilities(
+    pre_compiled_lib: Option<Arc<FullyCompiledProgram>>,
+    modules: &UniqueMap<ModuleIdent, H::ModuleDefinition>,
+) -> DatatypeDeclaredAbilities {
+    let all_modules = modules
+        .key_cloned_iter()
+        .chain(pre_compiled_lib.iter().flat_map(|pre_compiled| {
+            pre_compiled
+                .hlir
+                .modules
+                .key_cloned_iter()
+                .filter(|(mident, _m)| !modules.contains_key(mident))
+        }));
+    let datatype_declared_abilities = all_modules.map(|(m, mdef)| {
+        let smap = mdef.structs.ref_map(|_s, sdef| sdef.abilities.clone());
+        let emap = mdef.enums.ref_map(|_e, edef| edef.abilities.clone());
+        (
+            m,
+            smap.union_with(&emap, |_x, _y, _z| {
+                panic!("ICE should have failed in naming")
+            }),
+        )
+    });
+
+    UniqueMap::maybe_from_iter(datatype_declared_abilities).unwrap()
+}
+
 impl<'env> Context<'env> {
     pub fn new(
         env: &'env mut CompilationEnv,
-        pre_compiled_lib: Option<Arc<FullyCompiledProgram>>,
-        modules: &UniqueMap<ModuleIdent, H::ModuleDefinition>,
+        datatype_declared_abilities: &'env DatatypeDeclaredAbilities,
     ) -> Self {
-        let all_modules = modules
-            .key_cloned_iter()
-            .chain(pre_compiled_lib.iter().flat_map(|pre_compiled| {
-                pre_compiled
-                    .hlir
-                    .modules
-                    .key_cloned_iter()
-                    .filter(|(mident, _m)| !modules.contains_key(mident))
-            }));
-        let datatype_declared_abilities = all_modules.map(|(m, mdef)| {
-            let smap = mdef.structs.ref_map(|_s, sdef| sdef.abilities.clone());
-            let emap = mdef.enums.ref_map(|_e, edef| edef.abilities.clone());
-            (
-                m,
-                smap.union_with(&emap, |_x, _y, _z| {
-                    panic!("ICE should have failed in naming")
-                }),
-            )
-        });
-
-        let datatype_declared_abilities =
-            UniqueMap::maybe_from_iter(datatype_declared_abilities).unwrap();
         Context {
             env,
             current_package: None,
@@ -152,7 +158,8 @@ pub fn program(
 ) -> G::Program {
     let H::Program { modules: hmodules } = prog;
 
-    let mut context = Context::new(compilation_env, pre_compiled_lib, &hmodules);
+    let datatype_declared_abilities = datatype_declared_abilities(pre_compiled_lib, &hmodules);
+    let mut context = Context::new(compilation_env, &datatype_declared_abilities);
 
     let modules = modules(&mut context, hmodules);
 
@@ -165,17 +172,55 @@ fn modules(
     context: &mut Context,
     hmodules: UniqueMap<ModuleIdent, H::ModuleDefinition>,
 ) -> UniqueMap<ModuleIdent, G::ModuleDefinition> {
-    let modules = hmodules
-        .into_iter()
-        .map(|(mname, m)| module(context, mname, m));
+    let mut modules = vec![];
+    let mut hfunctions = vec![];
+    for (mname, mdef) in hmodules {
+        let (mdef, functions) = module(context, mname, mdef);
+        for (fname, fdef) in functions {
+            let module_info = (mdef.package_name, mdef.warning_filter.clone());
+            hfunctions.push((mname, module_info, fname, fdef));
+        }
+        modules.push((mname, mdef));
+    }
+
+    // Function bodies are independent of each other, so they are translated (and checked) in
+    // parallel
+    let datatype_declared_abilities = context.datatype_declared_abilities;
+    let functions = context.env.par_map(
+        hfunctions,
+        |env, (mname, (package_name, warning_filter), fname, fdef)| {
+            let mut context = Context::new(env, datatype_declared_abilities);
+            context.current_package = package_name;
+            context.env.add_warning_filter_scope(warning_filter);
+            let fdef = function(&mut context, mname, fname, fdef);
+            context.env.pop_warning_filter_scope();
+            (mname, fname, fdef)
+        },
+    );
+    let mut functions_by_module: BTreeMap<ModuleIdent, Vec<(FunctionName, G::Function)>> =
+        BTreeMap::new();
+    for (mname, fname, fdef) in functions {
+        functions_by_module
+            .entry(mname)
+            .or_default()
+            .push((fname, fdef));
+    }
+
+    let modules = modules.into_iter().map(|(mname, mut mdef)| {
+        let functions = functions_by_module.remove(&mname).unwrap_or_default();
+        mdef.functions = UniqueMap::maybe_from_iter(functions.into_iter()).unwrap();
+        (mname, mdef)
+    });
     UniqueMap::maybe_from_iter(modules).unwrap()
 }
 
+/// Translates everything in the module but its functions, which are returned for `modules` to
+/// translate.
 fn module(
     context: &mut Context,
     module_ident: ModuleIdent,
     mdef: H::ModuleDefinition,
-) -> (ModuleIdent, G::ModuleDefinition) {
+) -> (G::ModuleDefinition, UniqueMap<FunctionName, H::Function>) {
     let H::ModuleDefinition {
         warning_filter,
         package_name,
@@ -191,11 +236,9 @@ fn module(
     context.current_package = package_name;
     context.env.add_warning_filter_scope(warning_filter.clone());
     let constants = constants(context, module_ident, hconstants);
-    let functions = hfunctions.map(|name, f| function(context, module_ident, name, f));
     context.env.pop_warning_filter_scope();
     context.current_package = None;
     (
-        module_ident,
         G::ModuleDefinition {
             warning_filter,
             package_name,
@@ -206,8 +249,9 @@ fn module(
             structs,
             enums,
             constants,
-            functions,
+            functions: UniqueMap::new(),
         },
+        hfunctions,
     )
 }
 
@@ -493,7 +537,7 @@ fn constant_(
         package: context.current_package,
         module,
         member: cfgir::MemberName::Constant(name.0),
-        datatype_declared_abilities: &context.datatype_declared_abilities,
+        datatype_declared_abilities: context.datatype_declared_abilities,
         attributes,
         entry: None,
         visibility: H::Visibility::Internal,
@@ -652,7 +696,7 @@ fn function_body(
                 package: context.current_package,
                 module,
                 member: cfgir::MemberName::Function(name.0),
-                datatype_declared_abilities: &context.datatype_declared_abilities,
+                datatype_declared_abilities: context.datatype_declared_abilities,
                 attributes,
                 entry,
                 visibility,
@@ -1040,7 +1084,7 @@ fn visit_function(
         package: context.current_package,
         module: mident,
         member: cfgir::MemberName::Function(name.0),
-        datatype_declared_abilities: &context.datatype_declared_abilities,
+        datatype_declared_abilities: context.datatype_declared_abilities,
         attributes,
         entry: *entry,
         visibility: *visibility,
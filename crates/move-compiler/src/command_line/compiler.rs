@@ -134,8 +134,13 @@ impl Compiler {
             } in all_pkgs
             {
                 let name = if let Some((name, config)) = name {
-                    let prev = package_configs.insert(name, config);
-                    anyhow::ensure!(prev.is_none(), "Duplicate package entry for '{name}'");
+                    // A package may contribute both targets and dependencies, as long as they are
+                    // compiled under the same configuration
+                    let prev = package_configs.insert(name, config.clone());
+                    anyhow::ensure!(
+                        prev.is_none() || prev == Some(config),
+                        "Duplicate package entry for '{name}'"
+                    );
                     Some(name)
                 } else {
                     None
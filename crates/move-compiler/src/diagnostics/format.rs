@@ -0,0 +1,393 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Machine readable renderings of diagnostics, as JSON or as a SARIF 2.1.0 log.
+//!
+//! Lines and columns are 1-based, end positions are exclusive.
+
+use crate::diagnostics::{
+    codes::{Category, Severity},
+    Diagnostic, MappedFiles,
+};
+use move_ir_types::location::Loc;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+//**************************************************************************************************
+// Format
+//**************************************************************************************************
+
+/// How diagnostics are reported
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticsFormat {
+    /// Rendered source snippets, for terminals
+    #[default]
+    Human,
+    /// A JSON array of diagnostics
+    Json,
+    /// A SARIF 2.1.0 log
+    Sarif,
+}
+
+impl DiagnosticsFormat {
+    pub const HUMAN: &'static str = "human";
+    pub const JSON: &'static str = "json";
+    pub const SARIF: &'static str = "sarif";
+}
+
+impl FromStr for DiagnosticsFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            Self::HUMAN => Self::Human,
+            Self::JSON => Self::Json,
+            Self::SARIF => Self::Sarif,
+            _ => anyhow::bail!(
+                "Unknown diagnostics format \"{s}\". Expected one of: \"{}\", \"{}\", \"{}\"",
+                Self::HUMAN,
+                Self::JSON,
+                Self::SARIF
+            ),
+        })
+    }
+}
+
+impl fmt::Display for DiagnosticsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Human => write!(f, "{}", Self::HUMAN),
+            Self::Json => write!(f, "{}", Self::JSON),
+            Self::Sarif => write!(f, "{}", Self::SARIF),
+        }
+    }
+}
+
+//**************************************************************************************************
+// JSON
+//**************************************************************************************************
+
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    code: String,
+    severity: &'static str,
+    message: &'static str,
+    category: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    external_prefix: Option<&'static str>,
+    warning_filter: Option<String>,
+    primary_label: JsonLabel,
+    secondary_labels: Vec<JsonLabel>,
+    notes: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel {
+    span: Span,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Span {
+    file: String,
+    start: Position,
+    end: Position,
+}
+
+#[derive(Serialize)]
+struct Position {
+    line: usize,
+    column: usize,
+    byte: usize,
+}
+
+pub(super) fn to_json(mapping: &MappedFiles, diags: Vec<Diagnostic>) -> Vec<u8> {
+    let diags: Vec<_> = diags
+        .into_iter()
+        .map(|diag| json_diagnostic(mapping, diag))
+        .collect();
+    to_bytes(&diags)
+}
+
+fn json_diagnostic(mapping: &MappedFiles, diag: Diagnostic) -> JsonDiagnostic {
+    let Diagnostic {
+        info,
+        primary_label,
+        secondary_labels,
+        notes,
+        warning_filter,
+    } = diag;
+    let label = |(loc, message): (Loc, String)| JsonLabel {
+        span: span(mapping, loc),
+        message,
+    };
+    let (code, message) = info.clone().render();
+    JsonDiagnostic {
+        code,
+        severity: severity_name(info.severity()),
+        message,
+        category: info.category(),
+        category_name: category_name(info.external_prefix(), info.category()),
+        external_prefix: info.external_prefix(),
+        warning_filter,
+        primary_label: label(primary_label),
+        secondary_labels: secondary_labels.into_iter().map(label).collect(),
+        notes,
+    }
+}
+
+//**************************************************************************************************
+// SARIF
+//**************************************************************************************************
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_TOOL: &str = "move-compiler";
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    short_description: SarifMessage,
+    properties: SarifRuleProperties,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRuleProperties {
+    category: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    warning_filter: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related_locations: Vec<SarifLocation>,
+    properties: SarifResultProperties,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResultProperties {
+    severity: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    notes: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<usize>,
+    physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<SarifMessage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+    byte_offset: usize,
+    byte_length: usize,
+}
+
+pub(super) fn to_sarif(mapping: &MappedFiles, diags: Vec<Diagnostic>) -> Vec<u8> {
+    let mut rules = vec![];
+    let mut rule_indices = BTreeMap::new();
+    let mut results = vec![];
+    for diag in diags {
+        let Diagnostic {
+            info,
+            primary_label: (primary_loc, primary_msg),
+            secondary_labels,
+            notes,
+            warning_filter,
+        } = diag;
+        let (code, message) = info.clone().render();
+        let rule_index = *rule_indices.entry(code.clone()).or_insert_with(|| {
+            rules.push(SarifRule {
+                id: code.clone(),
+                short_description: SarifMessage {
+                    text: message.to_string(),
+                },
+                properties: SarifRuleProperties {
+                    category: info.category(),
+                    category_name: category_name(info.external_prefix(), info.category()),
+                    warning_filter,
+                },
+            });
+            rules.len() - 1
+        });
+        let related_locations = secondary_labels
+            .into_iter()
+            .enumerate()
+            .map(|(id, (loc, msg))| SarifLocation {
+                id: Some(id),
+                physical_location: physical_location(mapping, loc),
+                message: Some(SarifMessage { text: msg }),
+            })
+            .collect();
+        results.push(SarifResult {
+            rule_id: code,
+            rule_index,
+            level: sarif_level(info.severity()),
+            message: SarifMessage {
+                text: format!("{message}: {primary_msg}"),
+            },
+            locations: vec![SarifLocation {
+                id: None,
+                physical_location: physical_location(mapping, primary_loc),
+                message: None,
+            }],
+            related_locations,
+            properties: SarifResultProperties {
+                severity: severity_name(info.severity()),
+                notes,
+            },
+        })
+    }
+    to_bytes(&SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: SARIF_TOOL,
+                    rules,
+                },
+            },
+            results,
+        }],
+    })
+}
+
+fn physical_location(mapping: &MappedFiles, loc: Loc) -> SarifPhysicalLocation {
+    let Span { file, start, end } = span(mapping, loc);
+    SarifPhysicalLocation {
+        artifact_location: SarifArtifactLocation { uri: file },
+        region: SarifRegion {
+            start_line: start.line,
+            start_column: start.column,
+            end_line: end.line,
+            end_column: end.column,
+            byte_offset: start.byte,
+            byte_length: end.byte - start.byte,
+        },
+    }
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::NonblockingError | Severity::BlockingError | Severity::Bug => "error",
+    }
+}
+
+//**************************************************************************************************
+// Utils
+//**************************************************************************************************
+
+fn span(mapping: &MappedFiles, loc: Loc) -> Span {
+    let location = mapping.location(loc);
+    let file = mapping
+        .files
+        .get(location.file_id)
+        .map(|file| file.name().to_string())
+        .unwrap_or_default();
+    let position = |pos: super::LineColLocation| Position {
+        line: pos.line,
+        column: pos.column + 1,
+        byte: pos.byte,
+    };
+    Span {
+        file,
+        start: position(location.start),
+        end: position(location.end),
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => "warning",
+        Severity::NonblockingError => "nonblocking_error",
+        Severity::BlockingError => "blocking_error",
+        Severity::Bug => "bug",
+    }
+}
+
+/// The name of a compiler category, `None` for external diagnostics such as lints
+fn category_name(external_prefix: Option<&str>, category: u8) -> Option<String> {
+    if external_prefix.is_some() {
+        return None;
+    }
+    Category::try_from(category)
+        .ok()
+        .map(|category| format!("{category:?}"))
+}
+
+fn to_bytes(value: &impl Serialize) -> Vec<u8> {
+    let mut bytes = serde_json::to_vec_pretty(value).expect("ICE diagnostics serialization failed");
+    bytes.push(b'\n');
+    bytes
+}
@@ -3,6 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod codes;
+mod format;
+
+pub use format::DiagnosticsFormat;
 
 use crate::{
     command_line::COLOR_MODE_ENV_VAR,
@@ -56,6 +59,8 @@ pub struct Diagnostic {
     primary_label: (Loc, String),
     secondary_labels: Vec<(Loc, String)>,
     notes: Vec<String>,
+    // the `#[allow(..)]` filter suppressing this warning, if it has a known name
+    warning_filter: Option<String>,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
@@ -110,6 +115,7 @@ pub struct Migration {
 }
 
 /// A mapping from file ids to file contents along with the mapping of filehash to fileID.
+#[derive(Clone)]
 pub struct MappedFiles {
     files: SimpleFiles<Symbol, Arc<str>>,
     file_mapping: HashMap<FileHash, FileId>,
@@ -210,23 +216,52 @@ impl MappedFiles {
 //**************************************************************************************************
 
 pub fn report_diagnostics(files: &FilesSourceText, diags: Diagnostics) -> ! {
+    report_diagnostics_with_format(files, diags, DiagnosticsFormat::Human)
+}
+
+pub fn report_diagnostics_with_format(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: DiagnosticsFormat,
+) -> ! {
     let should_exit = true;
-    report_diagnostics_impl(files, diags, should_exit);
+    report_diagnostics_impl(files, diags, format, should_exit);
     std::process::exit(1)
 }
 
 pub fn report_warnings(files: &FilesSourceText, warnings: Diagnostics) {
+    report_warnings_with_format(files, warnings, DiagnosticsFormat::Human)
+}
+
+pub fn report_warnings_with_format(
+    files: &FilesSourceText,
+    warnings: Diagnostics,
+    format: DiagnosticsFormat,
+) {
     if warnings.is_empty() {
         return;
     }
     debug_assert!(warnings.max_severity().unwrap() == Severity::Warning);
-    report_diagnostics_impl(files, warnings, false)
+    report_diagnostics_impl(files, warnings, format, false)
 }
 
-fn report_diagnostics_impl(files: &FilesSourceText, diags: Diagnostics, should_exit: bool) {
-    let color_choice = env_color();
-    let mut writer = StandardStream::stderr(color_choice);
-    output_diagnostics(&mut writer, files, diags);
+fn report_diagnostics_impl(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: DiagnosticsFormat,
+    should_exit: bool,
+) {
+    match format {
+        DiagnosticsFormat::Human => {
+            let color_choice = env_color();
+            let mut writer = StandardStream::stderr(color_choice);
+            output_diagnostics(&mut writer, files, diags);
+        }
+        DiagnosticsFormat::Json | DiagnosticsFormat::Sarif => {
+            let buf = report_diagnostics_to_buffer_with_format(files, diags, format);
+            let _ = std::io::stderr().write_all(&buf);
+        }
+    }
     if should_exit {
         std::process::exit(1);
     }
@@ -280,6 +315,20 @@ pub fn report_diagnostics_to_buffer(
     writer.into_inner()
 }
 
+/// Renders `diags` in `format`, with the environment color for the human readable format
+pub fn report_diagnostics_to_buffer_with_format(
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    format: DiagnosticsFormat,
+) -> Vec<u8> {
+    let mapping = || MappedFiles::new(files.clone());
+    match format {
+        DiagnosticsFormat::Human => report_diagnostics_to_buffer_with_env_color(files, diags),
+        DiagnosticsFormat::Json => format::to_json(&mapping(), reported_diagnostics(diags)),
+        DiagnosticsFormat::Sarif => format::to_sarif(&mapping(), reported_diagnostics(diags)),
+    }
+}
+
 fn env_color() -> ColorChoice {
     match read_env_var(COLOR_MODE_ENV_VAR).as_str() {
         "NONE" => ColorChoice::Never,
@@ -299,8 +348,16 @@ fn output_diagnostics<W: WriteColor>(
 }
 
 fn render_diagnostics(writer: &mut dyn WriteColor, mapping: MappedFiles, diags: Diagnostics) {
+    for diag in reported_diagnostics(diags) {
+        let rendered = render_diagnostic(&mapping, diag);
+        emit(writer, &Config::default(), &mapping.files, &rendered).unwrap()
+    }
+}
+
+/// The diagnostics to report, sorted by primary location and without duplicates
+fn reported_diagnostics(diags: Diagnostics) -> Vec<Diagnostic> {
     let Diagnostics(Some(mut diags)) = diags else {
-        return;
+        return vec![];
     };
 
     // Do not render / report migration diagnostics.
@@ -312,14 +369,11 @@ fn render_diagnostics(writer: &mut dyn WriteColor, mapping: MappedFiles, diags:
         loc1.cmp(loc2)
     });
     let mut seen: HashSet<Diagnostic> = HashSet::new();
-    for diag in diags.diagnostics {
-        if seen.contains(&diag) {
-            continue;
-        }
-        seen.insert(diag.clone());
-        let rendered = render_diagnostic(&mapping, diag);
-        emit(writer, &Config::default(), &mapping.files, &rendered).unwrap()
-    }
+    diags
+        .diagnostics
+        .into_iter()
+        .filter(|diag| seen.insert(diag.clone()))
+        .collect()
 }
 
 fn convert_loc(mapped_files: &MappedFiles, loc: Loc) -> (FileId, Range<usize>) {
@@ -345,6 +399,7 @@ fn render_diagnostic(
         primary_label,
         secondary_labels,
         notes,
+        warning_filter: _,
     } = diag;
     let mut diag = csr::diagnostic::Diagnostic::new(info.severity().into_codespan_severity());
     let (code, message) = info.render();
@@ -470,7 +525,7 @@ impl Diagnostics {
     pub fn extend(&mut self, other: Self) {
         let Self(Some(Diagnostics_ {
             diagnostics,
-            filtered_source_diagnostics: _,
+            filtered_source_diagnostics,
             severity_count,
         })) = other
         else {
@@ -484,6 +539,9 @@ impl Diagnostics {
             *inner.severity_count.entry(sev).or_insert(0) += count;
         }
         inner.diagnostics.extend(diagnostics);
+        inner
+            .filtered_source_diagnostics
+            .extend(filtered_source_diagnostics);
     }
 
     pub fn into_vec(self) -> Vec<Diagnostic> {
@@ -506,6 +564,7 @@ impl Diagnostics {
                 primary_label,
                 secondary_labels,
                 notes,
+                warning_filter: _,
             } = diag;
             let csr_diag = (
                 info.severity().into_codespan_severity(),
@@ -582,6 +641,7 @@ impl Diagnostic {
                 .map(|(loc, msg)| (loc, msg.to_string()))
                 .collect(),
             notes: notes.into_iter().map(|msg| msg.to_string()).collect(),
+            warning_filter: None,
         }
     }
 
@@ -625,10 +685,18 @@ impl Diagnostic {
         self.notes.push(msg.to_string())
     }
 
+    pub(crate) fn set_warning_filter(&mut self, filter: String) {
+        self.warning_filter = Some(filter)
+    }
+
     pub fn info(&self) -> &DiagnosticInfo {
         &self.info
     }
 
+    pub fn warning_filter(&self) -> Option<&str> {
+        self.warning_filter.as_deref()
+    }
+
     pub fn primary_msg(&self) -> &str {
         &self.primary_label.1
     }
@@ -639,7 +707,8 @@ impl Diagnostic {
 
     pub fn is_migration(&self) -> bool {
         const MIGRATION_CATEGORY: u8 = codes::Category::Migration as u8;
-        self.info.category() == MIGRATION_CATEGORY
+        // external diagnostics, e.g. lints, have their own categories
+        self.info.external_prefix().is_none() && self.info.category() == MIGRATION_CATEGORY
     }
 }
 
@@ -0,0 +1,578 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A source formatter for Move.
+//!
+//! The formatter works on the token stream of a file rather than on its AST, so that every
+//! comment, doc comment included, is kept where it was written. It normalizes the indentation
+//! (four spaces per nesting level), the spacing between tokens and the blank lines, and breaks the
+//! blocks containing statements over several lines. Other line breaks are left to the author.
+//!
+//! The file is parsed first so that only valid Move is formatted, and the output is checked to
+//! hold the same tokens and comments as the input.
+
+use crate::{
+    diag,
+    diagnostics::Diagnostics,
+    editions::Edition,
+    parser::{
+        comments::verify_string,
+        lexer::{Lexer, Tok},
+        syntax::{make_loc, parse_file_string},
+    },
+    shared::{CompilationEnv, Flags, PackageConfig},
+};
+use move_command_line_common::files::FileHash;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Width of one level of indentation
+const INDENT: &str = "    ";
+
+/// Maximum number of consecutive blank lines kept
+const MAX_BLANK_LINES: usize = 1;
+
+/// Formats the Move source `input`, or returns the diagnostics of a file that does not parse
+pub fn format_string(
+    file_hash: FileHash,
+    input: &str,
+    edition: Edition,
+) -> Result<String, Diagnostics> {
+    verify_string(file_hash, input)?;
+    let config = PackageConfig {
+        edition,
+        ..PackageConfig::default()
+    };
+    let mut env = CompilationEnv::new(Flags::empty(), vec![], BTreeMap::new(), Some(config));
+    parse_file_string(&mut env, file_hash, input, None)?;
+
+    let source = lex(file_hash, input, edition)?;
+    let output = Formatter::new(&source).format();
+
+    // The formatter only moves whitespace around
+    let formatted = lex(file_hash, &output, edition)?;
+    if !source.same_contents(&formatted) {
+        let loc = make_loc(file_hash, 0, 0);
+        return Err(Diagnostics::from(vec![diag!(
+            Bug::ICE,
+            (
+                loc,
+                "Formatting changed the tokens or the comments of the file"
+            )
+        )]));
+    }
+    Ok(output)
+}
+
+//**************************************************************************************************
+// Tokens and trivia
+//**************************************************************************************************
+
+struct Token<'a> {
+    tok: Tok,
+    text: &'a str,
+    /// Whitespace and comments before the token
+    trivia: Vec<Trivia<'a>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Trivia<'a> {
+    /// A run of whitespace, with the number of line breaks in it
+    Space { newlines: usize },
+    /// A `//` comment, without the line break ending it
+    LineComment(&'a str),
+    /// A `/* */` comment, possibly nested or over several lines
+    BlockComment(&'a str),
+}
+
+struct Source<'a> {
+    /// The tokens of the file, ending with `Tok::EOF`
+    tokens: Vec<Token<'a>>,
+}
+
+fn lex(file_hash: FileHash, input: &str, edition: Edition) -> Result<Source<'_>, Diagnostics> {
+    let mut lexer = Lexer::new(input, file_hash, edition);
+    let mut tokens = vec![];
+    loop {
+        lexer
+            .advance()
+            .map_err(|diag| Diagnostics::from(vec![*diag]))?;
+        let trivia_start = lexer.previous_end_loc();
+        let loc = lexer.current_token_loc();
+        let (start, end) = (loc.start() as usize, loc.end() as usize);
+        let tok = lexer.peek();
+        tokens.push(Token {
+            tok,
+            text: &input[start..end],
+            trivia: trivia(&input[trivia_start..start]),
+        });
+        if tok == Tok::EOF {
+            break;
+        }
+    }
+    Ok(Source { tokens })
+}
+
+/// Splits the text between two tokens into whitespace and comments
+fn trivia(mut text: &str) -> Vec<Trivia<'_>> {
+    let mut trivia = vec![];
+    while !text.is_empty() {
+        let rest = text.trim_start();
+        if rest.len() < text.len() {
+            let newlines = text[..text.len() - rest.len()].matches('\n').count();
+            trivia.push(Trivia::Space { newlines });
+        }
+        text = rest;
+        if text.starts_with("//") {
+            let end = text.find('\n').unwrap_or(text.len());
+            trivia.push(Trivia::LineComment(text[..end].trim_end()));
+            text = &text[end..];
+        } else if text.starts_with("/*") {
+            let end = block_comment_len(text);
+            trivia.push(Trivia::BlockComment(&text[..end]));
+            text = &text[end..];
+        } else if !text.is_empty() {
+            // not reachable for text the lexer skipped, kept as a comment to lose nothing
+            trivia.push(Trivia::BlockComment(text));
+            break;
+        }
+    }
+    trivia
+}
+
+/// Length of the (nested) block comment `text` starts with
+fn block_comment_len(text: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    let bytes = text.as_bytes();
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+    text.len()
+}
+
+impl<'a> Source<'a> {
+    fn same_contents(&self, other: &Source) -> bool {
+        fn contents<'a>(source: &'a Source) -> impl Iterator<Item = (Option<Tok>, &'a str)> {
+            source.tokens.iter().flat_map(|token| {
+                token
+                    .trivia
+                    .iter()
+                    .filter_map(|trivia| match trivia {
+                        Trivia::Space { .. } => None,
+                        Trivia::LineComment(text) | Trivia::BlockComment(text) => {
+                            Some((None, *text))
+                        }
+                    })
+                    .chain(std::iter::once((Some(token.tok), token.text)))
+            })
+        }
+        contents(self).eq(contents(other))
+    }
+}
+
+//**************************************************************************************************
+// Formatter
+//**************************************************************************************************
+
+/// A delimiter opened and not yet closed
+struct Open {
+    tok: Tok,
+    /// The output line it was opened on
+    line: usize,
+    /// The indentation level of that line
+    line_level: usize,
+    /// Whether the lines until its closing delimiter are indented
+    indents: bool,
+    /// A block broken over several lines, one statement per line
+    expanded: bool,
+    /// The braces of `use a::{b, c}`, written without inner spaces
+    use_group: bool,
+    /// The line of the `let` statement being written in this block
+    let_line: Option<usize>,
+}
+
+struct Formatter<'a, 'b> {
+    tokens: &'b [Token<'a>],
+    /// The indices in `tokens` of the `{` of blocks holding a `;` of their own
+    has_statements: BTreeSet<usize>,
+    out: String,
+    stack: Vec<Open>,
+    line: usize,
+    /// The indentation level of the current line
+    line_level: usize,
+    at_line_start: bool,
+    /// Pending line breaks, written before the next token or comment
+    newlines: usize,
+    /// Whether the next line break follows an opening delimiter, and drops blank lines
+    skip_blank_lines: bool,
+    prev: Option<Tok>,
+    /// Whether a comment separates the previous token from the next one
+    prev_spaced: bool,
+    /// Whether the previous token is a `*` or a `&` used as a binary operator
+    prev_binary: bool,
+}
+
+impl<'a, 'b> Formatter<'a, 'b> {
+    fn new(source: &'b Source<'a>) -> Self {
+        let tokens = &source.tokens;
+        let mut has_statements = BTreeSet::new();
+        let mut braces = vec![];
+        for (i, token) in tokens.iter().enumerate() {
+            match token.tok {
+                Tok::LBrace | Tok::LParen | Tok::LBracket => braces.push(i),
+                Tok::RBrace | Tok::RParen | Tok::RBracket => {
+                    braces.pop();
+                }
+                Tok::Semicolon => {
+                    if let Some(open) = braces.last() {
+                        if tokens[*open].tok == Tok::LBrace {
+                            has_statements.insert(*open);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        Self {
+            tokens,
+            has_statements,
+            out: String::new(),
+            stack: vec![],
+            line: 0,
+            line_level: 0,
+            at_line_start: true,
+            newlines: 0,
+            skip_blank_lines: false,
+            prev: None,
+            prev_spaced: false,
+            prev_binary: false,
+        }
+    }
+
+    fn format(mut self) -> String {
+        for (i, token) in self.tokens.iter().enumerate() {
+            let forced = self.forces_newline_before(token.tok);
+            self.trivia(&token.trivia, forced);
+            if token.tok == Tok::EOF {
+                break;
+            }
+            self.token(i, token);
+        }
+        let mut out = self.out;
+        out.truncate(out.trim_end().len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Whether `next` must start a new line, given the tokens written so far
+    fn forces_newline_before(&self, next: Tok) -> bool {
+        let Some(prev) = self.prev else {
+            return false;
+        };
+        let in_expanded = self.stack.last().map_or(false, |open| open.expanded);
+        match (prev, next) {
+            (_, Tok::EOF) => false,
+            (_, Tok::RBrace) => in_expanded,
+            (Tok::LBrace | Tok::Semicolon, _) => in_expanded,
+            (Tok::RBrace, next) => in_expanded && starts_item_or_statement(next),
+            _ => false,
+        }
+    }
+
+    fn trivia(&mut self, trivia: &[Trivia], forced: bool) {
+        for item in trivia {
+            match item {
+                Trivia::Space { newlines } => self.newlines = self.newlines.max(*newlines),
+                Trivia::LineComment(text) | Trivia::BlockComment(text) => {
+                    let is_line = matches!(item, Trivia::LineComment(_));
+                    self.write_pending_newlines();
+                    if self.at_line_start {
+                        self.indent(None);
+                    } else if !matches!(self.prev, Some(Tok::LParen | Tok::LBracket)) {
+                        self.out.push(' ');
+                    }
+                    self.out.push_str(text);
+                    self.line += text.matches('\n').count();
+                    self.skip_blank_lines = false;
+                    if is_line {
+                        self.newlines = self.newlines.max(1);
+                    } else {
+                        self.prev_spaced = true;
+                    }
+                }
+            }
+        }
+        if forced {
+            self.newlines = self.newlines.max(1);
+        }
+    }
+
+    fn token(&mut self, i: usize, token: &Token) {
+        let tok = token.tok;
+        let had_space = matches!(token.trivia.last(), Some(Trivia::Space { .. }));
+        let is_close = matches!(tok, Tok::RBrace | Tok::RParen | Tok::RBracket);
+        // no blank line at the end of a block
+        if is_close {
+            self.newlines = self.newlines.min(1);
+        }
+        self.write_pending_newlines();
+        let closed = if is_close { self.stack.pop() } else { None };
+        if self.at_line_start {
+            match &closed {
+                // a closing delimiter starting a line is aligned with the line opening it
+                Some(open) => self.indent_to(open.line_level),
+                None => self.indent(Some(tok)),
+            }
+        } else if self.space_between(tok, had_space || self.prev_spaced, closed.as_ref()) {
+            self.out.push(' ');
+        }
+        // `&mut` is lexed with the space following it
+        self.out.push_str(token.text.trim_end());
+        self.prev_spaced = false;
+        let binary = matches!(tok, Tok::Star | Tok::Amp) && self.prev.map_or(false, ends_operand);
+
+        match tok {
+            Tok::LBrace | Tok::LParen | Tok::LBracket => self.stack.push(Open {
+                tok,
+                line: self.line,
+                line_level: self.line_level,
+                indents: false,
+                expanded: self.has_statements.contains(&i),
+                use_group: tok == Tok::LBrace && self.prev == Some(Tok::ColonColon),
+                let_line: None,
+            }),
+            Tok::Let => {
+                if let Some(open) = self.stack.last_mut().filter(|open| open.tok == Tok::LBrace) {
+                    open.let_line = Some(self.line)
+                }
+            }
+            Tok::Semicolon => {
+                if let Some(open) = self.stack.last_mut() {
+                    open.let_line = None
+                }
+            }
+            _ => (),
+        }
+        self.prev = Some(tok);
+        self.prev_binary = binary;
+        // no blank line at the start of a block
+        self.skip_blank_lines = matches!(tok, Tok::LBrace | Tok::LParen | Tok::LBracket);
+    }
+
+    fn write_pending_newlines(&mut self) {
+        if self.newlines == 0 || self.out.is_empty() {
+            self.newlines = 0;
+            return;
+        }
+        let max = if self.skip_blank_lines {
+            1
+        } else {
+            MAX_BLANK_LINES + 1
+        };
+        for _ in 0..self.newlines.min(max) {
+            self.newline();
+        }
+        self.newlines = 0;
+        self.skip_blank_lines = false;
+    }
+
+    fn newline(&mut self) {
+        if let Some(open) = self.stack.last_mut() {
+            if open.line == self.line {
+                open.indents = true;
+            }
+        }
+        let len = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(len);
+        self.out.push('\n');
+        self.line += 1;
+        self.at_line_start = true;
+    }
+
+    /// Indents a line starting with `first`, `None` for a comment
+    fn indent(&mut self, first: Option<Tok>) {
+        let mut level = self
+            .stack
+            .iter()
+            .rev()
+            .find(|open| open.indents)
+            .map_or(0, |open| open.line_level + 1);
+        if self.is_continuation(first) {
+            level += 1;
+        }
+        self.indent_to(level)
+    }
+
+    fn indent_to(&mut self, level: usize) {
+        for _ in 0..level {
+            self.out.push_str(INDENT);
+        }
+        self.line_level = level;
+        self.at_line_start = false;
+    }
+
+    /// Whether the line starting with `first` continues the expression of the previous line: the
+    /// value of a `let` or an operation broken around its operator
+    fn is_continuation(&self, first: Option<Tok>) -> bool {
+        let in_let = self.stack.last().map_or(false, |open| {
+            open.let_line.map_or(false, |line| line != self.line)
+        });
+        let prev_ends_expr = self.prev.map_or(false, is_binary_operator);
+        let starts_with_operator = first.map_or(false, |tok| {
+            tok == Tok::Period || (is_binary_operator(tok) && tok != Tok::Equal)
+        });
+        in_let || prev_ends_expr || starts_with_operator
+    }
+
+    fn space_between(&self, next: Tok, had_space: bool, closed: Option<&Open>) -> bool {
+        use Tok::*;
+        let Some(prev) = self.prev else {
+            return false;
+        };
+        match (prev, next) {
+            // tokens never preceded by a space
+            (_, Comma | Semicolon | Period | Colon | RParen | RBracket) => false,
+            // `::` also starts global paths, `::a::b`
+            (
+                Identifier | RestrictedIdentifier | SyntaxIdentifier | Greater | GreaterGreater,
+                ColonColon,
+            ) => false,
+            (_, RBrace) => !(prev == LBrace || closed.map_or(false, |open| open.use_group)),
+            // tokens never followed by a space
+            (LParen | LBracket | ColonColon | Period | AtSign | NumSign | Exclaim, _) => false,
+            (LBrace, _) => !self.stack.last().map_or(false, |open| open.use_group),
+            // tokens always followed by a space
+            (Comma | Semicolon | Colon | AmpMut, _) => true,
+            // calls, macro calls and indexing
+            (Identifier | RestrictedIdentifier | SyntaxIdentifier, LParen | LBracket | Exclaim) => {
+                false
+            }
+            (RParen | RBracket, LBracket) => false,
+            (Public, LParen) => false,
+            (_, LBrace) => true,
+            (prev, next) if is_spaced_operator(prev) || is_spaced_operator(next) => true,
+            // `*` and `&` are also unary, binary after an operand
+            (prev, Star | Amp) if ends_operand(prev) => true,
+            (Star | Amp, _) if self.prev_binary => true,
+            (If | While | Match | For | Let, LParen) => true,
+            (prev, next) if is_keyword(prev) => had_space || !matches!(next, LParen | LBracket),
+            _ => had_space,
+        }
+    }
+}
+
+/// Tokens starting a module member or a statement
+fn starts_item_or_statement(tok: Tok) -> bool {
+    matches!(
+        tok,
+        Tok::Fun
+            | Tok::Public
+            | Tok::Native
+            | Tok::Struct
+            | Tok::Enum
+            | Tok::Const
+            | Tok::Use
+            | Tok::Friend
+            | Tok::Spec
+            | Tok::NumSign
+            | Tok::Let
+            | Tok::Module
+    )
+}
+
+/// Operators written with a space on both sides. `*` and `&` are spaced when binary, and the
+/// operators that may close a type argument list or a lambda keep their original spacing.
+fn is_spaced_operator(tok: Tok) -> bool {
+    matches!(
+        tok,
+        Tok::Equal
+            | Tok::EqualEqual
+            | Tok::ExclaimEqual
+            | Tok::LessEqual
+            | Tok::GreaterEqual
+            | Tok::AmpAmp
+            | Tok::PipePipe
+            | Tok::EqualGreater
+            | Tok::EqualEqualGreater
+            | Tok::LessEqualEqualGreater
+            | Tok::MinusGreater
+            | Tok::Plus
+            | Tok::Minus
+            | Tok::Slash
+            | Tok::Percent
+            | Tok::Caret
+            | Tok::LessLess
+    )
+}
+
+/// Tokens ending an operand, after which `*` and `&` are binary operators
+fn ends_operand(tok: Tok) -> bool {
+    matches!(
+        tok,
+        Tok::Identifier
+            | Tok::RestrictedIdentifier
+            | Tok::SyntaxIdentifier
+            | Tok::NumValue
+            | Tok::NumTypedValue
+            | Tok::ByteStringValue
+            | Tok::True
+            | Tok::False
+            | Tok::RParen
+            | Tok::RBracket
+    )
+}
+
+/// Operators after which an expression continues on the next line
+fn is_binary_operator(tok: Tok) -> bool {
+    is_spaced_operator(tok) && !matches!(tok, Tok::PipePipe | Tok::EqualGreater | Tok::MinusGreater)
+}
+
+fn is_keyword(tok: Tok) -> bool {
+    use Tok::*;
+    matches!(
+        tok,
+        Abort
+            | Acquires
+            | As
+            | Break
+            | Continue
+            | Copy
+            | Else
+            | False
+            | If
+            | Invariant
+            | Let
+            | Loop
+            | Module
+            | Move
+            | Native
+            | Public
+            | Return
+            | Spec
+            | Struct
+            | True
+            | Use
+            | While
+            | Fun
+            | Const
+            | Friend
+            | Mut
+            | Enum
+            | Type
+            | Match
+            | For
+    )
+}
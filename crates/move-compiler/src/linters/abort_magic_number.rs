@@ -0,0 +1,99 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags `abort` and `assert!` with a literal abort code, e.g. `abort 42`. A named
+//! constant documents the error and keeps its code in one place. Test code is not checked.
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::ModuleIdent,
+    parser::ast::FunctionName,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    int_value, literal_value, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX,
+};
+
+const ABORT_MAGIC_NUMBER_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::AbortMagicNumber as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "abort code is a magic number",
+);
+
+pub struct AbortMagicNumberVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for AbortMagicNumberVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_module_custom(&mut self, _ident: ModuleIdent, mdef: &mut T::ModuleDefinition) -> bool {
+        // tests commonly use literal abort codes
+        mdef.attributes.is_test_or_test_only()
+    }
+
+    fn visit_function_custom(
+        &mut self,
+        _module: ModuleIdent,
+        _function_name: FunctionName,
+        fdef: &mut T::Function,
+    ) -> bool {
+        fdef.attributes.is_test_or_test_only()
+    }
+
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        let code = match &exp.exp.value {
+            E::Abort(code) => code,
+            E::Builtin(b, args) if matches!(b.value, T::BuiltinFunction_::Assert(_)) => {
+                let E::ExpList(args) = &args.exp.value else {
+                    return false;
+                };
+                match &args[..] {
+                    [_, T::ExpListItem::Single(code, _)] => code,
+                    _ => return false,
+                }
+            }
+            _ => return false,
+        };
+        if let Some(value) = literal_value(code).and_then(int_value) {
+            let msg = format!("Abort code {value} is a magic number");
+            let mut d = diag!(ABORT_MAGIC_NUMBER_DIAG, (code.exp.loc, msg));
+            d.add_note(
+                "Define the abort code as a named constant, e.g. 'const EInvalid: u64 = ...;'",
+            );
+            self.env.add_diag(d);
+        }
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags comparisons whose result is known at compile time, either because both
+//! operands are literals (e.g. `1 < 2`) or because one operand is a literal at the bounds of the
+//! integer type of the other (e.g. `x >= 0` or `x > 255` for a `u8`).
+
+use move_core_types::u256::U256;
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::Value_,
+    parser::ast::BinOp_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    int_bits, int_value, literal_value, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE,
+    LINT_WARNING_PREFIX,
+};
+
+const CONSTANT_COMPARISON_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::ConstantComparison as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "comparison is always the same",
+);
+
+pub struct ConstantComparisonVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for ConstantComparisonVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use BinOp_ as B;
+        let T::UnannotatedExp_::BinopExp(lhs, op, operand_ty, rhs) = &exp.exp.value else {
+            return false;
+        };
+        if !matches!(op.value, B::Eq | B::Neq | B::Lt | B::Gt | B::Le | B::Ge) {
+            return false;
+        }
+        let (result, note) = match (literal_value(lhs), literal_value(rhs)) {
+            (Some(l), Some(r)) => {
+                let Some(result) = compare_literals(l, op.value, r) else {
+                    return false;
+                };
+                (result, "Both operands are constant values".to_owned())
+            }
+            (lit, None) | (None, lit @ Some(_)) => {
+                let Some(c) = lit.and_then(int_value) else {
+                    return false;
+                };
+                let Some(bits) = int_bits(operand_ty) else {
+                    return false;
+                };
+                // normalize to `x op c`
+                let op = if literal_value(lhs).is_some() {
+                    flip(op.value)
+                } else {
+                    op.value
+                };
+                let max = U256::max_value() >> ((256 - bits) as u8);
+                let Some(result) = compare_with_bounds(op, c, max) else {
+                    return false;
+                };
+                let note = format!("A 'u{bits}' value is always between 0 and {max}");
+                (result, note)
+            }
+        };
+        let msg = format!("This comparison is always '{result}'");
+        let mut d = diag!(CONSTANT_COMPARISON_DIAG, (exp.exp.loc, msg));
+        d.add_note(note);
+        self.env.add_diag(d);
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
+
+fn compare_literals(lhs: &Value_, op: BinOp_, rhs: &Value_) -> Option<bool> {
+    use BinOp_ as B;
+    if let (Value_::Bool(l), Value_::Bool(r)) = (lhs, rhs) {
+        return match op {
+            B::Eq => Some(l == r),
+            B::Neq => Some(l != r),
+            _ => None,
+        };
+    }
+    let (l, r) = (int_value(lhs)?, int_value(rhs)?);
+    Some(match op {
+        B::Eq => l == r,
+        B::Neq => l != r,
+        B::Lt => l < r,
+        B::Gt => l > r,
+        B::Le => l <= r,
+        B::Ge => l >= r,
+        _ => return None,
+    })
+}
+
+/// The result of `x op c` for any `x` in `0..=max`, if it does not depend on `x`
+fn compare_with_bounds(op: BinOp_, c: U256, max: U256) -> Option<bool> {
+    use BinOp_ as B;
+    match op {
+        B::Lt if c == U256::zero() => Some(false),
+        B::Ge if c == U256::zero() => Some(true),
+        B::Le if c >= max => Some(true),
+        B::Gt if c >= max => Some(false),
+        _ => None,
+    }
+}
+
+fn flip(op: BinOp_) -> BinOp_ {
+    use BinOp_ as B;
+    match op {
+        B::Lt => B::Gt,
+        B::Gt => B::Lt,
+        B::Le => B::Ge,
+        B::Ge => B::Le,
+        op => op,
+    }
+}
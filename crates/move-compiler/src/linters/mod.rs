@@ -1,9 +1,24 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+use move_core_types::u256::U256;
 use move_symbol_pool::Symbol;
 
-use crate::{command_line::compiler::Visitor, diagnostics::codes::WarningFilter};
+use crate::{
+    cfgir::visitor::AbstractInterpreterVisitor,
+    command_line::compiler::Visitor,
+    diagnostics::codes::WarningFilter,
+    expansion::ast::Value_,
+    naming::ast::{BuiltinTypeName_, Type},
+    typing::{ast as T, visitor::TypingVisitor},
+};
+
+pub mod abort_magic_number;
+pub mod constant_comparison;
+pub mod redundant_copy;
+pub mod self_assignment;
+pub mod shift_overflow;
+pub mod while_true;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LintLevel {
@@ -18,15 +33,140 @@ pub enum LintLevel {
 pub const ALLOW_ATTR_CATEGORY: &str = "lint";
 pub const LINT_WARNING_PREFIX: &str = "Lint ";
 
+pub const WHILE_TRUE_FILTER_NAME: &str = "while_true";
+pub const SELF_ASSIGNMENT_FILTER_NAME: &str = "self_assignment";
+pub const REDUNDANT_COPY_FILTER_NAME: &str = "redundant_copy";
+pub const CONSTANT_COMPARISON_FILTER_NAME: &str = "constant_comparison";
+pub const SHIFT_OVERFLOW_FILTER_NAME: &str = "shift_overflow";
+pub const ABORT_MAGIC_NUMBER_FILTER_NAME: &str = "abort_magic_number";
+
+/// Categories of the core linters. They share the `Lint ` prefix with the Sui mode linters, so
+/// they are numbered after the categories of `sui_mode::linters::LinterDiagCategory` to keep the
+/// codes of both sets of linters distinct.
+pub enum LinterDiagCategory {
+    WhileTrue = 10,
+    SelfAssignment,
+    RedundantCopy,
+    ConstantComparison,
+    ShiftOverflow,
+    AbortMagicNumber,
+}
+
+/// A default code for each linter category (as long as only one code per category is used, no other
+/// codes are needed, otherwise they should be defined to be unique per-category).
+pub const LINTER_DEFAULT_DIAG_CODE: u8 = 1;
+
 pub fn known_filters() -> (Option<Symbol>, Vec<WarningFilter>) {
-    (Some(ALLOW_ATTR_CATEGORY.into()), vec![])
+    let filters = vec![
+        WarningFilter::All(Some(LINT_WARNING_PREFIX)),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::WhileTrue as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(WHILE_TRUE_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::SelfAssignment as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(SELF_ASSIGNMENT_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::RedundantCopy as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(REDUNDANT_COPY_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::ConstantComparison as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(CONSTANT_COMPARISON_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::ShiftOverflow as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(SHIFT_OVERFLOW_FILTER_NAME),
+        ),
+        WarningFilter::code(
+            Some(LINT_WARNING_PREFIX),
+            LinterDiagCategory::AbortMagicNumber as u8,
+            LINTER_DEFAULT_DIAG_CODE,
+            Some(ABORT_MAGIC_NUMBER_FILTER_NAME),
+        ),
+    ];
+    (Some(ALLOW_ATTR_CATEGORY.into()), filters)
 }
 
 pub fn linter_visitors(level: LintLevel) -> Vec<Visitor> {
+    let default = || {
+        vec![
+            while_true::WhileTrueVisitor.visitor(),
+            self_assignment::SelfAssignmentVisitor.visitor(),
+            constant_comparison::ConstantComparisonVisitor.visitor(),
+            shift_overflow::ShiftOverflowVisitor.visitor(),
+        ]
+    };
     match level {
         LintLevel::None => vec![],
-        LintLevel::Default | LintLevel::All => {
-            vec![]
+        LintLevel::Default => default(),
+        LintLevel::All => {
+            let mut visitors = default();
+            visitors.extend([
+                redundant_copy::RedundantCopyVerifier.visitor(),
+                abort_magic_number::AbortMagicNumberVisitor.visitor(),
+            ]);
+            visitors
         }
     }
 }
+
+//**************************************************************************************************
+// Utils
+//**************************************************************************************************
+
+/// Strips type annotations around an expression
+pub fn strip_annotations(e: &T::Exp) -> &T::Exp {
+    match &e.exp.value {
+        T::UnannotatedExp_::Annotate(inner, _) => strip_annotations(inner),
+        _ => e,
+    }
+}
+
+/// The literal value of an expression, if any
+pub fn literal_value(e: &T::Exp) -> Option<&Value_> {
+    match &strip_annotations(e).exp.value {
+        T::UnannotatedExp_::Value(v) => Some(&v.value),
+        _ => None,
+    }
+}
+
+/// The value of an integer literal
+pub fn int_value(v: &Value_) -> Option<U256> {
+    Some(match v {
+        Value_::U8(n) => U256::from(*n),
+        Value_::U16(n) => U256::from(*n),
+        Value_::U32(n) => U256::from(*n),
+        Value_::U64(n) => U256::from(*n),
+        Value_::U128(n) => U256::from(*n),
+        Value_::U256(n) | Value_::InferredNum(n) => *n,
+        Value_::Address(_) | Value_::Bool(_) | Value_::Bytearray(_) => return None,
+    })
+}
+
+/// The number of bits of an integer type
+pub fn int_bits(t: &Type) -> Option<u16> {
+    Some(match &t.value.builtin_name()?.value {
+        BuiltinTypeName_::U8 => 8,
+        BuiltinTypeName_::U16 => 16,
+        BuiltinTypeName_::U32 => 32,
+        BuiltinTypeName_::U64 => 64,
+        BuiltinTypeName_::U128 => 128,
+        BuiltinTypeName_::U256 => 256,
+        BuiltinTypeName_::Address
+        | BuiltinTypeName_::Signer
+        | BuiltinTypeName_::Vector
+        | BuiltinTypeName_::Bool => return None,
+    })
+}
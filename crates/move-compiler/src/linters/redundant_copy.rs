@@ -0,0 +1,267 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags explicit `copy x` where `x` is not used afterwards. The copy is redundant:
+//! the value can be moved instead, which the compiler already does for implicit uses.
+//!
+//! Locals that are borrowed anywhere in the function are skipped, as the explicit copy might be
+//! needed to use the value while it is borrowed.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use move_proc_macros::growing_stack;
+
+use crate::{
+    cfgir::{
+        self,
+        cfg::{ImmForwardCFG, CFG},
+        visitor::AbstractInterpreterVisitor,
+        CFGContext,
+    },
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        Diagnostics,
+    },
+    hlir::{
+        ast::{Command, Command_, Exp, LValue, LValue_, Label, UnannotatedExp_, Var},
+        translate::{display_var, DisplayVar},
+    },
+    shared::CompilationEnv,
+};
+
+use super::{LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX};
+
+const REDUNDANT_COPY_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::RedundantCopy as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "redundant copy",
+);
+
+pub struct RedundantCopyVerifier;
+
+struct Context<'a> {
+    borrowed: &'a BTreeSet<Var>,
+    /// `None` while computing the liveness fixpoint, diagnostics are only collected once it is
+    /// reached
+    diags: Option<&'a mut Diagnostics>,
+}
+
+impl AbstractInterpreterVisitor for RedundantCopyVerifier {
+    fn verify(
+        &mut self,
+        _env: &CompilationEnv,
+        _program: &cfgir::ast::Program,
+        _context: &CFGContext,
+        cfg: &ImmForwardCFG,
+    ) -> Diagnostics {
+        let mut borrowed = BTreeSet::new();
+        for block in cfg.blocks().values() {
+            for cmd in block {
+                command_borrows(&mut borrowed, cmd);
+            }
+        }
+
+        // live variables at the start of each block
+        let mut live_in: BTreeMap<Label, BTreeSet<Var>> = cfg
+            .blocks()
+            .keys()
+            .map(|lbl| (*lbl, BTreeSet::new()))
+            .collect();
+        let block_live_out = |live_in: &BTreeMap<Label, BTreeSet<Var>>, lbl: Label| {
+            cfg.successors(lbl)
+                .iter()
+                .flat_map(|succ| live_in[succ].iter().copied())
+                .collect::<BTreeSet<_>>()
+        };
+        loop {
+            let mut changed = false;
+            for (lbl, block) in cfg.blocks().iter().rev() {
+                let mut live = block_live_out(&live_in, *lbl);
+                let mut context = Context {
+                    borrowed: &borrowed,
+                    diags: None,
+                };
+                for cmd in block.iter().rev() {
+                    command(&mut context, &mut live, cmd);
+                }
+                if live_in[lbl] != live {
+                    live_in.insert(*lbl, live);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut diags = Diagnostics::new();
+        for (lbl, block) in cfg.blocks() {
+            let mut live = block_live_out(&live_in, *lbl);
+            let mut context = Context {
+                borrowed: &borrowed,
+                diags: Some(&mut diags),
+            };
+            for cmd in block.iter().rev() {
+                command(&mut context, &mut live, cmd);
+            }
+        }
+        diags
+    }
+}
+
+//**************************************************************************************************
+// Liveness
+//**************************************************************************************************
+
+// Commands and expressions are traversed in reverse evaluation order, so `live` holds the
+// variables used after the one being visited
+
+fn command(context: &mut Context, live: &mut BTreeSet<Var>, sp!(_, cmd_): &Command) {
+    use Command_ as C;
+    match cmd_ {
+        C::Assign(_, ls, e) => {
+            ls.iter().for_each(|l| lvalue(live, l));
+            exp(context, live, e);
+        }
+        C::Mutate(el, er) => {
+            exp(context, live, el);
+            exp(context, live, er)
+        }
+        C::Return { exp: e, .. }
+        | C::Abort(e)
+        | C::IgnoreAndPop { exp: e, .. }
+        | C::JumpIf { cond: e, .. }
+        | C::VariantSwitch { subject: e, .. } => exp(context, live, e),
+
+        C::Jump { .. } | C::Break(_) | C::Continue(_) => (),
+    }
+}
+
+fn lvalue(live: &mut BTreeSet<Var>, sp!(_, l_): &LValue) {
+    use LValue_ as L;
+    match l_ {
+        L::Ignore => (),
+        L::Var { var, .. } => {
+            live.remove(var);
+        }
+        L::Unpack(_, _, fields) => fields.iter().for_each(|(_, l)| lvalue(live, l)),
+        L::UnpackVariant(_, _, _, _, _, fields) => fields.iter().for_each(|(_, l)| lvalue(live, l)),
+    }
+}
+
+#[growing_stack]
+fn exp(context: &mut Context, live: &mut BTreeSet<Var>, parent_e: &Exp) {
+    use UnannotatedExp_ as E;
+    match &parent_e.exp.value {
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Constant(_)
+        | E::UnresolvedError
+        | E::ErrorConstant(_)
+        | E::Unreachable => (),
+
+        E::BorrowLocal(_, var) | E::Move { var, .. } => {
+            live.insert(*var);
+        }
+
+        E::Copy { var, from_user } => {
+            let is_last_use = live.insert(*var);
+            if is_last_use && *from_user && !context.borrowed.contains(var) {
+                if let (Some(diags), DisplayVar::Orig(name)) =
+                    (context.diags.as_mut(), display_var(var.value()))
+                {
+                    let msg = format!(
+                        "'{name}' is not used after this copy, \
+                        remove 'copy' or replace it with 'move'"
+                    );
+                    diags.add(diag!(REDUNDANT_COPY_DIAG, (parent_e.exp.loc, msg)));
+                }
+            }
+        }
+
+        E::ModuleCall(mcall) => mcall
+            .arguments
+            .iter()
+            .rev()
+            .for_each(|arg| exp(context, live, arg)),
+        E::Vector(_, _, _, args) | E::Multiple(args) => {
+            args.iter().rev().for_each(|arg| exp(context, live, arg))
+        }
+        E::Freeze(e)
+        | E::Dereference(e)
+        | E::UnaryExp(_, e)
+        | E::Borrow(_, e, _, _)
+        | E::Cast(e, _) => exp(context, live, e),
+
+        E::BinopExp(e1, _, e2) => {
+            exp(context, live, e2);
+            exp(context, live, e1)
+        }
+
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => fields
+            .iter()
+            .rev()
+            .for_each(|(_, _, e)| exp(context, live, e)),
+    }
+}
+
+//**************************************************************************************************
+// Borrowed locals
+//**************************************************************************************************
+
+fn command_borrows(borrowed: &mut BTreeSet<Var>, sp!(_, cmd_): &Command) {
+    use Command_ as C;
+    match cmd_ {
+        C::Assign(_, _, e)
+        | C::Return { exp: e, .. }
+        | C::Abort(e)
+        | C::IgnoreAndPop { exp: e, .. }
+        | C::JumpIf { cond: e, .. }
+        | C::VariantSwitch { subject: e, .. } => exp_borrows(borrowed, e),
+        C::Mutate(el, er) => {
+            exp_borrows(borrowed, el);
+            exp_borrows(borrowed, er)
+        }
+        C::Jump { .. } | C::Break(_) | C::Continue(_) => (),
+    }
+}
+
+#[growing_stack]
+fn exp_borrows(borrowed: &mut BTreeSet<Var>, e: &Exp) {
+    use UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::BorrowLocal(_, var) => {
+            borrowed.insert(*var);
+        }
+        E::ModuleCall(mcall) => mcall
+            .arguments
+            .iter()
+            .for_each(|arg| exp_borrows(borrowed, arg)),
+        E::Vector(_, _, _, args) | E::Multiple(args) => {
+            args.iter().for_each(|arg| exp_borrows(borrowed, arg))
+        }
+        E::Freeze(e)
+        | E::Dereference(e)
+        | E::UnaryExp(_, e)
+        | E::Borrow(_, e, _, _)
+        | E::Cast(e, _) => exp_borrows(borrowed, e),
+        E::BinopExp(e1, _, e2) => {
+            exp_borrows(borrowed, e1);
+            exp_borrows(borrowed, e2)
+        }
+        E::Pack(_, _, fields) | E::PackVariant(_, _, _, fields) => {
+            fields.iter().for_each(|(_, _, e)| exp_borrows(borrowed, e))
+        }
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Constant(_)
+        | E::UnresolvedError
+        | E::ErrorConstant(_)
+        | E::Unreachable
+        | E::Move { .. }
+        | E::Copy { .. } => (),
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags assignments of a local or a field to itself, e.g. `x = x` or
+//! `s.f = s.f`. Such assignments have no effect and are most likely a typo.
+
+use move_symbol_pool::Symbol;
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    naming::ast::Var_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv, Identifier},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{strip_annotations, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX};
+
+const SELF_ASSIGNMENT_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::SelfAssignment as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "unnecessary self-assignment",
+);
+
+pub struct SelfAssignmentVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for SelfAssignmentVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        use T::UnannotatedExp_ as E;
+        let assigned = match &exp.exp.value {
+            E::Assign(sp!(_, lvalues), _, rhs) => {
+                match (&lvalues[..], &strip_annotations(rhs).exp.value) {
+                    (
+                        [sp!(_, T::LValue_::Var { var: lhs, .. })],
+                        E::Copy { var: rhs, .. } | E::Move { var: rhs, .. },
+                    ) if lhs.value == rhs.value => Some(lhs.value.name.to_string()),
+                    _ => None,
+                }
+            }
+            E::Mutate(lhs, rhs) => {
+                let E::Dereference(rhs) = &strip_annotations(rhs).exp.value else {
+                    return false;
+                };
+                match (path(lhs), path(rhs)) {
+                    (Some(lhs), Some(rhs)) if lhs == rhs => Some(display_path(&lhs)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        if let Some(assigned) = assigned {
+            let msg = format!("'{assigned}' is assigned to itself");
+            self.env
+                .add_diag(diag!(SELF_ASSIGNMENT_DIAG, (exp.exp.loc, msg)));
+        }
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
+
+/// The local and the fields accessed by a reference or value expression, e.g. `x.f.g`
+fn path(e: &T::Exp) -> Option<(Var_, Vec<Symbol>)> {
+    use T::UnannotatedExp_ as E;
+    match &strip_annotations(e).exp.value {
+        E::Copy { var, .. } | E::Move { var, .. } | E::BorrowLocal(_, var) => {
+            Some((var.value, vec![]))
+        }
+        E::TempBorrow(_, e) => path(e),
+        E::Builtin(b, e) if matches!(b.value, T::BuiltinFunction_::Freeze(_)) => path(e),
+        E::Borrow(_, e, field) => {
+            let (var, mut fields) = path(e)?;
+            fields.push(field.value());
+            Some((var, fields))
+        }
+        _ => None,
+    }
+}
+
+fn display_path((var, fields): &(Var_, Vec<Symbol>)) -> String {
+    std::iter::once(var.name)
+        .chain(fields.iter().copied())
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
@@ -0,0 +1,86 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags shifts by a literal amount that is greater than or equal to the number of
+//! bits of the shifted integer type, e.g. `x << 64` for a `u64`. Such shifts always abort.
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    parser::ast::BinOp_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{
+    int_bits, int_value, literal_value, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE,
+    LINT_WARNING_PREFIX,
+};
+
+const SHIFT_OVERFLOW_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::ShiftOverflow as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "shift amount out of range",
+);
+
+pub struct ShiftOverflowVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for ShiftOverflowVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        let T::UnannotatedExp_::BinopExp(_, op, operand_ty, rhs) = &exp.exp.value else {
+            return false;
+        };
+        if !matches!(op.value, BinOp_::Shl | BinOp_::Shr) {
+            return false;
+        }
+        let Some(amount) = literal_value(rhs).and_then(int_value) else {
+            return false;
+        };
+        let Some(bits) = int_bits(operand_ty) else {
+            return false;
+        };
+        if amount >= bits.into() {
+            let msg = format!(
+                "Shifting a 'u{bits}' by {amount} bits always aborts, \
+                the shift amount must be less than {bits}"
+            );
+            self.env.add_diag(diag!(
+                SHIFT_OVERFLOW_DIAG,
+                (rhs.exp.loc, msg),
+                (op.loc, format!("Shift of a 'u{bits}'")),
+            ));
+        }
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This analysis flags `while (true)` loops, which should be written as `loop` instead.
+
+use crate::{
+    diag,
+    diagnostics::{
+        codes::{custom, DiagnosticInfo, Severity},
+        WarningFilters,
+    },
+    expansion::ast::Value_,
+    shared::{program_info::TypingProgramInfo, CompilationEnv},
+    typing::{
+        ast as T,
+        visitor::{TypingVisitorConstructor, TypingVisitorContext},
+    },
+};
+
+use super::{literal_value, LinterDiagCategory, LINTER_DEFAULT_DIAG_CODE, LINT_WARNING_PREFIX};
+
+const WHILE_TRUE_DIAG: DiagnosticInfo = custom(
+    LINT_WARNING_PREFIX,
+    Severity::Warning,
+    LinterDiagCategory::WhileTrue as u8,
+    LINTER_DEFAULT_DIAG_CODE,
+    "unnecessary 'while (true)', replace with 'loop'",
+);
+
+pub struct WhileTrueVisitor;
+pub struct Context<'a> {
+    env: &'a mut CompilationEnv,
+}
+
+impl TypingVisitorConstructor for WhileTrueVisitor {
+    type Context<'a> = Context<'a>;
+
+    fn context<'a>(
+        env: &'a mut CompilationEnv,
+        _program_info: &'a TypingProgramInfo,
+        _program: &T::Program_,
+    ) -> Self::Context<'a> {
+        Context { env }
+    }
+}
+
+impl TypingVisitorContext for Context<'_> {
+    fn visit_exp_custom(&mut self, exp: &mut T::Exp) -> bool {
+        let T::UnannotatedExp_::While(_, cond, _) = &exp.exp.value else {
+            return false;
+        };
+        if matches!(literal_value(cond), Some(Value_::Bool(true))) {
+            let msg = "A 'loop' is more explicit and does not need a condition to be evaluated";
+            let mut d = diag!(WHILE_TRUE_DIAG, (cond.exp.loc, msg));
+            d.add_note("Use 'loop' instead of 'while (true)'");
+            self.env.add_diag(d);
+        }
+        false
+    }
+
+    fn add_warning_filter_scope(&mut self, filter: WarningFilters) {
+        self.env.add_warning_filter_scope(filter)
+    }
+
+    fn pop_warning_filter_scope(&mut self) {
+        self.env.pop_warning_filter_scope()
+    }
+}
@@ -23,6 +23,7 @@ use move_command_line_common::files::FileHash;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use petgraph::{algo::astar as petgraph_astar, graphmap::DiGraphMap};
+use rayon::prelude::*;
 use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
@@ -234,7 +235,23 @@ pub struct CompilationEnv {
         BTreeMap<crate::naming::ast::BuiltinTypeName_, crate::expansion::ast::ModuleIdent>,
     // TODO(tzakian): Remove the global counter and use this counter instead
     // pub counter: u64,
-    mapped_files: MappedFiles,
+    mapped_files: Arc<MappedFiles>,
+    /// For environments forked by `par_map`, whether errors had been reported before the fork
+    forked_with_errors: bool,
+}
+
+/// The state a `CompilationEnv` is forked from, for use by another thread.
+struct ForkedEnv {
+    flags: Flags,
+    warning_filter: Vec<WarningFilters>,
+    package_configs: BTreeMap<Symbol, PackageConfig>,
+    default_config: PackageConfig,
+    known_filters: BTreeMap<FilterPrefix, BTreeMap<FilterName, BTreeSet<WarningFilter>>>,
+    known_filter_names: BTreeMap<DiagnosticsID, (FilterPrefix, FilterName)>,
+    prim_definers:
+        BTreeMap<crate::naming::ast::BuiltinTypeName_, crate::expansion::ast::ModuleIdent>,
+    mapped_files: Arc<MappedFiles>,
+    has_errors: bool,
 }
 
 macro_rules! known_code_filter {
@@ -350,7 +367,8 @@ impl CompilationEnv {
             known_filters,
             known_filter_names,
             prim_definers: BTreeMap::new(),
-            mapped_files: MappedFiles::empty(),
+            mapped_files: Arc::new(MappedFiles::empty()),
+            forked_with_errors: false,
         }
     }
 
@@ -360,7 +378,7 @@ impl CompilationEnv {
         file_name: FileName,
         source_text: Arc<str>,
     ) {
-        self.mapped_files.add(file_hash, file_name, source_text)
+        Arc::make_mut(&mut self.mapped_files).add(file_hash, file_name, source_text)
     }
 
     pub fn file_mapping(&self) -> &MappedFiles {
@@ -386,13 +404,15 @@ impl CompilationEnv {
             // TODO do we want a centralized place for tips like this?
             if diag.info().severity() == Severity::Warning {
                 if let Some((prefix, name)) = self.known_filter_names.get(&diag.info().id()) {
+                    let filter = format_allow_attr(*prefix, *name);
                     let help = format!(
                         "This warning can be suppressed with '#[{}({})]' \
                          applied to the 'module' or module member ('const', 'fun', or 'struct')",
                         known_attributes::DiagnosticAttribute::ALLOW,
-                        format_allow_attr(*prefix, *name),
+                        filter,
                     );
-                    diag.add_note(help)
+                    diag.add_note(help);
+                    diag.set_warning_filter(filter)
                 }
                 if self.flags.warnings_are_errors() {
                     diag = diag.set_severity(Severity::NonblockingError)
@@ -417,7 +437,7 @@ impl CompilationEnv {
 
     pub fn has_errors(&self) -> bool {
         // Non-blocking Error is the min level considered an error
-        self.has_diags_at_or_above_severity(Severity::NonblockingError)
+        self.forked_with_errors || self.has_diags_at_or_above_severity(Severity::NonblockingError)
     }
 
     pub fn count_diags(&self) -> usize {
@@ -591,6 +611,63 @@ impl CompilationEnv {
     pub fn primitive_definer(&self, t: N::BuiltinTypeName_) -> Option<&E::ModuleIdent> {
         self.prim_definers.get(&t)
     }
+
+    /// Applies `f` to each of `items` in parallel. Each application gets its own environment,
+    /// forked from this one without its visitors, and the diagnostics it reports are added to
+    /// this environment in the order of `items`, as if they had been processed sequentially.
+    pub fn par_map<T: Send, U: Send>(
+        &mut self,
+        items: Vec<T>,
+        f: impl Fn(&mut CompilationEnv, T) -> U + Sync + Send,
+    ) -> Vec<U> {
+        let forked = ForkedEnv {
+            flags: self.flags.clone(),
+            warning_filter: self.warning_filter.clone(),
+            package_configs: self.package_configs.clone(),
+            default_config: self.default_config.clone(),
+            known_filters: self.known_filters.clone(),
+            known_filter_names: self.known_filter_names.clone(),
+            prim_definers: self.prim_definers.clone(),
+            mapped_files: self.mapped_files.clone(),
+            has_errors: self.has_errors(),
+        };
+        let results: Vec<(U, Diagnostics)> = items
+            .into_par_iter()
+            .map_init(
+                || forked.env(),
+                |env, item| {
+                    let result = f(env, item);
+                    (result, std::mem::take(&mut env.diags))
+                },
+            )
+            .collect();
+        results
+            .into_iter()
+            .map(|(result, diags)| {
+                // Diagnostics were already filtered by the forked environment
+                self.diags.extend(diags);
+                result
+            })
+            .collect()
+    }
+}
+
+impl ForkedEnv {
+    fn env(&self) -> CompilationEnv {
+        CompilationEnv {
+            flags: self.flags.clone(),
+            warning_filter: self.warning_filter.clone(),
+            diags: Diagnostics::new(),
+            visitors: Rc::new(Visitors::new(vec![])),
+            package_configs: self.package_configs.clone(),
+            default_config: self.default_config.clone(),
+            known_filters: self.known_filters.clone(),
+            known_filter_names: self.known_filter_names.clone(),
+            prim_definers: self.prim_definers.clone(),
+            mapped_files: self.mapped_files.clone(),
+            forked_with_errors: self.has_errors,
+        }
+    }
 }
 
 pub fn format_allow_attr(attr_name: FilterPrefix, filter: FilterName) -> String {
@@ -5,9 +5,9 @@ use std::collections::HashMap;
 
 use move_binary_format::{
     file_format::{
-        Bytecode, CodeUnit, FunctionDefinition, FunctionDefinitionIndex, FunctionHandleIndex,
-        IdentifierIndex, ModuleHandleIndex, Signature, SignatureToken, StructDefinition,
-        StructDefinitionIndex, StructFieldInformation, StructHandleIndex, TableIndex,
+        Bytecode, CodeUnit, EnumDefinition, FunctionDefinition, FunctionDefinitionIndex,
+        FunctionHandleIndex, IdentifierIndex, ModuleHandleIndex, Signature, SignatureToken,
+        StructDefinition, StructFieldInformation, StructHandleIndex, TableIndex,
     },
     internals::ModuleIndex,
     CompiledModule,
@@ -25,8 +25,8 @@ use move_symbol_pool::Symbol;
 ///   their original order.
 ///
 /// - Struct and Function Handles are sorted so that definitions in the module come first, in
-///   definition order, and remaining handles follow, in lexicographical order by fully-qualified
-///   name.
+///   definition order (structs before enums), and remaining handles follow, in lexicographical
+///   order by fully-qualified name.
 ///
 /// - Friend Declarations are sorted in lexical order (by address name and module name), followed by
 ///   unnamed addresses in their original order.
@@ -95,6 +95,15 @@ pub fn in_module(
         };
     }
 
+    for def in &mut module.enum_defs {
+        for variant in &mut def.variants {
+            remap!(IdentifierIndex, variant.variant_name, identifiers);
+            for field in &mut variant.fields {
+                remap!(IdentifierIndex, field.name, identifiers);
+            }
+        }
+    }
+
     // 1 (c). Update ordering for identifiers.  Note that updates need to happen before other
     //        handles are re-ordered, so that they can continue referencing identifiers in their own
     //        comparators.
@@ -138,14 +147,14 @@ pub fn in_module(
     apply_permutation(&mut module.module_handles, modules);
 
     // 3 (a). Choose ordering for datatype handles.
-    let struct_defs = struct_definition_order(&module.struct_defs);
+    let datatype_defs = datatype_definition_order(&module.struct_defs, &module.enum_defs);
     let structs = permutation(&module.struct_handles, |ix, handle| {
         if handle.module == module.self_handle_idx() {
             // Order structs from this module first, and in definition order
-            let Some(def_position) = struct_defs.get(&StructHandleIndex(ix)) else {
+            let Some(def_position) = datatype_defs.get(&StructHandleIndex(ix)) else {
                 panic!("ICE struct handle from module without definition: {handle:?}");
             };
-            ReferenceKey::Internal(def_position.0)
+            ReferenceKey::Internal(*def_position)
         } else {
             // Order the remaining handles afterwards, in lexicographical order of module, then
             // struct name.
@@ -166,6 +175,15 @@ pub fn in_module(
         };
     }
 
+    for def in &mut module.enum_defs {
+        remap!(StructHandleIndex, def.enum_handle, structs);
+        for variant in &mut def.variants {
+            for field in &mut variant.fields {
+                remap_signature_token(&mut field.signature.0, &structs);
+            }
+        }
+    }
+
     for Signature(tokens) in &mut module.signatures {
         for token in tokens {
             remap_signature_token(token, &structs);
@@ -228,14 +246,19 @@ pub fn in_module(
     });
 }
 
-/// Reverses mapping from `StructDefinition(Index)` to `StructHandle`, so that handles for structs
-/// defined in a module can be arranged in definition order.
-fn struct_definition_order(
-    defs: &[StructDefinition],
-) -> HashMap<StructHandleIndex, StructDefinitionIndex> {
-    defs.iter()
+/// Reverses mapping from `StructDefinition(Index)` and `EnumDefinition(Index)` to `StructHandle`,
+/// so that handles for datatypes defined in a module can be arranged in definition order, with
+/// structs preceding enums.
+fn datatype_definition_order(
+    struct_defs: &[StructDefinition],
+    enum_defs: &[EnumDefinition],
+) -> HashMap<StructHandleIndex, TableIndex> {
+    struct_defs
+        .iter()
+        .map(|def| def.struct_handle)
+        .chain(enum_defs.iter().map(|def| def.enum_handle))
         .enumerate()
-        .map(|(ix, def)| (def.struct_handle, StructDefinitionIndex(ix as TableIndex)))
+        .map(|(ix, handle)| (handle, ix as TableIndex))
         .collect()
 }
 
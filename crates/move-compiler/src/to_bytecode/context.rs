@@ -4,7 +4,7 @@
 
 use crate::{
     expansion::ast::{Address, ModuleIdent, ModuleIdent_},
-    parser::ast::{ConstantName, DatatypeName, FunctionName},
+    parser::ast::{ConstantName, DatatypeName, FunctionName, VariantName},
     shared::{CompilationEnv, NumericalAddress},
 };
 use move_core_types::account_address::AccountAddress as MoveAddress;
@@ -246,6 +246,10 @@ impl<'a> Context<'a> {
         IR::StructName(n.0.value)
     }
 
+    fn translate_variant_name(n: VariantName) -> IR::VariantName {
+        IR::VariantName(n.0.value)
+    }
+
     fn translate_constant_name(n: ConstantName) -> IR::ConstantName {
         IR::ConstantName(n.0.value)
     }
@@ -266,6 +270,18 @@ impl<'a> Context<'a> {
         Self::translate_datatype_name(s)
     }
 
+    pub fn enum_definition_name(&self, m: &ModuleIdent, e: DatatypeName) -> IR::StructName {
+        assert!(
+            self.is_current_module(m),
+            "ICE invalid enum definition lookup"
+        );
+        Self::translate_datatype_name(e)
+    }
+
+    pub fn variant_name(&self, v: VariantName) -> IR::VariantName {
+        Self::translate_variant_name(v)
+    }
+
     pub fn qualified_datatype_name(
         &mut self,
         m: &ModuleIdent,
@@ -56,6 +56,11 @@ fn remap_labels(blocks: &mut IR::BytecodeBlocks, map: &HashMap<IR::BlockLabel_,
                 B::Branch(lbl) | B::BrTrue(lbl) | B::BrFalse(lbl) => {
                     *lbl = map[lbl].clone();
                 }
+                B::VariantSwitch(_, arms) => {
+                    for (_, lbl) in arms {
+                        *lbl = map[lbl].clone();
+                    }
+                }
                 _ => (),
             }
         }
@@ -129,8 +129,6 @@ pub fn program(
     pre_compiled_lib: Option<Arc<FullyCompiledProgram>>,
     prog: G::Program,
 ) -> Vec<AnnotatedCompiledUnit> {
-    let mut units = vec![];
-
     let (orderings, ddecls, fdecls) = extract_decls(compilation_env, pre_compiled_lib, &prog);
     let G::Program { modules: gmodules } = prog;
 
@@ -139,12 +137,14 @@ pub fn program(
         .filter(|(_, mdef)| mdef.is_source_module)
         .collect::<Vec<_>>();
     source_modules.sort_by_key(|(_, mdef)| mdef.dependency_order);
-    for (m, mdef) in source_modules {
-        if let Some(unit) = module(compilation_env, m, mdef, &orderings, &ddecls, &fdecls) {
-            units.push(unit)
-        }
-    }
-    units
+    // Modules only depend on the declarations of others, so they can be compiled in parallel
+    compilation_env
+        .par_map(source_modules, |env, (m, mdef)| {
+            module(env, m, mdef, &orderings, &ddecls, &fdecls)
+        })
+        .into_iter()
+        .flatten()
+        .collect()
 }
 
 fn module(
@@ -171,21 +171,9 @@ fn module(
         functions: gfunctions,
     } = mdef;
 
-    if !genums.is_empty() {
-        for (name, _) in genums {
-            let mut diag = diag!(
-                Editions::FeatureInDevelopment,
-                (name.loc(), "Enums are not supported in bytecode.")
-            );
-            diag.add_note("This feature is currently in development.");
-            compilation_env.add_diag(diag);
-        }
-        return None;
-    }
-
     let mut context = Context::new(compilation_env, package_name, Some(&ident));
     let structs = struct_defs(&mut context, &ident, gstructs);
-    // let enums = struct_defs(&mut context, &ident, genums);
+    let enums = enum_defs(&mut context, &ident, genums);
 
     let constants = constants(&mut context, &ident, gconstants);
     let (collected_function_infos, functions) = functions(&mut context, &ident, gfunctions);
@@ -226,6 +214,7 @@ fn module(
         imports,
         explicit_dependency_declarations,
         structs,
+        enums,
         constants,
         functions,
     };
@@ -410,6 +399,72 @@ fn struct_fields(
     }
 }
 
+//**************************************************************************************************
+// Enums
+//**************************************************************************************************
+
+fn enum_defs(
+    context: &mut Context,
+    m: &ModuleIdent,
+    enums: UniqueMap<DatatypeName, H::EnumDefinition>,
+) -> Vec<IR::EnumDefinition> {
+    let mut enums = enums.into_iter().collect::<Vec<_>>();
+    enums.sort_by_key(|(_, e)| e.index);
+    enums
+        .into_iter()
+        .map(|(e, edef)| enum_def(context, m, e, edef))
+        .collect()
+}
+
+fn enum_def(
+    context: &mut Context,
+    m: &ModuleIdent,
+    e: DatatypeName,
+    edef: H::EnumDefinition,
+) -> IR::EnumDefinition {
+    let H::EnumDefinition {
+        warning_filter: _warning_filter,
+        index: _index,
+        attributes: _attributes,
+        abilities: abs,
+        type_parameters: tys,
+        variants,
+    } = edef;
+    let loc = e.loc();
+    let name = context.enum_definition_name(m, e);
+    let abilities = abilities(&abs);
+    let type_formals = datatype_type_parameters(tys);
+    let mut variants = variants.into_iter().collect::<Vec<_>>();
+    // the position of a variant in the declaration is its tag
+    variants.sort_by_key(|(_, v)| v.index);
+    let variants = variants
+        .into_iter()
+        .map(|(v, vdef)| {
+            let fields = vdef
+                .fields
+                .into_iter()
+                .map(|(f, ty)| (field(f), base_type(context, ty)))
+                .collect();
+            sp(
+                vdef.loc,
+                IR::VariantDefinition_ {
+                    name: context.variant_name(v),
+                    fields,
+                },
+            )
+        })
+        .collect();
+    sp(
+        loc,
+        IR::EnumDefinition_ {
+            name,
+            abilities,
+            type_formals,
+            variants,
+        },
+    )
+}
+
 //**************************************************************************************************
 // Constants
 //**************************************************************************************************
@@ -847,13 +902,18 @@ fn command(context: &mut Context, code: &mut IR::BytecodeBlock, sp!(loc, cmd_):
             code.push(sp(loc, B::BrFalse(label(if_false))));
             code.push(sp(loc, B::Branch(label(if_true))));
         }
-        C::VariantSwitch { .. } => {
-            let mut diag = diag!(
-                Editions::FeatureInDevelopment,
-                (loc, "Variant switching is not supported in bytecode.")
-            );
-            diag.add_note("This feature is currently in development.");
-            context.env.add_diag(diag);
+        C::VariantSwitch {
+            subject,
+            enum_name,
+            arms,
+        } => {
+            exp(context, code, subject);
+            let n = context.enum_definition_name(context.current_module().unwrap(), enum_name);
+            let arms = arms
+                .into_iter()
+                .map(|(v, target)| (context.variant_name(v), label(target)))
+                .collect();
+            code.push(sp(loc, B::VariantSwitch(n, arms)));
         }
         C::Break(_) | C::Continue(_) => panic!("ICE break/continue not translated to jumps"),
     }
@@ -904,13 +964,18 @@ fn lvalue(context: &mut Context, code: &mut IR::BytecodeBlock, sp!(loc, l_): H::
             lvalues_(context, code, field_ls.into_iter().map(|(_, l)| l));
         }
 
-        L::UnpackVariant(..) => {
-            let mut diag = diag!(
-                Editions::FeatureInDevelopment,
-                (loc, "Switching is not supported in bytecode.")
-            );
-            diag.add_note("This feature is currently in development.");
-            context.env.add_diag(diag);
+        L::UnpackVariant(e, v, unpack_type, _rhs_loc, tys, field_ls) => {
+            let n = context.enum_definition_name(context.current_module().unwrap(), e);
+            let v = context.variant_name(v);
+            let tys = base_types(context, tys);
+            let instr = match unpack_type {
+                H::UnpackType::ByValue => B::UnpackVariant(n, v, tys),
+                H::UnpackType::ByImmRef => B::UnpackVariantImmRef(n, v, tys),
+                H::UnpackType::ByMutRef => B::UnpackVariantMutRef(n, v, tys),
+            };
+            code.push(sp(loc, instr));
+
+            lvalues_(context, code, field_ls.into_iter().map(|(_, l)| l));
         }
     }
 }
@@ -1030,13 +1095,13 @@ fn exp(context: &mut Context, code: &mut IR::BytecodeBlock, e: H::Exp) {
             code.push(sp(loc, B::Pack(n, base_types(context, tys))))
         }
 
-        E::PackVariant(..) => {
-            let mut diag = diag!(
-                Editions::FeatureInDevelopment,
-                (loc, "Variant packing is not supported in bytecode.")
-            );
-            diag.add_note("This feature is currently in development.");
-            context.env.add_diag(diag);
+        E::PackVariant(e, v, tys, field_args) => {
+            for (_, _, earg) in field_args {
+                exp(context, code, earg);
+            }
+            let n = context.enum_definition_name(context.current_module().unwrap(), e);
+            let v = context.variant_name(v);
+            code.push(sp(loc, B::PackVariant(n, v, base_types(context, tys))))
         }
 
         E::Vector(_, n, bt, args) => {
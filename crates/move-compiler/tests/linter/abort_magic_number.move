@@ -0,0 +1,32 @@
+module a::m {
+    const EInvalid: u64 = 0;
+
+    public fun magic_numbers(x: u64) {
+        assert!(x > 0, 1);
+        if (x == 1) abort 2;
+        if (x == 2) abort (3: u64);
+    }
+
+    public fun named_constants(x: u64) {
+        assert!(x > 0, EInvalid);
+        if (x == 1) abort EInvalid;
+        if (x == 2) abort x;
+    }
+
+    #[allow(lint(abort_magic_number))]
+    public fun abort_magic_number_allowed() {
+        abort 0
+    }
+
+    #[test]
+    fun test_magic_number() {
+        assert!(1 + 1 == 3, 0);
+    }
+}
+
+#[test_only]
+module a::tests {
+    public fun magic_number() {
+        abort 0
+    }
+}
@@ -0,0 +1,43 @@
+module a::m {
+    public fun unsigned_bounds(x: u64, y: u8, z: u256): vector<bool> {
+        vector[
+            x >= 0,
+            x < 0,
+            0 <= x,
+            0 > x,
+            y <= 255,
+            y > 255,
+            255 >= y,
+            255 < y,
+            z <= 115792089237316195423570985008687907853269984665640564039457584007913129639935,
+        ]
+    }
+
+    public fun literals(): vector<bool> {
+        vector[
+            1 < 2,
+            1 == 1,
+            1 != 1,
+            2 >= 3,
+            true == false,
+            true != false,
+        ]
+    }
+
+    public fun not_constant(x: u64, y: u8, b: bool): vector<bool> {
+        vector[
+            x > 0,
+            x <= 0,
+            y < 255,
+            y >= 255,
+            x == 0,
+            b == true,
+            @0x1 == @0x1,
+        ]
+    }
+
+    #[allow(lint(constant_comparison))]
+    public fun constant_comparison_allowed(x: u64): bool {
+        x >= 0
+    }
+}
@@ -0,0 +1,41 @@
+module a::m {
+    struct S has copy, drop { f: u64 }
+
+    public fun redundant(x: u64, v: vector<u64>, s: S): (u64, vector<u64>, S) {
+        let y = copy x;
+        let w = copy v;
+        let t = copy s;
+        (y, w, t)
+    }
+
+    public fun redundant_in_branch(x: u64, b: bool): u64 {
+        if (b) copy x else x + 1
+    }
+
+    public fun used_after(x: u64, v: vector<u64>): (u64, u64, vector<u64>, vector<u64>) {
+        let y = copy x;
+        let w = copy v;
+        (y, x, w, v)
+    }
+
+    public fun used_in_loop(x: u64): u64 {
+        let i = 0;
+        let sum = 0;
+        while (i < 10) {
+            sum = sum + copy x;
+            i = i + 1;
+        };
+        sum
+    }
+
+    public fun borrowed(s: S): (u64, S) {
+        let r = &s;
+        let t = copy s;
+        (r.f, t)
+    }
+
+    #[allow(lint(redundant_copy))]
+    public fun redundant_copy_allowed(x: u64): u64 {
+        copy x
+    }
+}
@@ -0,0 +1,34 @@
+module a::m {
+    struct S has copy, drop { f: u64, g: u64, s: T }
+    struct T has copy, drop { f: u64 }
+
+    public fun locals(x: u64, v: vector<u64>): (u64, vector<u64>) {
+        x = x;
+        v = v;
+        (x, v)
+    }
+
+    public fun fields(s: S, r: &mut S): S {
+        s.f = s.f;
+        s.s.f = s.s.f;
+        r.g = r.g;
+        r.s.f = r.s.f;
+        *r = *r;
+        s
+    }
+
+    public fun not_self_assignment(x: u64, y: u64, s: S, r: &mut S): (u64, S) {
+        x = y;
+        s.f = s.g;
+        s.s.f = s.f;
+        r.f = s.f;
+        *r = s;
+        (x, s)
+    }
+
+    #[allow(lint(self_assignment))]
+    public fun self_assignment_allowed(x: u64): u64 {
+        x = x;
+        x
+    }
+}
@@ -0,0 +1,14 @@
+module a::m {
+    public fun out_of_range(x8: u8, x64: u64, x128: u128): (u8, u64, u128, u8) {
+        (x8 << 8, x64 >> 64, x128 << 200, x8 >> (9: u8))
+    }
+
+    public fun in_range(x8: u8, x64: u64, x128: u128, x256: u256, n: u8): (u8, u64, u128, u256, u64) {
+        (x8 << 7, x64 >> 63, x128 << 127, x256 >> 255, x64 << n)
+    }
+
+    #[allow(lint(shift_overflow))]
+    public fun shift_overflow_allowed(x: u8): u8 {
+        x << 8
+    }
+}
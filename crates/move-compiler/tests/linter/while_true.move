@@ -0,0 +1,20 @@
+module a::m {
+    public fun while_true(): u64 {
+        let i = 0;
+        while (true) {
+            i = i + 1;
+            if (i > 10) break
+        };
+        while ((true: bool)) break;
+        // not constant conditions
+        while (i < 20) i = i + 1;
+        while (false) ();
+        loop { if (i > 30) break; i = i + 1 };
+        i
+    }
+
+    #[allow(lint(while_true))]
+    public fun while_true_allowed() {
+        while (true) break
+    }
+}
@@ -4,14 +4,14 @@
 
 use crate::{
     account_address::AccountAddress,
-    annotated_visitor::{visit_struct, visit_value, Error as VError, Visitor},
+    annotated_visitor::{visit_struct, visit_value, visit_variant, Error as VError, Visitor},
     identifier::Identifier,
     language_storage::{StructTag, TypeTag},
     runtime_value as R, u256,
 };
 use anyhow::Result as AResult;
 use serde::{
-    de::Error as DeError,
+    de::{Error as DeError, VariantAccess},
     ser::{SerializeMap, SerializeSeq, SerializeStruct},
     Deserialize, Serialize,
 };
@@ -29,12 +29,27 @@ pub const MOVE_STRUCT_TYPE: &str = "type";
 /// In the `WithTypes` configuration, a Move struct gets serialized into a Serde struct with this as the second field
 pub const MOVE_STRUCT_FIELDS: &str = "fields";
 
+/// In the `WithTypes` configuration, a Move enum value gets serialized into a Serde struct with this name
+pub const MOVE_VARIANT_NAME: &str = "variant";
+
+/// In the `WithTypes` configuration, a Move enum value gets serialized into a Serde struct with the
+/// name of its variant as this field
+pub const MOVE_VARIANT_TAG_NAME: &str = "variant_name";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct MoveStruct {
     pub type_: StructTag,
     pub fields: Vec<(Identifier, MoveValue)>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveVariant {
+    pub type_: StructTag,
+    pub variant_name: Identifier,
+    pub tag: u16,
+    pub fields: Vec<(Identifier, MoveValue)>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MoveValue {
     U8(u8),
@@ -49,6 +64,8 @@ pub enum MoveValue {
     U16(u16),
     U32(u32),
     U256(u256::U256),
+    // NOTE: Added in bytecode version v7, do not reorder!
+    Variant(MoveVariant),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +87,13 @@ pub struct MoveStructLayout {
     pub fields: Vec<MoveFieldLayout>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveEnumLayout {
+    pub type_: StructTag,
+    /// The name and fields of each variant, indexed by variant tag
+    pub variants: Vec<(Identifier, Vec<MoveFieldLayout>)>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MoveTypeLayout {
     #[serde(rename(serialize = "bool", deserialize = "bool"))]
@@ -96,6 +120,10 @@ pub enum MoveTypeLayout {
     U32,
     #[serde(rename(serialize = "u256", deserialize = "u256"))]
     U256,
+
+    // NOTE: Added in bytecode version v7, do not reorder!
+    #[serde(rename(serialize = "enum", deserialize = "enum"))]
+    Enum(MoveEnumLayout),
 }
 
 impl MoveValue {
@@ -146,6 +174,7 @@ impl MoveValue {
     pub fn undecorate(self) -> R::MoveValue {
         match self {
             Self::Struct(s) => R::MoveValue::Struct(s.undecorate()),
+            Self::Variant(v) => R::MoveValue::Variant(v.undecorate()),
             Self::Vector(vals) => {
                 R::MoveValue::Vector(vals.into_iter().map(MoveValue::undecorate).collect())
             }
@@ -217,6 +246,60 @@ impl MoveStruct {
     }
 }
 
+impl MoveVariant {
+    pub fn new(
+        type_: StructTag,
+        variant_name: Identifier,
+        tag: u16,
+        fields: Vec<(Identifier, MoveValue)>,
+    ) -> Self {
+        Self {
+            type_,
+            variant_name,
+            tag,
+            fields,
+        }
+    }
+
+    pub fn simple_deserialize(blob: &[u8], ty: &MoveEnumLayout) -> AResult<Self> {
+        Ok(bcs::from_bytes_seed(ty, blob)?)
+    }
+
+    /// Like `MoveValue::visit_deserialize` (see for details), but specialized to visiting an enum
+    /// value (the `blob` is known to be a serialized Move enum value, and the layout is a
+    /// `MoveEnumLayout`).
+    pub fn visit_deserialize<V: Visitor>(
+        mut blob: &[u8],
+        ty: &MoveEnumLayout,
+        visitor: &mut V,
+    ) -> AResult<V::Value>
+    where
+        V::Error: std::error::Error + Send + Sync + 'static,
+    {
+        let res = visit_variant(&mut blob, ty, visitor)?;
+        if blob.is_empty() {
+            Ok(res)
+        } else {
+            Err(VError::TrailingBytes(blob.len()).into())
+        }
+    }
+
+    pub fn into_fields(self) -> Vec<MoveValue> {
+        self.fields.into_iter().map(|(_, f)| f).collect()
+    }
+
+    pub fn undecorate(self) -> R::MoveVariant {
+        R::MoveVariant {
+            tag: self.tag,
+            fields: self
+                .into_fields()
+                .into_iter()
+                .map(MoveValue::undecorate)
+                .collect(),
+        }
+    }
+}
+
 impl MoveStructLayout {
     pub fn new(type_: StructTag, fields: Vec<MoveFieldLayout>) -> Self {
         Self { type_, fields }
@@ -227,6 +310,12 @@ impl MoveStructLayout {
     }
 }
 
+impl MoveEnumLayout {
+    pub fn new(type_: StructTag, variants: Vec<(Identifier, Vec<MoveFieldLayout>)>) -> Self {
+        Self { type_, variants }
+    }
+}
+
 impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
     type Value = MoveValue;
     fn deserialize<D: serde::de::Deserializer<'d>>(
@@ -248,6 +337,7 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
                 AccountAddress::deserialize(deserializer).map(MoveValue::Signer)
             }
             MoveTypeLayout::Struct(ty) => Ok(MoveValue::Struct(ty.deserialize(deserializer)?)),
+            MoveTypeLayout::Enum(ty) => Ok(MoveValue::Variant(ty.deserialize(deserializer)?)),
             MoveTypeLayout::Vector(layout) => Ok(MoveValue::Vector(
                 deserializer.deserialize_seq(VectorElementVisitor(layout))?,
             )),
@@ -327,10 +417,52 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveStructLayout {
     }
 }
 
+impl<'d> serde::de::DeserializeSeed<'d> for &MoveEnumLayout {
+    type Value = MoveVariant;
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_enum(R::MOVE_ENUM_NAME, &[], DecoratedEnumVisitor(self))
+    }
+}
+
+struct DecoratedEnumVisitor<'a>(&'a MoveEnumLayout);
+
+impl<'d, 'a> serde::de::Visitor<'d> for DecoratedEnumVisitor<'a> {
+    type Value = MoveVariant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        let (tag, variant) = data.variant::<u32>()?;
+        let Some((variant_name, layout)) = self.0.variants.get(tag as usize) else {
+            return Err(A::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(tag as u64),
+                &self,
+            ));
+        };
+        let fields = variant.tuple_variant(layout.len(), DecoratedStructFieldVisitor(layout))?;
+        Ok(MoveVariant {
+            type_: self.0.type_.clone(),
+            variant_name: variant_name.clone(),
+            tag: tag as u16,
+            fields,
+        })
+    }
+}
+
 impl serde::Serialize for MoveValue {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
             MoveValue::Struct(s) => s.serialize(serializer),
+            MoveValue::Variant(v) => v.serialize(serializer),
             MoveValue::Bool(b) => serializer.serialize_bool(*b),
             MoveValue::U8(i) => serializer.serialize_u8(*i),
             MoveValue::U16(i) => serializer.serialize_u16(*i),
@@ -378,6 +510,18 @@ impl serde::Serialize for MoveStruct {
     }
 }
 
+impl serde::Serialize for MoveVariant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Serialized like a Move struct (see above), with the name of the variant as an additional
+        // field.
+        let mut t = serializer.serialize_struct(MOVE_VARIANT_NAME, 3)?;
+        t.serialize_field(MOVE_STRUCT_TYPE, &self.type_.to_string())?;
+        t.serialize_field(MOVE_VARIANT_TAG_NAME, &self.variant_name.to_string())?;
+        t.serialize_field(MOVE_STRUCT_FIELDS, &MoveFields(&self.fields))?;
+        t.end()
+    }
+}
+
 impl fmt::Display for MoveTypeLayout {
     fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
         use MoveTypeLayout::*;
@@ -395,6 +539,8 @@ impl fmt::Display for MoveTypeLayout {
             Vector(typ) => write!(f, "vector<{typ}>"),
             Struct(s) if f.alternate() => write!(f, "{s:#}"),
             Struct(s) => write!(f, "{s}"),
+            Enum(e) if f.alternate() => write!(f, "{e:#}"),
+            Enum(e) => write!(f, "{e}"),
         }
     }
 }
@@ -426,6 +572,32 @@ impl fmt::Display for MoveStructLayout {
     }
 }
 
+impl fmt::Display for MoveEnumLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
+        use DebugAsDisplay as DD;
+        write!(f, "enum ")?;
+        write!(f, "{} ", self.type_)?;
+        let mut map = f.debug_map();
+        for (variant_name, fields) in &self.variants {
+            map.entry(&DD(variant_name), &DD(&VariantFieldsLayout(fields)));
+        }
+        map.finish()
+    }
+}
+
+struct VariantFieldsLayout<'a>(&'a [MoveFieldLayout]);
+
+impl fmt::Display for VariantFieldsLayout<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
+        use DebugAsDisplay as DD;
+        let mut map = f.debug_map();
+        for field in self.0 {
+            map.entry(&DD(&field.name), &DD(&field.layout));
+        }
+        map.finish()
+    }
+}
+
 impl From<&MoveTypeLayout> for TypeTag {
     fn from(val: &MoveTypeLayout) -> TypeTag {
         match val {
@@ -443,6 +615,7 @@ impl From<&MoveTypeLayout> for TypeTag {
                 TypeTag::Vector(Box::new(inner_type.into()))
             }
             MoveTypeLayout::Struct(v) => TypeTag::Struct(Box::new(v.into())),
+            MoveTypeLayout::Enum(e) => TypeTag::Struct(Box::new(e.type_.clone())),
         }
     }
 }
@@ -476,6 +649,7 @@ impl fmt::Display for MoveValue {
                 list.finish()
             }
             MoveValue::Struct(s) => fmt::Display::fmt(s, f),
+            MoveValue::Variant(v) => fmt::Display::fmt(v, f),
         }
     }
 }
@@ -492,3 +666,16 @@ impl fmt::Display for MoveStruct {
         map.finish()
     }
 }
+
+impl fmt::Display for MoveVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DebugAsDisplay as DD;
+        fmt::Display::fmt(&self.type_, f)?;
+        write!(f, "::{} ", self.variant_name)?;
+        let mut map = f.debug_map();
+        for (field, value) in &self.fields {
+            map.entry(&DD(field), &DD(value));
+        }
+        map.finish()
+    }
+}
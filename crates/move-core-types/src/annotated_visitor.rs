@@ -5,7 +5,8 @@ use std::io::Read;
 
 use crate::{
     account_address::AccountAddress,
-    annotated_value::{MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
+    annotated_value::{MoveEnumLayout, MoveFieldLayout, MoveStructLayout, MoveTypeLayout},
+    identifier::IdentStr,
     u256::U256,
 };
 
@@ -46,13 +47,18 @@ pub trait Visitor {
         &mut self,
         driver: &mut StructDriver<'_, '_, '_>,
     ) -> Result<Self::Value, Self::Error>;
+
+    fn visit_variant(
+        &mut self,
+        driver: &mut VariantDriver<'_, '_, '_>,
+    ) -> Result<Self::Value, Self::Error>;
 }
 
 /// A traversal is a special kind of visitor that doesn't return any values. The trait comes with
 /// default implementations for every variant that do nothing, allowing an implementor to focus on
 /// only the cases they care about.
 ///
-/// Note that the default implementation for structs and vectors recurse down into their elements. A
+/// Note that the default implementation for structs, enum variants and vectors recurse down into their elements. A
 /// traversal that doesn't want to look inside structs and vectors needs to provide a custom
 /// implementation with an empty body:
 ///
@@ -112,6 +118,14 @@ pub trait Traversal {
         while driver.next_field(self)?.is_some() {}
         Ok(())
     }
+
+    fn traverse_variant(
+        &mut self,
+        driver: &mut VariantDriver<'_, '_, '_>,
+    ) -> Result<(), Self::Error> {
+        while driver.next_field(self)?.is_some() {}
+        Ok(())
+    }
 }
 
 /// Default implementation converting any traversal into a visitor.
@@ -168,6 +182,13 @@ impl<T: Traversal + ?Sized> Visitor for T {
     ) -> Result<Self::Value, Self::Error> {
         self.traverse_struct(driver)
     }
+
+    fn visit_variant(
+        &mut self,
+        driver: &mut VariantDriver<'_, '_, '_>,
+    ) -> Result<Self::Value, Self::Error> {
+        self.traverse_variant(driver)
+    }
 }
 
 /// Exposes information about a vector being visited (the element layout) to a visitor
@@ -189,6 +210,16 @@ pub struct StructDriver<'r, 'b, 'l> {
     off: usize,
 }
 
+/// Exposes information about an enum value being visited (its layout, the variant it holds, details
+/// about the next field to be visited) to a visitor implementation, and allows that visitor to
+/// progress the traversal (by visiting or skipping fields).
+pub struct VariantDriver<'r, 'b, 'l> {
+    bytes: &'r mut &'b [u8],
+    layout: &'l MoveEnumLayout,
+    tag: u16,
+    off: usize,
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("unexpected end of input")]
@@ -199,6 +230,9 @@ pub enum Error {
 
     #[error("trailing {0} byte(s) at the end of input")]
     TrailingBytes(usize),
+
+    #[error("unexpected variant tag: {0}")]
+    UnexpectedVariantTag(u64),
 }
 
 /// The null traversal implements `Traversal` and `Visitor` but without doing anything (does not
@@ -311,6 +345,64 @@ impl<'r, 'b, 'l> StructDriver<'r, 'b, 'l> {
     }
 }
 
+impl<'r, 'b, 'l> VariantDriver<'r, 'b, 'l> {
+    fn new(bytes: &'r mut &'b [u8], layout: &'l MoveEnumLayout, tag: u16) -> Self {
+        Self {
+            bytes,
+            layout,
+            tag,
+            off: 0,
+        }
+    }
+
+    /// The layout of the enum being visited.
+    pub fn enum_layout(&self) -> &'l MoveEnumLayout {
+        self.layout
+    }
+
+    /// The tag of the variant being visited.
+    pub fn tag(&self) -> u16 {
+        self.tag
+    }
+
+    /// The name of the variant being visited.
+    pub fn variant_name(&self) -> &'l IdentStr {
+        &self.layout.variants[self.tag as usize].0
+    }
+
+    /// The layouts of the fields of the variant being visited.
+    pub fn variant_layout(&self) -> &'l [MoveFieldLayout] {
+        &self.layout.variants[self.tag as usize].1
+    }
+
+    /// The layout of the next field to be visited (if there is one), or `None` otherwise.
+    pub fn peek_field(&self) -> Option<&'l MoveFieldLayout> {
+        self.variant_layout().get(self.off)
+    }
+
+    /// Visit the next field in the variant. Behaves like `StructDriver::next_field` (see for
+    /// details).
+    pub fn next_field<V: Visitor + ?Sized>(
+        &mut self,
+        visitor: &mut V,
+    ) -> Result<Option<(&'l MoveFieldLayout, V::Value)>, V::Error> {
+        let Some(field) = self.peek_field() else {
+            return Ok(None);
+        };
+
+        let res = visit_value(self.bytes, &field.layout, visitor)?;
+        self.off += 1;
+        Ok(Some((field, res)))
+    }
+
+    /// Skip the next field. Returns the layout of the field that was visited if there was one, or
+    /// `None` if there was none. Can return an error if there was a deserialization error.
+    pub fn skip_field(&mut self) -> Result<Option<&'l MoveFieldLayout>, Error> {
+        self.next_field(&mut NullTraversal)
+            .map(|res| res.map(|(f, _)| f))
+    }
+}
+
 /// Visit a serialized Move value with the provided `layout`, held in `bytes`, using the provided
 /// visitor to build a value out of it. See `annoted_value::MoveValue::visit_deserialize` for
 /// details.
@@ -346,6 +438,7 @@ pub(crate) fn visit_value<V: Visitor + ?Sized>(
         }
 
         L::Struct(l) => visit_struct(bytes, l, visitor),
+        L::Enum(l) => visit_variant(bytes, l, visitor),
     }
 }
 
@@ -362,6 +455,23 @@ pub(crate) fn visit_struct<V: Visitor + ?Sized>(
     Ok(res)
 }
 
+/// Like `visit_value` but specialized to visiting an enum value (where the `bytes` is known to be a
+/// serialized move enum value), and the layout is an enum layout.
+pub(crate) fn visit_variant<V: Visitor + ?Sized>(
+    bytes: &mut &[u8],
+    layout: &MoveEnumLayout,
+    visitor: &mut V,
+) -> Result<V::Value, V::Error> {
+    let tag = leb128::read::unsigned(bytes).map_err(|_| Error::UnexpectedEof)?;
+    if tag >= layout.variants.len() as u64 {
+        return Err(Error::UnexpectedVariantTag(tag).into());
+    }
+    let mut driver = VariantDriver::new(bytes, layout, tag as u16);
+    let res = visitor.visit_variant(&mut driver)?;
+    while driver.skip_field()?.is_some() {}
+    Ok(res)
+}
+
 fn read_exact<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], Error> {
     let mut buf = [0u8; N];
     bytes
@@ -6,8 +6,8 @@ use crate::{account_address::AccountAddress, annotated_value as A, fmt_list, u25
 use anyhow::{anyhow, Result as AResult};
 use move_proc_macros::test_variant_order;
 use serde::{
-    de::Error as DeError,
-    ser::{SerializeSeq, SerializeTuple},
+    de::{Error as DeError, VariantAccess},
+    ser::{SerializeSeq, SerializeTuple, SerializeTupleVariant},
     Deserialize, Serialize,
 };
 use std::fmt::{self, Debug};
@@ -21,9 +21,23 @@ pub const MOVE_STRUCT_TYPE: &str = "type";
 /// In the `WithTypes` configuration, a Move struct gets serialized into a Serde struct with this as the second field
 pub const MOVE_STRUCT_FIELDS: &str = "fields";
 
+/// The name of the Serde enum a Move enum value gets serialized into. Only the tag of the variant
+/// and its fields are part of the serialized value, so all variants share a placeholder name.
+pub const MOVE_ENUM_NAME: &str = "enum";
+
+/// The placeholder name of the variant of a Serde enum a Move enum value gets serialized into.
+pub const MOVE_VARIANT_NAME: &str = "variant";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct MoveStruct(pub Vec<MoveValue>);
 
+/// A value of a Move enum: the tag of its variant and the fields of that variant.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveVariant {
+    pub tag: u16,
+    pub fields: Vec<MoveValue>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum MoveValue {
     U8(u8),
@@ -38,11 +52,17 @@ pub enum MoveValue {
     U16(u16),
     U32(u32),
     U256(u256::U256),
+    // NOTE: Added in bytecode version v7, do not reorder!
+    Variant(MoveVariant),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveStructLayout(pub Vec<MoveTypeLayout>);
 
+/// The layout of a Move enum: the layouts of the fields of each variant, indexed by variant tag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveEnumLayout(pub Vec<Vec<MoveTypeLayout>>);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[test_variant_order(src/unit_tests/staged_enum_variant_order/move_type_layout.yaml)]
 pub enum MoveTypeLayout {
@@ -70,6 +90,10 @@ pub enum MoveTypeLayout {
     U32,
     #[serde(rename(serialize = "u256", deserialize = "u256"))]
     U256,
+
+    // NOTE: Added in bytecode version v7, do not reorder!
+    #[serde(rename(serialize = "enum", deserialize = "enum"))]
+    Enum(MoveEnumLayout),
 }
 
 impl MoveValue {
@@ -115,6 +139,9 @@ impl MoveValue {
             (MoveValue::Struct(s), A::MoveTypeLayout::Struct(l)) => {
                 A::MoveValue::Struct(s.decorate(l))
             }
+            (MoveValue::Variant(v), A::MoveTypeLayout::Enum(l)) => {
+                A::MoveValue::Variant(v.decorate(l))
+            }
             (MoveValue::Vector(vals), A::MoveTypeLayout::Vector(t)) => {
                 A::MoveValue::Vector(vals.into_iter().map(|v| v.decorate(t)).collect())
             }
@@ -175,6 +202,40 @@ impl MoveStruct {
     }
 }
 
+impl MoveVariant {
+    pub fn new(tag: u16, fields: Vec<MoveValue>) -> Self {
+        Self { tag, fields }
+    }
+
+    pub fn simple_deserialize(blob: &[u8], ty: &MoveEnumLayout) -> AResult<Self> {
+        Ok(bcs::from_bytes_seed(ty, blob)?)
+    }
+
+    pub fn decorate(self, layout: &A::MoveEnumLayout) -> A::MoveVariant {
+        let MoveVariant { tag, fields } = self;
+        let A::MoveEnumLayout { type_, variants } = layout;
+        let (variant_name, field_layouts) = &variants[tag as usize];
+        A::MoveVariant {
+            type_: type_.clone(),
+            variant_name: variant_name.clone(),
+            tag,
+            fields: fields
+                .into_iter()
+                .zip(field_layouts)
+                .map(|(v, l)| (l.name.clone(), v.decorate(&l.layout)))
+                .collect(),
+        }
+    }
+
+    pub fn fields(&self) -> &[MoveValue] {
+        &self.fields
+    }
+
+    pub fn into_fields(self) -> Vec<MoveValue> {
+        self.fields
+    }
+}
+
 impl MoveStructLayout {
     pub fn new(types: Vec<MoveTypeLayout>) -> Self {
         Self(types)
@@ -189,6 +250,16 @@ impl MoveStructLayout {
     }
 }
 
+impl MoveEnumLayout {
+    pub fn new(variants: Vec<Vec<MoveTypeLayout>>) -> Self {
+        Self(variants)
+    }
+
+    pub fn variants(&self) -> &[Vec<MoveTypeLayout>] {
+        &self.0
+    }
+}
+
 impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
     type Value = MoveValue;
     fn deserialize<D: serde::de::Deserializer<'d>>(
@@ -210,6 +281,7 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveTypeLayout {
                 AccountAddress::deserialize(deserializer).map(MoveValue::Signer)
             }
             MoveTypeLayout::Struct(ty) => Ok(MoveValue::Struct(ty.deserialize(deserializer)?)),
+            MoveTypeLayout::Enum(ty) => Ok(MoveValue::Variant(ty.deserialize(deserializer)?)),
             MoveTypeLayout::Vector(layout) => Ok(MoveValue::Vector(
                 deserializer.deserialize_seq(VectorElementVisitor(layout))?,
             )),
@@ -276,10 +348,50 @@ impl<'d> serde::de::DeserializeSeed<'d> for &MoveStructLayout {
     }
 }
 
+impl<'d> serde::de::DeserializeSeed<'d> for &MoveEnumLayout {
+    type Value = MoveVariant;
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_enum(MOVE_ENUM_NAME, &[], EnumVariantVisitor(&self.0))
+    }
+}
+
+struct EnumVariantVisitor<'a>(&'a [Vec<MoveTypeLayout>]);
+
+impl<'d, 'a> serde::de::Visitor<'d> for EnumVariantVisitor<'a> {
+    type Value = MoveVariant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        let (tag, variant) = data.variant::<u32>()?;
+        let Some(layout) = self.0.get(tag as usize) else {
+            return Err(A::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(tag as u64),
+                &self,
+            ));
+        };
+        let fields = variant.tuple_variant(layout.len(), StructFieldVisitor(layout))?;
+        Ok(MoveVariant {
+            tag: tag as u16,
+            fields,
+        })
+    }
+}
+
 impl serde::Serialize for MoveValue {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         match self {
             MoveValue::Struct(s) => s.serialize(serializer),
+            MoveValue::Variant(v) => v.serialize(serializer),
             MoveValue::Bool(b) => serializer.serialize_bool(*b),
             MoveValue::U8(i) => serializer.serialize_u8(*i),
             MoveValue::U16(i) => serializer.serialize_u16(*i),
@@ -310,6 +422,21 @@ impl serde::Serialize for MoveStruct {
     }
 }
 
+impl serde::Serialize for MoveVariant {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut t = serializer.serialize_tuple_variant(
+            MOVE_ENUM_NAME,
+            self.tag as u32,
+            MOVE_VARIANT_NAME,
+            self.fields.len(),
+        )?;
+        for v in self.fields.iter() {
+            t.serialize_field(v)?;
+        }
+        t.end()
+    }
+}
+
 impl fmt::Display for MoveTypeLayout {
     fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
         use MoveTypeLayout::*;
@@ -327,6 +454,8 @@ impl fmt::Display for MoveTypeLayout {
             Vector(typ) => write!(f, "vector<{typ}>"),
             Struct(s) if f.alternate() => write!(f, "{s:#}"),
             Struct(s) => write!(f, "{s}"),
+            Enum(e) if f.alternate() => write!(f, "{e:#}"),
+            Enum(e) => write!(f, "{e}"),
         }
     }
 }
@@ -359,6 +488,20 @@ impl fmt::Display for MoveStructLayout {
     }
 }
 
+impl fmt::Display for MoveEnumLayout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> std::fmt::Result {
+        use DebugAsDisplay as DD;
+
+        write!(f, "enum ")?;
+        let mut map = f.debug_map();
+        for (tag, fields) in self.0.iter().enumerate() {
+            map.entry(&tag, &DD(&MoveStructLayout(fields.clone())));
+        }
+
+        map.finish()
+    }
+}
+
 impl fmt::Display for MoveValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -374,6 +517,7 @@ impl fmt::Display for MoveValue {
             MoveValue::Signer(a) => write!(f, "signer({})", a.to_hex_literal()),
             MoveValue::Vector(v) => fmt_list(f, "vector[", v, "]"),
             MoveValue::Struct(s) => fmt::Display::fmt(s, f),
+            MoveValue::Variant(v) => fmt::Display::fmt(v, f),
         }
     }
 }
@@ -383,3 +527,9 @@ impl fmt::Display for MoveStruct {
         fmt_list(f, "struct[", &self.0, "]")
     }
 }
+
+impl fmt::Display for MoveVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_list(f, &format!("variant#{}[", self.tag), &self.fields, "]")
+    }
+}
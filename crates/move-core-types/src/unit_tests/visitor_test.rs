@@ -5,8 +5,13 @@ use std::{fmt::Write, str::FromStr};
 
 use crate::{
     account_address::AccountAddress,
-    annotated_value::{MoveFieldLayout, MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue},
-    annotated_visitor::{self, NullTraversal, StructDriver, Traversal, VecDriver, Visitor},
+    annotated_value::{
+        MoveEnumLayout, MoveFieldLayout, MoveStruct, MoveStructLayout, MoveTypeLayout, MoveValue,
+        MoveVariant,
+    },
+    annotated_visitor::{
+        self, NullTraversal, StructDriver, Traversal, VariantDriver, VecDriver, Visitor,
+    },
     identifier::Identifier,
     language_storage::StructTag,
     u256::U256,
@@ -314,6 +319,34 @@ fn nested_typed_struct_visit() {
             let type_ = driver.struct_layout().type_.clone();
             Ok(V::Struct(MoveStruct { type_, fields }))
         }
+
+        fn visit_variant(
+            &mut self,
+            driver: &mut VariantDriver<'_, '_, '_>,
+        ) -> Result<Self::Value, Self::Error> {
+            let variant_name = driver.variant_name().to_owned();
+            write!(self.output, "\n[{}] variant {variant_name}", self.depth).unwrap();
+
+            let mut fields = vec![];
+            let mut field_visitor = Self {
+                depth: self.depth + 1,
+                output: std::mem::take(&mut self.output),
+            };
+
+            while let Some((field, value)) = driver.next_field(&mut field_visitor)? {
+                fields.push((field.name.clone(), value));
+            }
+
+            self.output = field_visitor.output;
+            let type_ = driver.enum_layout().type_.clone();
+            let tag = driver.tag();
+            Ok(V::Variant(MoveVariant {
+                type_,
+                variant_name,
+                tag,
+                fields,
+            }))
+        }
     }
 
     let type_layout = layout_(
@@ -461,6 +494,13 @@ fn peek_field_test() {
         ) -> Result<Self::Value, Self::Error> {
             Ok(None)
         }
+
+        fn visit_variant(
+            &mut self,
+            _: &mut VariantDriver<'_, '_, '_>,
+        ) -> Result<Self::Value, Self::Error> {
+            Ok(None)
+        }
     }
 
     let type_layout = layout_(
@@ -509,6 +549,64 @@ fn peek_field_test() {
     assert_eq!(visit_struct(&["d", "e"]), Some(45));
 }
 
+#[test]
+fn variant_visit() {
+    use MoveTypeLayout as T;
+    use MoveValue as V;
+
+    let type_ = StructTag::from_str("0x0::foo::Shape").unwrap();
+    let field = |name: &str, layout| MoveFieldLayout::new(Identifier::new(name).unwrap(), layout);
+    let layout = MoveEnumLayout::new(
+        type_.clone(),
+        vec![
+            (Identifier::new("Empty").unwrap(), vec![]),
+            (
+                Identifier::new("Rect").unwrap(),
+                vec![field("w", T::U64), field("h", T::U8)],
+            ),
+        ],
+    );
+
+    let value = V::Variant(MoveVariant::new(
+        type_,
+        Identifier::new("Rect").unwrap(),
+        1,
+        vec![
+            (Identifier::new("w").unwrap(), V::U64(3)),
+            (Identifier::new("h").unwrap(), V::U8(4)),
+        ],
+    ));
+
+    let bytes = serialize(value.clone());
+    assert_eq!(bytes, vec![1, 3, 0, 0, 0, 0, 0, 0, 0, 4]);
+    assert_eq!(
+        MoveValue::simple_deserialize(&bytes, &T::Enum(layout.clone())).unwrap(),
+        value,
+    );
+
+    let mut count = 0usize;
+    struct CountFields<'c>(&'c mut usize);
+    impl Traversal for CountFields<'_> {
+        type Error = annotated_visitor::Error;
+
+        fn traverse_u64(&mut self, _: u64) -> Result<(), Self::Error> {
+            *self.0 += 1;
+            Ok(())
+        }
+
+        fn traverse_u8(&mut self, _: u8) -> Result<(), Self::Error> {
+            *self.0 += 1;
+            Ok(())
+        }
+    }
+
+    MoveVariant::visit_deserialize(&bytes, &layout, &mut CountFields(&mut count)).unwrap();
+    assert_eq!(count, 2);
+
+    let err = MoveVariant::visit_deserialize(&[2], &layout, &mut NullTraversal).unwrap_err();
+    assert_eq!(err.to_string(), "unexpected variant tag: 2");
+}
+
 /// Create a struct value for test purposes.
 fn value_(rep: &str, fields: Vec<(&str, MoveValue)>) -> MoveValue {
     let type_ = StructTag::from_str(rep).unwrap();
@@ -83,6 +83,18 @@ macro_rules! derive_status_try_from_repr {
             }
         }
 
+        impl std::str::FromStr for $enum_name {
+            type Err = &'static str;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(
+                        stringify!($variant) => Ok($enum_name::$variant),
+                    )*
+                    _ => Err("invalid StatusCode name"),
+                }
+            }
+        }
+
         #[cfg(any(test, feature = "fuzzing"))]
         const STATUS_CODE_VALUES: &'static [$repr_ty] = &[
             $($value),*
@@ -308,6 +320,17 @@ pub enum StatusCode {
     // or took too long to run based on metering policies
     PROGRAM_TOO_COMPLEX = 1130,
 
+    // An enum definition has no variants
+    ZERO_SIZED_ENUM = 1131,
+    // An enum definition has more variants than allowed
+    MAX_VARIANTS_REACHED = 1132,
+    // A jump table is malformed, or a variant switch does not use it with the enum it covers
+    INVALID_ENUM_SWITCH = 1133,
+    // The operand of a variant switch is not a reference to an enum
+    ENUM_SWITCH_BAD_OPERAND = 1134,
+    // Type mismatch for an enum pack or unpack
+    ENUM_TYPE_MISMATCH = 1135,
+
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
     // Invariant Violation Errors: 2000-2999
@@ -380,6 +403,8 @@ pub enum StatusCode {
     STORAGE_WRITE_LIMIT_REACHED = 4027,
     MEMORY_LIMIT_EXCEEDED = 4028,
     VM_MAX_TYPE_NODES_REACHED = 4029,
+    // An enum value was unpacked as a variant other than its own
+    VARIANT_TAG_MISMATCH = 4030,
 
     // A reserved status to represent an unknown vm status.
     // this is std::u64::MAX, but we can't pattern match on that, so put the hardcoded value in
@@ -529,3 +554,17 @@ fn test_status_codes() {
         assert_eq!(*major_status_code, to_major_status_code);
     }
 }
+
+#[test]
+fn test_status_code_names() {
+    use std::str::FromStr;
+    for major_status_code in STATUS_CODE_VALUES.iter() {
+        let status = StatusCode::try_from(*major_status_code).unwrap();
+        assert_eq!(
+            StatusCode::from_str(&format!("{:?}", status)),
+            Ok(status),
+            "StatusCode name does not round trip"
+        );
+    }
+    assert!(StatusCode::from_str("NOT_A_STATUS_CODE").is_err());
+}
@@ -10,11 +10,12 @@ use move_core_types::{
     account_address::AccountAddress,
     identifier::{IdentStr, Identifier},
 };
+use move_trace_format::format::{TraceEvent, TraceReader};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
     fs::File,
-    io::{BufRead, BufReader, Read, Write},
+    io::{BufReader, Read, Write},
     path::Path,
 };
 
@@ -57,6 +58,45 @@ pub struct TraceMap {
     pub exec_maps: BTreeMap<String, Vec<TraceEntry>>,
 }
 
+/// Calls `f` on every instruction executed in a VM trace file, see `move_trace_format`. Each trace
+/// of the file is a separate execution, identified by its index in the file.
+fn for_each_traced_instruction(
+    file: File,
+    mut f: impl FnMut(&str, AccountAddress, Identifier, Identifier, u64),
+) {
+    for (exec_idx, trace) in TraceReader::new(BufReader::new(file)).enumerate() {
+        let trace = trace.unwrap();
+        let exec_id = exec_idx.to_string();
+        let mut frames = BTreeMap::new();
+        for event in trace.events {
+            match event {
+                TraceEvent::OpenFrame {
+                    frame_id,
+                    module,
+                    function_name,
+                    ..
+                } => {
+                    frames.insert(frame_id, (module, function_name));
+                }
+                TraceEvent::Instruction { frame_id, pc, .. } => {
+                    let (module, function_name) = &frames[&frame_id];
+                    f(
+                        &exec_id,
+                        *module.address(),
+                        module.name().to_owned(),
+                        Identifier::new(function_name.as_str()).unwrap(),
+                        pc as u64,
+                    )
+                }
+                TraceEvent::CloseFrame { .. }
+                | TraceEvent::LoadModule { .. }
+                | TraceEvent::LoadResource { .. }
+                | TraceEvent::Error { .. } => (),
+            }
+        }
+    }
+}
+
 impl CoverageMap {
     /// Takes in a file containing a raw VM trace, and returns an updated coverage map.
     pub fn update_coverage_from_trace_file<P: AsRef<Path> + std::fmt::Debug>(
@@ -65,27 +105,9 @@ impl CoverageMap {
     ) -> Self {
         let file = File::open(&filename)
             .unwrap_or_else(|_| panic!("Unable to open coverage trace file '{:?}'", filename));
-        for line in BufReader::new(file).lines() {
-            let line = line.unwrap();
-            let mut splits = line.split(',');
-            let exec_id = splits.next().unwrap();
-            let context = splits.next().unwrap();
-            let pc = splits.next().unwrap().parse::<u64>().unwrap();
-
-            let mut context_segs: Vec<_> = context.split("::").collect();
-            let is_script = context_segs.len() == 2;
-            if !is_script {
-                let func_name = Identifier::new(context_segs.pop().unwrap()).unwrap();
-                let module_name = Identifier::new(context_segs.pop().unwrap()).unwrap();
-                let module_addr =
-                    AccountAddress::from_hex_literal(context_segs.pop().unwrap()).unwrap();
-                self.insert(exec_id, module_addr, module_name, func_name, pc);
-            } else {
-                // Don't count scripts (for now)
-                assert_eq!(context_segs.pop().unwrap(), "main",);
-                assert_eq!(context_segs.pop().unwrap(), "Script",);
-            }
-        }
+        for_each_traced_instruction(file, |exec_id, module_addr, module_name, func_name, pc| {
+            self.insert(exec_id, module_addr, module_name, func_name, pc)
+        });
         self
     }
 
@@ -268,27 +290,9 @@ impl TraceMap {
     /// Takes in a file containing a raw VM trace, and returns an updated coverage map.
     pub fn update_from_trace_file<P: AsRef<Path>>(mut self, filename: P) -> Self {
         let file = File::open(filename).unwrap();
-        for line in BufReader::new(file).lines() {
-            let line = line.unwrap();
-            let mut splits = line.split(',');
-            let exec_id = splits.next().unwrap();
-            let context = splits.next().unwrap();
-            let pc = splits.next().unwrap().parse::<u64>().unwrap();
-
-            let mut context_segs: Vec<_> = context.split("::").collect();
-            let is_script = context_segs.len() == 2;
-            if !is_script {
-                let func_name = Identifier::new(context_segs.pop().unwrap()).unwrap();
-                let module_name = Identifier::new(context_segs.pop().unwrap()).unwrap();
-                let module_addr =
-                    AccountAddress::from_hex_literal(context_segs.pop().unwrap()).unwrap();
-                self.insert(exec_id, module_addr, module_name, func_name, pc);
-            } else {
-                // Don't count scripts (for now)
-                assert_eq!(context_segs.pop().unwrap(), "main",);
-                assert_eq!(context_segs.pop().unwrap(), "Script",);
-            }
-        }
+        for_each_traced_instruction(file, |exec_id, module_addr, module_name, func_name, pc| {
+            self.insert(exec_id, module_addr, module_name, func_name, pc)
+        });
         self
     }
 
@@ -10,10 +10,11 @@ use colored::*;
 use move_binary_format::{
     control_flow_graph::{ControlFlowGraph, VMControlFlowGraph},
     file_format::{
-        Ability, AbilitySet, Bytecode, CodeUnit, Constant, FieldHandleIndex, FunctionDefinition,
-        FunctionDefinitionIndex, FunctionHandle, ModuleHandle, Signature, SignatureIndex,
-        SignatureToken, StructDefinition, StructDefinitionIndex, StructFieldInformation,
-        StructTypeParameter, TableIndex, TypeSignature, Visibility,
+        Ability, AbilitySet, Bytecode, CodeUnit, Constant, EnumDefinition, EnumDefinitionIndex,
+        FieldHandleIndex, FunctionDefinition, FunctionDefinitionIndex, FunctionHandle,
+        ModuleHandle, Signature, SignatureIndex, SignatureToken, StructDefinition,
+        StructDefinitionIndex, StructFieldInformation, StructTypeParameter, TableIndex,
+        TypeSignature, VariantJumpTable, VariantTag, Visibility,
     },
     CompiledModule,
 };
@@ -203,6 +204,16 @@ impl<'a> Disassembler<'a> {
             .struct_def_at(struct_definition_index))
     }
 
+    fn get_enum_def(&self, enum_definition_index: EnumDefinitionIndex) -> Result<&EnumDefinition> {
+        if enum_definition_index.0 as usize >= self.source_mapper.bytecode.enum_defs().len() {
+            bail!("Invalid enum definition index supplied when marking enum")
+        }
+        Ok(self
+            .source_mapper
+            .bytecode
+            .enum_def_at(enum_definition_index))
+    }
+
     //***************************************************************************
     // Code Coverage Helpers
     //***************************************************************************
@@ -334,6 +345,44 @@ impl<'a> Disassembler<'a> {
         Ok((name, Self::format_type_params(&type_arguments)))
     }
 
+    fn enum_name(&self, enum_def: &EnumDefinition) -> String {
+        let enum_handle = self
+            .source_mapper
+            .bytecode
+            .struct_handle_at(enum_def.enum_handle);
+        self.source_mapper
+            .bytecode
+            .identifier_at(enum_handle.name)
+            .to_string()
+    }
+
+    fn variant_type_info(
+        &self,
+        enum_idx: EnumDefinitionIndex,
+        variant: VariantTag,
+        signature: &Signature,
+        type_param_context: &[SourceName],
+    ) -> Result<(String, String)> {
+        let enum_definition = self.get_enum_def(enum_idx)?;
+        let variant_definition = enum_definition
+            .variant(variant)
+            .ok_or_else(|| format_err!("Bad variant tag"))?;
+        let type_arguments = signature
+            .0
+            .iter()
+            .map(|sig_tok| self.disassemble_sig_tok(sig_tok.clone(), type_param_context))
+            .collect::<Result<Vec<String>>>()?;
+
+        let variant_name = self
+            .source_mapper
+            .bytecode
+            .identifier_at(variant_definition.variant_name);
+        Ok((
+            format!("{}::{}", self.enum_name(enum_definition), variant_name),
+            Self::format_type_params(&type_arguments),
+        ))
+    }
+
     fn name_for_parameter_or_local(
         &self,
         local_idx: usize,
@@ -508,6 +557,7 @@ impl<'a> Disassembler<'a> {
         parameters: &Signature,
         instruction: &Bytecode,
         locals_sigs: &Signature,
+        jump_tables: &[VariantJumpTable],
         function_source_map: &FunctionSourceMap,
         default_location: &Loc,
     ) -> Result<String> {
@@ -664,6 +714,79 @@ impl<'a> Disassembler<'a> {
                     struct_idx, name, ty_params
                 ))
             }
+            Bytecode::PackVariant(handle_idx)
+            | Bytecode::UnpackVariant(handle_idx)
+            | Bytecode::UnpackVariantImmRef(handle_idx)
+            | Bytecode::UnpackVariantMutRef(handle_idx) => {
+                let handle = self.source_mapper.bytecode.variant_handle_at(*handle_idx);
+                let (name, ty_params) = self.variant_type_info(
+                    handle.enum_def,
+                    handle.variant,
+                    &Signature(vec![]),
+                    &function_source_map.type_parameters,
+                )?;
+                let op = match instruction {
+                    Bytecode::PackVariant(_) => "PackVariant",
+                    Bytecode::UnpackVariant(_) => "UnpackVariant",
+                    Bytecode::UnpackVariantImmRef(_) => "UnpackVariantImmRef",
+                    _ => "UnpackVariantMutRef",
+                };
+                Ok(format!("{}[{}]({}{})", op, handle_idx, name, ty_params))
+            }
+            Bytecode::PackVariantGeneric(handle_idx)
+            | Bytecode::UnpackVariantGeneric(handle_idx)
+            | Bytecode::UnpackVariantGenericImmRef(handle_idx)
+            | Bytecode::UnpackVariantGenericMutRef(handle_idx) => {
+                let handle = self
+                    .source_mapper
+                    .bytecode
+                    .variant_instantiation_handle_at(*handle_idx);
+                let enum_inst = self
+                    .source_mapper
+                    .bytecode
+                    .enum_instantiation_at(handle.enum_def);
+                let type_params = self
+                    .source_mapper
+                    .bytecode
+                    .signature_at(enum_inst.type_parameters);
+                let (name, ty_params) = self.variant_type_info(
+                    enum_inst.def,
+                    handle.variant,
+                    type_params,
+                    &function_source_map.type_parameters,
+                )?;
+                let op = match instruction {
+                    Bytecode::PackVariantGeneric(_) => "PackVariantGeneric",
+                    Bytecode::UnpackVariantGeneric(_) => "UnpackVariantGeneric",
+                    Bytecode::UnpackVariantGenericImmRef(_) => "UnpackVariantGenericImmRef",
+                    _ => "UnpackVariantGenericMutRef",
+                };
+                Ok(format!("{}[{}]({}{})", op, handle_idx, name, ty_params))
+            }
+            Bytecode::VariantSwitch(jt_idx) => {
+                let jump_table = jump_tables
+                    .get(jt_idx.0 as usize)
+                    .ok_or_else(|| format_err!("Bad jump table index"))?;
+                let enum_definition = self.get_enum_def(jump_table.head_enum)?;
+                let targets = enum_definition
+                    .variants
+                    .iter()
+                    .zip(&jump_table.jump_table)
+                    .map(|(variant, offset)| {
+                        let variant_name = self
+                            .source_mapper
+                            .bytecode
+                            .identifier_at(variant.variant_name);
+                        format!("{} => {}", variant_name, offset)
+                    })
+                    .collect::<Vec<_>>();
+                Ok(format!(
+                    "VariantSwitch[{}]({}: {})",
+                    jt_idx,
+                    self.enum_name(enum_definition),
+                    targets.join(", ")
+                ))
+            }
             Bytecode::ExistsDeprecated(struct_idx) => {
                 let (name, ty_params) = self.struct_type_info(
                     *struct_idx,
@@ -923,6 +1046,7 @@ impl<'a> Disassembler<'a> {
                     parameters,
                     instruction,
                     locals_sigs,
+                    &code.jump_tables,
                     function_source_map,
                     decl_location,
                 )
@@ -942,7 +1066,7 @@ impl<'a> Disassembler<'a> {
             .collect();
 
         if self.options.print_basic_blocks {
-            let cfg = VMControlFlowGraph::new(&code.code);
+            let cfg = VMControlFlowGraph::new(&code.code, &code.jump_tables);
             for (block_number, block_id) in cfg.blocks().iter().enumerate() {
                 instrs.insert(
                     *block_id as usize + block_number,
@@ -1216,6 +1340,76 @@ impl<'a> Disassembler<'a> {
         ))
     }
 
+    pub fn disassemble_enum_def(&self, enum_def_idx: EnumDefinitionIndex) -> Result<String> {
+        let enum_definition = self.get_enum_def(enum_def_idx)?;
+        let enum_handle = self
+            .source_mapper
+            .bytecode
+            .struct_handle_at(enum_definition.enum_handle);
+        let enum_source_map = self
+            .source_mapper
+            .source_map
+            .get_enum_source_map(enum_def_idx)?;
+
+        let abilities = if enum_handle.abilities == AbilitySet::EMPTY {
+            String::new()
+        } else {
+            let ability_vec: Vec<_> = enum_handle
+                .abilities
+                .into_iter()
+                .map(Self::format_ability)
+                .collect();
+            format!(" has {}", ability_vec.join(", "))
+        };
+
+        let name = self.enum_name(enum_definition);
+
+        let ty_params = Self::disassemble_struct_type_formals(
+            &enum_source_map.type_parameters,
+            &enum_handle.type_parameters,
+        );
+        let variants = enum_definition
+            .variants
+            .iter()
+            .map(|variant| {
+                let variant_name = self
+                    .source_mapper
+                    .bytecode
+                    .identifier_at(variant.variant_name);
+                let fields = variant
+                    .fields
+                    .iter()
+                    .map(|field_definition| {
+                        let field_name = self
+                            .source_mapper
+                            .bytecode
+                            .identifier_at(field_definition.name);
+                        let ty_str = self.disassemble_sig_tok(
+                            field_definition.signature.0.clone(),
+                            &enum_source_map.type_parameters,
+                        )?;
+                        Ok(format!("{}: {}", field_name, ty_str))
+                    })
+                    .collect::<Result<Vec<String>>>()?;
+                Ok(if fields.is_empty() {
+                    variant_name.to_string()
+                } else {
+                    format!("{} {{ {} }}", variant_name, fields.join(", "))
+                })
+            })
+            .collect::<Result<Vec<String>>>()?;
+
+        Ok(format!(
+            "enum {name}{ty_params}{abilities} {{
+	{variants}
+}}",
+            name = name,
+            ty_params = ty_params,
+            abilities = abilities,
+            variants = &variants.join(",\n\t"),
+        ))
+    }
+
     pub fn disassemble_constant(
         &self,
         constant_index: usize,
@@ -1250,9 +1444,12 @@ impl<'a> Disassembler<'a> {
             .iter()
             .filter_map(|h| self.get_import_string(h))
             .collect::<Vec<String>>();
-        let struct_defs: Vec<String> = (0..self.source_mapper.bytecode.struct_defs().len())
+        let mut struct_defs: Vec<String> = (0..self.source_mapper.bytecode.struct_defs().len())
             .map(|i| self.disassemble_struct_def(StructDefinitionIndex(i as TableIndex)))
             .collect::<Result<Vec<String>>>()?;
+        for i in 0..self.source_mapper.bytecode.enum_defs().len() {
+            struct_defs.push(self.disassemble_enum_def(EnumDefinitionIndex(i as TableIndex))?);
+        }
 
         let constants: Vec<String> = self
             .source_mapper
@@ -99,6 +99,11 @@ pub extern "C" fn initialize(_argc: *const isize, _argv: *const *const *const u8
     }));
 
 
+    // Make libFuzzer explore the control flow of the Move code, not only the
+    // code paths of the interpreter.
+    #[cfg(feature = "move-coverage")]
+    run_move::coverage::register();
+
     // Initialize the `RUST_LIBFUZZER_DEBUG_PATH` cell with the path so it can be
     // reused with little overhead.
     if let Ok(path) = std::env::var("RUST_LIBFUZZER_DEBUG_PATH") {
@@ -331,6 +336,110 @@ macro_rules! fuzz_target {
     };
 }
 
+/// Define a fuzz target whose arguments are derived from the signature of the
+/// fuzzed Move function.
+///
+/// The input is decoded into well-typed values of the function parameters
+/// (integers, `bool`, `address`, vectors, `std::string::String`,
+/// `std::option::Option` and structs of these), so the target does not need to
+/// describe its arguments. Inputs that cannot be decoded are rejected.
+///
+/// ```no_run
+/// #![no_main]
+///
+/// libfuzzer::fuzz_function_target!();
+/// ```
+///
+/// The fuzzed function is set in the fuzz project manifest, see
+/// `run_move::manifest::FuzzMetadata`.
+#[macro_export]
+macro_rules! fuzz_function_target {
+    () => {
+        const _: () = {
+            /// Auto-generated function
+            #[no_mangle]
+            pub extern "C" fn rust_fuzzer_test_input(bytes: &[u8]) -> i32 {
+                __libfuzzer_sys_run(bytes).to_libfuzzer_code()
+            }
+
+            // See `fuzz_target!` for why this is split to a separate function.
+            #[inline(never)]
+            fn __libfuzzer_sys_run(bytes: &[u8]) -> $crate::Corpus {
+                $crate::run_move::run_generated(bytes)
+            }
+        };
+    };
+}
+
+/// Define a stateful fuzz target calling a sequence of public functions of a
+/// Move module.
+///
+/// The input is decoded into a sequence of calls, values returned by a call
+/// being available to the following ones. All the calls run in the same VM
+/// session. `move fuzz fmt` prints the call sequence of an input.
+///
+/// ```no_run
+/// #![no_main]
+///
+/// libfuzzer::fuzz_sequence_target!();
+/// ```
+///
+/// The fuzzed module is set in the fuzz project manifest, see
+/// `run_move::manifest::FuzzMetadata`.
+#[macro_export]
+macro_rules! fuzz_sequence_target {
+    () => {
+        const _: () = {
+            /// Auto-generated function
+            #[no_mangle]
+            pub extern "C" fn rust_fuzzer_test_input(bytes: &[u8]) -> i32 {
+                __libfuzzer_sys_run(bytes).to_libfuzzer_code()
+            }
+
+            // See `fuzz_target!` for why this is split to a separate function.
+            #[inline(never)]
+            fn __libfuzzer_sys_run(bytes: &[u8]) -> $crate::Corpus {
+                $crate::run_move::run_sequence(bytes)
+            }
+        };
+    };
+}
+
+/// Define a fuzz target for the deserializer and the bytecode verifier, fed
+/// with compiled modules.
+///
+/// The target comes with a custom mutator applying typed mutations to the
+/// modules of the corpus (out-of-bounds indices, mutated signatures, swapped
+/// instructions), so seed the corpus with valid modules.
+///
+/// ```no_run
+/// #![no_main]
+///
+/// libfuzzer::fuzz_module_target!();
+/// ```
+#[macro_export]
+macro_rules! fuzz_module_target {
+    () => {
+        const _: () = {
+            /// Auto-generated function
+            #[no_mangle]
+            pub extern "C" fn rust_fuzzer_test_input(bytes: &[u8]) -> i32 {
+                __libfuzzer_sys_run(bytes).to_libfuzzer_code()
+            }
+
+            // See `fuzz_target!` for why this is split to a separate function.
+            #[inline(never)]
+            fn __libfuzzer_sys_run(bytes: &[u8]) -> $crate::Corpus {
+                $crate::run_move::run_module(bytes)
+            }
+        };
+
+        $crate::fuzz_mutator!(|data: &mut [u8], size: usize, max_size: usize, seed: u32| {
+            $crate::run_move::module_mutator::mutate_module(data, size, max_size, seed)
+        });
+    };
+}
+
 /// Define a custom mutator.
 ///
 /// This is optional, and libFuzzer will use its own, default mutation strategy
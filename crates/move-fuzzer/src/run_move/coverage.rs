@@ -0,0 +1,63 @@
+use move_vm_runtime::coverage::register_counters;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Number of Move branch-hit counters reported to libFuzzer
+pub const NUM_COUNTERS: usize = 1 << 16;
+
+/// Number of counters encoding the gas used by an execution, four per power of two
+pub const NUM_GAS_COUNTERS: usize = 4 * 64;
+
+/// Counters are cleared by libFuzzer a word at a time
+#[repr(C, align(64))]
+struct Counters<const N: usize>([AtomicU8; N]);
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicU8 = AtomicU8::new(0);
+
+///
+/// Branch-hit counters of the Move code executed by the fuzz target, see
+/// `move_vm_runtime::coverage`.
+///
+/// They live in the section libFuzzer scans for extra counters, so they are used as coverage
+/// feedback together with the counters of the Rust code. libFuzzer only supports extra counters
+/// on ELF platforms: elsewhere the fuzzer is only guided by the coverage of the interpreter.
+///
+#[used]
+#[cfg_attr(
+    any(target_os = "linux", target_os = "freebsd", target_os = "netbsd"),
+    link_section = "__libfuzzer_extra_counters"
+)]
+static MOVE_COUNTERS: Counters<NUM_COUNTERS> = Counters([ZERO; NUM_COUNTERS]);
+
+/// Gas used by the execution, set by `record_gas_used`
+#[used]
+#[cfg_attr(
+    any(target_os = "linux", target_os = "freebsd", target_os = "netbsd"),
+    link_section = "__libfuzzer_extra_counters"
+)]
+static MOVE_GAS_COUNTERS: Counters<NUM_GAS_COUNTERS> = Counters([ZERO; NUM_GAS_COUNTERS]);
+
+/// Makes the Move VM update the counters in the libFuzzer extra counters section
+pub fn register() {
+    register_counters(&MOVE_COUNTERS.0);
+}
+
+///
+/// Reports the gas used by an execution to libFuzzer. The amount is bucketed in quarters of
+/// powers of two, each bucket with its own counter, so an input using more gas than all the
+/// previous ones hits a new counter and is kept in the corpus: the fuzzer climbs towards
+/// expensive inputs.
+///
+pub fn record_gas_used(gas_used: u64) {
+    if gas_used == 0 {
+        return;
+    }
+    let log = 63 - gas_used.leading_zeros() as usize;
+    // The two bits following the most significant one
+    let quarter = if log >= 2 {
+        (gas_used >> (log - 2)) & 3
+    } else {
+        (gas_used << (2 - log)) & 3
+    };
+    MOVE_GAS_COUNTERS.0[4 * log + quarter as usize].fetch_add(1, Ordering::Relaxed);
+}
@@ -0,0 +1,143 @@
+use move_binary_format::{file_format::Bytecode, CompiledModule};
+use move_core_types::{runtime_value::MoveValue, u256::U256};
+use std::{collections::BTreeSet, io};
+
+///
+/// A libFuzzer dictionary of the constants of compiled Move modules.
+///
+/// Entries are collected from the constant pool, the integer immediates of the bytecode (abort
+/// codes included), the neighbours of the immediates compared against and the address pool.
+/// They are encoded as the argument decoders read them: integers in little endian on their
+/// width, addresses on 32 bytes and byte vectors as their raw content.
+///
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    entries: BTreeSet<Vec<u8>>,
+}
+
+impl Dictionary {
+    /// An empty dictionary
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the constants of `module`
+    pub fn add_module(&mut self, module: &CompiledModule) {
+        for constant in module.constant_pool() {
+            if let Some(value) = constant.deserialize_constant() {
+                self.add_value(&value);
+            }
+        }
+        for address in module.address_identifiers() {
+            self.add(address.to_vec());
+        }
+        for code in module
+            .function_defs()
+            .iter()
+            .filter_map(|def| def.code.as_ref())
+        {
+            for (i, instr) in code.code.iter().enumerate() {
+                let (value, width) = match immediate(instr) {
+                    Some(immediate) => immediate,
+                    None => continue,
+                };
+                self.add_int(value, width);
+                // Off-by-one inputs reach both sides of a comparison
+                if let Some(Bytecode::Lt | Bytecode::Gt | Bytecode::Le | Bytecode::Ge) =
+                    code.code.get(i + 1)
+                {
+                    if let Some(prev) = value.checked_sub(U256::from(1u8)) {
+                        self.add_int(prev, width);
+                    }
+                    if let Some(next) = value.checked_add(U256::from(1u8)) {
+                        self.add_int(next, width);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of distinct entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the dictionary has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the dictionary in the libFuzzer format, one quoted entry per line
+    pub fn write(&self, w: &mut impl io::Write) -> io::Result<()> {
+        for entry in &self.entries {
+            let mut line = String::with_capacity(entry.len() + 2);
+            for byte in entry {
+                match byte {
+                    b'"' | b'\\' => line.push_str(&format!("\\{}", *byte as char)),
+                    0x20..=0x7e => line.push(*byte as char),
+                    _ => line.push_str(&format!("\\x{:02X}", byte)),
+                }
+            }
+            writeln!(w, "\"{}\"", line)?;
+        }
+        Ok(())
+    }
+
+    fn add_value(&mut self, value: &MoveValue) {
+        match value {
+            MoveValue::U8(n) => self.add(vec![*n]),
+            MoveValue::U16(n) => self.add(n.to_le_bytes().to_vec()),
+            MoveValue::U32(n) => self.add(n.to_le_bytes().to_vec()),
+            MoveValue::U64(n) => self.add(n.to_le_bytes().to_vec()),
+            MoveValue::U128(n) => self.add(n.to_le_bytes().to_vec()),
+            MoveValue::U256(n) => self.add(n.to_le_bytes().to_vec()),
+            MoveValue::Address(a) => self.add(a.to_vec()),
+            MoveValue::Vector(elems) => match bytes_of(elems) {
+                Some(bytes) => self.add(bytes),
+                None => elems.iter().for_each(|elem| self.add_value(elem)),
+            },
+            MoveValue::Bool(_)
+            | MoveValue::Signer(_)
+            | MoveValue::Struct(_)
+            | MoveValue::Variant(_) => (),
+        }
+    }
+
+    /// Adds the `width` low bytes of `value`, if it fits
+    fn add_int(&mut self, value: U256, width: usize) {
+        let bytes = value.to_le_bytes();
+        if bytes[width..].iter().all(|byte| *byte == 0) {
+            self.add(bytes[..width].to_vec());
+        }
+    }
+
+    fn add(&mut self, entry: Vec<u8>) {
+        if !entry.is_empty() {
+            self.entries.insert(entry);
+        }
+    }
+}
+
+/// The content of a `vector<u8>`
+fn bytes_of(elems: &[MoveValue]) -> Option<Vec<u8>> {
+    elems
+        .iter()
+        .map(|elem| match elem {
+            MoveValue::U8(byte) => Some(*byte),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The integer loaded by `instr` and its width in bytes
+fn immediate(instr: &Bytecode) -> Option<(U256, usize)> {
+    Some(match instr {
+        Bytecode::LdU8(n) => (U256::from(*n), 1),
+        Bytecode::LdU16(n) => (U256::from(*n), 2),
+        Bytecode::LdU32(n) => (U256::from(*n), 4),
+        Bytecode::LdU64(n) => (U256::from(*n), 8),
+        Bytecode::LdU128(n) => (U256::from(**n), 16),
+        Bytecode::LdU256(n) => (**n, 32),
+        _ => return None,
+    })
+}
@@ -0,0 +1,318 @@
+use anyhow::{anyhow, bail, Result};
+use move_binary_format::{
+    errors::{Location, VMError, VMResult},
+    file_format::{CodeOffset, FunctionDefinitionIndex, LocalIndex, TableIndex},
+    CompiledModule, IndexKind,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    effects::{ChangeSet, Event},
+    identifier::IdentStr,
+    language_storage::ModuleId,
+    vm_status::StatusCode,
+};
+use move_vm_test_utils::{
+    gas_schedule::{Gas, GasStatus, INITIAL_COST_SCHEDULE},
+    InMemoryStorage,
+};
+use std::fmt::{self, Debug};
+
+///
+/// Runs the fuzzed function through every execution cut of `move-execution` and the latest
+/// execution layer, and fails on the first observable difference: the verification of the
+/// published modules, the status of the execution, the return values, the gas used or the
+/// effects of the session.
+///
+/// The latest execution layer is the reference the cuts are compared against. All the
+/// executions are metered with the `INITIAL_COST_SCHEDULE` gas schedule.
+///
+/// Enabled for a target with `differential = true` in the `fuzz/Cargo.toml` manifest, the fuzz
+/// project must depend on `move-fuzzer` with the `differential` feature. Sequence targets are not
+/// supported.
+///
+pub struct Differential {
+    /// The latest execution layer first
+    cuts: Vec<Box<dyn ExecutionCut>>,
+}
+
+impl Differential {
+    /// Loads every execution cut and checks that they agree on the verification of `modules`
+    pub fn new<'a>(modules: impl IntoIterator<Item = &'a CompiledModule>) -> Result<Self> {
+        let cuts: Vec<Box<dyn ExecutionCut>> = vec![
+            Box::new(Latest::new()?),
+            Box::new(V2::new()?),
+            Box::new(V1::new()?),
+            Box::new(V0::new()?),
+        ];
+        for module in modules {
+            let reference = Status::of(&cuts[0].verify(module));
+            for cut in &cuts[1..] {
+                let status = Status::of(&cut.verify(module));
+                if status != reference {
+                    bail!(
+                        "verification of {} diverges between the {} and {} execution layers\n  {}: {:?}\n  {}: {:?}",
+                        module.self_id(),
+                        cuts[0].name(),
+                        cut.name(),
+                        cuts[0].name(),
+                        reference,
+                        cut.name(),
+                        status
+                    );
+                }
+            }
+        }
+        Ok(Self { cuts })
+    }
+
+    /// Calls `function` with `args` in a new session of every execution cut
+    pub fn compare(
+        &self,
+        storage: &InMemoryStorage,
+        module: &ModuleId,
+        function: &IdentStr,
+        args: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> Result<()> {
+        let (reference, cuts) = self
+            .cuts
+            .split_first()
+            .ok_or_else(|| anyhow!("no execution layer to compare"))?;
+        let expected = reference.execute(storage, module, function, args.clone(), gas_budget);
+        for cut in cuts {
+            let outcome = cut.execute(storage, module, function, args.clone(), gas_budget);
+            if let Some((what, expected, found)) = expected.diff(&outcome) {
+                bail!(
+                    "{} of {}::{} diverges between the {} and {} execution layers\n  {}: {}\n  {}: {}",
+                    what,
+                    module,
+                    function,
+                    reference.name(),
+                    cut.name(),
+                    reference.name(),
+                    expected,
+                    cut.name(),
+                    found
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Differential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.cuts.iter().map(|cut| cut.name()))
+            .finish()
+    }
+}
+
+/// An execution layer: a VM, its natives and its bytecode verifier
+trait ExecutionCut: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn verify(&self, module: &CompiledModule) -> VMResult<()>;
+
+    fn execute(
+        &self,
+        storage: &InMemoryStorage,
+        module: &ModuleId,
+        function: &IdentStr,
+        args: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> Outcome;
+}
+
+/// Declares the `ExecutionCut` built from the given runtime, natives and verifier crates
+macro_rules! execution_cut {
+    ($cut:ident, $name:literal, $runtime:ident, $natives:ident, $verifier:ident) => {
+        struct $cut {
+            vm: $runtime::move_vm::MoveVM,
+        }
+
+        impl $cut {
+            fn new() -> Result<Self> {
+                let addr = AccountAddress::ONE;
+                let natives = $natives::all_natives(addr, $natives::GasParameters::zeros())
+                    .into_iter()
+                    .chain($natives::nursery_natives(
+                        /* silent */ true,
+                        addr,
+                        $natives::NurseryGasParameters::zeros(),
+                    ));
+                let vm = $runtime::move_vm::MoveVM::new(natives)
+                    .map_err(|e| anyhow!("failed to create the {} Move VM: {:?}", $name, e))?;
+                Ok(Self { vm })
+            }
+        }
+
+        impl ExecutionCut for $cut {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn verify(&self, module: &CompiledModule) -> VMResult<()> {
+                $verifier::verify_module_unmetered(module)
+            }
+
+            fn execute(
+                &self,
+                storage: &InMemoryStorage,
+                module: &ModuleId,
+                function: &IdentStr,
+                args: Vec<Vec<u8>>,
+                gas_budget: u64,
+            ) -> Outcome {
+                let mut gas = GasStatus::new(&INITIAL_COST_SCHEDULE, Gas::new(gas_budget));
+                let mut session = self.vm.new_session(storage);
+                let res = session
+                    .execute_function_bypass_visibility(module, function, vec![], args, &mut gas)
+                    .map(|values| {
+                        (
+                            values.return_values.into_iter().map(|(v, _)| v).collect(),
+                            values
+                                .mutable_reference_outputs
+                                .into_iter()
+                                .map(|(i, v, _)| (i, v))
+                                .collect(),
+                        )
+                    });
+                let gas_used = gas_budget.saturating_sub(u64::from(gas.remaining_gas()));
+                Outcome::new(res, || session.finish().0, gas_used)
+            }
+        }
+    };
+}
+
+execution_cut!(
+    Latest,
+    "latest",
+    move_vm_runtime,
+    move_stdlib_natives,
+    move_bytecode_verifier
+);
+execution_cut!(
+    V2,
+    "v2",
+    move_vm_runtime_v2,
+    move_stdlib_natives_v2,
+    move_bytecode_verifier_v2
+);
+execution_cut!(
+    V1,
+    "v1",
+    move_vm_runtime_v1,
+    move_stdlib_natives_v1,
+    move_bytecode_verifier_v1
+);
+execution_cut!(
+    V0,
+    "v0",
+    move_vm_runtime_v0,
+    move_stdlib_natives_v0,
+    move_bytecode_verifier_v0
+);
+
+/// The parts of a `VMError` that do not depend on the wording of the execution layer
+#[derive(Debug, PartialEq, Eq)]
+struct Status {
+    major_status: StatusCode,
+    sub_status: Option<u64>,
+    location: Location,
+    indices: Vec<(IndexKind, TableIndex)>,
+    offsets: Vec<(FunctionDefinitionIndex, CodeOffset)>,
+}
+
+impl Status {
+    /// `None` for a success
+    fn of<T>(res: &VMResult<T>) -> Option<Self> {
+        res.as_ref().err().map(Self::from_error)
+    }
+
+    fn from_error(err: &VMError) -> Self {
+        Self {
+            major_status: err.major_status(),
+            sub_status: err.sub_status(),
+            location: err.location().clone(),
+            indices: err.indices().clone(),
+            offsets: err.offsets().clone(),
+        }
+    }
+}
+
+/// The observable result of an execution
+#[derive(Debug, PartialEq, Eq)]
+struct Outcome {
+    status: Option<Status>,
+    return_values: Vec<Vec<u8>>,
+    mutable_reference_outputs: Vec<(LocalIndex, Vec<u8>)>,
+    gas_used: u64,
+    /// Effects of the session, only collected for successful executions
+    change_set: Option<ChangeSet>,
+    events: Vec<Event>,
+}
+
+type ReturnValues = (Vec<Vec<u8>>, Vec<(LocalIndex, Vec<u8>)>);
+
+impl Outcome {
+    fn new(
+        res: VMResult<ReturnValues>,
+        finish: impl FnOnce() -> VMResult<(ChangeSet, Vec<Event>)>,
+        gas_used: u64,
+    ) -> Self {
+        let mut outcome = Self {
+            status: None,
+            return_values: vec![],
+            mutable_reference_outputs: vec![],
+            gas_used,
+            change_set: None,
+            events: vec![],
+        };
+        match res.and_then(|values| Ok((values, finish()?))) {
+            Ok(((return_values, mutable_reference_outputs), (change_set, events))) => {
+                outcome.return_values = return_values;
+                outcome.mutable_reference_outputs = mutable_reference_outputs;
+                outcome.change_set = Some(change_set);
+                outcome.events = events;
+            }
+            Err(err) => outcome.status = Some(Status::from_error(&err)),
+        }
+        outcome
+    }
+
+    /// The first difference with `other`: what differs, and both values
+    fn diff(&self, other: &Self) -> Option<(&'static str, String, String)> {
+        fn show(
+            what: &'static str,
+            a: &impl Debug,
+            b: &impl Debug,
+        ) -> (&'static str, String, String) {
+            (what, format!("{:?}", a), format!("{:?}", b))
+        }
+
+        if self.status != other.status {
+            Some(show("status", &self.status, &other.status))
+        } else if self.return_values != other.return_values {
+            Some(show(
+                "return values",
+                &self.return_values,
+                &other.return_values,
+            ))
+        } else if self.mutable_reference_outputs != other.mutable_reference_outputs {
+            Some(show(
+                "mutable reference outputs",
+                &self.mutable_reference_outputs,
+                &other.mutable_reference_outputs,
+            ))
+        } else if self.gas_used != other.gas_used {
+            Some(show("gas used", &self.gas_used, &other.gas_used))
+        } else if self.change_set != other.change_set {
+            Some(show("change set", &self.change_set, &other.change_set))
+        } else if self.events != other.events {
+            Some(show("events", &self.events, &other.events))
+        } else {
+            None
+        }
+    }
+}
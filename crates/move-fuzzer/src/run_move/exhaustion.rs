@@ -0,0 +1,462 @@
+use anyhow::{bail, Result};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_algebra::{InternalGas, NumArgs, NumBytes},
+    language_storage::ModuleId,
+    u256::U256,
+};
+use move_vm_profiler::GasProfiler;
+use move_vm_test_utils::gas_schedule::{Gas, GasStatus, INITIAL_COST_SCHEDULE};
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView, ValueVisitor},
+};
+use serde::Deserialize;
+use std::fmt;
+
+/// Gas units available to an execution when no budget is configured
+pub const DEFAULT_GAS_BUDGET: u64 = 100_000_000;
+
+///
+/// Resource-exhaustion thresholds of a fuzz target. When at least one threshold is set, the
+/// target runs with the `INITIAL_COST_SCHEDULE` gas schedule and any input making an execution
+/// go past a threshold is a finding. The gas used is also reported to libFuzzer as a feature, so
+/// that the fuzzer keeps the inputs reaching a new maximum.
+///
+/// The thresholds are configured in the `fuzz/Cargo.toml` manifest, either for all the targets
+/// or for a single one:
+///
+/// ```toml
+/// [package.metadata.move-fuzz.exhaustion]
+/// max_call_depth = 64
+///
+/// [package.metadata.move-fuzz.targets.fuzz_target_1.exhaustion]
+/// max_gas = 50000
+/// max_vector_len = 1024
+/// ```
+///
+/// Executions running out of `gas_budget` fail with `OUT_OF_GAS`, which is handled by the crash
+/// oracle, and exceed `max_gas` if it is lower than the budget.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExhaustionLimits {
+    /// Gas units available to an execution
+    pub gas_budget: u64,
+    /// Maximum gas units used by an execution
+    pub max_gas: Option<u64>,
+    /// Maximum depth of the call stack, the called function being at depth 1
+    pub max_call_depth: Option<u64>,
+    /// Maximum length of the vectors handled by an execution
+    pub max_vector_len: Option<u64>,
+}
+
+/// Threshold configuration as written in the manifest, unset fields keep the inherited value
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExhaustionConfig {
+    /// Gas units available to an execution
+    pub gas_budget: Option<u64>,
+    /// Maximum gas units used by an execution
+    pub max_gas: Option<u64>,
+    /// Maximum depth of the call stack
+    pub max_call_depth: Option<u64>,
+    /// Maximum length of the vectors handled by an execution
+    pub max_vector_len: Option<u64>,
+}
+
+/// Resources used by an execution
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceUsage {
+    /// Gas units charged
+    pub gas_used: u64,
+    /// Deepest call stack
+    pub max_call_depth: u64,
+    /// Longest vector
+    pub max_vector_len: u64,
+}
+
+impl ExhaustionLimits {
+    /// Builds the thresholds of a target from the configuration shared by all the targets and
+    /// the target specific one. Returns `None` if no threshold is set.
+    pub fn new(shared: &ExhaustionConfig, target: Option<&ExhaustionConfig>) -> Option<Self> {
+        let pick = |field: fn(&ExhaustionConfig) -> Option<u64>| {
+            target.and_then(field).or_else(|| field(shared))
+        };
+        let limits = Self {
+            gas_budget: pick(|c| c.gas_budget).unwrap_or(DEFAULT_GAS_BUDGET),
+            max_gas: pick(|c| c.max_gas),
+            max_call_depth: pick(|c| c.max_call_depth),
+            max_vector_len: pick(|c| c.max_vector_len),
+        };
+        (limits.max_gas.is_some()
+            || limits.max_call_depth.is_some()
+            || limits.max_vector_len.is_some())
+        .then_some(limits)
+    }
+
+    /// Fails, listing the exceeded thresholds, if `usage` goes past any of them
+    pub fn check(&self, usage: &ResourceUsage) -> Result<()> {
+        let exceeded: Vec<_> = [
+            ("gas used", usage.gas_used, self.max_gas),
+            ("call depth", usage.max_call_depth, self.max_call_depth),
+            ("vector length", usage.max_vector_len, self.max_vector_len),
+        ]
+        .into_iter()
+        .filter_map(|(name, used, max)| {
+            max.filter(|max| used > *max)
+                .map(|max| format!("{} {} > {}", name, used, max))
+        })
+        .collect();
+        if !exceeded.is_empty() {
+            bail!("resource thresholds exceeded: {}", exceeded.join(", "));
+        }
+        Ok(())
+    }
+}
+
+///
+/// Gas meter of the fuzz targets, also tracking the call depth and the vector lengths of the
+/// execution when resource-exhaustion thresholds are set.
+///
+/// Vector lengths are observed when vectors are packed and when values are moved, copied,
+/// read, written or dropped, so a vector only grown through a reference is measured when it is
+/// next used.
+///
+pub struct ExhaustionMeter {
+    gas: GasStatus<'static>,
+    /// `None` when no threshold is set: the execution is unmetered and nothing is tracked
+    limits: Option<ExhaustionLimits>,
+    call_depth: u64,
+    usage: ResourceUsage,
+}
+
+impl ExhaustionMeter {
+    /// A meter enforcing `limits`, or an unmetered one if `None`
+    pub fn new(limits: Option<&ExhaustionLimits>) -> Self {
+        let gas = match limits {
+            Some(limits) => GasStatus::new(&INITIAL_COST_SCHEDULE, Gas::new(limits.gas_budget)),
+            None => GasStatus::new_unmetered(),
+        };
+        Self {
+            gas,
+            limits: limits.cloned(),
+            // The called function is on the stack from the start
+            call_depth: 1,
+            usage: ResourceUsage {
+                max_call_depth: 1,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Resources used so far
+    pub fn usage(&self) -> ResourceUsage {
+        let gas_used = self.limits.as_ref().map_or(0, |limits| {
+            limits
+                .gas_budget
+                .saturating_sub(u64::from(self.gas.remaining_gas()))
+        });
+        ResourceUsage {
+            gas_used,
+            ..self.usage
+        }
+    }
+
+    /// Fails if the resources used so far go past the thresholds
+    pub fn check(&self) -> Result<()> {
+        match &self.limits {
+            Some(limits) => limits.check(&self.usage()),
+            None => Ok(()),
+        }
+    }
+
+    fn tracking(&self) -> bool {
+        self.limits.is_some()
+    }
+
+    fn enter(&mut self) {
+        if self.tracking() {
+            self.call_depth += 1;
+            self.usage.max_call_depth = self.usage.max_call_depth.max(self.call_depth);
+        }
+    }
+
+    fn exit(&mut self) {
+        if self.tracking() {
+            // Returning from the called function, the next execution starts at depth 1 again
+            self.call_depth = self.call_depth.saturating_sub(1).max(1);
+        }
+    }
+
+    fn observe_len(&mut self, len: u64) {
+        self.usage.max_vector_len = self.usage.max_vector_len.max(len);
+    }
+
+    fn observe(&mut self, val: &impl ValueView) {
+        if self.tracking() {
+            let mut visitor = VectorLengths(0);
+            val.visit(&mut visitor);
+            self.observe_len(visitor.0);
+        }
+    }
+}
+
+impl fmt::Debug for ExhaustionMeter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExhaustionMeter")
+            .field("limits", &self.limits)
+            .field("call_depth", &self.call_depth)
+            .field("usage", &self.usage())
+            .finish()
+    }
+}
+
+/// Longest vector found in a value
+struct VectorLengths(u64);
+
+impl ValueVisitor for VectorLengths {
+    fn visit_u8(&mut self, _depth: usize, _val: u8) {}
+    fn visit_u16(&mut self, _depth: usize, _val: u16) {}
+    fn visit_u32(&mut self, _depth: usize, _val: u32) {}
+    fn visit_u64(&mut self, _depth: usize, _val: u64) {}
+    fn visit_u128(&mut self, _depth: usize, _val: u128) {}
+    fn visit_u256(&mut self, _depth: usize, _val: U256) {}
+    fn visit_bool(&mut self, _depth: usize, _val: bool) {}
+    fn visit_address(&mut self, _depth: usize, _val: AccountAddress) {}
+
+    fn visit_struct(&mut self, _depth: usize, _len: usize) -> bool {
+        true
+    }
+
+    fn visit_vec(&mut self, _depth: usize, len: usize) -> bool {
+        self.0 = self.0.max(len as u64);
+        true
+    }
+
+    fn visit_ref(&mut self, _depth: usize, _is_global: bool) -> bool {
+        true
+    }
+
+    fn visit_vec_u8(&mut self, depth: usize, vals: &[u8]) {
+        self.visit_vec(depth, vals.len());
+    }
+}
+
+impl GasMeter for ExhaustionMeter {
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        self.gas.charge_simple_instr(instr)
+    }
+
+    fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()> {
+        self.observe(&popped_val);
+        self.gas.charge_pop(popped_val)
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.enter();
+        self.gas.charge_call(module_id, func_name, args, num_locals)
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.enter();
+        self.gas
+            .charge_call_generic(module_id, func_name, ty_args, args, num_locals)
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        self.gas.charge_ld_const(size)
+    }
+
+    fn charge_ld_const_after_deserialization(
+        &mut self,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.observe(&val);
+        self.gas.charge_ld_const_after_deserialization(val)
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.observe(&val);
+        self.gas.charge_copy_loc(val)
+    }
+
+    fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.observe(&val);
+        self.gas.charge_move_loc(val)
+    }
+
+    fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.observe(&val);
+        self.gas.charge_store_loc(val)
+    }
+
+    fn charge_pack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_pack(is_generic, args)
+    }
+
+    fn charge_unpack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_unpack(is_generic, args)
+    }
+
+    fn charge_pack_variant(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_pack_variant(is_generic, args)
+    }
+
+    fn charge_unpack_variant(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_unpack_variant(is_generic, args)
+    }
+
+    fn charge_variant_switch(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.gas.charge_variant_switch(val)
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.observe(&val);
+        self.gas.charge_read_ref(val)
+    }
+
+    fn charge_write_ref(
+        &mut self,
+        new_val: impl ValueView,
+        old_val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.observe(&new_val);
+        self.gas.charge_write_ref(new_val, old_val)
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.gas.charge_eq(lhs, rhs)
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.gas.charge_neq(lhs, rhs)
+    }
+
+    fn charge_vec_pack<'a>(
+        &mut self,
+        ty: impl TypeView + 'a,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        if self.tracking() {
+            self.observe_len(args.len() as u64);
+        }
+        self.gas.charge_vec_pack(ty, args)
+    }
+
+    fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.gas.charge_vec_len(ty)
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        is_mut: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_vec_borrow(is_mut, ty, is_success)
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_vec_push_back(ty, val)
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_vec_pop_back(ty, val)
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        ty: impl TypeView,
+        expect_num_elements: NumArgs,
+        elems: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas.charge_vec_unpack(ty, expect_num_elements, elems)
+    }
+
+    fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.gas.charge_vec_swap(ty)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+    ) -> PartialVMResult<()> {
+        // Native calls do not drop a frame when returning
+        self.exit();
+        self.gas.charge_native_function(amount, ret_vals)
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.gas
+            .charge_native_function_before_execution(ty_args, args)
+    }
+
+    fn charge_drop_frame(
+        &mut self,
+        locals: impl Iterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.exit();
+        if self.tracking() {
+            let locals: Vec<_> = locals.collect();
+            locals.iter().for_each(|local| self.observe(local));
+            self.gas.charge_drop_frame(locals.into_iter())
+        } else {
+            self.gas.charge_drop_frame(locals)
+        }
+    }
+
+    fn remaining_gas(&self) -> InternalGas {
+        GasMeter::remaining_gas(&self.gas)
+    }
+
+    fn get_profiler_mut(&mut self) -> Option<&mut GasProfiler> {
+        self.gas.get_profiler_mut()
+    }
+
+    fn set_profiler(&mut self, profiler: GasProfiler) {
+        self.gas.set_profiler(profiler)
+    }
+}
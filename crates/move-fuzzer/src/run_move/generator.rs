@@ -0,0 +1,222 @@
+use anyhow::{anyhow, bail, Result};
+use arbitrary::Unstructured;
+use move_binary_format::{
+    file_format::{SignatureToken, StructHandleIndex},
+    CompiledModule,
+};
+use move_bytecode_utils::{layout::TypeLayoutBuilder, module_cache::GetModule};
+use move_core_types::{
+    account_address::AccountAddress,
+    annotated_value as A,
+    identifier::IdentStr,
+    language_storage::{StructTag, TypeTag},
+    runtime_value::{MoveStruct, MoveValue, MoveVariant},
+    u256::U256,
+};
+
+/// Maximum number of elements of a generated vector
+pub const MAX_VECTOR_LENGTH: usize = 64;
+
+///
+/// Decodes the input provided by libFuzzer into well-typed arguments of a Move function.
+///
+/// The generator is derived from the parameter types of the function: every parameter is read
+/// from the input through `arbitrary`, following the type layout of its type. References are
+/// passed by value, as done by the VM for transaction arguments. Signers and generic functions
+/// are not supported.
+///
+#[derive(Debug, Clone)]
+pub struct ArgumentGenerator {
+    layouts: Vec<A::MoveTypeLayout>,
+}
+
+impl ArgumentGenerator {
+    /// Builds the generator of the arguments of `function`, declared in `module`. Structs in the
+    /// signature are resolved with `resolver`.
+    pub fn new(
+        module: &CompiledModule,
+        function: &IdentStr,
+        resolver: &impl GetModule,
+    ) -> Result<Self> {
+        let handle = module
+            .function_defs()
+            .iter()
+            .map(|def| module.function_handle_at(def.function))
+            .find(|handle| module.identifier_at(handle.name) == function)
+            .ok_or_else(|| {
+                anyhow!(
+                    "function {} not found in module {}",
+                    function,
+                    module.self_id()
+                )
+            })?;
+        if !handle.type_parameters.is_empty() {
+            bail!("generic functions are not supported");
+        }
+
+        let layouts = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .map(|token| {
+                let tag = type_tag(module, token)?;
+                TypeLayoutBuilder::build_with_types(&tag, resolver)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { layouts })
+    }
+
+    /// Layouts of the generated arguments, in order
+    pub fn layouts(&self) -> &[A::MoveTypeLayout] {
+        &self.layouts
+    }
+
+    /// Reads a value for each parameter of the function from `u`
+    pub fn generate(&self, u: &mut Unstructured) -> arbitrary::Result<Vec<MoveValue>> {
+        self.layouts
+            .iter()
+            .map(|layout| arbitrary_value(layout, u))
+            .collect()
+    }
+
+    /// Reads the arguments of the function from `u`, serialized with BCS
+    pub fn generate_bcs(&self, u: &mut Unstructured) -> arbitrary::Result<Vec<Vec<u8>>> {
+        Ok(self
+            .generate(u)?
+            .iter()
+            .map(|value| {
+                value
+                    .simple_serialize()
+                    .expect("generated values are always serializable")
+            })
+            .collect())
+    }
+}
+
+/// Reads a value of type `layout` from `u`
+pub fn arbitrary_value(
+    layout: &A::MoveTypeLayout,
+    u: &mut Unstructured,
+) -> arbitrary::Result<MoveValue> {
+    use A::MoveTypeLayout as L;
+    Ok(match layout {
+        L::Bool => MoveValue::Bool(u.arbitrary()?),
+        L::U8 => MoveValue::U8(u.arbitrary()?),
+        L::U16 => MoveValue::U16(u.arbitrary()?),
+        L::U32 => MoveValue::U32(u.arbitrary()?),
+        L::U64 => MoveValue::U64(u.arbitrary()?),
+        L::U128 => MoveValue::U128(u.arbitrary()?),
+        L::U256 => MoveValue::U256(U256::from_le_bytes(&u.arbitrary()?)),
+        L::Address => MoveValue::Address(AccountAddress::new(u.arbitrary()?)),
+        L::Signer => return Err(arbitrary::Error::IncorrectFormat),
+        L::Vector(elem) => MoveValue::Vector(arbitrary_vector(elem, MAX_VECTOR_LENGTH, u)?),
+        L::Struct(layout) if is_utf8_string(&layout.type_) => {
+            MoveValue::vector_u8(u.arbitrary::<String>()?.into_bytes())
+        }
+        L::Struct(layout) if is_ascii_string(&layout.type_) => MoveValue::vector_u8(
+            u.arbitrary::<Vec<u8>>()?
+                .into_iter()
+                .map(|byte| byte & 0x7f)
+                .collect(),
+        ),
+        // An option is a vector holding at most one element
+        L::Struct(layout) if is_option(&layout.type_) => match &layout.fields[..] {
+            [field] => match &field.layout {
+                L::Vector(elem) => MoveValue::Struct(MoveStruct::new(vec![MoveValue::Vector(
+                    arbitrary_vector(elem, 1, u)?,
+                )])),
+                _ => return Err(arbitrary::Error::IncorrectFormat),
+            },
+            _ => return Err(arbitrary::Error::IncorrectFormat),
+        },
+        L::Struct(layout) => MoveValue::Struct(MoveStruct::new(
+            layout
+                .fields
+                .iter()
+                .map(|field| arbitrary_value(&field.layout, u))
+                .collect::<arbitrary::Result<_>>()?,
+        )),
+        L::Enum(layout) => {
+            if layout.variants.is_empty() {
+                return Err(arbitrary::Error::IncorrectFormat);
+            }
+            let tag = u.choose_index(layout.variants.len())?;
+            MoveValue::Variant(MoveVariant::new(
+                tag as u16,
+                layout.variants[tag]
+                    .1
+                    .iter()
+                    .map(|field| arbitrary_value(&field.layout, u))
+                    .collect::<arbitrary::Result<_>>()?,
+            ))
+        }
+    })
+}
+
+fn arbitrary_vector(
+    elem: &A::MoveTypeLayout,
+    max_len: usize,
+    u: &mut Unstructured,
+) -> arbitrary::Result<Vec<MoveValue>> {
+    let len = u.int_in_range(0..=max_len)?;
+    (0..len).map(|_| arbitrary_value(elem, u)).collect()
+}
+
+/// Converts a parameter type into a type tag. References are replaced by the referenced type.
+pub fn type_tag(module: &CompiledModule, token: &SignatureToken) -> Result<TypeTag> {
+    use SignatureToken as S;
+    Ok(match token {
+        S::Bool => TypeTag::Bool,
+        S::U8 => TypeTag::U8,
+        S::U16 => TypeTag::U16,
+        S::U32 => TypeTag::U32,
+        S::U64 => TypeTag::U64,
+        S::U128 => TypeTag::U128,
+        S::U256 => TypeTag::U256,
+        S::Address => TypeTag::Address,
+        S::Vector(inner) => TypeTag::Vector(Box::new(type_tag(module, inner)?)),
+        S::Reference(inner) | S::MutableReference(inner) => type_tag(module, inner)?,
+        S::Struct(idx) => TypeTag::Struct(Box::new(struct_tag(module, *idx, vec![]))),
+        S::StructInstantiation(inst) => {
+            let (idx, type_args) = &**inst;
+            let type_params = type_args
+                .iter()
+                .map(|arg| type_tag(module, arg))
+                .collect::<Result<_>>()?;
+            TypeTag::Struct(Box::new(struct_tag(module, *idx, type_params)))
+        }
+        S::Signer => bail!("parameters of type signer are not supported"),
+        S::TypeParameter(_) => bail!("generic parameters are not supported"),
+    })
+}
+
+fn struct_tag(
+    module: &CompiledModule,
+    idx: StructHandleIndex,
+    type_params: Vec<TypeTag>,
+) -> StructTag {
+    let handle = module.struct_handle_at(idx);
+    let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+    StructTag {
+        address: *module_id.address(),
+        module: module_id.name().to_owned(),
+        name: module.identifier_at(handle.name).to_owned(),
+        type_params,
+    }
+}
+
+fn is_std_type(tag: &StructTag, module: &str, name: &str) -> bool {
+    tag.address == AccountAddress::ONE && tag.module.as_str() == module && tag.name.as_str() == name
+}
+
+fn is_utf8_string(tag: &StructTag) -> bool {
+    is_std_type(tag, "string", "String")
+}
+
+fn is_ascii_string(tag: &StructTag) -> bool {
+    is_std_type(tag, "ascii", "String")
+}
+
+fn is_option(tag: &StructTag) -> bool {
+    is_std_type(tag, "option", "Option")
+}
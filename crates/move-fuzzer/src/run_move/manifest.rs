@@ -0,0 +1,86 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::{collections::BTreeMap, fs, path::Path};
+
+use crate::run_move::{exhaustion::ExhaustionConfig, oracle::OracleConfig};
+
+///
+/// The `[package.metadata.move-fuzz]` section of the fuzz project manifest (`fuzz/Cargo.toml`).
+///
+/// ```toml
+/// [package.metadata.move-fuzz.oracle]
+/// report = ["ARITHMETIC_ERROR"]
+///
+/// [package.metadata.move-fuzz.exhaustion]
+/// max_gas = 50000
+///
+/// [package.metadata.move-fuzz.targets.swap]
+/// function = "0x2::pool::swap"
+///
+/// [package.metadata.move-fuzz.targets.pool]
+/// sequence = "0x2::pool"
+///
+/// [package.metadata.move-fuzz.targets.math]
+/// function = "0x2::math::mul_div"
+/// differential = true
+/// ```
+///
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct FuzzMetadata {
+    /// Oracle shared by all the targets
+    pub oracle: OracleConfig,
+    /// Resource-exhaustion thresholds shared by all the targets
+    pub exhaustion: ExhaustionConfig,
+    /// Whether the targets compare their executions across the execution layers, see
+    /// `Differential`
+    pub differential: bool,
+    /// Per-target configuration, keyed by target name
+    pub targets: BTreeMap<String, TargetMetadata>,
+}
+
+/// Configuration of a single fuzz target
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TargetMetadata {
+    /// Fully qualified name of the package function to fuzz. Targets without one run the `main`
+    /// function of the Move module named as the target.
+    pub function: Option<String>,
+    /// Module whose public functions are called in sequence, see `SequenceGenerator`
+    pub sequence: Option<String>,
+    /// Overrides of the shared oracle
+    pub oracle: OracleConfig,
+    /// Overrides of the shared resource-exhaustion thresholds
+    pub exhaustion: ExhaustionConfig,
+    /// Overrides the shared `differential` setting
+    pub differential: Option<bool>,
+}
+
+impl FuzzMetadata {
+    /// Reads the `move-fuzz` metadata of the manifest at `manifest_path`, an absent section
+    /// yields the default configuration
+    pub fn from_manifest(manifest_path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+        let manifest: toml::Value = toml::from_str(&contents)
+            .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+        match manifest
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("move-fuzz"))
+        {
+            Some(metadata) => metadata.clone().try_into().with_context(|| {
+                format!(
+                    "invalid move-fuzz configuration in {}",
+                    manifest_path.display()
+                )
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Returns the configuration of `target`, if any
+    pub fn target(&self, target: &str) -> Option<&TargetMetadata> {
+        self.targets.get(target)
+    }
+}
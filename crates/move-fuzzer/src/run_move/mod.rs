@@ -1,27 +1,126 @@
-use anyhow::{Result, Error, anyhow};
-use move_core_types::{account_address::AccountAddress, errmap::ErrorMapping, language_storage::TypeTag, transaction_argument::TransactionArgument};
+use anyhow::{anyhow, bail, Context, Result};
+use arbitrary::Unstructured;
+use move_binary_format::{errors::VMError, CompiledModule};
+use move_bytecode_utils::module_cache::GetModule;
+use move_command_line_common::types::{ParsedFqName, ParsedModuleId};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    transaction_argument::convert_txn_args, vm_status::StatusCode,
+};
+use move_package::compilation::{
+    compiled_package::{CompiledPackage, OnDiskCompiledPackage},
+    package_layout::CompiledPackageLayout,
+};
 use move_stdlib_natives::{all_natives, nursery_natives, GasParameters, NurseryGasParameters};
-use move_package::BuildConfig;
+use move_vm_config::runtime::{VMConfig, VMTracerConfig};
+use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
+use move_vm_test_utils::InMemoryStorage;
+use once_cell::sync::OnceCell;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
 
-use std::{env, fmt, path::PathBuf, process::Command};
-use std::fmt::Debug;
-use move_core_types::identifier::Identifier;
-
-use move_core_types::u256::U256;
-use move_core_types::vm_status::StatusCode;
-use move_core_types::vm_status::StatusCode::ABORTED;
-use move_vm_runtime::native_functions::NativeFunction;
-use crate::run_move::move_args::ToTransactionArgument;
+#[cfg(feature = "differential")]
+use crate::run_move::differential::Differential;
+use crate::run_move::exhaustion::{ExhaustionLimits, ExhaustionMeter};
+use crate::run_move::generator::ArgumentGenerator;
+use crate::run_move::manifest::{FuzzMetadata, TargetMetadata};
 use crate::run_move::move_args::MoveArg;
+use crate::run_move::move_args::ToTransactionArgument;
+use crate::run_move::oracle::CrashOracle;
+use crate::run_move::repro::ReproTest;
+use crate::run_move::sequence::{Sequence, SequenceGenerator, ValuePool, MAX_SEQUENCE_LENGTH};
+use crate::Corpus;
 
 ///
-/// todo: docs
+/// Conversion of fuzzer generated data into Move transaction arguments
 ///
 pub mod move_args;
 
+///
+/// Per-target configuration read from the fuzz project manifest
+///
+pub mod manifest;
+
+///
+/// Classification of failed executions into findings and expected failures
+///
+pub mod oracle;
+
+///
+/// Generation of well-typed Move arguments from the signature of the fuzzed function
+///
+pub mod generator;
+
+///
+/// Stateful fuzzing of a module through sequences of calls to its public functions
+///
+pub mod sequence;
+
+///
+/// libFuzzer dictionaries of the constants of the fuzzed code
+///
+pub mod dictionary;
+
+///
+/// Resource-exhaustion thresholds and the gas meter enforcing them
+///
+pub mod exhaustion;
+
+///
+/// Structure-aware mutations of compiled modules, for fuzzing the deserializer and the verifier
+///
+pub mod module_mutator;
+
+///
+/// Differential execution of the fuzz target across the `move-execution` cuts
+///
+#[cfg(feature = "differential")]
+pub mod differential;
+
+///
+/// Move unit tests reproducing fuzz inputs
+///
+pub mod repro;
 
 ///
-/// todo: docs
+/// Move-level coverage feedback for libFuzzer
+///
+#[cfg(feature = "move-coverage")]
+pub mod coverage;
+
+/// Fuzz project directory, relative to the directory the fuzz target is started from
+const DEFAULT_FUZZ_DIR: &str = "fuzz";
+
+/// Environment variable overriding `DEFAULT_FUZZ_DIR`, set by `move fuzz` when running a target
+pub const FUZZ_DIR_ENV_VAR: &str = "MOVE_FUZZ_DIR";
+
+/// Environment variable set by `move fuzz repro` to the file the Move test reproducing the input
+/// is written to, instead of running the target
+pub const REPRO_PATH_ENV_VAR: &str = "MOVE_FUZZ_REPRO_PATH";
+
+/// Environment variable set by `move fuzz repro` to the name of the reproducing test
+pub const REPRO_NAME_ENV_VAR: &str = "MOVE_FUZZ_REPRO_NAME";
+
+/// Environment variable set by `move fuzz coverage` to the file the execution trace of the Move
+/// code is appended to, see `move_trace_format`
+pub const TRACE_PATH_ENV_VAR: &str = "MOVE_FUZZ_TRACE_PATH";
+
+/// Name of the Move function called by targets that are not bound to a package function
+pub const ENTRY_FUNCTION_NAME: &str = "main";
+
+/// The fuzz target is loaded once per process and shared by all the executions
+static TARGET: OnceCell<MoveTarget> = OnceCell::new();
+
+/// Output file and name of the reproducing test, read once from the environment
+static REPRO: OnceCell<Option<(PathBuf, String)>> = OnceCell::new();
+
+///
+/// Executes the Move fuzz target with the given arguments.
+///
+/// Panics, so that libFuzzer reports a crash, when the target's `CrashOracle` considers the
+/// failure a finding, or when the fuzz target cannot be loaded at all.
 ///
 pub fn run(data: MoveArg) {
     if let Err(e) = run_aux(data) {
@@ -30,71 +129,479 @@ pub fn run(data: MoveArg) {
 }
 
 fn run_aux(data: MoveArg) -> Result<()> {
-    let script_name: Option<String> = None;
-    let bytecode_version : Option<u32> = None;
-    //let storage_dir = PathBuf::from(DEFAULT_STORAGE_DIR);
-    let signers : Vec<String> = Vec::new();
-    let type_args: Vec<String> = vec![];
-    let gas_budget : Option<GasParameters> = None;
-    let dry_run = false;
-
-    let cmd_args = env::args().collect::<Vec<String>>();
-    let target_path = PathBuf::from(&cmd_args[0]);
-
-    let target_name = target_path.file_name().unwrap();
-
-    let target_dir = PathBuf::from("./fuzz");
-    let mut script_file = target_dir.clone();
-    //script_file.push(format!("sources/{}.move", target_name.to_str().unwrap()));
-    script_file.push(format!("build/move-fuzz_target/bytecode_scripts/main.mv"));
-    // let move_args = Move {
-    //     package_path: Some(target_dir),
-    //     verbose: false,
-    //     build_config: BuildConfig::default(),
-    // };
-
-    let args = data.to_transaction_argument();
-
-    let error_descriptions: ErrorMapping = bcs::from_bytes(move_stdlib::error_descriptions())?;
-
-    let cost_table = &move_vm_test_utils::gas_schedule::INITIAL_COST_SCHEDULE;
+    let target = TARGET.get_or_try_init(MoveTarget::load)?;
+    let args = convert_txn_args(&data.to_transaction_argument());
+    if let Some((path, name)) = repro() {
+        return target.write_repro(path, name, args);
+    }
+    target.execute(args)
+}
+
+///
+/// Executes the Move fuzz target with arguments decoded from `bytes` according to the signature
+/// of the fuzzed function, see `ArgumentGenerator`.
+///
+/// Inputs that cannot be decoded are rejected. Findings are reported as in `run`.
+///
+pub fn run_generated(bytes: &[u8]) -> Corpus {
+    match run_generated_aux(bytes) {
+        Ok(corpus) => corpus,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+fn run_generated_aux(bytes: &[u8]) -> Result<Corpus> {
+    let target = TARGET.get_or_try_init(MoveTarget::load)?;
+    let generator = target.generator()?;
+    let mut u = Unstructured::new(bytes);
+    let args = match generator.generate(&mut u) {
+        Ok(args) => args,
+        Err(_) => return Ok(Corpus::Reject),
+    };
+
+    // `RUST_LIBFUZZER_DEBUG_PATH` is set by `cargo fuzz fmt` to print the decoded input
+    if let Some(path) = crate::RUST_LIBFUZZER_DEBUG_PATH.get() {
+        fs::write(path, format!("{:#?}\n", args))
+            .with_context(|| format!("failed to write {}", path))?;
+        return Ok(Corpus::Reject);
+    }
+
+    let args = args
+        .iter()
+        .map(|arg| {
+            arg.simple_serialize()
+                .ok_or_else(|| anyhow!("failed to serialize argument {:?}", arg))
+        })
+        .collect::<Result<_>>()?;
+    if let Some((path, name)) = repro() {
+        target.write_repro(path, name, args)?;
+        return Ok(Corpus::Reject);
+    }
+    target.execute(args)?;
+    Ok(Corpus::Keep)
+}
+
+///
+/// Executes the sequence of calls decoded from `bytes` against the module of the Move fuzz
+/// target, see `SequenceGenerator`. All the calls run in the same session.
+///
+/// The sequence stops at the first failed call. When the failure is a finding, the panic message
+/// contains the executed call sequence.
+///
+pub fn run_sequence(bytes: &[u8]) -> Corpus {
+    match run_sequence_aux(bytes) {
+        Ok(corpus) => corpus,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+fn run_sequence_aux(bytes: &[u8]) -> Result<Corpus> {
+    let target = TARGET.get_or_try_init(MoveTarget::load)?;
+    let mut u = Unstructured::new(bytes);
+    let mut meter = target.meter();
+    let (sequence, failure) = target.execute_sequence(&mut u, &mut meter)?;
+
+    // `RUST_LIBFUZZER_DEBUG_PATH` is set by `cargo fuzz fmt` to print the decoded input
+    if let Some(path) = crate::RUST_LIBFUZZER_DEBUG_PATH.get() {
+        fs::write(path, sequence.to_string())
+            .with_context(|| format!("failed to write {}", path))?;
+        return Ok(Corpus::Reject);
+    }
+
+    if let Some((path, name)) = repro() {
+        let mut test = ReproTest::new(name, &sequence.module)?;
+        test.sequence(&sequence)?;
+        write_repro(path, &test.render(failure.as_ref()))?;
+        return Ok(Corpus::Reject);
+    }
+
+    if let Some(err) = failure.filter(|err| target.oracle.is_crash(err)) {
+        bail!(
+            "Move execution of the call sequence failed: {}\n{}",
+            describe_error(&err),
+            sequence
+        );
+    }
+    target.check_usage(&meter).with_context(|| {
+        format!(
+            "Move execution of the call sequence exhausts resources\n{}",
+            sequence
+        )
+    })?;
+    Ok(if sequence.calls.is_empty() {
+        Corpus::Reject
+    } else {
+        Corpus::Keep
+    })
+}
+
+///
+/// Deserializes `bytes` into a compiled module and runs the bytecode verifier on it, see
+/// `module_mutator::mutate_module` for the matching mutator.
+///
+/// Inputs that are not modules are rejected. Panics when the module does not round-trip through
+/// the serializer, or when the deserializer or the verifier panic.
+///
+pub fn run_module(bytes: &[u8]) -> Corpus {
+    let module = match CompiledModule::deserialize_with_defaults(bytes) {
+        Ok(module) => module,
+        Err(_) => return Corpus::Reject,
+    };
+    let mut serialized = vec![];
+    if module.serialize(&mut serialized).is_ok() {
+        match CompiledModule::deserialize_with_defaults(&serialized) {
+            Ok(deserialized) if deserialized == module => (),
+            Ok(_) => panic!("module {} changes through serialization", module.self_id()),
+            Err(e) => panic!(
+                "serialized module {} does not deserialize: {:?}",
+                module.self_id(),
+                e
+            ),
+        }
+    }
+    let _ = move_bytecode_verifier::verify_module_unmetered(&module);
+    Corpus::Keep
+}
+
+/// A compiled fuzz target, published together with its dependencies in an in-memory storage
+struct MoveTarget {
+    vm: MoveVM,
+    storage: InMemoryStorage,
+    module: ModuleId,
+    /// Called function, `None` for sequence targets
+    function: Option<Identifier>,
+    oracle: CrashOracle,
+    /// Resource-exhaustion thresholds, `None` if the target runs unmetered
+    exhaustion: Option<ExhaustionLimits>,
+    /// Execution layers the target is compared across, `None` if it is not differential
+    #[cfg(feature = "differential")]
+    differential: Option<Differential>,
+    /// Built on first use, targets passing their own arguments may not be supported by it
+    generator: OnceCell<ArgumentGenerator>,
+    sequence: OnceCell<SequenceGenerator>,
+}
+
+impl MoveTarget {
+    fn load() -> Result<Self> {
+        let fuzz_dir = env::var_os(FUZZ_DIR_ENV_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_FUZZ_DIR));
+        let target_name = target_name()?;
+        let package = compiled_package(&fuzz_dir)?;
+        let metadata = FuzzMetadata::from_manifest(&fuzz_dir.join("Cargo.toml"))?;
+        let target_metadata = metadata.target(&target_name);
+        let oracle = CrashOracle::new(
+            &metadata.oracle,
+            target_metadata.map(|target| &target.oracle),
+        )?;
+        let exhaustion = ExhaustionLimits::new(
+            &metadata.exhaustion,
+            target_metadata.map(|target| &target.exhaustion),
+        );
+
+        let differential = target_metadata
+            .and_then(|target| target.differential)
+            .unwrap_or(metadata.differential);
+
+        let mut storage = InMemoryStorage::new();
+        for unit in package.all_compiled_units() {
+            storage.publish_or_overwrite_module(unit.module.self_id(), unit.serialize(None));
+        }
+
+        let (module, function) = match target_metadata {
+            Some(TargetMetadata {
+                sequence: Some(module),
+                ..
+            }) => (parse_module(module)?, None),
+            Some(TargetMetadata {
+                function: Some(function),
+                ..
+            }) => {
+                let (module, function) = parse_function(function)?;
+                (module, Some(function))
+            }
+            _ => {
+                let module = package
+                    .get_module_by_name_from_root(&target_name)
+                    .with_context(|| {
+                        format!("no Move module found for fuzz target `{}`", target_name)
+                    })?
+                    .unit
+                    .module
+                    .self_id();
+                (module, Some(Identifier::new(ENTRY_FUNCTION_NAME)?))
+            }
+        };
+
+        if differential && function.is_none() {
+            bail!("differential fuzzing does not support sequence targets");
+        }
+        #[cfg(feature = "differential")]
+        let differential = if differential {
+            Some(Differential::new(
+                package.all_compiled_units().map(|unit| &unit.module),
+            )?)
+        } else {
+            None
+        };
+        #[cfg(not(feature = "differential"))]
+        if differential {
+            bail!("differential fuzzing requires the `differential` feature of move-fuzzer");
+        }
+
+        let vm_config = VMConfig {
+            tracer_config: env::var_os(TRACE_PATH_ENV_VAR).map(|trace_path| VMTracerConfig {
+                trace_path: trace_path.into(),
+            }),
+            ..Default::default()
+        };
+        let vm = MoveVM::new_with_config(natives(), vm_config)
+            .map_err(|e| anyhow!("failed to create the Move VM: {:?}", e))?;
+
+        Ok(Self {
+            vm,
+            storage,
+            module,
+            function,
+            oracle,
+            exhaustion,
+            #[cfg(feature = "differential")]
+            differential,
+            generator: OnceCell::new(),
+            sequence: OnceCell::new(),
+        })
+    }
+
+    fn function(&self) -> Result<&Identifier> {
+        self.function.as_ref().ok_or_else(|| {
+            anyhow!(
+                "the fuzz target calls a sequence of functions of {}",
+                self.module
+            )
+        })
+    }
+
+    fn compiled_module(&self) -> Result<CompiledModule> {
+        (&self.storage)
+            .get_module_by_id(&self.module)
+            .ok()
+            .flatten()
+            .ok_or_else(|| anyhow!("module {} not found", self.module))
+    }
+
+    fn generator(&self) -> Result<&ArgumentGenerator> {
+        self.generator.get_or_try_init(|| {
+            let function = self.function()?;
+            ArgumentGenerator::new(&self.compiled_module()?, function, &&self.storage).with_context(
+                || {
+                    format!(
+                        "cannot generate the arguments of {}::{}",
+                        self.module, function
+                    )
+                },
+            )
+        })
+    }
+
+    fn sequence(&self) -> Result<&SequenceGenerator> {
+        self.sequence
+            .get_or_try_init(|| SequenceGenerator::new(&self.compiled_module()?, &&self.storage))
+    }
+
+    fn execute(&self, args: Vec<Vec<u8>>) -> Result<()> {
+        let function = self.function()?;
+        #[cfg(feature = "differential")]
+        if let Some(differential) = &self.differential {
+            let gas_budget = self
+                .exhaustion
+                .as_ref()
+                .map_or(exhaustion::DEFAULT_GAS_BUDGET, |limits| limits.gas_budget);
+            differential.compare(
+                &self.storage,
+                &self.module,
+                function,
+                args.clone(),
+                gas_budget,
+            )?;
+        }
+        let mut meter = self.meter();
+        if let Some(err) = self.call(args, &mut meter)? {
+            if self.oracle.is_crash(&err) {
+                bail!(
+                    "Move execution of {}::{} failed: {}",
+                    self.module,
+                    function,
+                    describe_error(&err)
+                );
+            }
+        }
+        self.check_usage(&meter).with_context(|| {
+            format!(
+                "Move execution of {}::{} exhausts resources",
+                self.module, function
+            )
+        })
+    }
+
+    /// Calls the target function in a new session, returning the error of a failed execution
+    fn call(&self, args: Vec<Vec<u8>>, meter: &mut ExhaustionMeter) -> Result<Option<VMError>> {
+        let function = self.function()?;
+        let mut session = self.vm.new_session(&self.storage);
+        let res =
+            session.execute_function_bypass_visibility(&self.module, function, vec![], args, meter);
+        Ok(res.err())
+    }
+
+    /// The gas meter of an execution, enforcing the resource-exhaustion thresholds if any
+    fn meter(&self) -> ExhaustionMeter {
+        ExhaustionMeter::new(self.exhaustion.as_ref())
+    }
+
+    /// Reports the gas used to libFuzzer and fails if the execution metered by `meter` went past
+    /// the resource-exhaustion thresholds
+    fn check_usage(&self, meter: &ExhaustionMeter) -> Result<()> {
+        #[cfg(feature = "move-coverage")]
+        if self.exhaustion.is_some() {
+            coverage::record_gas_used(meter.usage().gas_used);
+        }
+        meter.check()
+    }
+
+    /// Writes the Move test calling the target function with `args` to `path`
+    fn write_repro(&self, path: &Path, name: &str, args: Vec<Vec<u8>>) -> Result<()> {
+        let layouts = self.generator()?.layouts();
+        if layouts.len() != args.len() {
+            bail!(
+                "the input decodes into {} arguments, {}::{} takes {}",
+                args.len(),
+                self.module,
+                self.function()?,
+                layouts.len()
+            );
+        }
+        let typed_args: Vec<_> = args.iter().cloned().zip(layouts.iter().cloned()).collect();
+        let mut test = ReproTest::new(name, &self.module)?;
+        test.call(self.function()?, &typed_args)?;
+        let failure = self.call(args, &mut ExhaustionMeter::new(None))?;
+        write_repro(path, &test.render(failure.as_ref()))
+    }
+
+    /// Executes the calls decoded from `u` until the input is consumed or a call fails. Returns
+    /// the executed calls, and the error of the failed one.
+    fn execute_sequence(
+        &self,
+        u: &mut Unstructured,
+        meter: &mut ExhaustionMeter,
+    ) -> Result<(Sequence, Option<VMError>)> {
+        let generator = self.sequence()?;
+        let mut session = self.vm.new_session(&self.storage);
+        let mut pool = ValuePool::default();
+        let mut sequence = Sequence::new(self.module.clone());
+
+        while sequence.calls.len() < MAX_SEQUENCE_LENGTH && !u.is_empty() {
+            let (mut call, args) = match generator.next_call(&mut pool, u) {
+                Ok(Some(call)) => call,
+                Ok(None) | Err(_) => break,
+            };
+            let res = session.execute_function_bypass_visibility(
+                &self.module,
+                &call.function,
+                vec![],
+                args,
+                &mut *meter,
+            );
+            match res {
+                Ok(outputs) => {
+                    generator.complete_call(&mut call, &mut pool, outputs);
+                    sequence.calls.push(call);
+                }
+                Err(err) => {
+                    sequence.calls.push(call);
+                    return Ok((sequence, Some(err)));
+                }
+            }
+        }
+        Ok((sequence, None))
+    }
+}
+
+/// The output file and name of the reproducing test, when running for `move fuzz repro`
+fn repro() -> Option<(&'static Path, &'static str)> {
+    REPRO
+        .get_or_init(|| {
+            let path = env::var_os(REPRO_PATH_ENV_VAR)?;
+            let name = env::var(REPRO_NAME_ENV_VAR)
+                .unwrap_or_else(|_| repro::DEFAULT_TEST_NAME.to_string());
+            Some((PathBuf::from(path), name))
+        })
+        .as_ref()
+        .map(|(path, name)| (path.as_path(), name.as_str()))
+}
+
+fn write_repro(path: &Path, test: &str) -> Result<()> {
+    fs::write(path, test).with_context(|| format!("failed to write {}", path.display()))
+}
+
+fn describe_error(err: &VMError) -> String {
+    match (err.major_status(), err.sub_status()) {
+        (StatusCode::ABORTED, Some(code)) => {
+            format!("aborted with code {} in {:?}", code, err.location())
+        }
+        _ => format!("{:?}", err),
+    }
+}
+
+/// Parses the fully qualified name of a function with a numerical address, e.g. `0x2::pool::swap`
+fn parse_function(function: &str) -> Result<(ModuleId, Identifier)> {
+    let (module, name) = ParsedFqName::parse(function)?.into_fq_name(&|_| None)?;
+    Ok((module, Identifier::new(name)?))
+}
+
+/// Parses a module id with a numerical address, e.g. `0x2::pool`
+fn parse_module(module: &str) -> Result<ModuleId> {
+    ParsedModuleId::parse(module)?.into_module_id(&|_| None)
+}
+
+/// The fuzz target has the same name as the binary libFuzzer is running
+fn target_name() -> Result<String> {
+    let binary = env::args()
+        .next()
+        .ok_or_else(|| anyhow!("unable to determine the fuzz target binary"))?;
+    Path::new(&binary)
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| anyhow!("invalid fuzz target binary path: {}", binary))
+}
+
+/// Loads the Move package built by `move fuzz build` in `fuzz_dir`
+pub fn compiled_package(fuzz_dir: &Path) -> Result<CompiledPackage> {
+    let build_dir = fuzz_dir.join(CompiledPackageLayout::Root.path());
+    let entries = fs::read_dir(&build_dir).with_context(|| {
+        format!(
+            "failed to read {}, was the fuzz project built?",
+            build_dir.display()
+        )
+    })?;
+    for entry in entries {
+        let package_dir = entry?.path();
+        if package_dir
+            .join(CompiledPackageLayout::BuildInfo.path())
+            .is_file()
+        {
+            return OnDiskCompiledPackage::from_path(&package_dir)?.into_compiled_package();
+        }
+    }
+    bail!("no compiled Move package found in {}", build_dir.display())
+}
+
+fn natives() -> NativeFunctionTable {
     let addr = AccountAddress::from_hex_literal("0x1").unwrap();
-    let natives : Vec<(AccountAddress, Identifier, Identifier, NativeFunction)> = all_natives(addr, GasParameters::zeros())
+    all_natives(addr, GasParameters::zeros())
         .into_iter()
-        .collect();
-
-    // let context = PackageContext::new(&move_args.package_path, &move_args.build_config)?;
-
-    //let state = context.prepare_state(bytecode_version, &storage_dir)?;
-
-
-    // match sandbox::commands::run_and_check (
-    //     natives,
-    //     cost_table,
-    //     &error_descriptions,
-    //     &state,
-    //     context.package(),
-    //     &script_file,
-    //     &script_name,
-    //     &signers,
-    //     &args,
-    //     type_args.to_vec(),
-    //     gas_budget,
-    //     bytecode_version,
-    //     dry_run,
-    //     move_args.verbose,
-    // ) {
-    //     Ok(res) => {
-    //         if ! res {
-    //             Err(anyhow!("Terminating execution..."))
-    //         }
-    //         else {
-    //             Ok(())
-    //         }
-    //     }
-    //     Err(err) => {
-    //         Err(err)
-    //     }
-    // }
-    todo!();
-}
\ No newline at end of file
+        .chain(nursery_natives(
+            /* silent */ true,
+            addr,
+            NurseryGasParameters::zeros(),
+        ))
+        .collect()
+}
@@ -0,0 +1,146 @@
+use invalid_mutations::{
+    bounds::{
+        ApplyCodeUnitBoundsContext, ApplyOutOfBoundsContext, CodeUnitBoundsMutation,
+        OutOfBoundsMutation,
+    },
+    signature::{FieldRefMutation, SignatureRefMutation},
+};
+use move_binary_format::CompiledModule;
+use proptest::{
+    collection::vec,
+    prelude::*,
+    sample::{select, Index},
+    strategy::ValueTree,
+    test_runner::{Config, RngAlgorithm, TestRng, TestRunner},
+};
+
+use crate::fuzzer_mutate;
+
+/// Maximum number of mutations of a kind applied at once
+const MAX_MUTATIONS: usize = 4;
+
+/// Typed mutations of a compiled module
+#[derive(Clone, Copy, Debug)]
+enum Mutation {
+    /// Table indices pointing past the end of their table, see `OutOfBoundsMutation`
+    OutOfBounds,
+    /// Instruction operands pointing past the end of their table, see `CodeUnitBoundsMutation`
+    CodeUnitBounds,
+    /// Signature tokens replaced by references to them, see `SignatureRefMutation`
+    SignatureRef,
+    /// Field types replaced by references to them, see `FieldRefMutation`
+    FieldRef,
+    /// Two instructions of a function body swapped
+    SwapInstructions,
+}
+
+const MUTATIONS: &[Mutation] = &[
+    Mutation::OutOfBounds,
+    Mutation::CodeUnitBounds,
+    Mutation::SignatureRef,
+    Mutation::FieldRef,
+    Mutation::SwapInstructions,
+];
+
+///
+/// Structure-aware libFuzzer mutator of compiled modules.
+///
+/// `data[..size]` is deserialized into a `CompiledModule`, one kind of typed mutation from
+/// `invalid-mutations` is applied to it, and the module is serialized back into `data`. Inputs
+/// that do not deserialize, mutations that do not apply and modules that do not serialize within
+/// `max_size` bytes fall back to the default libFuzzer mutation. The mutations are a function of
+/// `seed` only, as libFuzzer requires.
+///
+/// Returns the new size of the input, to be called from `fuzz_mutator!`.
+///
+pub fn mutate_module(data: &mut [u8], size: usize, max_size: usize, seed: u32) -> usize {
+    let module = match CompiledModule::deserialize_with_defaults(&data[..size]) {
+        Ok(module) => module,
+        Err(_) => return fuzzer_mutate(data, size, max_size),
+    };
+    let mut bytes = vec![];
+    match mutate(module, &mut runner(seed)) {
+        Some(module) if module.serialize(&mut bytes).is_ok() && bytes.len() <= max_size => {
+            data[..bytes.len()].copy_from_slice(&bytes);
+            bytes.len()
+        }
+        _ => fuzzer_mutate(data, size, max_size),
+    }
+}
+
+/// Applies a mutation picked by `runner`, `None` if it does not apply to `module`
+fn mutate(mut module: CompiledModule, runner: &mut TestRunner) -> Option<CompiledModule> {
+    let applied = match sample(runner, select(MUTATIONS))? {
+        Mutation::OutOfBounds => {
+            let mutations = sample(
+                runner,
+                vec(OutOfBoundsMutation::strategy(), 1..MAX_MUTATIONS),
+            )?;
+            let (mutated, errors) = ApplyOutOfBoundsContext::new(module, mutations).apply();
+            module = mutated;
+            !errors.is_empty()
+        }
+        Mutation::CodeUnitBounds => {
+            let mutations = sample(
+                runner,
+                vec(CodeUnitBoundsMutation::strategy(), 1..MAX_MUTATIONS),
+            )?;
+            !ApplyCodeUnitBoundsContext::new(&mut module, mutations)
+                .apply()
+                .is_empty()
+        }
+        Mutation::SignatureRef => {
+            let mutations = sample(runner, vec(any::<(Index, Index)>(), 1..MAX_MUTATIONS))?;
+            SignatureRefMutation::new(&mut module, mutations).apply()
+        }
+        Mutation::FieldRef => {
+            let mutations = sample(runner, vec(any::<(Index, Index)>(), 1..MAX_MUTATIONS))?;
+            FieldRefMutation::new(&mut module, mutations).apply()
+        }
+        Mutation::SwapInstructions => {
+            let (function, first, second) = sample(runner, any::<(Index, Index, Index)>())?;
+            swap_instructions(&mut module, function, first, second)
+        }
+    };
+    applied.then_some(module)
+}
+
+/// Swaps two distinct instructions of a function body
+fn swap_instructions(
+    module: &mut CompiledModule,
+    function: Index,
+    first: Index,
+    second: Index,
+) -> bool {
+    let mut bodies: Vec<_> = module
+        .function_defs
+        .iter_mut()
+        .filter_map(|def| def.code.as_mut())
+        .filter(|code| code.code.len() > 1)
+        .collect();
+    if bodies.is_empty() {
+        return false;
+    }
+    let len = bodies.len();
+    let code = &mut bodies[function.index(len)].code;
+    let (first, second) = (first.index(code.len()), second.index(code.len()));
+    if code[first] == code[second] {
+        return false;
+    }
+    code.swap(first, second);
+    true
+}
+
+/// A deterministic runner drawing values from `seed`
+fn runner(seed: u32) -> TestRunner {
+    let mut bytes = [0u8; 32];
+    bytes[..4].copy_from_slice(&seed.to_le_bytes());
+    TestRunner::new_with_rng(
+        Config::default(),
+        TestRng::from_seed(RngAlgorithm::ChaCha, &bytes),
+    )
+}
+
+fn sample<S: Strategy>(runner: &mut TestRunner, strategy: S) -> Option<S::Value> {
+    strategy.new_tree(runner).ok().map(|tree| tree.current())
+}
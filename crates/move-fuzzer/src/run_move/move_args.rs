@@ -1,6 +1,6 @@
-use std::fmt::Debug;
 use move_core_types::transaction_argument::TransactionArgument;
 use move_core_types::u256::U256;
+use std::fmt::Debug;
 
 ///
 /// Trait to map which rust type can be an input for a move script
@@ -19,49 +19,49 @@ pub type MoveArg = Vec<Box<dyn ToTransactionArgument>>;
 
 impl ToTransactionArgument for u8 {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument::U8(*self)]
+        return vec![TransactionArgument::U8(*self)];
     }
 }
 
 impl ToTransactionArgument for u16 {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument::U16(*self)]
+        return vec![TransactionArgument::U16(*self)];
     }
 }
 
 impl ToTransactionArgument for u32 {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument::U32(*self)]
+        return vec![TransactionArgument::U32(*self)];
     }
 }
 
 impl ToTransactionArgument for u64 {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument::U64(*self)]
+        return vec![TransactionArgument::U64(*self)];
     }
 }
 
 impl ToTransactionArgument for u128 {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument::U128(*self)]
+        return vec![TransactionArgument::U128(*self)];
     }
 }
 
 impl ToTransactionArgument for U256 {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument::U256(*self)]
+        return vec![TransactionArgument::U256(*self)];
     }
 }
 
 impl ToTransactionArgument for bool {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument:: Bool(*self)]
+        return vec![TransactionArgument::Bool(*self)];
     }
 }
 
 impl ToTransactionArgument for Vec<u8> {
     fn to_transaction_argument(&self) -> Vec<TransactionArgument> {
-        return vec![TransactionArgument::U8Vector(self.to_vec())]
+        return vec![TransactionArgument::U8Vector(self.to_vec())];
     }
 }
 
@@ -75,4 +75,4 @@ impl ToTransactionArgument for Vec<Box<dyn ToTransactionArgument>> {
         }
         res
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,105 @@
+use anyhow::{anyhow, Result};
+use move_binary_format::errors::VMError;
+use move_core_types::vm_status::{StatusCode, StatusType};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+///
+/// Decides which failed executions of a fuzz target are findings, i.e. are reported to libFuzzer
+/// as crashes. Every other failure is considered an expected outcome, and the input is kept in the
+/// corpus as any successful execution.
+///
+/// The oracle of a target is configured in the `fuzz/Cargo.toml` manifest, either for all the
+/// targets or for a single one:
+///
+/// ```toml
+/// [package.metadata.move-fuzz.oracle]
+/// report = ["ARITHMETIC_ERROR", "VECTOR_OPERATION_ERROR"]
+///
+/// [package.metadata.move-fuzz.targets.fuzz_target_1.oracle]
+/// report = ["ARITHMETIC_ERROR", "OUT_OF_GAS"]
+/// ignore_abort_codes = [1, 2]
+/// ```
+///
+/// Invariant violations are always reported.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrashOracle {
+    /// Whether `abort`s are findings
+    pub report_aborts: bool,
+    /// Abort codes that are expected, e.g. the ones raised by `assert!`s on the input
+    pub ignore_abort_codes: BTreeSet<u64>,
+    /// Additional status codes that are findings
+    pub report: BTreeSet<StatusCode>,
+}
+
+/// Oracle configuration as written in the manifest, unset fields keep the inherited value
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OracleConfig {
+    /// Whether `abort`s are findings
+    pub report_aborts: Option<bool>,
+    /// Abort codes that are expected
+    pub ignore_abort_codes: Option<Vec<u64>>,
+    /// Names of the additional status codes that are findings
+    pub report: Option<Vec<String>>,
+}
+
+impl Default for CrashOracle {
+    fn default() -> Self {
+        Self {
+            report_aborts: true,
+            ignore_abort_codes: BTreeSet::new(),
+            report: BTreeSet::new(),
+        }
+    }
+}
+
+impl CrashOracle {
+    /// Builds the oracle of a target from the configuration shared by all the targets and the
+    /// target specific one. Without any configuration every abort is reported.
+    pub fn new(shared: &OracleConfig, target: Option<&OracleConfig>) -> Result<Self> {
+        let mut oracle = Self::default();
+        oracle.apply(shared)?;
+        if let Some(target) = target {
+            oracle.apply(target)?;
+        }
+        Ok(oracle)
+    }
+
+    /// Overrides the fields set in `config`
+    fn apply(&mut self, config: &OracleConfig) -> Result<()> {
+        if let Some(report_aborts) = config.report_aborts {
+            self.report_aborts = report_aborts;
+        }
+        if let Some(codes) = &config.ignore_abort_codes {
+            self.ignore_abort_codes = codes.iter().copied().collect();
+        }
+        if let Some(report) = &config.report {
+            self.report = report
+                .iter()
+                .map(|name| {
+                    name.parse::<StatusCode>()
+                        .map_err(|_| anyhow!("unknown status code `{}`", name))
+                })
+                .collect::<Result<_>>()?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the error has to be reported to libFuzzer as a crash
+    pub fn is_crash(&self, err: &VMError) -> bool {
+        if err.status_type() == StatusType::InvariantViolation {
+            return true;
+        }
+        match err.major_status() {
+            StatusCode::ABORTED => {
+                self.report_aborts
+                    && !err
+                        .sub_status()
+                        .map_or(false, |code| self.ignore_abort_codes.contains(&code))
+            }
+            status => self.report.contains(&status),
+        }
+    }
+}
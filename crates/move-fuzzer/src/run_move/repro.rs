@@ -0,0 +1,301 @@
+use anyhow::{anyhow, bail, Result};
+use move_binary_format::errors::{Location, VMError};
+use move_core_types::{
+    account_address::AccountAddress,
+    annotated_value as A,
+    identifier::{IdentStr, Identifier},
+    language_storage::{ModuleId, StructTag, TypeTag},
+    vm_status::StatusCode,
+};
+use std::fmt::Write;
+
+use crate::run_move::sequence::{ArgumentSource, Passing, Sequence};
+
+/// Name of the test module and function when none is provided
+pub const DEFAULT_TEST_NAME: &str = "fuzz_repro";
+
+///
+/// Writes a Move unit test replaying an input of a fuzz target.
+///
+/// The test lives in a `#[test_only]` module published at the address of the fuzzed module and
+/// calls the fuzzed functions with the decoded arguments written as Move literals. When the
+/// input makes the execution fail, the test expects the same failure.
+///
+/// Only the values of primitive types, vectors, `std::string::String`, `std::ascii::String` and
+/// `std::option::Option` can be written as literals, other structs must come from earlier calls
+/// of a sequence.
+///
+#[derive(Debug)]
+pub struct ReproTest {
+    name: Identifier,
+    module: ModuleId,
+    body: String,
+    /// Number of generated arguments bound to a local, to be borrowed
+    locals: usize,
+}
+
+impl ReproTest {
+    /// An empty test named `name` for the functions of `module`
+    pub fn new(name: &str, module: &ModuleId) -> Result<Self> {
+        Ok(Self {
+            name: Identifier::new(name)?,
+            module: module.clone(),
+            body: String::new(),
+            locals: 0,
+        })
+    }
+
+    /// Adds a call to `function` with the given BCS arguments
+    pub fn call(
+        &mut self,
+        function: &IdentStr,
+        args: &[(Vec<u8>, A::MoveTypeLayout)],
+    ) -> Result<()> {
+        let args = args
+            .iter()
+            .map(|(bytes, layout)| literal(bytes, layout))
+            .collect::<Result<Vec<_>>>()?;
+        writeln!(
+            self.body,
+            "        {}({});",
+            self.function_name(function),
+            args.join(", ")
+        )?;
+        Ok(())
+    }
+
+    /// Adds the calls of `sequence`, values returned by a call being bound to the locals `v<id>`
+    pub fn sequence(&mut self, sequence: &Sequence) -> Result<()> {
+        for call in &sequence.calls {
+            let mut args = Vec::with_capacity(call.args.len());
+            for arg in &call.args {
+                let value = match &arg.source {
+                    ArgumentSource::Generated(bytes, layout) => literal(bytes, layout)?,
+                    ArgumentSource::Pooled(id) => format!("v{}", id),
+                };
+                let value = match (arg.passing, &arg.source) {
+                    (Passing::Value, _) => value,
+                    (passing, ArgumentSource::Pooled(_)) => format!("{}{}", borrow(passing), value),
+                    (passing, ArgumentSource::Generated(..)) => {
+                        // Literals are bound to a local to be borrowed
+                        let local = format!("a{}", self.locals);
+                        self.locals += 1;
+                        writeln!(self.body, "        let {} = {};", local, value)?;
+                        format!("{}{}", borrow(passing), local)
+                    }
+                };
+                args.push(value);
+            }
+
+            let returns: Vec<_> = call
+                .returns
+                .iter()
+                .map(|id| id.map_or_else(|| "_".to_string(), |id| format!("v{}", id)))
+                .collect();
+            let bindings = match &returns[..] {
+                [] => String::new(),
+                [value] => format!("let {} = ", value),
+                values => format!("let ({}) = ", values.join(", ")),
+            };
+            writeln!(
+                self.body,
+                "        {}{}({});",
+                bindings,
+                self.function_name(&call.function),
+                args.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Renders the test module, expecting `failure` if the input makes the execution fail
+    pub fn render(&self, failure: Option<&VMError>) -> String {
+        let mut attributes = "    #[test]\n".to_string();
+        if let Some(err) = failure {
+            attributes.push_str(&format!(
+                "    #[expected_failure{}]\n",
+                expected_failure(err)
+            ));
+        }
+        // Values that are still in scope do not need to be consumed after a failing call
+        let end = if failure.is_some() && !self.body.is_empty() {
+            "        abort 0 // unreachable, the last call fails\n"
+        } else {
+            ""
+        };
+        format!(
+            "#[test_only]\n\
+             module {address}::{name} {{\n\
+             {attributes}    fun {name}() {{\n\
+             {body}{end}    }}\n\
+             }}\n",
+            address = self.module.address().to_hex_literal(),
+            name = self.name,
+            attributes = attributes,
+            body = self.body,
+            end = end,
+        )
+    }
+
+    fn function_name(&self, function: &IdentStr) -> String {
+        format!(
+            "{}::{}::{}",
+            self.module.address().to_hex_literal(),
+            self.module.name(),
+            function
+        )
+    }
+}
+
+fn borrow(passing: Passing) -> &'static str {
+    match passing {
+        Passing::Value => "",
+        Passing::Ref => "&",
+        Passing::MutRef => "&mut ",
+    }
+}
+
+/// Arguments of `#[expected_failure]` matching `err`
+fn expected_failure(err: &VMError) -> String {
+    let mut args = match (err.major_status(), err.sub_status()) {
+        (StatusCode::ABORTED, Some(code)) => vec![format!("abort_code = {}", code)],
+        (StatusCode::ARITHMETIC_ERROR, _) => vec!["arithmetic_error".to_string()],
+        (StatusCode::VECTOR_OPERATION_ERROR, Some(code)) => {
+            vec![
+                "vector_error".to_string(),
+                format!("minor_status = {}", code),
+            ]
+        }
+        (StatusCode::OUT_OF_GAS, _) => vec!["out_of_gas".to_string()],
+        (status, _) => vec![format!("major_status = {}", status as u64)],
+    };
+    if let Location::Module(module) = err.location() {
+        args.push(format!(
+            "location = {}::{}",
+            module.address().to_hex_literal(),
+            module.name()
+        ));
+    }
+    format!("({})", args.join(", "))
+}
+
+/// Writes the BCS value `bytes` of type `layout` as a Move expression
+fn literal(bytes: &[u8], layout: &A::MoveTypeLayout) -> Result<String> {
+    let value = A::MoveValue::simple_deserialize(bytes, layout)?;
+    value_literal(&value, layout)
+}
+
+fn value_literal(value: &A::MoveValue, layout: &A::MoveTypeLayout) -> Result<String> {
+    use A::{MoveTypeLayout as L, MoveValue as V};
+    Ok(match (value, layout) {
+        (V::Bool(b), _) => b.to_string(),
+        (V::U8(n), _) => format!("{}u8", n),
+        (V::U16(n), _) => format!("{}u16", n),
+        (V::U32(n), _) => format!("{}u32", n),
+        (V::U64(n), _) => format!("{}u64", n),
+        (V::U128(n), _) => format!("{}u128", n),
+        (V::U256(n), _) => format!("{}u256", n),
+        (V::Address(a), _) => format!("@{}", a.to_hex_literal()),
+        (V::Vector(elems), L::Vector(elem)) => match **elem {
+            L::U8 => format!("x\"{}\"", hex::encode(bytes_of(elems)?)),
+            _ => format!(
+                "vector<{}>[{}]",
+                type_name(elem),
+                elems
+                    .iter()
+                    .map(|value| value_literal(value, elem))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+        },
+        (V::Struct(s), L::Struct(layout)) if is_std_type(&layout.type_, "string", "String") => {
+            format!("0x1::string::utf8({})", string_bytes(s)?)
+        }
+        (V::Struct(s), L::Struct(layout)) if is_std_type(&layout.type_, "ascii", "String") => {
+            format!("0x1::ascii::string({})", string_bytes(s)?)
+        }
+        (V::Struct(s), L::Struct(layout)) if is_std_type(&layout.type_, "option", "Option") => {
+            match (&s.fields[..], &layout.fields[..]) {
+                ([(_, V::Vector(elems))], [field]) => match (&elems[..], &field.layout) {
+                    ([], L::Vector(elem)) => format!("0x1::option::none<{}>()", type_name(elem)),
+                    ([value], L::Vector(elem)) => {
+                        format!("0x1::option::some({})", value_literal(value, elem)?)
+                    }
+                    _ => bail!("invalid option value {}", value),
+                },
+                _ => bail!("invalid option value {}", value),
+            }
+        }
+        (V::Struct(_), L::Struct(layout)) => bail!(
+            "values of type {} cannot be written in a Move test",
+            struct_name(&layout.type_)
+        ),
+        (V::Variant(_), L::Enum(layout)) => bail!(
+            "values of type {} cannot be written in a Move test",
+            struct_name(&layout.type_)
+        ),
+        _ => bail!("value {} does not match its layout", value),
+    })
+}
+
+fn bytes_of(elems: &[A::MoveValue]) -> Result<Vec<u8>> {
+    elems
+        .iter()
+        .map(|elem| match elem {
+            A::MoveValue::U8(byte) => Ok(*byte),
+            _ => Err(anyhow!("expected a byte, found {}", elem)),
+        })
+        .collect()
+}
+
+/// The bytes of a string struct as a byte string literal
+fn string_bytes(s: &A::MoveStruct) -> Result<String> {
+    match &s.fields[..] {
+        [(_, A::MoveValue::Vector(bytes))] => Ok(format!("x\"{}\"", hex::encode(bytes_of(bytes)?))),
+        _ => bail!("invalid string value {}", s.type_),
+    }
+}
+
+fn type_name(layout: &A::MoveTypeLayout) -> String {
+    use A::MoveTypeLayout as L;
+    match layout {
+        L::Bool => "bool".to_string(),
+        L::U8 => "u8".to_string(),
+        L::U16 => "u16".to_string(),
+        L::U32 => "u32".to_string(),
+        L::U64 => "u64".to_string(),
+        L::U128 => "u128".to_string(),
+        L::U256 => "u256".to_string(),
+        L::Address => "address".to_string(),
+        L::Signer => "signer".to_string(),
+        L::Vector(elem) => format!("vector<{}>", type_name(elem)),
+        L::Struct(layout) => struct_name(&layout.type_),
+        L::Enum(layout) => struct_name(&layout.type_),
+    }
+}
+
+fn tag_name(tag: &TypeTag) -> String {
+    match tag {
+        TypeTag::Vector(elem) => format!("vector<{}>", tag_name(elem)),
+        TypeTag::Struct(tag) => struct_name(tag),
+        _ => tag.to_string(),
+    }
+}
+
+fn struct_name(tag: &StructTag) -> String {
+    let mut name = format!(
+        "{}::{}::{}",
+        tag.address.to_hex_literal(),
+        tag.module,
+        tag.name
+    );
+    if !tag.type_params.is_empty() {
+        let params: Vec<_> = tag.type_params.iter().map(tag_name).collect();
+        name.push_str(&format!("<{}>", params.join(", ")));
+    }
+    name
+}
+
+fn is_std_type(tag: &StructTag, module: &str, name: &str) -> bool {
+    tag.address == AccountAddress::ONE && tag.module.as_str() == module && tag.name.as_str() == name
+}
@@ -0,0 +1,402 @@
+use anyhow::{anyhow, bail, Result};
+use arbitrary::Unstructured;
+use move_binary_format::{
+    file_format::{FunctionHandleIndex, SignatureToken, Visibility},
+    CompiledModule,
+};
+use move_bytecode_utils::{layout::TypeLayoutBuilder, module_cache::GetModule};
+use move_core_types::{
+    annotated_value as A,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
+use move_vm_runtime::session::SerializedReturnValues;
+use std::fmt;
+
+use crate::run_move::generator::{arbitrary_value, type_tag};
+
+/// Maximum number of calls decoded from a single input
+pub const MAX_SEQUENCE_LENGTH: usize = 16;
+
+///
+/// Decodes the input provided by libFuzzer into a sequence of calls to the public and entry
+/// functions of a module.
+///
+/// Values returned by a call, whose type has `key` or `store`, are kept in a `ValuePool` and can
+/// be passed, by value or by reference, to the following calls. Arguments of types with `key`
+/// are only taken from the pool, so objects are always created by the module under test. Every
+/// other argument is either taken from the pool or generated as done by `ArgumentGenerator`.
+///
+/// Generic functions, functions taking a signer and functions returning references are not
+/// called.
+///
+#[derive(Debug, Clone)]
+pub struct SequenceGenerator {
+    module: ModuleId,
+    functions: Vec<SequenceFunction>,
+}
+
+#[derive(Debug, Clone)]
+struct SequenceFunction {
+    name: Identifier,
+    parameters: Vec<Parameter>,
+    /// Types of the returned values, `None` for the ones that are not kept in the pool
+    returns: Vec<Option<TypeTag>>,
+}
+
+#[derive(Debug, Clone)]
+struct Parameter {
+    passing: Passing,
+    tag: TypeTag,
+    /// Whether values passed to the parameter are copied instead of moved out of the pool
+    copy: bool,
+    /// Layout of the generated arguments, `None` if arguments only come from the pool
+    layout: Option<A::MoveTypeLayout>,
+}
+
+/// How an argument is passed to the called function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Passing {
+    /// By value
+    Value,
+    /// By immutable reference
+    Ref,
+    /// By mutable reference
+    MutRef,
+}
+
+/// The values produced by the calls of a sequence, identified by their position
+#[derive(Debug, Default)]
+pub struct ValuePool {
+    /// Values moved into a call are replaced by `None`
+    values: Vec<Option<(TypeTag, Vec<u8>)>>,
+}
+
+/// A call of a sequence
+#[derive(Debug, Clone)]
+pub struct Call {
+    /// Called function
+    pub function: Identifier,
+    /// Arguments of the call
+    pub args: Vec<Argument>,
+    /// Pool ids of the returned values, `None` for the ones that are not kept in the pool
+    pub returns: Vec<Option<usize>>,
+    index: usize,
+}
+
+/// An argument of a call
+#[derive(Debug, Clone)]
+pub struct Argument {
+    /// How the argument is passed
+    pub passing: Passing,
+    /// Where the argument comes from
+    pub source: ArgumentSource,
+}
+
+/// Origin of an argument
+#[derive(Debug, Clone)]
+pub enum ArgumentSource {
+    /// Generated from the fuzzer input, serialized with BCS
+    Generated(Vec<u8>, A::MoveTypeLayout),
+    /// Returned by an earlier call, identified by its pool id
+    Pooled(usize),
+}
+
+/// The calls decoded from a fuzzer input, displayed as a readable call sequence
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    /// Module of the called functions
+    pub module: ModuleId,
+    /// Executed calls, in order
+    pub calls: Vec<Call>,
+}
+
+impl SequenceGenerator {
+    /// Builds the generator of sequences of calls to the functions of `module`. Structs in the
+    /// signatures are resolved with `resolver`.
+    pub fn new(module: &CompiledModule, resolver: &impl GetModule) -> Result<Self> {
+        let functions: Vec<_> = module
+            .function_defs()
+            .iter()
+            .filter(|def| def.visibility == Visibility::Public || def.is_entry)
+            .filter_map(|def| SequenceFunction::new(module, def.function, resolver).ok())
+            .collect();
+        if functions.is_empty() {
+            bail!(
+                "module {} has no public function that can be fuzzed",
+                module.self_id()
+            );
+        }
+        Ok(Self {
+            module: module.self_id(),
+            functions,
+        })
+    }
+
+    /// Module of the called functions
+    pub fn module(&self) -> &ModuleId {
+        &self.module
+    }
+
+    ///
+    /// Reads the next call of the sequence from `u`, returning it together with its serialized
+    /// arguments. Values moved into the call are removed from `pool`.
+    ///
+    /// Returns `None` when no function can be called with the values in `pool`.
+    ///
+    pub fn next_call(
+        &self,
+        pool: &mut ValuePool,
+        u: &mut Unstructured,
+    ) -> arbitrary::Result<Option<(Call, Vec<Vec<u8>>)>> {
+        let callable: Vec<_> = self
+            .functions
+            .iter()
+            .enumerate()
+            .filter(|(_, function)| {
+                function
+                    .parameters
+                    .iter()
+                    .all(|param| param.layout.is_some() || pool.contains(&param.tag))
+            })
+            .collect();
+        if callable.is_empty() {
+            return Ok(None);
+        }
+        let (index, function) = *u.choose(&callable)?;
+
+        let mut args = Vec::with_capacity(function.parameters.len());
+        let mut bytes = Vec::with_capacity(function.parameters.len());
+        for param in &function.parameters {
+            let candidates = pool.candidates(&param.tag);
+            let from_pool =
+                !candidates.is_empty() && (param.layout.is_none() || u.arbitrary::<bool>()?);
+            let (source, arg) = if from_pool {
+                let id = *u.choose(&candidates)?;
+                let arg = if param.passing == Passing::Value && !param.copy {
+                    pool.take(id)
+                } else {
+                    pool.get(id)
+                };
+                (ArgumentSource::Pooled(id), arg)
+            } else if let Some(layout) = &param.layout {
+                let arg = arbitrary_value(layout, u)?
+                    .simple_serialize()
+                    .ok_or(arbitrary::Error::IncorrectFormat)?;
+                (ArgumentSource::Generated(arg.clone(), layout.clone()), arg)
+            } else {
+                // The only candidate was moved into an earlier argument of this call
+                return Ok(None);
+            };
+            args.push(Argument {
+                passing: param.passing,
+                source,
+            });
+            bytes.push(arg);
+        }
+
+        let call = Call {
+            function: function.name.clone(),
+            args,
+            returns: vec![],
+            index,
+        };
+        Ok(Some((call, bytes)))
+    }
+
+    /// Updates `pool` with the outputs of a successful `call`
+    pub fn complete_call(
+        &self,
+        call: &mut Call,
+        pool: &mut ValuePool,
+        outputs: SerializedReturnValues,
+    ) {
+        for (local, bytes, _) in outputs.mutable_reference_outputs {
+            if let Some(ArgumentSource::Pooled(id)) =
+                call.args.get(local as usize).map(|arg| &arg.source)
+            {
+                pool.set(*id, bytes);
+            }
+        }
+        let returns = &self.functions[call.index].returns;
+        call.returns = outputs
+            .return_values
+            .into_iter()
+            .zip(returns)
+            .map(|((bytes, _), tag)| tag.as_ref().map(|tag| pool.push(tag.clone(), bytes)))
+            .collect();
+    }
+}
+
+impl SequenceFunction {
+    fn new(
+        module: &CompiledModule,
+        idx: FunctionHandleIndex,
+        resolver: &impl GetModule,
+    ) -> Result<Self> {
+        let handle = module.function_handle_at(idx);
+        if !handle.type_parameters.is_empty() {
+            bail!("generic functions are not supported");
+        }
+        let parameters = module
+            .signature_at(handle.parameters)
+            .0
+            .iter()
+            .map(|token| Parameter::new(module, token, resolver))
+            .collect::<Result<_>>()?;
+        let returns = module
+            .signature_at(handle.return_)
+            .0
+            .iter()
+            .map(|token| {
+                if matches!(
+                    token,
+                    SignatureToken::Reference(_) | SignatureToken::MutableReference(_)
+                ) {
+                    bail!("functions returning references are not supported");
+                }
+                let abilities = module
+                    .abilities(token, &[])
+                    .map_err(|e| anyhow!("{:?}", e))?;
+                Ok((abilities.has_key() || abilities.has_store())
+                    .then(|| type_tag(module, token))
+                    .transpose()?)
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            name: module.identifier_at(handle.name).to_owned(),
+            parameters,
+            returns,
+        })
+    }
+}
+
+impl Parameter {
+    fn new(
+        module: &CompiledModule,
+        token: &SignatureToken,
+        resolver: &impl GetModule,
+    ) -> Result<Self> {
+        let (passing, inner) = match token {
+            SignatureToken::Reference(inner) => (Passing::Ref, &**inner),
+            SignatureToken::MutableReference(inner) => (Passing::MutRef, &**inner),
+            _ => (Passing::Value, token),
+        };
+        let tag = type_tag(module, inner)?;
+        let abilities = module
+            .abilities(inner, &[])
+            .map_err(|e| anyhow!("{:?}", e))?;
+        let layout = if abilities.has_key() {
+            None
+        } else {
+            Some(TypeLayoutBuilder::build_with_types(&tag, resolver)?)
+        };
+        Ok(Self {
+            passing,
+            tag,
+            copy: abilities.has_copy(),
+            layout,
+        })
+    }
+}
+
+impl ValuePool {
+    fn push(&mut self, tag: TypeTag, bytes: Vec<u8>) -> usize {
+        self.values.push(Some((tag, bytes)));
+        self.values.len() - 1
+    }
+
+    fn contains(&self, tag: &TypeTag) -> bool {
+        self.values.iter().flatten().any(|(t, _)| t == tag)
+    }
+
+    /// Ids of the values of type `tag`
+    fn candidates(&self, tag: &TypeTag) -> Vec<usize> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| matches!(value, Some((t, _)) if t == tag))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    fn get(&self, id: usize) -> Vec<u8> {
+        self.values[id]
+            .as_ref()
+            .map(|(_, bytes)| bytes.clone())
+            .expect("pooled values are only read while available")
+    }
+
+    fn take(&mut self, id: usize) -> Vec<u8> {
+        self.values[id]
+            .take()
+            .map(|(_, bytes)| bytes)
+            .expect("pooled values are only moved while available")
+    }
+
+    fn set(&mut self, id: usize, bytes: Vec<u8>) {
+        if let Some((_, value)) = &mut self.values[id] {
+            *value = bytes;
+        }
+    }
+}
+
+impl Sequence {
+    /// An empty sequence of calls to the functions of `module`
+    pub fn new(module: ModuleId) -> Self {
+        Self {
+            module,
+            calls: vec![],
+        }
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for call in &self.calls {
+            writeln!(f, "{}::{}", self.module, call)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.function)?;
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", arg)?;
+        }
+        write!(f, ")")?;
+        if !self.returns.is_empty() {
+            let returns: Vec<_> = self
+                .returns
+                .iter()
+                .map(|id| id.map_or_else(|| "_".to_string(), |id| format!("v{}", id)))
+                .collect();
+            write!(f, " -> {}", returns.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Argument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.passing {
+            Passing::Value => (),
+            Passing::Ref => write!(f, "&")?,
+            Passing::MutRef => write!(f, "&mut ")?,
+        }
+        match &self.source {
+            ArgumentSource::Generated(bytes, layout) => {
+                match A::MoveValue::simple_deserialize(bytes, layout) {
+                    Ok(value) => write!(f, "{}", value),
+                    Err(_) => write!(f, "{:?}", bytes),
+                }
+            }
+            ArgumentSource::Pooled(id) => write!(f, "v{}", id),
+        }
+    }
+}
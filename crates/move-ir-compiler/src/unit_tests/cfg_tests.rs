@@ -17,7 +17,7 @@ fn cfg_compile_script_ret() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 1);
     assert_eq!(cfg.num_blocks(), 1);
@@ -39,7 +39,7 @@ fn cfg_compile_script_let() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 1);
     assert_eq!(cfg.num_blocks(), 1);
     assert_eq!(cfg.reachable_from(0).len(), 1);
@@ -63,7 +63,7 @@ fn cfg_compile_if() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -90,7 +90,7 @@ fn cfg_compile_if_else() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -113,7 +113,7 @@ fn cfg_compile_if_else_with_else_return() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -144,7 +144,7 @@ fn cfg_compile_nested_if() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 7);
     assert_eq!(cfg.num_blocks(), 7);
     assert_eq!(cfg.reachable_from(8).len(), 3);
@@ -167,7 +167,7 @@ fn cfg_compile_if_else_with_if_return() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 4);
@@ -190,7 +190,7 @@ fn cfg_compile_if_else_with_two_returns() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
     assert_eq!(cfg.reachable_from(0).len(), 3);
@@ -216,7 +216,7 @@ fn cfg_compile_if_else_with_else_abort() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
@@ -240,7 +240,7 @@ fn cfg_compile_if_else_with_if_abort() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
@@ -264,7 +264,7 @@ fn cfg_compile_if_else_with_two_aborts() {
         } }
         ";
     let code = compile_module_with_single_function(text);
-    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code);
+    let cfg: VMControlFlowGraph = VMControlFlowGraph::new(&code, &[]);
     cfg.display();
     assert_eq!(cfg.blocks().len(), 4);
     assert_eq!(cfg.num_blocks(), 4);
@@ -1682,6 +1682,7 @@ fn parse_module(tokens: &mut Lexer) -> Result<ModuleDefinition, ParseError<Loc,
         vec![],
         structs,
         vec![],
+        vec![],
         functions,
     ))
 }
@@ -7,10 +7,11 @@ use anyhow::{bail, format_err, Result};
 use move_binary_format::{
     file_format::{
         Ability, AbilitySet, Bytecode, CodeOffset, CodeUnit, CompiledModule, Constant,
-        ConstantPoolIndex, FieldDefinition, FunctionDefinition, FunctionSignature, ModuleHandle,
-        Signature, SignatureToken, StructDefinition, StructDefinitionIndex, StructFieldInformation,
-        StructHandleIndex, StructTypeParameter, TableIndex, TypeParameterIndex, TypeSignature,
-        Visibility,
+        ConstantPoolIndex, EnumDefinition, FieldDefinition, FunctionDefinition, FunctionSignature,
+        ModuleHandle, Signature, SignatureToken, StructDefinition, StructDefinitionIndex,
+        StructFieldInformation, StructHandleIndex, StructTypeParameter, TableIndex,
+        TypeParameterIndex, TypeSignature, VariantDefinition, VariantJumpTable,
+        VariantJumpTableIndex, VariantTag, Visibility,
     },
     file_format_common::VERSION_MAX,
 };
@@ -80,6 +81,27 @@ macro_rules! record_src_loc {
             .source_map
             .add_top_level_struct_mapping($context.current_struct_definition_index(), $location)?;
     };
+    (enum_type_formals: $context:expr, $var:expr) => {
+        for (_, ty_var, _) in $var.iter() {
+            let source_name = (ty_var.value.0.as_str().to_owned(), ty_var.loc);
+            $context.source_map.add_enum_type_parameter_mapping(
+                $context.current_enum_definition_index(),
+                source_name,
+            )?;
+        }
+    };
+    (enum_decl: $context:expr, $location:expr) => {
+        $context
+            .source_map
+            .add_top_level_enum_mapping($context.current_enum_definition_index(), $location)?;
+    };
+    (variant: $context:expr, $idx:expr, $variant:expr) => {{
+        let source_name = ($variant.value.name.0.as_str().to_owned(), $variant.loc);
+        let field_locs = $variant.value.fields.iter().map(|(f, _)| f.loc).collect();
+        $context
+            .source_map
+            .add_enum_variant_mapping($idx, source_name, field_locs)?;
+    }};
     (const_decl: $context:expr, $const_index:expr, $name:expr) => {
         $context.source_map.add_const_mapping($const_index, $name)?;
     };
@@ -129,6 +151,9 @@ struct FunctionFrame {
     max_stack_depth: i64,
     cur_stack_depth: i64,
     type_parameters: HashMap<TypeVar_, TypeParameterIndex>,
+    // Jump tables of the `VariantSwitch` instructions, with offsets given as label indices until
+    // the labels are remapped to their actual offsets
+    jump_tables: Vec<VariantJumpTable>,
 }
 
 impl FunctionFrame {
@@ -139,6 +164,7 @@ impl FunctionFrame {
             max_stack_depth: 0,
             cur_stack_depth: 0,
             type_parameters,
+            jump_tables: vec![],
         }
     }
 
@@ -363,6 +389,17 @@ pub fn compile_module<'a>(
         context.declare_struct_handle_index(ident, abilities, type_parameters)?;
     }
 
+    // Enums are declared in the same way, they share the struct handle pool
+    for e in &module.enums {
+        let abilities = abilities(&e.value.abilities);
+        let ident = QualifiedStructIdent {
+            module: self_name,
+            name: e.value.name.clone(),
+        };
+        let type_parameters = struct_type_parameters(&e.value.type_formals);
+        context.declare_struct_handle_index(ident, abilities, type_parameters)?;
+    }
+
     for ir_constant in module.constants {
         // If the constant is an error constant in the source, then add the error constant's name
         // look up the constant's name, as a constant valeu -- this may be present already,
@@ -387,6 +424,7 @@ pub fn compile_module<'a>(
 
     // Compile definitions
     let struct_defs = compile_structs(&mut context, &self_name, module.structs)?;
+    let enum_defs = compile_enums(&mut context, &self_name, module.enums)?;
     let function_defs = compile_functions(&mut context, &self_name, module.functions)?;
 
     let (
@@ -402,6 +440,9 @@ pub fn compile_module<'a>(
             function_instantiations,
             struct_def_instantiations,
             field_instantiations,
+            enum_def_instantiations,
+            variant_handles,
+            variant_instantiation_handles,
         },
         _compiled_deps,
         source_map,
@@ -424,6 +465,10 @@ pub fn compile_module<'a>(
         metadata: vec![],
         struct_defs,
         function_defs,
+        enum_defs,
+        enum_def_instantiations,
+        variant_handles,
+        variant_instantiation_handles,
     };
     Ok((module, source_map))
 }
@@ -481,6 +526,9 @@ fn compile_explicit_dependency_declarations(
                 function_instantiations,
                 struct_def_instantiations,
                 field_instantiations,
+                enum_def_instantiations: _,
+                variant_handles: _,
+                variant_instantiation_handles: _,
             },
             compiled_deps,
             _source_map,
@@ -503,6 +551,10 @@ fn compile_explicit_dependency_declarations(
             metadata: vec![],
             struct_defs: vec![],
             function_defs: vec![],
+            enum_defs: vec![],
+            enum_def_instantiations: vec![],
+            variant_handles: vec![],
+            variant_instantiation_handles: vec![],
         };
         dependencies_acc = compiled_deps;
         dependencies_acc.insert(
@@ -706,6 +758,49 @@ fn compile_fields(
     })
 }
 
+fn compile_enums(
+    context: &mut Context,
+    self_name: &ModuleName,
+    enums: Vec<ast::EnumDefinition>,
+) -> Result<Vec<EnumDefinition>> {
+    let mut enum_defs = vec![];
+    for e in enums {
+        let eident = QualifiedStructIdent {
+            module: *self_name,
+            name: e.value.name.clone(),
+        };
+        let eh_idx = context.struct_handle_index(eident)?;
+        record_src_loc!(enum_decl: context, e.loc);
+        record_src_loc!(enum_type_formals: context, &e.value.type_formals);
+        let m = type_parameter_indexes(e.value.type_formals.iter().map(|formal| &formal.1))?;
+        let ed_idx = context.declare_enum_definition_index(e.value.name.clone())?;
+        let mut variants = vec![];
+        for (tag, variant) in e.value.variants.into_iter().enumerate() {
+            record_src_loc!(variant: context, ed_idx, variant);
+            let variant_name = context.identifier_index(variant.value.name.0)?;
+            let mut fields = vec![];
+            for (f, ty) in variant.value.fields {
+                let name = context.identifier_index(f.value.0)?;
+                let sig_token = compile_type(context, &m, &ty)?;
+                fields.push(FieldDefinition {
+                    name,
+                    signature: TypeSignature(sig_token),
+                });
+            }
+            context.declare_variant(e.value.name.clone(), variant.value.name, tag as VariantTag);
+            variants.push(VariantDefinition {
+                variant_name,
+                fields,
+            });
+        }
+        enum_defs.push(EnumDefinition {
+            enum_handle: eh_idx,
+            variants,
+        });
+    }
+    Ok(enum_defs)
+}
+
 fn compile_functions(
     context: &mut Context,
     self_name: &ModuleName,
@@ -830,6 +925,7 @@ fn compile_function_body(
     Ok(CodeUnit {
         locals: context.signature_index(locals_signature)?,
         code: compile_blocks(context, &mut function_frame, blocks)?,
+        jump_tables: vec![],
     })
 }
 
@@ -1466,9 +1562,12 @@ fn compile_function_body_bytecode(
     }
     let fake_to_actual = context.build_index_remapping(label_to_index);
     remap_branch_offsets(&mut code, &fake_to_actual);
+    let mut jump_tables = std::mem::take(&mut function_frame.jump_tables);
+    remap_jump_table_offsets(&mut jump_tables, &fake_to_actual);
     Ok(CodeUnit {
         locals: sig_idx,
         code,
+        jump_tables,
     })
 }
 
@@ -1570,6 +1669,99 @@ fn compile_bytecode(
                 Bytecode::UnpackGeneric(si_idx)
             }
         }
+        IRBytecode_::PackVariant(n, v, tys) => {
+            let def_idx = context.enum_definition_index(&n)?;
+            let tag = context.variant_tag(&n, &v)?;
+            if tys.is_empty() {
+                Bytecode::PackVariant(context.variant_handle_index(def_idx, tag)?)
+            } else {
+                let tokens = Signature(compile_types(
+                    context,
+                    function_frame.type_parameters(),
+                    &tys,
+                )?);
+                let type_actuals_id = context.signature_index(tokens)?;
+                let ei_idx = context.enum_instantiation_index(def_idx, type_actuals_id)?;
+                Bytecode::PackVariantGeneric(
+                    context.variant_instantiation_handle_index(ei_idx, tag)?,
+                )
+            }
+        }
+        IRBytecode_::UnpackVariant(n, v, tys) => {
+            let def_idx = context.enum_definition_index(&n)?;
+            let tag = context.variant_tag(&n, &v)?;
+            if tys.is_empty() {
+                Bytecode::UnpackVariant(context.variant_handle_index(def_idx, tag)?)
+            } else {
+                let tokens = Signature(compile_types(
+                    context,
+                    function_frame.type_parameters(),
+                    &tys,
+                )?);
+                let type_actuals_id = context.signature_index(tokens)?;
+                let ei_idx = context.enum_instantiation_index(def_idx, type_actuals_id)?;
+                Bytecode::UnpackVariantGeneric(
+                    context.variant_instantiation_handle_index(ei_idx, tag)?,
+                )
+            }
+        }
+        IRBytecode_::UnpackVariantImmRef(n, v, tys) => {
+            let def_idx = context.enum_definition_index(&n)?;
+            let tag = context.variant_tag(&n, &v)?;
+            if tys.is_empty() {
+                Bytecode::UnpackVariantImmRef(context.variant_handle_index(def_idx, tag)?)
+            } else {
+                let tokens = Signature(compile_types(
+                    context,
+                    function_frame.type_parameters(),
+                    &tys,
+                )?);
+                let type_actuals_id = context.signature_index(tokens)?;
+                let ei_idx = context.enum_instantiation_index(def_idx, type_actuals_id)?;
+                Bytecode::UnpackVariantGenericImmRef(
+                    context.variant_instantiation_handle_index(ei_idx, tag)?,
+                )
+            }
+        }
+        IRBytecode_::UnpackVariantMutRef(n, v, tys) => {
+            let def_idx = context.enum_definition_index(&n)?;
+            let tag = context.variant_tag(&n, &v)?;
+            if tys.is_empty() {
+                Bytecode::UnpackVariantMutRef(context.variant_handle_index(def_idx, tag)?)
+            } else {
+                let tokens = Signature(compile_types(
+                    context,
+                    function_frame.type_parameters(),
+                    &tys,
+                )?);
+                let type_actuals_id = context.signature_index(tokens)?;
+                let ei_idx = context.enum_instantiation_index(def_idx, type_actuals_id)?;
+                Bytecode::UnpackVariantGenericMutRef(
+                    context.variant_instantiation_handle_index(ei_idx, tag)?,
+                )
+            }
+        }
+        IRBytecode_::VariantSwitch(n, arms) => {
+            let head_enum = context.enum_definition_index(&n)?;
+            let mut offsets = vec![None; arms.len()];
+            for (v, lbl) in arms {
+                let tag = context.variant_tag(&n, &v)?;
+                match offsets.get_mut(tag as usize) {
+                    Some(offset @ None) => *offset = Some(context.label_index(lbl)?),
+                    _ => bail!("Invalid variant switch on {}: unexpected variant {}", n, v),
+                }
+            }
+            let jump_table = offsets.into_iter().map(Option::unwrap).collect();
+            let jt_idx = function_frame.jump_tables.len();
+            if jt_idx > TABLE_MAX_SIZE {
+                bail!("too many jump tables")
+            }
+            function_frame.jump_tables.push(VariantJumpTable {
+                head_enum,
+                jump_table,
+            });
+            Bytecode::VariantSwitch(VariantJumpTableIndex(jt_idx as TableIndex))
+        }
         IRBytecode_::ReadRef => Bytecode::ReadRef,
         IRBytecode_::WriteRef => Bytecode::WriteRef,
         IRBytecode_::FreezeRef => Bytecode::FreezeRef,
@@ -1709,6 +1901,17 @@ fn compile_bytecode(
     Ok(())
 }
 
+fn remap_jump_table_offsets(
+    jump_tables: &mut [VariantJumpTable],
+    fake_to_actual: &HashMap<u16, u16>,
+) {
+    for jump_table in jump_tables {
+        for offset in &mut jump_table.jump_table {
+            *offset = fake_to_actual[offset]
+        }
+    }
+}
+
 fn remap_branch_offsets(code: &mut Vec<Bytecode>, fake_to_actual: &HashMap<u16, u16>) {
     for instr in code {
         match instr {
@@ -60,6 +60,8 @@ pub struct ModuleDefinition {
     pub explicit_dependency_declarations: Vec<ModuleDependency>,
     /// the structs (including resources) that the module defines
     pub structs: Vec<StructDefinition>,
+    /// the enums that the module defines
+    pub enums: Vec<EnumDefinition>,
     /// the constants that the script defines. Only a utility, the identifiers are not carried into
     /// the Move bytecode
     pub constants: Vec<Constant>,
@@ -228,6 +230,36 @@ pub struct StructDefinition_ {
 /// The type of a StructDefinition along with its source location information
 pub type StructDefinition = Spanned<StructDefinition_>;
 
+/// Newtype for the name of a variant of an enum
+#[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct VariantName(pub Symbol);
+
+/// A Move enum
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumDefinition_ {
+    /// The declared abilities for the enum
+    pub abilities: BTreeSet<Ability>,
+    /// Human-readable name for the enum that also serves as a nominal type
+    pub name: StructName,
+    /// The list of formal type arguments
+    pub type_formals: Vec<StructTypeParameter>,
+    /// the variants of the enum, in declaration order
+    pub variants: Vec<VariantDefinition>,
+}
+/// The type of an EnumDefinition along with its source location information
+pub type EnumDefinition = Spanned<EnumDefinition_>;
+
+/// A variant of a Move enum
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantDefinition_ {
+    /// The name of the variant
+    pub name: VariantName,
+    /// the fields each instance of the variant has, possibly empty
+    pub fields: Fields<Type>,
+}
+/// The type of a VariantDefinition along with its source location information
+pub type VariantDefinition = Spanned<VariantDefinition_>;
+
 /// An explicit struct dependency
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StructDependency {
@@ -626,6 +658,11 @@ pub enum Bytecode_ {
     Call(ModuleName, FunctionName, Vec<Type>),
     Pack(StructName, Vec<Type>),
     Unpack(StructName, Vec<Type>),
+    PackVariant(StructName, VariantName, Vec<Type>),
+    UnpackVariant(StructName, VariantName, Vec<Type>),
+    UnpackVariantImmRef(StructName, VariantName, Vec<Type>),
+    UnpackVariantMutRef(StructName, VariantName, Vec<Type>),
+    VariantSwitch(StructName, Vec<(VariantName, BlockLabel_)>),
     ReadRef,
     WriteRef,
     FreezeRef,
@@ -722,7 +759,7 @@ impl ModuleIdent {
 
 impl ModuleDefinition {
     /// Creates a new `ModuleDefinition` from its string name, dependencies, structs+resources,
-    /// and procedures
+    /// enums and procedures
     /// Does not verify the correctness of any internal properties of its elements
     pub fn new(
         loc: Loc,
@@ -731,6 +768,7 @@ impl ModuleDefinition {
         imports: Vec<ImportDefinition>,
         explicit_dependency_declarations: Vec<ModuleDependency>,
         structs: Vec<StructDefinition>,
+        enums: Vec<EnumDefinition>,
         constants: Vec<Constant>,
         functions: Vec<(FunctionName, Function)>,
     ) -> Self {
@@ -741,6 +779,7 @@ impl ModuleDefinition {
             imports,
             explicit_dependency_declarations,
             structs,
+            enums,
             constants,
             functions,
         }
@@ -1084,6 +1123,12 @@ impl fmt::Display for ModuleDefinition {
         }
         writeln!(f, ")")?;
 
+        writeln!(f, "Enums(")?;
+        for enum_def in &self.enums {
+            writeln!(f, "{}, ", enum_def)?;
+        }
+        writeln!(f, ")")?;
+
         writeln!(f, "Constants(")?;
         for constant in &self.constants {
             writeln!(f, "{};", constant)?;
@@ -1157,6 +1202,26 @@ impl fmt::Display for StructDefinition_ {
     }
 }
 
+impl fmt::Display for EnumDefinition_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Enum({}{}, ",
+            self.name,
+            format_struct_type_formals(&self.type_formals)
+        )?;
+        for variant in &self.variants {
+            writeln!(
+                f,
+                "{}{}",
+                variant.value.name,
+                format_fields(&variant.value.fields)
+            )?;
+        }
+        write!(f, ")")
+    }
+}
+
 impl fmt::Display for Constant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -1193,6 +1258,12 @@ impl fmt::Display for StructName {
     }
 }
 
+impl fmt::Display for VariantName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl fmt::Display for FunctionName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.0)
@@ -1581,6 +1652,35 @@ impl fmt::Display for Bytecode_ {
             Bytecode_::Call(m, n, tys) => write!(f, "Call {}.{}{}", m, n, format_type_actuals(tys)),
             Bytecode_::Pack(n, tys) => write!(f, "Pack {}{}", n, format_type_actuals(tys)),
             Bytecode_::Unpack(n, tys) => write!(f, "Unpack {}{}", n, format_type_actuals(tys)),
+            Bytecode_::PackVariant(n, v, tys) => {
+                write!(f, "PackVariant {}{}::{}", n, format_type_actuals(tys), v)
+            }
+            Bytecode_::UnpackVariant(n, v, tys) => {
+                write!(f, "UnpackVariant {}{}::{}", n, format_type_actuals(tys), v)
+            }
+            Bytecode_::UnpackVariantImmRef(n, v, tys) => write!(
+                f,
+                "UnpackVariantImmRef {}{}::{}",
+                n,
+                format_type_actuals(tys),
+                v
+            ),
+            Bytecode_::UnpackVariantMutRef(n, v, tys) => write!(
+                f,
+                "UnpackVariantMutRef {}{}::{}",
+                n,
+                format_type_actuals(tys),
+                v
+            ),
+            Bytecode_::VariantSwitch(n, arms) => write!(
+                f,
+                "VariantSwitch {} [{}]",
+                n,
+                arms.iter()
+                    .map(|(v, lbl)| format!("{} => {}", v, &lbl.0))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             Bytecode_::ReadRef => write!(f, "ReadRef"),
             Bytecode_::WriteRef => write!(f, "WriteRef"),
             Bytecode_::FreezeRef => write!(f, "FreezeRef"),
@@ -1662,7 +1762,7 @@ fn format_move_value(v: &MoveValue) -> String {
                 .join(", ");
             format!("vector[{}]", items)
         }
-        MoveValue::Struct(_) | MoveValue::Signer(_) => {
+        MoveValue::Struct(_) | MoveValue::Variant(_) | MoveValue::Signer(_) => {
             panic!("Should be inexpressible as a constant")
         }
         MoveValue::U16(u) => format!("{}u16", u),
@@ -15,7 +15,8 @@ use anyhow::Result;
 use move_compiler::{
     compiled_unit::AnnotatedCompiledUnit,
     diagnostics::{
-        report_diagnostics_to_buffer_with_env_color, report_warnings, FilesSourceText, Migration,
+        report_diagnostics_to_buffer_with_env_color, report_diagnostics_to_buffer_with_format,
+        report_diagnostics_with_format, Diagnostics, FilesSourceText, Migration,
     },
     editions::Edition,
     shared::PackagePaths,
@@ -89,7 +90,10 @@ impl BuildPlan {
 
     /// Compilation results in the process exit upon warning/failure
     pub fn compile<W: Write>(&self, writer: &mut W) -> Result<CompiledPackage> {
-        self.compile_with_driver(writer, |compiler| compiler.build_and_report())
+        let format = self.resolution_graph.build_options.diagnostics_format;
+        self.compile_with_cache(writer, |files, error_diags| {
+            report_diagnostics_with_format(files, error_diags, format)
+        })
     }
 
     /// Compilation results in the process exit upon warning/failure
@@ -133,23 +137,14 @@ impl BuildPlan {
 
     /// Compilation process does not exit even if warnings/failures are encountered
     pub fn compile_no_exit<W: Write>(&self, writer: &mut W) -> Result<CompiledPackage> {
-        self.compile_with_driver(writer, |compiler| {
-            let (files, units_res) = compiler.build()?;
-            match units_res {
-                Ok((units, warning_diags)) => {
-                    report_warnings(&files, warning_diags);
-                    Ok((files, units))
-                }
-                Err(error_diags) => {
-                    assert!(!error_diags.is_empty());
-                    let diags_buf =
-                        report_diagnostics_to_buffer_with_env_color(&files, error_diags);
-                    if let Err(err) = std::io::stdout().write_all(&diags_buf) {
-                        anyhow::bail!("Cannot output compiler diagnostics: {}", err);
-                    }
-                    anyhow::bail!("Compilation error");
-                }
+        let format = self.resolution_graph.build_options.diagnostics_format;
+        self.compile_with_cache(writer, |files, error_diags| {
+            assert!(!error_diags.is_empty());
+            let diags_buf = report_diagnostics_to_buffer_with_format(files, error_diags, format);
+            if let Err(err) = std::io::stdout().write_all(&diags_buf) {
+                return anyhow::anyhow!("Cannot output compiler diagnostics: {}", err);
             }
+            anyhow::anyhow!("Compilation error")
         })
     }
 
@@ -250,6 +245,37 @@ impl BuildPlan {
         Ok(compiled)
     }
 
+    /// Compiles the package, reusing the modules of source files that are unaffected by the
+    /// changes since the previous build. Warnings are reported as they are found, and errors are
+    /// handed to `report_errors`.
+    fn compile_with_cache<W: Write>(
+        &self,
+        writer: &mut W,
+        report_errors: impl FnOnce(&FilesSourceText, Diagnostics) -> anyhow::Error,
+    ) -> Result<CompiledPackage> {
+        let CompilationDependencies {
+            root_package,
+            project_root,
+            transitive_dependencies,
+        } = self.compute_dependencies();
+
+        let compiled = CompiledPackage::build_all_with_cache(
+            writer,
+            self.compiler_vfs_root.clone(),
+            &project_root,
+            root_package,
+            transitive_dependencies,
+            &self.resolution_graph,
+            report_errors,
+        )?;
+
+        Self::clean(
+            &project_root.join(CompiledPackageLayout::Root.path()),
+            self.sorted_deps.iter().copied().collect(),
+        )?;
+        Ok(compiled)
+    }
+
     // Clean out old packages that are no longer used, or no longer used under the current
     // compilation flags
     fn clean(build_root: &Path, keep_paths: BTreeSet<PackageName>) -> Result<()> {
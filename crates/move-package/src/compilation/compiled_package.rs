@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    compilation::package_layout::CompiledPackageLayout,
+    compilation::{
+        incremental::{CompilationCache, CompiledSources, SourceDigests},
+        package_layout::CompiledPackageLayout,
+    },
     resolution::resolution_graph::{Package, Renaming, ResolvedGraph, ResolvedTable},
     source_package::{
         layout::{SourcePackageLayout, REFERENCE_TEMPLATE_FILENAME},
@@ -26,7 +29,7 @@ use move_command_line_common::{
 };
 use move_compiler::{
     compiled_unit::{AnnotatedCompiledUnit, CompiledUnit, NamedCompiledModule},
-    diagnostics::FilesSourceText,
+    diagnostics::{Diagnostics, FilesSourceText},
     editions::Flavor,
     linters,
     shared::{NamedAddressMap, NumericalAddress, PackageConfig, PackagePaths},
@@ -294,22 +297,72 @@ impl OnDiskCompiledPackage {
         })
     }
 
-    fn save_compiled_unit(
-        &self,
-        package_name: Symbol,
-        compiled_unit: &CompiledUnitWithSource,
-    ) -> Result<()> {
+    /// Path of the artifacts for unit `unit_name` of `package_name`, relative to the directory of
+    /// each category of artifact.
+    fn compiled_unit_file(&self, package_name: Symbol, unit_name: Symbol) -> PathBuf {
         let root_package = self.package.compiled_package_info.package_name;
         assert!(self.root_path.ends_with(root_package.as_str()));
-        let category_dir = CompiledPackageLayout::CompiledModules.path();
-        let file_path = if root_package == package_name {
+        if root_package == package_name {
             PathBuf::new()
         } else {
             CompiledPackageLayout::Dependencies
                 .path()
                 .join(package_name.as_str())
         }
-        .join(compiled_unit.unit.name.as_str());
+        .join(unit_name.as_str())
+    }
+
+    /// Path of the bytecode saved for unit `unit_name` of `package_name`.
+    pub(crate) fn compiled_unit_bytecode_path(
+        &self,
+        package_name: Symbol,
+        unit_name: Symbol,
+    ) -> PathBuf {
+        self.root_path
+            .join(CompiledPackageLayout::CompiledModules.path())
+            .join(self.compiled_unit_file(package_name, unit_name))
+            .with_extension(MOVE_COMPILED_EXTENSION)
+    }
+
+    /// Loads the bytecode and source map saved for unit `unit_name` of `package_name`, which was
+    /// compiled from `source_path`.
+    pub(crate) fn load_compiled_unit(
+        &self,
+        package_name: Symbol,
+        unit_name: Symbol,
+        source_path: PathBuf,
+    ) -> Result<CompiledUnitWithSource> {
+        let bytecode_bytes =
+            std::fs::read(self.compiled_unit_bytecode_path(package_name, unit_name))?;
+        let source_map = source_map_from_file(
+            &self
+                .root_path
+                .join(CompiledPackageLayout::SourceMaps.path())
+                .join(self.compiled_unit_file(package_name, unit_name))
+                .with_extension(SOURCE_MAP_EXTENSION),
+        )?;
+        let module = CompiledModule::deserialize_with_defaults(&bytecode_bytes)?;
+        let address = NumericalAddress::new(
+            module.self_id().address().into_bytes(),
+            move_compiler::shared::NumberFormat::Hex,
+        );
+        let unit = NamedCompiledModule {
+            package_name: Some(package_name),
+            address,
+            name: unit_name,
+            module,
+            source_map,
+        };
+        Ok(CompiledUnitWithSource { unit, source_path })
+    }
+
+    fn save_compiled_unit(
+        &self,
+        package_name: Symbol,
+        compiled_unit: &CompiledUnitWithSource,
+    ) -> Result<()> {
+        let category_dir = CompiledPackageLayout::CompiledModules.path();
+        let file_path = self.compiled_unit_file(package_name, compiled_unit.unit.name);
 
         self.save_under(
             category_dir
@@ -442,6 +495,42 @@ impl CompiledPackage {
         resolution_graph: &ResolvedGraph,
         mut compiler_driver: impl FnMut(Compiler) -> Result<T>,
     ) -> Result<BuildResult<T>> {
+        let BuildResult {
+            root_package_name,
+            sources_package_paths,
+            immediate_dependencies,
+            deps_package_paths,
+            result: (),
+        } = Self::prepare_build(
+            w,
+            &resolved_package,
+            transitive_dependencies,
+            resolution_graph,
+        )?;
+
+        // Partition deps_package according whether src is available
+        let (src_deps, bytecode_deps) = partition_deps(deps_package_paths.clone());
+
+        // invoke the compiler
+        let mut paths = src_deps;
+        paths.push(sources_package_paths.clone());
+        let compiler = Self::make_compiler(vfs_root, paths, bytecode_deps, resolution_graph)?;
+        Ok(BuildResult {
+            root_package_name,
+            sources_package_paths,
+            immediate_dependencies,
+            deps_package_paths,
+            result: compiler_driver(compiler)?,
+        })
+    }
+
+    /// Announces the build and gathers the source and dependency paths to hand to the compiler.
+    fn prepare_build<W: Write>(
+        w: &mut W,
+        resolved_package: &Package,
+        transitive_dependencies: Vec<DependencyInfo>,
+        resolution_graph: &ResolvedGraph,
+    ) -> Result<BuildResult<()>> {
         let immediate_dependencies = transitive_dependencies
             .iter()
             .filter(|&dep| dep.is_immediate)
@@ -456,20 +545,14 @@ impl CompiledPackage {
         // gather source/dep files with their address mappings
         let (sources_package_paths, deps_package_paths) = make_source_and_deps_for_compiler(
             resolution_graph,
-            &resolved_package,
+            resolved_package,
             transitive_dependencies,
         )?;
-        let flags = resolution_graph.build_options.compiler_flags();
-        // Partition deps_package according whether src is available
-        let (src_deps, bytecode_deps): (Vec<_>, Vec<_>) = deps_package_paths
-            .clone()
-            .into_iter()
-            .partition_map(|(p, b)| match b {
-                ModuleFormat::Source => Either::Left(p),
-                ModuleFormat::Bytecode => Either::Right(p),
-            });
         // If bytecode dependency is not empty, do not allow renaming
-        if !bytecode_deps.is_empty() {
+        if deps_package_paths
+            .iter()
+            .any(|(_, format)| matches!(format, ModuleFormat::Bytecode))
+        {
             if let Some(pkg_name) = resolution_graph.contains_renaming() {
                 anyhow::bail!(
                     "Found address renaming in package '{}' when \
@@ -479,19 +562,30 @@ impl CompiledPackage {
             }
         }
 
-        // invoke the compiler
-        let mut paths = src_deps;
-        paths.push(sources_package_paths.clone());
+        Ok(BuildResult {
+            root_package_name,
+            sources_package_paths,
+            immediate_dependencies,
+            deps_package_paths,
+            result: (),
+        })
+    }
 
+    /// Sets up a compiler for `targets` with the flags and linters of this build.
+    pub(crate) fn make_compiler(
+        vfs_root: Option<VfsPath>,
+        targets: Vec<PackagePaths>,
+        deps: Vec<PackagePaths>,
+        resolution_graph: &ResolvedGraph,
+    ) -> Result<Compiler> {
+        let flags = resolution_graph.build_options.compiler_flags();
         let lint_level = resolution_graph.build_options.lint_flag.get();
         let sui_mode = resolution_graph
             .build_options
             .default_flavor
             .map_or(false, |f| f == Flavor::Sui);
 
-        let mut compiler = Compiler::from_package_paths(vfs_root, paths, bytecode_deps)
-            .unwrap()
-            .set_flags(flags);
+        let mut compiler = Compiler::from_package_paths(vfs_root, targets, deps)?.set_flags(flags);
         if sui_mode {
             let (filter_attr_name, filters) = sui_mode::linters::known_filters();
             compiler = compiler
@@ -499,16 +593,9 @@ impl CompiledPackage {
                 .add_visitors(sui_mode::linters::linter_visitors(lint_level))
         }
         let (filter_attr_name, filters) = linters::known_filters();
-        compiler = compiler
+        Ok(compiler
             .add_custom_known_filters(filter_attr_name, filters)
-            .add_visitors(linters::linter_visitors(lint_level));
-        Ok(BuildResult {
-            root_package_name,
-            sources_package_paths,
-            immediate_dependencies,
-            deps_package_paths,
-            result: compiler_driver(compiler)?,
-        })
+            .add_visitors(linters::linter_visitors(lint_level)))
     }
 
     pub(crate) fn build_for_result<W: Write, T>(
@@ -554,15 +641,134 @@ impl CompiledPackage {
             compiler_driver,
         )?;
         let (file_map, all_compiled_units) = result;
+        Self::assemble(
+            project_root,
+            resolved_package,
+            resolution_graph,
+            BuildResult {
+                root_package_name,
+                sources_package_paths,
+                immediate_dependencies,
+                deps_package_paths,
+                result: units_with_source(&file_map, all_compiled_units),
+            },
+        )
+    }
+
+    /// Like `build_all`, but only recompiles source files that changed since the last build --
+    /// in their contents, their package's configuration, or the interfaces of the modules they
+    /// depend on -- reusing the modules of the remaining files from the build directory. Falls
+    /// back to a full build when there is no usable cache, when recompilation is forced, or when
+    /// compiling the changed files alone fails, in which case `report_errors` is given the errors
+    /// of the full build.
+    pub(crate) fn build_all_with_cache<W: Write>(
+        w: &mut W,
+        vfs_root: Option<VfsPath>,
+        project_root: &Path,
+        resolved_package: Package,
+        transitive_dependencies: Vec<DependencyInfo>,
+        resolution_graph: &ResolvedGraph,
+        report_errors: impl FnOnce(&FilesSourceText, Diagnostics) -> anyhow::Error,
+    ) -> Result<CompiledPackage> {
+        let BuildResult {
+            root_package_name,
+            sources_package_paths,
+            immediate_dependencies,
+            deps_package_paths,
+            result: (),
+        } = Self::prepare_build(
+            w,
+            &resolved_package,
+            transitive_dependencies,
+            resolution_graph,
+        )?;
+        let (mut source_packages, bytecode_deps) = partition_deps(deps_package_paths.clone());
+        source_packages.push(sources_package_paths.clone());
+
+        let root_path = project_root
+            .join(CompiledPackageLayout::Root.path())
+            .join(root_package_name.as_str());
+        // The cache refers to files on disk, so it cannot be used with a virtual file system
+        let digests = match vfs_root {
+            None => Some(SourceDigests::new(
+                &source_packages,
+                &resolution_graph.build_options,
+            )?),
+            Some(_) => None,
+        };
+        let incremental = match &digests {
+            Some(digests) if !resolution_graph.build_options.force_recompilation => {
+                CompilationCache::load(&root_path).and_then(|cache| {
+                    cache.plan(&root_path, &source_packages, &bytecode_deps, digests)
+                })
+            }
+            _ => None,
+        };
+
+        let incremental_sources = match incremental {
+            Some(build) => build.compile(&bytecode_deps, resolution_graph)?,
+            None => None,
+        };
+        let CompiledSources { units, warnings } = match incremental_sources {
+            Some(compiled) => compiled,
+            None => {
+                let compiler = Self::make_compiler(
+                    vfs_root,
+                    source_packages.clone(),
+                    bytecode_deps.clone(),
+                    resolution_graph,
+                )?;
+                let format = resolution_graph.build_options.diagnostics_format;
+                match CompiledSources::build(compiler, format)? {
+                    Ok(compiled) => compiled,
+                    Err((files, error_diags)) => return Err(report_errors(&files, error_diags)),
+                }
+            }
+        };
+
+        let compiled_package = Self::assemble(
+            project_root,
+            resolved_package,
+            resolution_graph,
+            BuildResult {
+                root_package_name,
+                sources_package_paths,
+                immediate_dependencies,
+                deps_package_paths,
+                result: units,
+            },
+        )?;
+        if let Some(digests) = digests {
+            CompilationCache::new(
+                &source_packages,
+                &bytecode_deps,
+                &digests,
+                compiled_package.all_compiled_units_with_source(),
+                &warnings,
+            )?
+            .save(&root_path)?;
+        }
+        Ok(compiled_package)
+    }
+
+    /// Splits `compiled_units` between the root package and its dependencies, generates docs if
+    /// requested, and saves the resulting package to disk.
+    fn assemble(
+        project_root: &Path,
+        resolved_package: Package,
+        resolution_graph: &ResolvedGraph,
+        build: BuildResult<Vec<(PackageName, CompiledUnitWithSource)>>,
+    ) -> Result<CompiledPackage> {
+        let BuildResult {
+            root_package_name,
+            sources_package_paths,
+            immediate_dependencies,
+            deps_package_paths,
+            result: compiled_units,
+        } = build;
         let mut root_compiled_units = vec![];
         let mut deps_compiled_units = vec![];
-        for annot_unit in all_compiled_units {
-            let source_path = PathBuf::from(file_map[&annot_unit.loc().file_hash()].0.as_str());
-            let package_name = annot_unit.named_module.package_name.unwrap();
-            let unit = CompiledUnitWithSource {
-                unit: annot_unit.into_compiled_unit(),
-                source_path,
-            };
+        for (package_name, unit) in compiled_units {
             if package_name == root_package_name {
                 root_compiled_units.push(unit)
             } else {
@@ -853,3 +1059,34 @@ pub(crate) fn make_deps_for_compiler_internal(
         })
         .collect::<Result<Vec<_>>>()
 }
+
+/// Separates dependencies available as source from those only available as bytecode.
+pub(crate) fn partition_deps(
+    deps_package_paths: Vec<(PackagePaths, ModuleFormat)>,
+) -> (Vec<PackagePaths>, Vec<PackagePaths>) {
+    deps_package_paths
+        .into_iter()
+        .partition_map(|(p, b)| match b {
+            ModuleFormat::Source => Either::Left(p),
+            ModuleFormat::Bytecode => Either::Right(p),
+        })
+}
+
+/// Pairs each compiled unit with the package it belongs to and the path of its source file.
+pub(crate) fn units_with_source(
+    file_map: &FilesSourceText,
+    compiled_units: Vec<AnnotatedCompiledUnit>,
+) -> Vec<(PackageName, CompiledUnitWithSource)> {
+    compiled_units
+        .into_iter()
+        .map(|annot_unit| {
+            let source_path = PathBuf::from(file_map[&annot_unit.loc().file_hash()].0.as_str());
+            let package_name = annot_unit.named_module.package_name.unwrap();
+            let unit = CompiledUnitWithSource {
+                unit: annot_unit.into_compiled_unit(),
+                source_path,
+            };
+            (package_name, unit)
+        })
+        .collect()
+}
@@ -0,0 +1,482 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A per-file cache of the modules compiled by the previous build of a package, so that a
+//! rebuild only recompiles the source files that changed and the files that depend on them.
+//!
+//! Each source file is keyed by a digest of its contents and of the configuration of the package
+//! it belongs to (edition, flavor, named addresses, compiler flags). Each module compiled from it
+//! records a digest of its interface, and the interface digests of the modules it depended on
+//! when it was compiled. A file is reused if its key is unchanged and all of the interfaces its
+//! modules depend on are unchanged as well; otherwise it is recompiled.
+//!
+//! Recompiled files are compiled against the bytecode of the reused modules, like bytecode
+//! dependencies. Files declaring anything that dependents cannot see through bytecode (macros,
+//! method aliases and enums) are instead handed to the compiler as source dependencies, and their
+//! interface digest is the digest of the whole file. As uses of macros and method aliases are not
+//! recorded in bytecode, any change to one of these files rebuilds the package from scratch.
+
+use crate::{
+    compilation::{
+        compiled_package::{
+            units_with_source, CompiledPackage, CompiledUnitWithSource, OnDiskCompiledPackage,
+        },
+        package_layout::CompiledPackageLayout,
+    },
+    resolution::{
+        digest::{digest_str, hashed_files_digest},
+        resolution_graph::ResolvedGraph,
+    },
+    source_package::parsed_manifest::PackageName,
+    BuildConfig,
+};
+use anyhow::Result;
+use move_binary_format::file_format::CompiledModule;
+use move_command_line_common::{env::get_bytecode_version_from_env, files::FileHash};
+use move_compiler::{
+    diagnostics::{report_warnings_with_format, Diagnostics, DiagnosticsFormat, FilesSourceText},
+    editions::Edition,
+    interface_generator::write_module_to_string,
+    parser::lexer::{Lexer, Tok},
+    shared::{PackageConfig, PackagePaths},
+    Compiler,
+};
+use move_core_types::language_storage::ModuleId;
+use move_symbol_pool::Symbol;
+use petgraph::graphmap::DiGraphMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+/// The modules compiled from each source file of a build, by path.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct CompilationCache {
+    sources: BTreeMap<Symbol, CachedSource>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSource {
+    package: PackageName,
+    /// Digest of the file's contents and its package's configuration
+    digest: String,
+    /// Whether dependents can be compiled against the bytecode of the file's modules
+    bytecode_interface: bool,
+    /// Whether compiling the file produced warnings, which are reported again on every build
+    warnings: bool,
+    modules: Vec<CachedModule>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedModule {
+    id: String,
+    name: Symbol,
+    /// Digest of what the module exposes to the modules depending on it
+    interface: String,
+    /// Interface digests of the modules this module was compiled against, by module ID
+    dependencies: BTreeMap<String, String>,
+}
+
+/// The units compiled by a build, along with the source files that had warnings.
+pub(crate) struct CompiledSources {
+    pub(crate) units: Vec<(PackageName, CompiledUnitWithSource)>,
+    pub(crate) warnings: BTreeSet<Symbol>,
+}
+
+/// The digests of the source files of a build, as recorded in the `CompilationCache`.
+pub(crate) struct SourceDigests(BTreeMap<Symbol, String>);
+
+/// The files that need to be recompiled, and the inputs to compile them against.
+pub(crate) struct IncrementalBuild {
+    /// Source files to recompile, grouped by package
+    targets: Vec<PackagePaths>,
+    /// Reused files that must be compiled from source to be depended on, grouped by package
+    source_deps: Vec<PackagePaths>,
+    /// Bytecode of the remaining reused modules
+    bytecode_interfaces: Vec<Symbol>,
+    /// Units of all reused files, loaded from the build directory
+    cached_units: Vec<(PackageName, CompiledUnitWithSource)>,
+}
+
+impl SourceDigests {
+    pub(crate) fn new(
+        source_packages: &[PackagePaths],
+        build_options: &BuildConfig,
+    ) -> Result<Self> {
+        let mut digests = BTreeMap::new();
+        for package in source_packages {
+            let config = config_digest(package, build_options);
+            for path in &package.paths {
+                let contents = std::fs::read(path.as_str())?;
+                let digest = hashed_files_digest(vec![digest_str(&contents), config.clone()]);
+                digests.insert(*path, digest);
+            }
+        }
+        Ok(Self(digests))
+    }
+}
+
+impl CompilationCache {
+    /// Loads the cache saved by the previous build under `root_path`, if any.
+    pub(crate) fn load(root_path: &Path) -> Option<Self> {
+        let path = root_path.join(CompiledPackageLayout::CompilationCache.path());
+        serde_yaml::from_slice(&std::fs::read(path).ok()?).ok()
+    }
+
+    pub(crate) fn save(&self, root_path: &Path) -> Result<()> {
+        let path = root_path.join(CompiledPackageLayout::CompilationCache.path());
+        std::fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Records the units of a build of `source_packages` against `bytecode_deps`.
+    pub(crate) fn new<'a>(
+        source_packages: &[PackagePaths],
+        bytecode_deps: &[PackagePaths],
+        digests: &SourceDigests,
+        compiled_units: impl Iterator<Item = &'a CompiledUnitWithSource>,
+        warnings: &BTreeSet<Symbol>,
+    ) -> Result<Self> {
+        let mut modules_by_source: BTreeMap<Symbol, Vec<&CompiledModule>> = BTreeMap::new();
+        for unit in compiled_units {
+            let path = Symbol::from(unit.source_path.to_string_lossy().as_ref());
+            modules_by_source
+                .entry(path)
+                .or_default()
+                .push(&unit.unit.module);
+        }
+
+        // Compute the interface of every module first, as dependencies can be in any file
+        let mut interfaces = bytecode_interfaces(bytecode_deps)?;
+        let mut sources = BTreeMap::new();
+        for package in source_packages {
+            let Some((package_name, config)) = &package.name else {
+                continue;
+            };
+            for path in &package.paths {
+                let (Some(digest), Some(modules)) =
+                    (digests.0.get(path), modules_by_source.get(path))
+                else {
+                    continue;
+                };
+                let bytecode_interface = modules.iter().all(|m| m.enum_defs().is_empty())
+                    && !has_source_only_declarations(
+                        &std::fs::read_to_string(path.as_str())?,
+                        config.edition,
+                    );
+                let mut cached_modules = vec![];
+                for module in modules {
+                    let interface = if bytecode_interface {
+                        interface_digest(module)?
+                    } else {
+                        digest.clone()
+                    };
+                    let id = module.self_id();
+                    cached_modules.push(CachedModule {
+                        id: id.to_string(),
+                        name: Symbol::from(id.name().as_str()),
+                        interface: interface.clone(),
+                        dependencies: BTreeMap::new(),
+                    });
+                    interfaces.insert(id, interface);
+                }
+                let source = CachedSource {
+                    package: *package_name,
+                    digest: digest.clone(),
+                    bytecode_interface,
+                    warnings: warnings.contains(path),
+                    modules: cached_modules,
+                };
+                sources.insert(*path, source);
+            }
+        }
+
+        for (path, source) in &mut sources {
+            for (cached, module) in source.modules.iter_mut().zip(&modules_by_source[path]) {
+                cached.dependencies = module
+                    .immediate_dependencies()
+                    .into_iter()
+                    .map(|dep| {
+                        let interface = interfaces.get(&dep).cloned().unwrap_or_default();
+                        (dep.to_string(), interface)
+                    })
+                    .collect();
+            }
+        }
+        Ok(Self { sources })
+    }
+
+    /// Determines which files of `source_packages` must be recompiled, and loads the units of the
+    /// others from the previous build under `root_path`. Files that had warnings are recompiled
+    /// even if they are unchanged, so that their warnings are reported. Returns `None` if nothing
+    /// from the previous build can be reused.
+    pub(crate) fn plan(
+        &self,
+        root_path: &Path,
+        source_packages: &[PackagePaths],
+        bytecode_deps: &[PackagePaths],
+        digests: &SourceDigests,
+    ) -> Option<IncrementalBuild> {
+        let on_disk = OnDiskCompiledPackage::from_path(root_path).ok()?;
+        let bytecode_interfaces = bytecode_interfaces(bytecode_deps).ok()?;
+
+        // Files that are unchanged themselves
+        let mut reused: BTreeMap<Symbol, &CachedSource> = source_packages
+            .iter()
+            .flat_map(|package| &package.paths)
+            .filter_map(|path| {
+                let source = self.sources.get(path)?;
+                (digests.0.get(path) == Some(&source.digest)).then_some((*path, source))
+            })
+            .collect();
+        if reused.is_empty() {
+            return None;
+        }
+
+        // Recompile files that depend on a changed interface, until that no longer changes the
+        // set of interfaces available
+        loop {
+            let interfaces: BTreeMap<String, &str> = bytecode_interfaces
+                .iter()
+                .map(|(id, interface)| (id.to_string(), interface.as_str()))
+                .chain(reused.values().flat_map(|source| {
+                    source
+                        .modules
+                        .iter()
+                        .map(|m| (m.id.clone(), m.interface.as_str()))
+                }))
+                .collect();
+            let stale = reused
+                .iter()
+                .filter(|(_, source)| {
+                    source.modules.iter().any(|m| {
+                        m.dependencies.iter().any(|(dep, interface)| {
+                            interfaces.get(dep) != Some(&interface.as_str())
+                        })
+                    })
+                })
+                .map(|(path, _)| *path)
+                .collect::<Vec<_>>();
+            if stale.is_empty() {
+                break;
+            }
+            for path in stale {
+                reused.remove(&path);
+            }
+        }
+
+        // Uses of macros and method aliases leave no trace in bytecode, so there is no telling
+        // which files depend on one that changed
+        if self
+            .sources
+            .iter()
+            .any(|(path, source)| !source.bytecode_interface && !reused.contains_key(path))
+        {
+            return None;
+        }
+
+        let mut build = IncrementalBuild {
+            targets: vec![],
+            source_deps: vec![],
+            bytecode_interfaces: vec![],
+            cached_units: vec![],
+        };
+        for package in source_packages {
+            let (package_reused, mut package_targets): (Vec<Symbol>, Vec<Symbol>) = package
+                .paths
+                .iter()
+                .partition(|path| reused.contains_key(path));
+            let (package_warned, package_reused): (Vec<Symbol>, Vec<Symbol>) = package_reused
+                .into_iter()
+                .partition(|path| reused[path].warnings);
+            package_targets.extend(package_warned);
+            let mut package_source_deps = vec![];
+            for path in package_reused {
+                let source = reused[&path];
+                for module in &source.modules {
+                    let unit = on_disk
+                        .load_compiled_unit(
+                            source.package,
+                            module.name,
+                            PathBuf::from(path.as_str()),
+                        )
+                        .ok()?;
+                    build.cached_units.push((source.package, unit));
+                    if source.bytecode_interface {
+                        let bytecode_path =
+                            on_disk.compiled_unit_bytecode_path(source.package, module.name);
+                        build
+                            .bytecode_interfaces
+                            .push(Symbol::from(bytecode_path.to_string_lossy().as_ref()));
+                    }
+                }
+                if !source.bytecode_interface {
+                    package_source_deps.push(path);
+                }
+            }
+            for (paths, packages) in [
+                (package_targets, &mut build.targets),
+                (package_source_deps, &mut build.source_deps),
+            ] {
+                if !paths.is_empty() {
+                    packages.push(PackagePaths {
+                        name: package.name.clone(),
+                        paths,
+                        named_address_map: package.named_address_map.clone(),
+                    });
+                }
+            }
+        }
+        Some(build)
+    }
+}
+
+impl IncrementalBuild {
+    /// Compiles the changed files against the reused ones, and returns the units of the whole
+    /// build in dependency order. Returns `None` if the changed files fail to compile on their
+    /// own, in which case the package should be rebuilt from scratch.
+    pub(crate) fn compile(
+        self,
+        bytecode_deps: &[PackagePaths],
+        resolution_graph: &ResolvedGraph,
+    ) -> Result<Option<CompiledSources>> {
+        let Self {
+            targets,
+            source_deps,
+            bytecode_interfaces,
+            mut cached_units,
+        } = self;
+        if targets.is_empty() {
+            return Ok(Some(CompiledSources {
+                units: cached_units,
+                warnings: BTreeSet::new(),
+            }));
+        }
+
+        let mut deps = bytecode_deps.to_vec();
+        deps.extend(source_deps);
+        deps.push(PackagePaths {
+            name: None,
+            paths: bytecode_interfaces,
+            named_address_map: BTreeMap::new(),
+        });
+        let compiler = CompiledPackage::make_compiler(None, targets, deps, resolution_graph)?
+            .set_default_config(PackageConfig {
+                is_dependency: true,
+                ..PackageConfig::default()
+            });
+        let format = resolution_graph.build_options.diagnostics_format;
+        let Ok(CompiledSources { units, warnings }) = CompiledSources::build(compiler, format)?
+        else {
+            return Ok(None);
+        };
+        cached_units.extend(units);
+        Ok(Some(CompiledSources {
+            units: dependency_order(cached_units)?,
+            warnings,
+        }))
+    }
+}
+
+impl CompiledSources {
+    /// Runs `compiler` and reports its warnings, or returns its errors if compilation failed.
+    pub(crate) fn build(
+        compiler: Compiler,
+        format: DiagnosticsFormat,
+    ) -> Result<Result<Self, (FilesSourceText, Diagnostics)>> {
+        let (files, units_res) = compiler.build()?;
+        let (units, warning_diags) = match units_res {
+            Ok(res) => res,
+            Err(error_diags) => return Ok(Err((files, error_diags))),
+        };
+        let warnings = warning_diags
+            .clone()
+            .into_vec()
+            .iter()
+            .map(|diag| files[&diag.primary_loc().file_hash()].0)
+            .collect();
+        report_warnings_with_format(&files, warning_diags, format);
+        Ok(Ok(Self {
+            units: units_with_source(&files, units),
+            warnings,
+        }))
+    }
+}
+
+/// Digest of everything besides a file's contents that affects how it is compiled.
+fn config_digest(package: &PackagePaths, build_options: &BuildConfig) -> String {
+    let config = format!(
+        "{:?} {:?} {:?} {:?} {:?} {}",
+        package.name,
+        package.named_address_map,
+        build_options.compiler_flags(),
+        build_options.lint_flag,
+        get_bytecode_version_from_env(),
+        env!("CARGO_PKG_VERSION"),
+    );
+    digest_str(config.as_bytes())
+}
+
+/// Interface digests of the modules of bytecode dependencies, which are their bytecode.
+fn bytecode_interfaces(bytecode_deps: &[PackagePaths]) -> Result<BTreeMap<ModuleId, String>> {
+    let mut interfaces = BTreeMap::new();
+    for path in bytecode_deps.iter().flat_map(|package| &package.paths) {
+        let bytes = std::fs::read(path.as_str())?;
+        let module = CompiledModule::deserialize_with_defaults(&bytes)?;
+        interfaces.insert(module.self_id(), digest_str(&bytes));
+    }
+    Ok(interfaces)
+}
+
+fn interface_digest(module: &CompiledModule) -> Result<String> {
+    let (_, interface) = write_module_to_string(&BTreeMap::<ModuleId, String>::new(), module)?;
+    Ok(digest_str(interface.as_bytes()))
+}
+
+/// Whether `source` declares macros or method aliases, which are not preserved in bytecode.
+fn has_source_only_declarations(source: &str, edition: Edition) -> bool {
+    let mut lexer = Lexer::new(source, FileHash::new(source), edition);
+    let mut prev = (Tok::EOF, "");
+    loop {
+        if lexer.advance().is_err() {
+            // Be conservative about files that do not lex
+            return true;
+        }
+        let tok = lexer.peek();
+        match (prev, tok) {
+            (_, Tok::EOF) => return false,
+            ((Tok::Identifier, "macro"), Tok::Fun) | ((Tok::Use, _), Tok::Fun) => return true,
+            _ => prev = (tok, lexer.content()),
+        }
+    }
+}
+
+/// Orders `units` so that each module comes after the modules it depends on. Dependencies on
+/// modules outside of `units` are ignored.
+fn dependency_order(
+    units: Vec<(PackageName, CompiledUnitWithSource)>,
+) -> Result<Vec<(PackageName, CompiledUnitWithSource)>> {
+    let indices = units
+        .iter()
+        .enumerate()
+        .map(|(idx, (_, unit))| (unit.unit.module.self_id(), idx))
+        .collect::<BTreeMap<_, _>>();
+    let mut graph = DiGraphMap::<usize, ()>::new();
+    for (idx, (_, unit)) in units.iter().enumerate() {
+        graph.add_node(idx);
+        for dep in unit.unit.module.immediate_dependencies() {
+            if let Some(dep_idx) = indices.get(&dep) {
+                graph.add_edge(*dep_idx, idx, ());
+            }
+        }
+    }
+    let Ok(order) = petgraph::algo::toposort(&graph, None) else {
+        anyhow::bail!("Circular dependency detected")
+    };
+    let mut units = units.into_iter().map(Some).collect::<Vec<_>>();
+    Ok(order
+        .into_iter()
+        .filter_map(|idx| units[idx].take())
+        .collect())
+}
@@ -14,6 +14,7 @@ pub enum CompiledPackageLayout {
     LockFiles,
     CompiledModules,
     CompiledDocs,
+    CompilationCache,
 }
 
 impl CompiledPackageLayout {
@@ -27,6 +28,7 @@ impl CompiledPackageLayout {
             Self::LockFiles => "locks",
             Self::CompiledModules => "bytecode_modules",
             Self::CompiledDocs => "docs",
+            Self::CompilationCache => "CompilationCache.yaml",
         };
         Path::new(path)
     }
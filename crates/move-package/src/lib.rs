@@ -15,6 +15,7 @@ use anyhow::{anyhow, Result};
 use clap::*;
 use lock_file::LockFile;
 use move_compiler::{
+    diagnostics::DiagnosticsFormat,
     editions::{Edition, Flavor},
     Flags,
 };
@@ -101,6 +102,10 @@ pub struct BuildConfig {
     #[clap(long = move_compiler::command_line::WARNINGS_ARE_ERRORS, global = true)]
     pub warnings_are_errors: bool,
 
+    /// Format of the compiler diagnostics: "human", "json" or "sarif"
+    #[clap(long = "diagnostics-format", global = true, default_value_t)]
+    pub diagnostics_format: DiagnosticsFormat,
+
     /// Additional named address mapping. Useful for tools in rust
     #[clap(skip)]
     pub additional_named_addresses: BTreeMap<String, AccountAddress>,
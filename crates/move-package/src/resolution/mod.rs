@@ -18,7 +18,7 @@ use self::dependency_graph::DependencyGraphBuilder;
 
 pub mod dependency_cache;
 pub mod dependency_graph;
-mod digest;
+pub(crate) mod digest;
 pub mod resolution_graph;
 pub mod resolving_table;
 
@@ -0,0 +1,145 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_package::{
+    compilation::{compiled_package::CompiledPackage, package_layout::CompiledPackageLayout},
+    BuildConfig,
+};
+use std::{collections::BTreeMap, path::Path};
+use tempfile::{tempdir, TempDir};
+
+const MANIFEST: &str = r#"
+[package]
+name = "Inc"
+edition = "2024.alpha"
+
+[addresses]
+inc = "0x42"
+"#;
+
+fn package(sources: &[(&str, &str)]) -> TempDir {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("Move.toml"), MANIFEST).unwrap();
+    std::fs::create_dir(dir.path().join("sources")).unwrap();
+    for (name, contents) in sources {
+        write_source(dir.path(), name, contents);
+    }
+    dir
+}
+
+fn write_source(path: &Path, name: &str, contents: &str) {
+    std::fs::write(
+        path.join("sources").join(name).with_extension("move"),
+        contents,
+    )
+    .unwrap();
+}
+
+fn build(path: &Path, force_recompilation: bool) -> anyhow::Result<CompiledPackage> {
+    BuildConfig {
+        install_dir: Some(path.to_path_buf()),
+        force_recompilation,
+        ..Default::default()
+    }
+    .compile_package_no_exit(path, &mut Vec::new())
+}
+
+fn bytecode(package: &CompiledPackage) -> BTreeMap<String, Vec<u8>> {
+    package
+        .root_modules()
+        .map(|unit| {
+            let mut bytes = vec![];
+            unit.unit.module.serialize(&mut bytes).unwrap();
+            (unit.unit.name.to_string(), bytes)
+        })
+        .collect()
+}
+
+#[test]
+fn test_rebuild_matches_full_build() {
+    let dir = package(&[
+        ("a", "module inc::a { public fun f(): u64 { 1 } }"),
+        (
+            "b",
+            "module inc::b { public fun g(): u64 { inc::a::f() + 1 } }",
+        ),
+        ("c", "module inc::c { public fun h(): u64 { 3 } }"),
+    ]);
+    let path = dir.path();
+    let first = build(path, false).unwrap();
+    assert!(path
+        .join(CompiledPackageLayout::Root.path())
+        .join("Inc")
+        .join(CompiledPackageLayout::CompilationCache.path())
+        .is_file());
+
+    // Nothing changed
+    assert_eq!(bytecode(&build(path, false).unwrap()), bytecode(&first));
+
+    write_source(path, "a", "module inc::a { public fun f(): u64 { 2 } }");
+    let incremental = build(path, false).unwrap();
+    assert_ne!(bytecode(&incremental), bytecode(&first));
+    assert_eq!(
+        bytecode(&incremental),
+        bytecode(&build(path, true).unwrap())
+    );
+}
+
+#[test]
+fn test_interface_change_recompiles_dependents() {
+    let dir = package(&[
+        ("a", "module inc::a { public fun f(): u64 { 1 } }"),
+        (
+            "b",
+            "module inc::b { public fun g(): u64 { inc::a::f() + 1 } }",
+        ),
+    ]);
+    let path = dir.path();
+    build(path, false).unwrap();
+
+    write_source(
+        path,
+        "a",
+        "module inc::a { public fun f(x: u64): u64 { x } }",
+    );
+    assert!(build(path, false).is_err());
+}
+
+#[test]
+fn test_macros_are_compiled_from_source() {
+    let dir = package(&[
+        (
+            "a",
+            "module inc::a { public macro fun m($x: u64): u64 { $x + 1 } }",
+        ),
+        (
+            "b",
+            "module inc::b { public fun g(): u64 { inc::a::m!(1) } }",
+        ),
+        ("c", "module inc::c { public fun h(): u64 { 3 } }"),
+    ]);
+    let path = dir.path();
+    let first = build(path, false).unwrap();
+
+    // `b` can still be compiled against `a`, which only exists in source
+    write_source(path, "c", "module inc::c { public fun h(): u64 { 4 } }");
+    let incremental = build(path, false).unwrap();
+    assert_eq!(incremental.root_modules().count(), 3);
+    assert_eq!(
+        bytecode(&incremental),
+        bytecode(&build(path, true).unwrap())
+    );
+
+    // Changing the macro changes the code of its callers
+    write_source(
+        path,
+        "a",
+        "module inc::a { public macro fun m($x: u64): u64 { $x + 2 } }",
+    );
+    let incremental = build(path, false).unwrap();
+    assert_ne!(bytecode(&incremental)["b"], bytecode(&first)["b"]);
+    assert_eq!(
+        bytecode(&incremental),
+        bytecode(&build(path, true).unwrap())
+    );
+}
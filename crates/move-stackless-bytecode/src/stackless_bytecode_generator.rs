@@ -1203,6 +1203,17 @@ impl<'a> StacklessBytecodeGenerator<'a> {
                     None,
                 ))
             }
+            MoveBytecode::PackVariant(_)
+            | MoveBytecode::PackVariantGeneric(_)
+            | MoveBytecode::UnpackVariant(_)
+            | MoveBytecode::UnpackVariantImmRef(_)
+            | MoveBytecode::UnpackVariantMutRef(_)
+            | MoveBytecode::UnpackVariantGeneric(_)
+            | MoveBytecode::UnpackVariantGenericImmRef(_)
+            | MoveBytecode::UnpackVariantGenericMutRef(_)
+            | MoveBytecode::VariantSwitch(_) => {
+                panic!("Variants are not supported by stackless bytecode.")
+            }
             MoveBytecode::VecSwap(sig) => {
                 let tys = self.get_type_params(*sig);
                 let operand3_index = self.temp_stack.pop().unwrap();
@@ -0,0 +1,238 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Context, Result};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::{BufRead, Lines},
+};
+
+/// Version of the trace format, bumped on any incompatible change to the events.
+pub const TRACE_VERSION: u64 = 1;
+
+/// The first line of every trace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceHeader {
+    pub version: u64,
+}
+
+/// The trace of a session of the VM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveTrace {
+    pub version: u64,
+    pub events: Vec<TraceEvent>,
+}
+
+/// An event of the execution of a session, in the order they happen.
+///
+/// Every function called by the session opens a frame, which is closed when it returns. An error
+/// ends the call: the frames still open at that point are never closed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// A function is called, by the session or by a call instruction.
+    OpenFrame {
+        /// Identifies the frame in the rest of the trace.
+        frame_id: u64,
+        module: ModuleId,
+        function_name: String,
+        /// Index of the function definition in its module.
+        function_index: u16,
+        type_args: Vec<TypeTag>,
+        args: Vec<TraceValue>,
+        is_native: bool,
+        /// Gas left once the call is charged.
+        gas_left: u64,
+    },
+    /// An instruction of the innermost open frame is executed.
+    Instruction {
+        frame_id: u64,
+        pc: u16,
+        instruction: String,
+        /// Gas left before executing the instruction.
+        gas_left: u64,
+        gas_charged: u64,
+        /// Effects on the operand stack and the locals of the frame, in order. Call instructions
+        /// have none: the arguments are popped when the callee frame opens.
+        effects: Vec<Effect>,
+    },
+    /// A function returns, leaving its return values at the top of the operand stack.
+    CloseFrame {
+        frame_id: u64,
+        return_values: Vec<TraceValue>,
+        gas_left: u64,
+    },
+    /// A module is read from storage.
+    LoadModule { module: ModuleId, size: u64 },
+    /// A resource is read from storage, `size` is `None` if it does not exist.
+    LoadResource {
+        address: AccountAddress,
+        resource: StructTag,
+        size: Option<u64>,
+    },
+    /// Execution failed.
+    Error {
+        status: String,
+        sub_status: Option<u64>,
+    },
+}
+
+/// An effect of an instruction on the operand stack or the locals of its frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Effect {
+    Push(TraceValue),
+    Pop(TraceValue),
+    /// A local is copied, or moved out of.
+    ReadLocal {
+        index: u16,
+        value: TraceValue,
+        moved: bool,
+    },
+    WriteLocal {
+        index: u16,
+        value: TraceValue,
+    },
+}
+
+/// A runtime value. Values carry no type in the VM: structs are only their fields, and integers
+/// their width.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceValue {
+    /// A local that was moved out of, or never written.
+    Invalid,
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    /// Written in decimal, as JSON numbers commonly lose precision beyond 64 bits.
+    U128(String),
+    U256(String),
+    Bool(bool),
+    Address(AccountAddress),
+    Vector(Vec<TraceValue>),
+    Struct(Vec<TraceValue>),
+    Variant {
+        tag: u16,
+        fields: Vec<TraceValue>,
+    },
+    /// A reference, with the value it points to.
+    Reference(Box<TraceValue>),
+}
+
+impl fmt::Display for TraceValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn comma_separated(f: &mut fmt::Formatter, values: &[TraceValue]) -> fmt::Result {
+            for (idx, value) in values.iter().enumerate() {
+                if idx > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", value)?;
+            }
+            Ok(())
+        }
+
+        match self {
+            TraceValue::Invalid => write!(f, "invalid"),
+            TraceValue::U8(x) => write!(f, "{}u8", x),
+            TraceValue::U16(x) => write!(f, "{}u16", x),
+            TraceValue::U32(x) => write!(f, "{}u32", x),
+            TraceValue::U64(x) => write!(f, "{}u64", x),
+            TraceValue::U128(x) => write!(f, "{}u128", x),
+            TraceValue::U256(x) => write!(f, "{}u256", x),
+            TraceValue::Bool(x) => write!(f, "{}", x),
+            TraceValue::Address(x) => write!(f, "@{}", x.to_hex_literal()),
+            TraceValue::Vector(values) => {
+                write!(f, "[")?;
+                comma_separated(f, values)?;
+                write!(f, "]")
+            }
+            TraceValue::Struct(fields) => {
+                write!(f, "{{")?;
+                comma_separated(f, fields)?;
+                write!(f, "}}")
+            }
+            TraceValue::Variant { tag, fields } => {
+                write!(f, "#{} {{", tag)?;
+                comma_separated(f, fields)?;
+                write!(f, "}}")
+            }
+            TraceValue::Reference(value) => write!(f, "&{}", value),
+        }
+    }
+}
+
+/// Reads the traces of a trace file one at a time.
+pub struct TraceReader<R> {
+    lines: Lines<R>,
+    line: usize,
+    /// The next trace, whose header was read while looking for the end of the previous one.
+    next: Option<MoveTrace>,
+}
+
+impl<R: BufRead> TraceReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            lines: reader.lines(),
+            line: 0,
+            next: None,
+        }
+    }
+
+    fn start_trace(&self, line: &str) -> Result<MoveTrace> {
+        let header: TraceHeader = serde_json::from_str(line)
+            .with_context(|| format!("Line {}: expected a trace header or event", self.line))?;
+        if header.version != TRACE_VERSION {
+            bail!(
+                "Line {}: unsupported trace version {}, expected {}",
+                self.line,
+                header.version,
+                TRACE_VERSION
+            );
+        }
+        Ok(MoveTrace {
+            version: header.version,
+            events: vec![],
+        })
+    }
+}
+
+impl<R: BufRead> Iterator for TraceReader<R> {
+    type Item = Result<MoveTrace>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut trace = self.next.take();
+        while let Some(line) = self.lines.next() {
+            self.line += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err.into())),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let (Some(trace), Ok(event)) = (&mut trace, serde_json::from_str(&line)) {
+                trace.events.push(event);
+                continue;
+            }
+            let next = match self.start_trace(&line) {
+                Ok(next) => next,
+                Err(err) => return Some(Err(err)),
+            };
+            match trace {
+                None => trace = Some(next),
+                Some(_) => {
+                    self.next = Some(next);
+                    break;
+                }
+            }
+        }
+        trace.map(Ok)
+    }
+}
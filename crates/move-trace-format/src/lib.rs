@@ -0,0 +1,11 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured execution traces of the Move VM.
+//!
+//! A session of the VM with a `tracer::Tracer` reports every call, instruction and storage access
+//! it executes as a `format::TraceEvent`. Traces are stored as JSON lines: a `format::TraceHeader`
+//! followed by one event per line, and several traces can be appended to the same file.
+
+pub mod format;
+pub mod tracer;
@@ -0,0 +1,66 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::format::{TraceEvent, TraceHeader, TRACE_VERSION};
+use std::{fs::OpenOptions, io::Write, path::PathBuf, sync::Mutex};
+
+/// Receives the events of the execution of a session of the VM.
+pub trait Tracer {
+    fn notify(&mut self, event: &TraceEvent);
+}
+
+/// Serializes concurrent appends to trace files.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+/// A `Tracer` appending the trace of its session to a file once the session is dropped, so that
+/// the traces of concurrent sessions are not interleaved.
+pub struct FileTracer {
+    path: PathBuf,
+    buffer: Vec<u8>,
+}
+
+impl FileTracer {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            buffer: vec![],
+        }
+    }
+
+    fn write_line(&mut self, line: &impl serde::Serialize) {
+        serde_json::to_writer(&mut self.buffer, line).expect("trace events must serialize");
+        self.buffer.push(b'\n');
+    }
+}
+
+impl Tracer for FileTracer {
+    fn notify(&mut self, event: &TraceEvent) {
+        if self.buffer.is_empty() {
+            self.write_line(&TraceHeader {
+                version: TRACE_VERSION,
+            });
+        }
+        self.write_line(event);
+    }
+}
+
+impl Drop for FileTracer {
+    fn drop(&mut self) {
+        // Sessions that executed nothing leave no trace
+        if self.buffer.is_empty() {
+            return;
+        }
+        let _lock = APPEND_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(&self.buffer));
+        if let Err(err) = result {
+            // Panicking while unwinding would abort the process
+            if !std::thread::panicking() {
+                panic!("Unable to write trace file {:?}: {}", self.path, err);
+            }
+        }
+    }
+}
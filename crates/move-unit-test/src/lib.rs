@@ -24,6 +24,7 @@ use std::{
     collections::BTreeMap,
     io::{Result, Write},
     marker::Send,
+    path::PathBuf,
     sync::Mutex,
 };
 
@@ -100,6 +101,10 @@ pub struct UnitTestingConfig {
     /// Verbose mode
     #[clap(short = 'v', long = "verbose")]
     pub verbose: bool,
+
+    /// Append the execution trace of each test to this file, see `move_trace_format`
+    #[clap(long = "trace")]
+    pub trace_path: Option<PathBuf>,
 }
 
 fn format_module_id(module_id: &ModuleId) -> String {
@@ -125,6 +130,7 @@ impl UnitTestingConfig {
             verbose: false,
             list: false,
             named_address_values: vec![],
+            trace_path: None,
         }
     }
 
@@ -211,6 +217,7 @@ impl UnitTestingConfig {
             self.check_stackless_vm,
             self.verbose,
             self.report_stacktrace_on_abort,
+            self.trace_path.clone(),
             test_plan,
             native_function_table,
             cost_table,
@@ -40,13 +40,16 @@ use move_stackless_bytecode_interpreter::{
     shared::bridge::adapt_move_vm_result,
     StacklessBytecodeInterpreter,
 };
+use move_vm_config::runtime::{VMConfig, VMTracerConfig};
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use move_vm_test_utils::{
     gas_schedule::{unit_cost_schedule, CostTable, Gas, GasStatus},
     InMemoryStorage,
 };
 use rayon::prelude::*;
-use std::{collections::BTreeMap, io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{
+    collections::BTreeMap, io::Write, marker::Send, path::PathBuf, sync::Mutex, time::Instant,
+};
 
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 
@@ -61,6 +64,7 @@ pub struct SharedTestingConfig {
     named_address_values: BTreeMap<String, NumericalAddress>,
     check_stackless_vm: bool,
     verbose: bool,
+    trace_path: Option<PathBuf>,
 }
 
 pub struct TestRunner {
@@ -123,6 +127,7 @@ impl TestRunner {
         check_stackless_vm: bool,
         verbose: bool,
         report_stacktrace_on_abort: bool,
+        trace_path: Option<PathBuf>,
         tests: TestPlan,
         // TODO: maybe we should require the clients to always pass in a list of native functions so
         // we don't have to make assumptions about their gas parameters.
@@ -159,6 +164,7 @@ impl TestRunner {
                 check_stackless_vm,
                 verbose,
                 named_address_values,
+                trace_path,
             },
             num_threads,
             tests,
@@ -260,7 +266,15 @@ impl SharedTestingConfig {
         VMResult<Vec<Vec<u8>>>,
         TestRunInfo,
     ) {
-        let move_vm = MoveVM::new(self.native_function_table.clone()).unwrap();
+        let vm_config = VMConfig {
+            tracer_config: self
+                .trace_path
+                .clone()
+                .map(|trace_path| VMTracerConfig { trace_path }),
+            ..Default::default()
+        };
+        let move_vm =
+            MoveVM::new_with_config(self.native_function_table.clone(), vm_config).unwrap();
         let extensions = extensions::new_extensions();
         let mut session =
             move_vm.new_session_with_extensions(&self.starting_storage_state, extensions);
@@ -28,6 +28,8 @@ pub struct VMConfig {
     pub check_no_extraneous_bytes_during_deserialization: bool,
     // Configs for profiling VM
     pub profiler_config: Option<VMProfilerConfig>,
+    // Configs for tracing the execution of each session of the VM
+    pub tracer_config: Option<VMTracerConfig>,
     // When this flag is set to true, errors from the VM will be augmented with execution state
     // (stacktrace etc.)
     pub error_execution_state: bool,
@@ -44,6 +46,7 @@ impl Default for VMConfig {
             enable_invariant_violation_check_in_swap_loc: true,
             check_no_extraneous_bytes_during_deserialization: false,
             profiler_config: None,
+            tracer_config: None,
             error_execution_state: true,
             binary_config: BinaryConfig::with_extraneous_bytes_check(false),
         }
@@ -102,6 +105,12 @@ impl VMProfilerConfig {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct VMTracerConfig {
+    /// File the trace of each session is appended to, see `move_trace_format`
+    pub trace_path: std::path::PathBuf,
+}
+
 pub fn get_default_output_filepath() -> std::path::PathBuf {
     let mut default_name = std::path::PathBuf::from(".");
     default_name.push("gas_profile.json");
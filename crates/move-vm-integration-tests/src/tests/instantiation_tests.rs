@@ -437,6 +437,7 @@ fn make_module(
                 code: Some(CodeUnit {
                     locals: SignatureIndex(locals_idx as u16),
                     code,
+                    jump_tables: vec![],
                 }),
             },
             FunctionDefinition {
@@ -459,6 +460,7 @@ fn make_module(
                         CallGeneric(FunctionInstantiationIndex(0)),
                         Ret,
                     ],
+                    jump_tables: vec![],
                 }),
             },
             FunctionDefinition {
@@ -469,6 +471,7 @@ fn make_module(
                 code: Some(CodeUnit {
                     locals: SignatureIndex(locals_idx as u16),
                     code: vec![Ret],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -505,6 +508,10 @@ fn make_module(
         friend_decls: vec![],
         field_instantiations: vec![],
         metadata: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     // uncomment to see the module generated
     // println!("Module: {:#?}", module);
@@ -69,6 +69,7 @@ fn merge_borrow_states_infinite_loop() {
                 BrTrue(11),
                 Ret,
             ],
+            jump_tables: vec![],
         }),
     }];
     move_bytecode_verifier::verify_module_unmetered(&m).expect("verify failed");
@@ -44,6 +44,7 @@ fn leak_with_abort() {
                 LdU64(0),
                 Abort,
             ],
+            jump_tables: vec![],
         }),
     }];
     let module_id = m.self_id();
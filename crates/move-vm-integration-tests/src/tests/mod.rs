@@ -13,3 +13,4 @@ mod leak_tests;
 mod loader_tests;
 mod nested_loop_tests;
 mod return_value_tests;
+mod tracing_tests;
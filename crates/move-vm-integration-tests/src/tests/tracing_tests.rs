@@ -0,0 +1,247 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::compiler::{as_module, compile_units};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+    resolver::ModuleResolver,
+    runtime_value::MoveValue,
+};
+use move_trace_format::{
+    format::{Effect, TraceEvent, TraceReader, TraceValue, TRACE_VERSION},
+    tracer::Tracer,
+};
+use move_vm_config::runtime::{VMConfig, VMTracerConfig};
+use move_vm_runtime::move_vm::MoveVM;
+use move_vm_test_utils::InMemoryStorage;
+use move_vm_types::gas::UnmeteredGasMeter;
+use std::{cell::RefCell, fs::File, io::BufReader, rc::Rc};
+
+const TEST_ADDR: AccountAddress = AccountAddress::new([42; AccountAddress::LENGTH]);
+
+const CODE: &str = r#"
+    module 0x{ADDR}::M {
+        fun add(x: u64, y: u64): u64 {
+            x + y
+        }
+
+        public fun foo<T>(x: u64): u64 {
+            let y = add(x, 1);
+            y
+        }
+
+        public fun fail() {
+            abort 7
+        }
+    }
+"#;
+
+#[derive(Clone, Default)]
+struct CollectingTracer(Rc<RefCell<Vec<TraceEvent>>>);
+
+impl Tracer for CollectingTracer {
+    fn notify(&mut self, event: &TraceEvent) {
+        self.0.borrow_mut().push(event.clone())
+    }
+}
+
+fn setup() -> (InMemoryStorage, ModuleId) {
+    let code = CODE.replace("{ADDR}", &TEST_ADDR.to_hex());
+    let mut units = compile_units(&code).unwrap();
+    let m = as_module(units.pop().unwrap());
+    let mut blob = vec![];
+    m.serialize(&mut blob).unwrap();
+
+    let mut storage = InMemoryStorage::new();
+    let module_id = ModuleId::new(TEST_ADDR, Identifier::new("M").unwrap());
+    storage.publish_or_overwrite_module(module_id.clone(), blob);
+    (storage, module_id)
+}
+
+fn run(
+    vm: &MoveVM,
+    storage: &InMemoryStorage,
+    module_id: &ModuleId,
+    function_name: &str,
+    tracer: Option<CollectingTracer>,
+) {
+    let mut sess = vm.new_session(storage);
+    if let Some(tracer) = tracer {
+        sess.set_tracer(Box::new(tracer));
+    }
+    let (ty_args, args) = match function_name {
+        "foo" => (
+            vec![sess.load_type(&TypeTag::U8).unwrap()],
+            vec![MoveValue::U64(41).simple_serialize().unwrap()],
+        ),
+        _ => (vec![], vec![]),
+    };
+    let _ = sess.execute_function_bypass_visibility(
+        module_id,
+        &Identifier::new(function_name).unwrap(),
+        ty_args,
+        args,
+        &mut UnmeteredGasMeter,
+    );
+}
+
+fn open_frame_names(events: &[TraceEvent]) -> Vec<&str> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            TraceEvent::OpenFrame { function_name, .. } => Some(function_name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn trace_calls_and_instructions() {
+    let (storage, module_id) = setup();
+    let vm = MoveVM::new(vec![]).unwrap();
+    let tracer = CollectingTracer::default();
+    run(&vm, &storage, &module_id, "foo", Some(tracer.clone()));
+    let events = tracer.0.borrow();
+
+    assert_eq!(
+        events[0],
+        TraceEvent::LoadModule {
+            module: module_id.clone(),
+            size: storage.get_module(&module_id).unwrap().unwrap().len() as u64,
+        }
+    );
+    assert_eq!(open_frame_names(&events), vec!["foo", "add"]);
+    match &events[1] {
+        TraceEvent::OpenFrame {
+            frame_id,
+            type_args,
+            args,
+            is_native,
+            ..
+        } => {
+            assert_eq!(*frame_id, 0);
+            assert_eq!(type_args, &vec![TypeTag::U8]);
+            assert_eq!(args, &vec![TraceValue::U64(41)]);
+            assert!(!is_native);
+        }
+        event => panic!("Expected the frame of foo to open, got {:?}", event),
+    }
+    assert!(events.contains(&TraceEvent::OpenFrame {
+        frame_id: 1,
+        module: module_id.clone(),
+        function_name: "add".to_string(),
+        function_index: 0,
+        type_args: vec![],
+        args: vec![TraceValue::U64(41), TraceValue::U64(1)],
+        is_native: false,
+        gas_left: u64::MAX,
+    }));
+
+    let add = events
+        .iter()
+        .find_map(|event| match event {
+            TraceEvent::Instruction {
+                frame_id: 1,
+                instruction,
+                effects,
+                ..
+            } if instruction == "Add" => Some(effects),
+            _ => None,
+        })
+        .expect("Add should be traced in the frame of add");
+    assert_eq!(
+        add,
+        &vec![
+            Effect::Pop(TraceValue::U64(1)),
+            Effect::Pop(TraceValue::U64(41)),
+            Effect::Push(TraceValue::U64(42)),
+        ]
+    );
+    assert!(events.iter().any(|event| matches!(
+        event,
+        TraceEvent::Instruction { frame_id: 0, effects, .. }
+            if effects.iter().any(|effect| matches!(
+                effect,
+                Effect::ReadLocal { index: 0, value: TraceValue::U64(41), .. }
+            ))
+    )));
+
+    let closes: Vec<_> = events
+        .iter()
+        .filter(|event| matches!(event, TraceEvent::CloseFrame { .. }))
+        .collect();
+    assert_eq!(
+        closes,
+        vec![
+            &TraceEvent::CloseFrame {
+                frame_id: 1,
+                return_values: vec![TraceValue::U64(42)],
+                gas_left: u64::MAX,
+            },
+            &TraceEvent::CloseFrame {
+                frame_id: 0,
+                return_values: vec![TraceValue::U64(42)],
+                gas_left: u64::MAX,
+            },
+        ]
+    );
+    assert!(matches!(
+        events.last(),
+        Some(TraceEvent::CloseFrame { frame_id: 0, .. })
+    ));
+}
+
+#[test]
+fn trace_abort() {
+    let (storage, module_id) = setup();
+    let vm = MoveVM::new(vec![]).unwrap();
+    let tracer = CollectingTracer::default();
+    run(&vm, &storage, &module_id, "fail", Some(tracer.clone()));
+    let events = tracer.0.borrow();
+
+    assert_eq!(open_frame_names(&events), vec!["fail"]);
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, TraceEvent::CloseFrame { .. })));
+    assert_eq!(
+        events.last(),
+        Some(&TraceEvent::Error {
+            status: "ABORTED".to_string(),
+            sub_status: Some(7),
+        })
+    );
+}
+
+#[test]
+fn trace_sessions_to_file() {
+    let (storage, module_id) = setup();
+    let trace_dir = tempfile::tempdir().unwrap();
+    let trace_path = trace_dir.path().join("trace");
+    let vm = MoveVM::new_with_config(
+        vec![],
+        VMConfig {
+            tracer_config: Some(VMTracerConfig {
+                trace_path: trace_path.clone(),
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    run(&vm, &storage, &module_id, "foo", None);
+    run(&vm, &storage, &module_id, "fail", None);
+
+    let traces = TraceReader::new(BufReader::new(File::open(trace_path).unwrap()))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(traces.len(), 2);
+    assert!(traces.iter().all(|trace| trace.version == TRACE_VERSION));
+    assert_eq!(open_frame_names(&traces[0].events), vec!["foo", "add"]);
+    // The module is cached by the VM, the second session does not load it again
+    assert_eq!(open_frame_names(&traces[1].events), vec!["fail"]);
+    assert!(matches!(
+        traces[1].events.first(),
+        Some(TraceEvent::OpenFrame { .. })
+    ));
+}
@@ -0,0 +1,55 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Branch-hit counters of the executed Move code, used as coverage feedback by fuzzers.
+//!
+//! Once a counter map is registered with `register_counters`, the interpreter increments a
+//! counter for every function entry and every branch it takes, identified by the module, the
+//! function and the source and destination code offsets. Branches are hashed into the map, so
+//! distinct branches may share a counter.
+
+use move_binary_format::file_format::FunctionDefinitionIndex;
+use move_core_types::language_storage::ModuleId;
+use once_cell::sync::OnceCell;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+static COUNTERS: OnceCell<&'static [AtomicU8]> = OnceCell::new();
+
+/// Source offset of the edge recorded when a function is entered
+const ENTRY_OFFSET: u16 = u16::MAX;
+
+/// Registers the counter map updated by the interpreter. Only the first registration is kept,
+/// returns `false` if a map was already registered.
+pub fn register_counters(counters: &'static [AtomicU8]) -> bool {
+    COUNTERS.set(counters).is_ok()
+}
+
+/// Key identifying a function in the counter map, stable across executions
+pub(crate) fn function_key(module: &ModuleId, index: FunctionDefinitionIndex) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    module.hash(&mut hasher);
+    index.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Records the entry in the function identified by `function_key`
+pub(crate) fn record_entry(function_key: u64) {
+    record_branch(function_key, ENTRY_OFFSET, 0)
+}
+
+/// Records a branch from `from` to `to` in the function identified by `function_key`
+pub(crate) fn record_branch(function_key: u64, from: u16, to: u16) {
+    if let Some(counters) = COUNTERS.get() {
+        if counters.is_empty() {
+            return;
+        }
+        let edge = ((from as u64) << 16) | to as u64;
+        let hash = (function_key ^ edge).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        let idx = (hash >> 32) as usize % counters.len();
+        counters[idx].fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -6,14 +6,86 @@ use crate::{
     interpreter::Interpreter,
     loader::{Function, Loader},
 };
-use move_binary_format::file_format::Bytecode;
+use move_binary_format::file_format::{Bytecode, FunctionDefinitionIndex};
+use move_core_types::{
+    annotated_value::MoveValue,
+    language_storage::{ModuleId, TypeTag},
+};
 use move_vm_types::values::{self, Locals};
 use std::{
     collections::BTreeSet,
     io::{self, Write},
     str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
 };
 
+/// A debugger controlling execution, e.g. to serve the Debug Adapter Protocol. It is installed for
+/// the whole process with `set_debugger`, and consulted before each instruction the interpreter
+/// executes, on the thread executing it.
+pub trait Debugger: Send {
+    /// Whether execution should stop before the instruction at `pc` in `function` of `module`,
+    /// with `depth` frames on the call stack (including the current one).
+    fn should_stop(
+        &mut self,
+        module: &ModuleId,
+        function: FunctionDefinitionIndex,
+        pc: u16,
+        depth: usize,
+    ) -> bool;
+
+    /// Called when execution stops, with the frames on the call stack, innermost last. Execution
+    /// resumes when this returns.
+    fn stopped(&mut self, frames: Vec<DebugFrame>);
+}
+
+/// A frame on the call stack, as seen by a `Debugger`.
+#[derive(Debug, Clone)]
+pub struct DebugFrame {
+    pub module: ModuleId,
+    pub function: FunctionDefinitionIndex,
+    pub function_name: String,
+    pub type_arguments: Vec<TypeTag>,
+    pub pc: u16,
+    /// The values of the parameters and locals of the function, `None` for those that are not
+    /// set. References are shown as the value they refer to.
+    pub locals: Vec<Option<MoveValue>>,
+}
+
+static DEBUGGER: Mutex<Option<Box<dyn Debugger>>> = Mutex::new(None);
+
+// Checked before each instruction, to avoid taking the lock when no debugger is installed
+static DEBUGGER_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Install `debugger` for all executions in this process, replacing the current one. `None`
+/// uninstalls it.
+pub fn set_debugger(debugger: Option<Box<dyn Debugger>>) {
+    let mut current = DEBUGGER.lock().unwrap();
+    DEBUGGER_INSTALLED.store(debugger.is_some(), Ordering::Relaxed);
+    *current = debugger;
+}
+
+pub(crate) fn debugger_installed() -> bool {
+    DEBUGGER_INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Stop in the installed debugger if it asks to, computing the frames it is given with `frames`
+pub(crate) fn maybe_stop(
+    module: &ModuleId,
+    function: FunctionDefinitionIndex,
+    pc: u16,
+    depth: usize,
+    frames: impl FnOnce() -> Vec<DebugFrame>,
+) {
+    if let Some(debugger) = DEBUGGER.lock().unwrap().as_mut() {
+        if debugger.should_stop(module, function, pc, depth) {
+            debugger.stopped(frames())
+        }
+    }
+}
+
 #[derive(Debug)]
 enum DebugCommand {
     PrintStack,
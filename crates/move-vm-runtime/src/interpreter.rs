@@ -2,10 +2,13 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(any(debug_assertions, feature = "debugging"))]
+use crate::debug::DebugFrame;
 use crate::{
     loader::{Function, Loader, Resolver},
     native_functions::NativeContext,
     trace,
+    tracing::VMTracer,
 };
 use fail::fail_point;
 use move_binary_format::{
@@ -18,6 +21,7 @@ use move_core_types::{
     language_storage::TypeTag,
     vm_status::{StatusCode, StatusType},
 };
+use move_trace_format::format::Effect;
 use move_vm_config::runtime::VMRuntimeLimitsConfig;
 #[cfg(feature = "gas-profiler")]
 use move_vm_profiler::GasProfiler;
@@ -29,8 +33,8 @@ use move_vm_types::{
     gas::{GasMeter, SimpleInstruction},
     loaded_data::runtime_types::Type,
     values::{
-        self, IntegerValue, Locals, Reference, Struct, StructRef, VMValueCast, Value, Vector,
-        VectorRef,
+        self, IntegerValue, Locals, Reference, Struct, StructRef, VMValueCast, Value, Variant,
+        VariantRef, Vector, VectorRef,
     },
     views::TypeView,
 };
@@ -109,16 +113,28 @@ impl Interpreter {
         data_store: &mut impl DataStore,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Option<VMTracer>,
         loader: &Loader,
     ) -> VMResult<Vec<Value>> {
         let mut interpreter = Interpreter {
-            operand_stack: Stack::new(),
+            operand_stack: Stack::new(tracer.is_some()),
             call_stack: CallStack::new(),
             runtime_limits_config: loader.vm_config().runtime_limits_config.clone(),
         };
         profile_open_frame!(gas_meter, function.pretty_string());
 
         if function.is_native() {
+            if let Some(tracer) = tracer {
+                tracer
+                    .open_frame(
+                        &function,
+                        &ty_args,
+                        &args,
+                        loader,
+                        gas_meter.remaining_gas(),
+                    )
+                    .map_err(|e| e.finish(Location::Undefined))?;
+            }
             for arg in args {
                 interpreter
                     .operand_stack
@@ -143,10 +159,14 @@ impl Interpreter {
 
             profile_close_frame!(gas_meter, function.pretty_string());
 
+            if let Some(tracer) = tracer {
+                tracer.close_frame(&return_values, gas_meter.remaining_gas());
+            }
+
             Ok(return_values.into_iter().collect())
         } else {
             interpreter.execute_main(
-                loader, data_store, gas_meter, extensions, function, ty_args, args,
+                loader, data_store, gas_meter, extensions, tracer, function, ty_args, args,
             )
         }
     }
@@ -163,10 +183,22 @@ impl Interpreter {
         data_store: &mut impl DataStore,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Option<VMTracer>,
         function: Arc<Function>,
         ty_args: Vec<Type>,
         args: Vec<Value>,
     ) -> VMResult<Vec<Value>> {
+        if let Some(tracer) = tracer {
+            tracer
+                .open_frame(
+                    &function,
+                    &ty_args,
+                    &args,
+                    loader,
+                    gas_meter.remaining_gas(),
+                )
+                .map_err(|e| self.set_location(e))?;
+        }
         let mut locals = Locals::new(function.local_count());
         for (i, value) in args.into_iter().enumerate() {
             locals
@@ -187,7 +219,7 @@ impl Interpreter {
         loop {
             let resolver = current_frame.resolver(link_context, loader);
             let exit_code = current_frame //self
-                .execute_code(&resolver, &mut self, gas_meter)
+                .execute_code(&resolver, &mut self, gas_meter, tracer)
                 .map_err(|err| self.maybe_core_dump(err, &current_frame))?;
             match exit_code {
                 ExitCode::Return => {
@@ -203,6 +235,15 @@ impl Interpreter {
 
                     profile_close_frame!(gas_meter, current_frame.function.pretty_string());
 
+                    if let Some(tracer) = tracer {
+                        tracer.close_frame(
+                            self.operand_stack
+                                .last_n(current_frame.function.return_type_count())
+                                .map_err(|e| set_err_info!(current_frame, e))?,
+                            gas_meter.remaining_gas(),
+                        );
+                    }
+
                     if let Some(frame) = self.call_stack.pop() {
                         // Note: the caller will find the callee's return values at the top of the shared operand stack
                         current_frame = frame;
@@ -230,9 +271,13 @@ impl Interpreter {
                             (func.local_count() as u64).into(),
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
+                    self.trace_open_frame(tracer, &func, &[], loader, gas_meter)
+                        .map_err(|e| set_err_info!(current_frame, e))?;
 
                     if func.is_native() {
-                        self.call_native(&resolver, gas_meter, extensions, func, vec![])?;
+                        self.call_native(&resolver, gas_meter, extensions, func.clone(), vec![])?;
+                        self.trace_close_native_frame(tracer, &func, gas_meter)
+                            .map_err(|e| set_err_info!(current_frame, e))?;
 
                         current_frame.pc += 1; // advance past the Call instruction in the caller
 
@@ -274,9 +319,13 @@ impl Interpreter {
                             (func.local_count() as u64).into(),
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
+                    self.trace_open_frame(tracer, &func, &ty_args, loader, gas_meter)
+                        .map_err(|e| set_err_info!(current_frame, e))?;
 
                     if func.is_native() {
-                        self.call_native(&resolver, gas_meter, extensions, func, ty_args)?;
+                        self.call_native(&resolver, gas_meter, extensions, func.clone(), ty_args)?;
+                        self.trace_close_native_frame(tracer, &func, gas_meter)
+                            .map_err(|e| set_err_info!(current_frame, e))?;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         profile_close_frame!(gas_meter, func_name.clone());
 
@@ -297,6 +346,44 @@ impl Interpreter {
         }
     }
 
+    /// Trace the call to `func`, whose arguments are at the top of the operand stack.
+    fn trace_open_frame(
+        &self,
+        tracer: &mut Option<VMTracer>,
+        func: &Function,
+        ty_args: &[Type],
+        loader: &Loader,
+        gas_meter: &impl GasMeter,
+    ) -> PartialVMResult<()> {
+        match tracer {
+            Some(tracer) => tracer.open_frame(
+                func,
+                ty_args,
+                self.operand_stack.last_n(func.arg_count())?,
+                loader,
+                gas_meter.remaining_gas(),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    /// Trace the return of the native `func`, whose return values are at the top of the operand
+    /// stack.
+    fn trace_close_native_frame(
+        &self,
+        tracer: &mut Option<VMTracer>,
+        func: &Function,
+        gas_meter: &impl GasMeter,
+    ) -> PartialVMResult<()> {
+        if let Some(tracer) = tracer {
+            tracer.close_frame(
+                self.operand_stack.last_n(func.return_type_count())?,
+                gas_meter.remaining_gas(),
+            );
+        }
+        Ok(())
+    }
+
     /// Returns a `Frame` if the call is to a Move function. Calls to native functions are
     /// "inlined" and this returns `None`.
     ///
@@ -595,6 +682,17 @@ impl Interpreter {
         Ok(())
     }
 
+    /// The frames on the call stack, ending with `current_frame`, as seen by a debugger
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    fn debug_frames(&self, current_frame: &Frame, loader: &Loader) -> Vec<DebugFrame> {
+        self.call_stack
+            .0
+            .iter()
+            .chain(std::iter::once(current_frame))
+            .map(|frame| frame.debug_frame(loader))
+            .collect()
+    }
+
     /// Generate a string which is the status of the interpreter: call stack, current bytecode
     /// stream, locals and operand stack.
     ///
@@ -686,18 +784,30 @@ const CALL_STACK_SIZE_LIMIT: usize = 1024;
 /// The operand stack.
 struct Stack {
     value: Vec<Value>,
+    /// Effects of the current instruction, only recorded when the execution is traced.
+    effects: Option<Vec<Effect>>,
 }
 
 impl Stack {
     /// Create a new empty operand stack.
-    fn new() -> Self {
-        Stack { value: vec![] }
+    fn new(record_effects: bool) -> Self {
+        Stack {
+            value: vec![],
+            effects: record_effects.then(Vec::new),
+        }
+    }
+
+    fn record(&mut self, effect: impl FnOnce() -> Effect) {
+        if let Some(effects) = &mut self.effects {
+            effects.push(effect())
+        }
     }
 
     /// Push a `Value` on the stack if the max stack size has not been reached. Abort execution
     /// otherwise.
     fn push(&mut self, value: Value) -> PartialVMResult<()> {
         if self.value.len() < OPERAND_STACK_SIZE_LIMIT {
+            self.record(|| Effect::Push(value.as_trace_value()));
             self.value.push(value);
             Ok(())
         } else {
@@ -707,9 +817,12 @@ impl Stack {
 
     /// Pop a `Value` off the stack or abort execution if the stack is empty.
     fn pop(&mut self) -> PartialVMResult<Value> {
-        self.value
+        let value = self
+            .value
             .pop()
-            .ok_or_else(|| PartialVMError::new(StatusCode::EMPTY_VALUE_STACK))
+            .ok_or_else(|| PartialVMError::new(StatusCode::EMPTY_VALUE_STACK))?;
+        self.record(|| Effect::Pop(value.as_trace_value()));
+        Ok(value)
     }
 
     /// Pop a `Value` of a given type off the stack. Abort if the value is not of the given
@@ -729,6 +842,9 @@ impl Stack {
             .checked_sub(n as usize)
             .ok_or_else(|| PartialVMError::new(StatusCode::EMPTY_VALUE_STACK))?;
         let args = self.value.split_off(remaining_stack_size);
+        for arg in args.iter().rev() {
+            self.record(|| Effect::Pop(arg.as_trace_value()));
+        }
         Ok(args)
     }
 
@@ -791,14 +907,63 @@ enum ExitCode {
 }
 
 impl Frame {
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    fn debug_frame(&self, loader: &Loader) -> DebugFrame {
+        let locals = self
+            .function
+            .local_types()
+            .iter()
+            .enumerate()
+            .map(|(idx, ty)| self.debug_local(loader, idx, ty).ok().flatten())
+            .collect();
+        DebugFrame {
+            module: self.function.module_id().clone(),
+            function: self.function.index(),
+            function_name: self.function.name().to_string(),
+            type_arguments: self
+                .ty_args
+                .iter()
+                .filter_map(|ty| loader.type_to_type_tag(ty).ok())
+                .collect(),
+            pc: self.pc,
+            locals,
+        }
+    }
+
+    /// The value of the local at `idx`, of type `ty`, annotated for a debugger
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    fn debug_local(
+        &self,
+        loader: &Loader,
+        idx: usize,
+        ty: &Type,
+    ) -> PartialVMResult<Option<move_core_types::annotated_value::MoveValue>> {
+        if self.locals.is_invalid(idx)? {
+            return Ok(None);
+        }
+        let value = self.locals.copy_loc(idx)?;
+        let (value, ty) = match ty.subst(&self.ty_args)? {
+            Type::Reference(ty) | Type::MutableReference(ty) => {
+                (value.value_as::<Reference>()?.read_ref()?, *ty)
+            }
+            ty => (value, ty),
+        };
+        let layout = loader.type_to_type_layout(&ty)?;
+        let annotated_layout = loader.type_to_fully_annotated_layout(&ty)?;
+        Ok(Some(
+            value.as_move_value(&layout).decorate(&annotated_layout),
+        ))
+    }
+
     /// Execute a Move function until a return or a call opcode is found.
     fn execute_code(
         &mut self,
         resolver: &Resolver,
         interpreter: &mut Interpreter,
         gas_meter: &mut impl GasMeter,
+        tracer: &mut Option<VMTracer>,
     ) -> VMResult<ExitCode> {
-        self.execute_code_impl(resolver, interpreter, gas_meter)
+        self.execute_code_impl(resolver, interpreter, gas_meter, tracer)
             .map_err(|e| {
                 let e = if resolver.loader().vm_config().error_execution_state {
                     e.with_exec_state(interpreter.get_internal_state())
@@ -908,6 +1073,11 @@ impl Frame {
             Bytecode::CopyLoc(idx) => {
                 // TODO(Gas): We should charge gas before copying the value.
                 let local = locals.copy_loc(*idx as usize)?;
+                interpreter.operand_stack.record(|| Effect::ReadLocal {
+                    index: *idx as u16,
+                    value: local.as_trace_value(),
+                    moved: false,
+                });
                 gas_meter.charge_copy_loc(&local)?;
                 interpreter.operand_stack.push(local)?;
             }
@@ -919,6 +1089,11 @@ impl Frame {
                         .vm_config()
                         .enable_invariant_violation_check_in_swap_loc,
                 )?;
+                interpreter.operand_stack.record(|| Effect::ReadLocal {
+                    index: *idx as u16,
+                    value: local.as_trace_value(),
+                    moved: true,
+                });
                 gas_meter.charge_move_loc(&local)?;
 
                 interpreter.operand_stack.push(local)?;
@@ -926,6 +1101,10 @@ impl Frame {
             Bytecode::StLoc(idx) => {
                 let value_to_store = interpreter.operand_stack.pop()?;
                 gas_meter.charge_store_loc(&value_to_store)?;
+                interpreter.operand_stack.record(|| Effect::WriteLocal {
+                    index: *idx as u16,
+                    value: value_to_store.as_trace_value(),
+                });
                 locals.store_loc(
                     *idx as usize,
                     value_to_store,
@@ -1024,6 +1203,91 @@ impl Frame {
                     interpreter.operand_stack.push(value)?;
                 }
             }
+            Bytecode::PackVariant(vidx) => {
+                let (field_count, variant_tag) = resolver.variant_field_count_and_tag(*vidx);
+                let enum_type = resolver.get_enum_type(*vidx);
+                Self::check_depth_of_type(resolver, &enum_type)?;
+                gas_meter.charge_pack_variant(
+                    false,
+                    interpreter.operand_stack.last_n(field_count as usize)?,
+                )?;
+                let args = interpreter.operand_stack.popn(field_count)?;
+                interpreter
+                    .operand_stack
+                    .push(Value::variant(Variant::pack(variant_tag, args)))?;
+            }
+            Bytecode::PackVariantGeneric(vidx) => {
+                let (field_count, variant_tag) =
+                    resolver.variant_instantiation_field_count_and_tag(*vidx);
+                let ty = resolver.instantiate_generic_enum(*vidx, ty_args)?;
+                Self::check_depth_of_type(resolver, &ty)?;
+                gas_meter.charge_pack_variant(
+                    true,
+                    interpreter.operand_stack.last_n(field_count as usize)?,
+                )?;
+                let args = interpreter.operand_stack.popn(field_count)?;
+                interpreter
+                    .operand_stack
+                    .push(Value::variant(Variant::pack(variant_tag, args)))?;
+            }
+            Bytecode::UnpackVariant(vidx) => {
+                let (_, variant_tag) = resolver.variant_field_count_and_tag(*vidx);
+                let variant = interpreter.operand_stack.pop_as::<Variant>()?;
+                variant.check_tag(variant_tag)?;
+
+                gas_meter.charge_unpack_variant(false, variant.field_views())?;
+
+                for value in variant.unpack()? {
+                    interpreter.operand_stack.push(value)?;
+                }
+            }
+            Bytecode::UnpackVariantGeneric(vidx) => {
+                let (_, variant_tag) = resolver.variant_instantiation_field_count_and_tag(*vidx);
+                let variant = interpreter.operand_stack.pop_as::<Variant>()?;
+                variant.check_tag(variant_tag)?;
+
+                gas_meter.charge_unpack_variant(true, variant.field_views())?;
+
+                for value in variant.unpack()? {
+                    interpreter.operand_stack.push(value)?;
+                }
+            }
+            Bytecode::UnpackVariantImmRef(vidx) | Bytecode::UnpackVariantMutRef(vidx) => {
+                let (_, variant_tag) = resolver.variant_field_count_and_tag(*vidx);
+                let reference = interpreter.operand_stack.pop_as::<VariantRef>()?;
+                reference.check_tag(variant_tag)?;
+                let field_refs = reference.unpack_variant()?;
+
+                gas_meter.charge_unpack_variant(false, field_refs.iter())?;
+
+                for value in field_refs {
+                    interpreter.operand_stack.push(value)?;
+                }
+            }
+            Bytecode::UnpackVariantGenericImmRef(vidx)
+            | Bytecode::UnpackVariantGenericMutRef(vidx) => {
+                let (_, variant_tag) = resolver.variant_instantiation_field_count_and_tag(*vidx);
+                let reference = interpreter.operand_stack.pop_as::<VariantRef>()?;
+                reference.check_tag(variant_tag)?;
+                let field_refs = reference.unpack_variant()?;
+
+                gas_meter.charge_unpack_variant(true, field_refs.iter())?;
+
+                for value in field_refs {
+                    interpreter.operand_stack.push(value)?;
+                }
+            }
+            Bytecode::VariantSwitch(jt_idx) => {
+                let reference = interpreter.operand_stack.pop_as::<VariantRef>()?;
+                gas_meter.charge_variant_switch(&reference)?;
+                let tag = reference.get_tag()?;
+                let jump_table = function.jump_table(*jt_idx);
+                *pc = *jump_table.jump_table.get(tag as usize).ok_or_else(|| {
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(format!("variant tag {} is not in the jump table", tag))
+                })?;
+                return Ok(InstrRet::Branch);
+            }
             Bytecode::ReadRef => {
                 let reference = interpreter.operand_stack.pop_as::<Reference>()?;
                 gas_meter.charge_read_ref(reference.value_view())?;
@@ -1281,8 +1545,13 @@ impl Frame {
         resolver: &Resolver,
         interpreter: &mut Interpreter,
         gas_meter: &mut impl GasMeter,
+        tracer: &mut Option<VMTracer>,
     ) -> PartialVMResult<ExitCode> {
         let code = self.function.code();
+        #[cfg(feature = "coverage-counters")]
+        if self.pc == 0 {
+            crate::coverage::record_entry(self.function.coverage_key());
+        }
         loop {
             for instruction in &code[self.pc as usize..] {
                 trace!(
@@ -1293,6 +1562,16 @@ impl Frame {
                     resolver,
                     interpreter
                 );
+                #[cfg(any(debug_assertions, feature = "debugging"))]
+                if crate::debug::debugger_installed() {
+                    crate::debug::maybe_stop(
+                        self.function.module_id(),
+                        self.function.index(),
+                        self.pc,
+                        interpreter.call_stack.0.len() + 1,
+                        || interpreter.debug_frames(self, resolver.loader()),
+                    );
+                }
 
                 fail_point!("move_vm::interpreter_loop", |_| {
                     Err(
@@ -1304,6 +1583,15 @@ impl Frame {
 
                 profile_open_instr!(gas_meter, format!("{:?}", instruction));
 
+                #[cfg(feature = "coverage-counters")]
+                let from = self.pc;
+
+                let pc = self.pc;
+                let gas_before = gas_meter.remaining_gas();
+                // Drop what was recorded outside of instructions, e.g. by calls and returns
+                if let Some(effects) = &mut interpreter.operand_stack.effects {
+                    effects.clear();
+                }
                 let r = Self::execute_instruction(
                     &mut self.pc,
                     &mut self.locals,
@@ -1313,10 +1601,43 @@ impl Frame {
                     interpreter,
                     gas_meter,
                     instruction,
-                )?;
+                );
+                if let Some(tracer) = tracer {
+                    let gas_after = gas_meter.remaining_gas();
+                    tracer.instruction(
+                        pc,
+                        instruction,
+                        gas_before,
+                        gas_before.saturating_sub(gas_after),
+                        interpreter
+                            .operand_stack
+                            .effects
+                            .as_mut()
+                            .map(std::mem::take)
+                            .unwrap_or_default(),
+                    );
+                }
+                let r = r?;
 
                 profile_close_instr!(gas_meter, format!("{:?}", instruction));
 
+                #[cfg(feature = "coverage-counters")]
+                match r {
+                    InstrRet::Ok
+                        if matches!(instruction, Bytecode::BrTrue(_) | Bytecode::BrFalse(_)) =>
+                    {
+                        crate::coverage::record_branch(
+                            self.function.coverage_key(),
+                            from,
+                            from.wrapping_add(1),
+                        )
+                    }
+                    InstrRet::Branch => {
+                        crate::coverage::record_branch(self.function.coverage_key(), from, self.pc)
+                    }
+                    _ => (),
+                }
+
                 match r {
                     InstrRet::Ok => (),
                     InstrRet::ExitCode(exit_code) => {
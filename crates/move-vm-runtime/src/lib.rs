@@ -10,6 +10,8 @@
 //! other blockchains can use it as well. The VM isn't there yet, but hopefully will be there
 //! soon.
 
+#[cfg(feature = "coverage-counters")]
+pub mod coverage;
 pub mod data_cache;
 mod interpreter;
 mod loader;
@@ -24,7 +26,7 @@ mod tracing;
 
 // Only include debugging functionality in debug builds
 #[cfg(any(debug_assertions, feature = "debugging"))]
-mod debug;
+pub mod debug;
 
 #[cfg(test)]
 mod unit_tests;
@@ -14,6 +14,8 @@ use move_binary_format::{
         FieldInstantiationIndex, FunctionDefinition, FunctionDefinitionIndex, FunctionHandleIndex,
         FunctionInstantiationIndex, SignatureIndex, SignatureToken, StructDefInstantiationIndex,
         StructDefinitionIndex, StructFieldInformation, TableIndex, TypeParameterIndex,
+        VariantHandleIndex, VariantInstantiationHandleIndex, VariantJumpTable,
+        VariantJumpTableIndex, VariantTag,
     },
     IndexKind,
 };
@@ -30,7 +32,7 @@ use move_core_types::{
 use move_vm_config::runtime::VMConfig;
 use move_vm_types::{
     data_store::DataStore,
-    loaded_data::runtime_types::{CachedStructIndex, DepthFormula, StructType, Type},
+    loaded_data::runtime_types::{CachedStructIndex, DepthFormula, StructType, Type, VariantType},
 };
 use parking_lot::RwLock;
 use std::{
@@ -216,7 +218,8 @@ impl ModuleCache {
         let link_context = data_store.link_context();
         let runtime_id = module.self_id();
 
-        // Add new structs and collect their field signatures
+        // Add new structs and enums and collect their field signatures. Structs have a single list
+        // of field signatures, enums have one per variant.
         let mut field_signatures = vec![];
         for (idx, struct_def) in module.struct_defs().iter().enumerate() {
             let struct_handle = module.struct_handle_at(struct_def.struct_handle);
@@ -242,6 +245,7 @@ impl ModuleCache {
                 StructType {
                     fields: vec![],
                     field_names,
+                    variants: None,
                     abilities: struct_handle.abilities,
                     type_parameters: struct_handle.type_parameters.clone(),
                     name: name.to_owned(),
@@ -257,16 +261,70 @@ impl ModuleCache {
             };
 
             let signatures: Vec<_> = fields.iter().map(|f| &f.signature.0).collect();
+            field_signatures.push(vec![signatures])
+        }
+
+        for (idx, enum_def) in module.enum_defs().iter().enumerate() {
+            let enum_handle = module.struct_handle_at(enum_def.enum_handle);
+            let name = module.identifier_at(enum_handle.name);
+            let enum_key = (runtime_id.clone(), name.to_owned());
+
+            if self.structs.contains(&enum_key) {
+                continue;
+            }
+
+            let variants = enum_def
+                .variants
+                .iter()
+                .map(|variant| VariantType {
+                    name: module.identifier_at(variant.variant_name).to_owned(),
+                    fields: vec![],
+                    field_names: variant
+                        .fields
+                        .iter()
+                        .map(|f| module.identifier_at(f.name).to_owned())
+                        .collect(),
+                })
+                .collect();
+
+            let defining_id = data_store.defining_module(&runtime_id, name)?;
+
+            self.structs.insert(
+                enum_key,
+                StructType {
+                    fields: vec![],
+                    field_names: vec![],
+                    variants: Some(variants),
+                    abilities: enum_handle.abilities,
+                    type_parameters: enum_handle.type_parameters.clone(),
+                    name: name.to_owned(),
+                    defining_id,
+                    runtime_id: runtime_id.clone(),
+                    struct_def: StructDefinitionIndex(idx as u16),
+                    depth: None,
+                },
+            )?;
+
+            let signatures: Vec<Vec<_>> = enum_def
+                .variants
+                .iter()
+                .map(|variant| variant.fields.iter().map(|f| &f.signature.0).collect())
+                .collect();
             field_signatures.push(signatures)
         }
 
         // Convert field signatures into types after adding all structs because field types might
         // refer to structs in the same module.
         let mut field_types = vec![];
-        for signature in field_signatures {
-            let tys: Vec<_> = signature
+        for signatures in field_signatures {
+            let tys: Vec<Vec<_>> = signatures
                 .iter()
-                .map(|tok| self.make_type(module, tok))
+                .map(|signature| {
+                    signature
+                        .iter()
+                        .map(|tok| self.make_type(module, tok))
+                        .collect::<PartialVMResult<_>>()
+                })
                 .collect::<PartialVMResult<_>>()?;
             field_types.push(tys);
         }
@@ -281,7 +339,7 @@ impl ModuleCache {
             .zip(self.structs.binaries.iter_mut().rev())
         {
             match Arc::get_mut(struct_type) {
-                Some(struct_type) => struct_type.fields = fields,
+                Some(struct_type) => Self::set_field_types(struct_type, fields),
                 None => {
                     // we have pending references to the `Arc` which is impossible,
                     // given the code that adds the `Arc` is above and no reference to
@@ -290,7 +348,7 @@ impl ModuleCache {
                     // over and log the issue.
                     error!("Arc<StructType> cannot have any live reference while publishing");
                     let mut struct_copy = (**struct_type).clone();
-                    struct_copy.fields = fields;
+                    Self::set_field_types(&mut struct_copy, fields);
                     *struct_type = Arc::new(struct_copy);
                 }
             }
@@ -319,7 +377,18 @@ impl ModuleCache {
 
         for (idx, func) in module.function_defs().iter().enumerate() {
             let findex = FunctionDefinitionIndex(idx as TableIndex);
-            let function = Function::new(natives, findex, func, module);
+            #[allow(unused_mut)]
+            let mut function = Function::new(natives, findex, func, module);
+            #[cfg(any(debug_assertions, feature = "debugging"))]
+            {
+                let handle = module.function_handle_at(func.function);
+                let locals = func.code.as_ref().map(|code| code.locals);
+                function.local_types = std::iter::once(handle.parameters)
+                    .chain(locals)
+                    .flat_map(|idx| &module.signature_at(idx).0)
+                    .map(|tok| self.make_type(module, tok))
+                    .collect::<PartialVMResult<_>>()?;
+            }
             self.functions.push(Arc::new(function));
         }
 
@@ -328,6 +397,19 @@ impl ModuleCache {
             .insert((link_context, runtime_id), loaded_module)
     }
 
+    // Set the field types of a struct (a single list of fields) or of an enum (one list per
+    // variant).
+    fn set_field_types(struct_type: &mut StructType, mut fields: Vec<Vec<Type>>) {
+        match &mut struct_type.variants {
+            Some(variants) => {
+                for (variant, fields) in variants.iter_mut().zip(fields) {
+                    variant.fields = fields;
+                }
+            }
+            None => struct_type.fields = fields.pop().unwrap_or_default(),
+        }
+    }
+
     // `make_type` is the entry point to "translate" a `SignatureToken` to a `Type`
     fn make_type(&self, module: &CompiledModule, tok: &SignatureToken) -> PartialVMResult<Type> {
         let res = match tok {
@@ -399,8 +481,7 @@ impl ModuleCache {
         }
 
         let formulas = struct_type
-            .fields
-            .iter()
+            .all_fields()
             .map(|field_type| self.calculate_depth_of_type(field_type, depth_cache))
             .collect::<PartialVMResult<Vec<_>>>()?;
         let mut formula = DepthFormula::normalize(formulas);
@@ -1476,6 +1557,40 @@ impl<'a> Resolver<'a> {
         ))))
     }
 
+    pub(crate) fn get_enum_type(&self, vidx: VariantHandleIndex) -> Type {
+        let variant = self.binary.loaded.variant_handle_at(vidx);
+        Type::Struct(variant.enum_idx)
+    }
+
+    pub(crate) fn instantiate_generic_enum(
+        &self,
+        vidx: VariantInstantiationHandleIndex,
+        ty_args: &[Type],
+    ) -> PartialVMResult<Type> {
+        let loaded_module = &*self.binary.loaded;
+        let variant = loaded_module.variant_instantiation_handle_at(vidx);
+        let enum_inst = loaded_module.enum_instantiation_at(variant.enum_inst);
+        let instantiation =
+            loaded_module.instantiation_signature_at(enum_inst.instantiation_idx)?;
+
+        // Same node count check as for struct instantiations
+        let mut sum_nodes = 1u64;
+        for ty in ty_args.iter().chain(instantiation.iter()) {
+            sum_nodes = sum_nodes.saturating_add(self.loader.count_type_nodes(ty));
+            if sum_nodes > MAX_TYPE_INSTANTIATION_NODES {
+                return Err(PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES));
+            }
+        }
+
+        Ok(Type::StructInstantiation(Box::new((
+            enum_inst.def,
+            instantiation
+                .iter()
+                .map(|ty| self.subst(ty, ty_args))
+                .collect::<PartialVMResult<_>>()?,
+        ))))
+    }
+
     fn single_type_at(&self, idx: SignatureIndex) -> &Type {
         self.binary.loaded.single_type_at(idx)
     }
@@ -1517,6 +1632,27 @@ impl<'a> Resolver<'a> {
         self.binary.loaded.field_instantiation_count(idx.0)
     }
 
+    //
+    // Variant resolution
+    //
+
+    // Returns the number of fields and the tag of the variant
+    pub(crate) fn variant_field_count_and_tag(
+        &self,
+        vidx: VariantHandleIndex,
+    ) -> (u16, VariantTag) {
+        let variant = self.binary.loaded.variant_handle_at(vidx);
+        (variant.field_count, variant.tag)
+    }
+
+    pub(crate) fn variant_instantiation_field_count_and_tag(
+        &self,
+        vidx: VariantInstantiationHandleIndex,
+    ) -> (u16, VariantTag) {
+        let variant = self.binary.loaded.variant_instantiation_handle_at(vidx);
+        (variant.field_count, variant.tag)
+    }
+
     pub(crate) fn type_to_type_layout(&self, ty: &Type) -> PartialVMResult<R::MoveTypeLayout> {
         self.loader.type_to_type_layout(ty)
     }
@@ -1557,6 +1693,17 @@ pub(crate) struct LoadedModule {
     // materialized instantiations, whether partial or not
     struct_instantiations: Vec<StructInstantiation>,
 
+    // enum definitions as indexes into the global table of types, enums share that table with
+    // structs
+    #[allow(dead_code)]
+    enums: Vec<CachedStructIndex>,
+    // materialized enum instantiations, whether partial or not
+    enum_instantiations: Vec<EnumInstantiation>,
+    // variants of enums defined in this module
+    variant_handles: Vec<VariantHandle>,
+    // variants of instantiations of enums defined in this module
+    variant_instantiation_handles: Vec<VariantInstantiationHandle>,
+
     // functions as indexes into the Loader function list
     // That is effectively an indirection over the ref table:
     // the instruction carries an index into this table which contains the index into the
@@ -1618,6 +1765,10 @@ impl LoadedModule {
         let mut struct_refs = vec![];
         let mut structs = vec![];
         let mut struct_instantiations = vec![];
+        let mut enums = vec![];
+        let mut enum_instantiations = vec![];
+        let mut variant_handles = vec![];
+        let mut variant_instantiation_handles = vec![];
         let mut function_refs = vec![];
         let mut function_instantiations = vec![];
         let mut field_handles = vec![];
@@ -1657,6 +1808,49 @@ impl LoadedModule {
             });
         }
 
+        for enum_def in module.enum_defs() {
+            enums.push(struct_refs[enum_def.enum_handle.0 as usize]);
+        }
+
+        for enum_inst in module.enum_instantiations() {
+            let instantiation_idx = enum_inst.type_parameters;
+            cache_signatures(
+                &mut instantiation_signatures,
+                module,
+                instantiation_idx,
+                cache,
+            )?;
+            enum_instantiations.push(EnumInstantiation {
+                def: enums[enum_inst.def.0 as usize],
+                instantiation_idx,
+            });
+        }
+
+        let variant_field_count = |enum_def_idx: usize, tag: VariantTag| {
+            module.enum_defs()[enum_def_idx].variants[tag as usize]
+                .fields
+                .len() as u16
+        };
+
+        for handle in module.variant_handles() {
+            let def = handle.enum_def.0 as usize;
+            variant_handles.push(VariantHandle {
+                field_count: variant_field_count(def, handle.variant),
+                enum_idx: enums[def],
+                tag: handle.variant,
+            });
+        }
+
+        for handle in module.variant_instantiation_handles() {
+            let enum_inst = module.enum_instantiation_at(handle.enum_def);
+            let def = enum_inst.def.0 as usize;
+            variant_instantiation_handles.push(VariantInstantiationHandle {
+                field_count: variant_field_count(def, handle.variant),
+                enum_inst: handle.enum_def.0 as usize,
+                tag: handle.variant,
+            });
+        }
+
         for func_handle in module.function_handles() {
             let func_name = module.identifier_at(func_handle.name);
             let module_handle = module.module_handle_at(func_handle.module);
@@ -1761,6 +1955,10 @@ impl LoadedModule {
             struct_refs,
             structs,
             struct_instantiations,
+            enums,
+            enum_instantiations,
+            variant_handles,
+            variant_instantiation_handles,
             function_refs,
             function_instantiations,
             field_handles,
@@ -1779,6 +1977,21 @@ impl LoadedModule {
         &self.struct_instantiations[idx as usize]
     }
 
+    fn enum_instantiation_at(&self, idx: usize) -> &EnumInstantiation {
+        &self.enum_instantiations[idx]
+    }
+
+    fn variant_handle_at(&self, idx: VariantHandleIndex) -> &VariantHandle {
+        &self.variant_handles[idx.0 as usize]
+    }
+
+    fn variant_instantiation_handle_at(
+        &self,
+        idx: VariantInstantiationHandleIndex,
+    ) -> &VariantInstantiationHandle {
+        &self.variant_instantiation_handles[idx.0 as usize]
+    }
+
     fn function_at(&self, idx: u16) -> usize {
         self.function_refs[idx as usize]
     }
@@ -1826,6 +2039,7 @@ pub(crate) struct Function {
     file_format_version: u32,
     index: FunctionDefinitionIndex,
     code: Vec<Bytecode>,
+    jump_tables: Vec<VariantJumpTable>,
     parameters: SignatureIndex,
     return_: SignatureIndex,
     type_parameters: Vec<AbilitySet>,
@@ -1836,6 +2050,11 @@ pub(crate) struct Function {
     parameters_len: usize,
     locals_len: usize,
     return_len: usize,
+    #[cfg(feature = "coverage-counters")]
+    coverage_key: u64,
+    // Types of the parameters and locals, for debuggers to decode their values
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    local_types: Vec<Type>,
 }
 
 impl Function {
@@ -1863,20 +2082,24 @@ impl Function {
         let parameters = handle.parameters;
         let parameters_len = module.signature_at(parameters).0.len();
         // Native functions do not have a code unit
-        let (code, locals_len) = match &def.code {
+        let (code, jump_tables, locals_len) = match &def.code {
             Some(code) => (
                 code.code.clone(),
+                code.jump_tables.clone(),
                 parameters_len + module.signature_at(code.locals).0.len(),
             ),
-            None => (vec![], 0),
+            None => (vec![], vec![], 0),
         };
         let return_ = handle.return_;
         let return_len = module.signature_at(return_).0.len();
         let type_parameters = handle.type_parameters.clone();
+        #[cfg(feature = "coverage-counters")]
+        let coverage_key = crate::coverage::function_key(&module_id, index);
         Self {
             file_format_version: module.version(),
             index,
             code,
+            jump_tables,
             parameters,
             return_,
             type_parameters,
@@ -1887,6 +2110,10 @@ impl Function {
             parameters_len,
             locals_len,
             return_len,
+            #[cfg(feature = "coverage-counters")]
+            coverage_key,
+            #[cfg(any(debug_assertions, feature = "debugging"))]
+            local_types: vec![],
         }
     }
 
@@ -1903,6 +2130,11 @@ impl Function {
         self.index
     }
 
+    #[cfg(feature = "coverage-counters")]
+    pub(crate) fn coverage_key(&self) -> u64 {
+        self.coverage_key
+    }
+
     pub(crate) fn get_resolver<'a>(
         &self,
         link_context: AccountAddress,
@@ -1928,10 +2160,19 @@ impl Function {
         self.name.as_str()
     }
 
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn local_types(&self) -> &[Type] {
+        &self.local_types
+    }
+
     pub(crate) fn code(&self) -> &[Bytecode] {
         &self.code
     }
 
+    pub(crate) fn jump_table(&self, idx: VariantJumpTableIndex) -> &VariantJumpTable {
+        &self.jump_tables[idx.0 as usize]
+    }
+
     pub(crate) fn type_parameters(&self) -> &[AbilitySet] {
         &self.type_parameters
     }
@@ -2012,6 +2253,33 @@ struct StructInstantiation {
     instantiation_idx: SignatureIndex,
 }
 
+#[derive(Debug)]
+struct EnumInstantiation {
+    // `ModuleCache::structs` global table index. It is the generic type.
+    def: CachedStructIndex,
+    instantiation_idx: SignatureIndex,
+}
+
+// A variant of an enum defined in the module
+#[derive(Debug)]
+struct VariantHandle {
+    // variant field count
+    field_count: u16,
+    // `ModuleCache::structs` global table index
+    enum_idx: CachedStructIndex,
+    tag: VariantTag,
+}
+
+// A variant of an enum instantiation defined in the module
+#[derive(Debug)]
+struct VariantInstantiationHandle {
+    // variant field count
+    field_count: u16,
+    // index into `LoadedModule::enum_instantiations`
+    enum_inst: usize,
+    tag: VariantTag,
+}
+
 // A field handle. The offset is the only used information when operating on a field
 #[derive(Debug)]
 struct FieldHandle {
@@ -2036,8 +2304,9 @@ struct FieldInstantiation {
 struct StructInfo {
     runtime_struct_tag: Option<StructTag>,
     defining_struct_tag: Option<StructTag>,
-    struct_layout: Option<R::MoveStructLayout>,
-    annotated_struct_layout: Option<A::MoveStructLayout>,
+    // the layout of the struct or enum
+    struct_layout: Option<R::MoveTypeLayout>,
+    annotated_struct_layout: Option<A::MoveTypeLayout>,
     node_count: Option<u64>,
     annotated_node_count: Option<u64>,
 }
@@ -2218,7 +2487,7 @@ impl Loader {
         ty_args: &[Type],
         count: &mut u64,
         depth: u64,
-    ) -> PartialVMResult<R::MoveStructLayout> {
+    ) -> PartialVMResult<R::MoveTypeLayout> {
         if let Some(struct_map) = self.type_cache.read().structs.get(&gidx) {
             if let Some(struct_info) = struct_map.get(ty_args) {
                 if let Some(node_count) = &struct_info.node_count {
@@ -2232,19 +2501,28 @@ impl Loader {
 
         let count_before = *count;
         let struct_type = self.module_cache.read().struct_at(gidx);
-        let field_tys = struct_type
-            .fields
-            .iter()
-            .map(|ty| self.subst(ty, ty_args))
-            .collect::<PartialVMResult<Vec<_>>>()?;
-        let field_layouts = field_tys
-            .iter()
-            .map(|ty| self.type_to_type_layout_impl(ty, count, depth + 1))
-            .collect::<PartialVMResult<Vec<_>>>()?;
+        let mut field_layouts = |fields: &[Type]| {
+            fields
+                .iter()
+                .map(|ty| {
+                    let ty = self.subst(ty, ty_args)?;
+                    self.type_to_type_layout_impl(&ty, count, depth + 1)
+                })
+                .collect::<PartialVMResult<Vec<_>>>()
+        };
+        let struct_layout = match &struct_type.variants {
+            None => R::MoveTypeLayout::Struct(R::MoveStructLayout::new(field_layouts(
+                &struct_type.fields,
+            )?)),
+            Some(variants) => R::MoveTypeLayout::Enum(R::MoveEnumLayout(
+                variants
+                    .iter()
+                    .map(|variant| field_layouts(&variant.fields))
+                    .collect::<PartialVMResult<Vec<_>>>()?,
+            )),
+        };
         let field_node_count = *count - count_before;
 
-        let struct_layout = R::MoveStructLayout::new(field_layouts);
-
         let mut cache = self.type_cache.write();
         let info = cache
             .structs
@@ -2284,17 +2562,10 @@ impl Loader {
             Type::Vector(ty) => R::MoveTypeLayout::Vector(Box::new(
                 self.type_to_type_layout_impl(ty, count, depth + 1)?,
             )),
-            Type::Struct(gidx) => R::MoveTypeLayout::Struct(self.struct_gidx_to_type_layout(
-                *gidx,
-                &[],
-                count,
-                depth,
-            )?),
+            Type::Struct(gidx) => self.struct_gidx_to_type_layout(*gidx, &[], count, depth)?,
             Type::StructInstantiation(struct_inst) => {
                 let (gidx, ty_args) = &**struct_inst;
-                R::MoveTypeLayout::Struct(
-                    self.struct_gidx_to_type_layout(*gidx, ty_args, count, depth)?,
-                )
+                self.struct_gidx_to_type_layout(*gidx, ty_args, count, depth)?
             }
             Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
                 return Err(
@@ -2311,7 +2582,7 @@ impl Loader {
         ty_args: &[Type],
         count: &mut u64,
         depth: u64,
-    ) -> PartialVMResult<A::MoveStructLayout> {
+    ) -> PartialVMResult<A::MoveTypeLayout> {
         if let Some(struct_map) = self.type_cache.read().structs.get(&gidx) {
             if let Some(struct_info) = struct_map.get(ty_args) {
                 if let Some(annotated_node_count) = &struct_info.annotated_node_count {
@@ -2324,27 +2595,44 @@ impl Loader {
         }
 
         let struct_type = self.module_cache.read().struct_at(gidx);
-        if struct_type.fields.len() != struct_type.field_names.len() {
-            return Err(
-                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR).with_message(
-                    "Field types did not match the length of field names in loaded struct"
-                        .to_owned(),
-                ),
-            );
-        }
         let count_before = *count;
         let struct_tag = self.struct_gidx_to_type_tag(gidx, ty_args, StructTagType::Defining)?;
-        let field_layouts = struct_type
-            .field_names
-            .iter()
-            .zip(&struct_type.fields)
-            .map(|(n, ty)| {
-                let ty = self.subst(ty, ty_args)?;
-                let l = self.type_to_fully_annotated_layout_impl(&ty, count, depth + 1)?;
-                Ok(A::MoveFieldLayout::new(n.clone(), l))
-            })
-            .collect::<PartialVMResult<Vec<_>>>()?;
-        let struct_layout = A::MoveStructLayout::new(struct_tag, field_layouts);
+        let mut field_layouts = |field_names: &[Identifier], fields: &[Type]| {
+            if fields.len() != field_names.len() {
+                return Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(
+                            "Field types did not match the length of field names in loaded struct"
+                                .to_owned(),
+                        ),
+                );
+            }
+            field_names
+                .iter()
+                .zip(fields)
+                .map(|(n, ty)| {
+                    let ty = self.subst(ty, ty_args)?;
+                    let l = self.type_to_fully_annotated_layout_impl(&ty, count, depth + 1)?;
+                    Ok(A::MoveFieldLayout::new(n.clone(), l))
+                })
+                .collect::<PartialVMResult<Vec<_>>>()
+        };
+        let struct_layout = match &struct_type.variants {
+            None => A::MoveTypeLayout::Struct(A::MoveStructLayout::new(
+                struct_tag,
+                field_layouts(&struct_type.field_names, &struct_type.fields)?,
+            )),
+            Some(variants) => A::MoveTypeLayout::Enum(A::MoveEnumLayout {
+                type_: struct_tag,
+                variants: variants
+                    .iter()
+                    .map(|variant| {
+                        let fields = field_layouts(&variant.field_names, &variant.fields)?;
+                        Ok((variant.name.clone(), fields))
+                    })
+                    .collect::<PartialVMResult<Vec<_>>>()?,
+            }),
+        };
         let field_node_count = *count - count_before;
 
         let mut cache = self.type_cache.write();
@@ -2386,14 +2674,12 @@ impl Loader {
             Type::Vector(ty) => A::MoveTypeLayout::Vector(Box::new(
                 self.type_to_fully_annotated_layout_impl(ty, count, depth + 1)?,
             )),
-            Type::Struct(gidx) => A::MoveTypeLayout::Struct(
-                self.struct_gidx_to_fully_annotated_layout(*gidx, &[], count, depth)?,
-            ),
+            Type::Struct(gidx) => {
+                self.struct_gidx_to_fully_annotated_layout(*gidx, &[], count, depth)?
+            }
             Type::StructInstantiation(struct_inst) => {
                 let (gidx, ty_args) = &**struct_inst;
-                A::MoveTypeLayout::Struct(
-                    self.struct_gidx_to_fully_annotated_layout(*gidx, ty_args, count, depth)?,
-                )
+                self.struct_gidx_to_fully_annotated_layout(*gidx, ty_args, count, depth)?
             }
             Type::Reference(_) | Type::MutableReference(_) | Type::TyParam(_) => {
                 return Err(
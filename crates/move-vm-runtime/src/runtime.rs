@@ -9,6 +9,7 @@ use crate::{
     native_extensions::NativeContextExtensions,
     native_functions::{NativeFunction, NativeFunctions},
     session::{LoadedFunctionInstantiation, SerializedReturnValues, Session},
+    tracing::{TracingDataStore, VMTracer},
 };
 use move_binary_format::{
     errors::{verification_error, Location, PartialVMError, PartialVMResult, VMResult},
@@ -25,6 +26,7 @@ use move_core_types::{
     runtime_value::MoveTypeLayout,
     vm_status::StatusCode,
 };
+use move_trace_format::tracer::FileTracer;
 use move_vm_config::runtime::VMConfig;
 use move_vm_types::{
     data_store::DataStore,
@@ -59,10 +61,17 @@ impl VMRuntime {
         remote: S,
         native_extensions: NativeContextExtensions<'r>,
     ) -> Session<'r, '_, S> {
+        let tracer = self
+            .loader
+            .vm_config()
+            .tracer_config
+            .as_ref()
+            .map(|config| VMTracer::new(Box::new(FileTracer::new(config.trace_path.clone()))));
         Session {
             runtime: self,
             data_cache: TransactionDataCache::new(remote, &self.loader),
             native_extensions,
+            tracer,
         }
     }
 
@@ -311,6 +320,7 @@ impl VMRuntime {
         data_store: &mut impl DataStore,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Option<VMTracer>,
     ) -> VMResult<SerializedReturnValues> {
         let arg_types = param_types
             .into_iter()
@@ -341,6 +351,7 @@ impl VMRuntime {
             data_store,
             gas_meter,
             extensions,
+            tracer,
             &self.loader,
         )?;
 
@@ -381,6 +392,7 @@ impl VMRuntime {
         data_store: &mut impl DataStore,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        tracer: &mut Option<VMTracer>,
         bypass_declared_entry_check: bool,
     ) -> VMResult<SerializedReturnValues> {
         use move_binary_format::file_format::SignatureIndex;
@@ -404,36 +416,60 @@ impl VMRuntime {
         } else {
             check_is_entry
         };
-        // load the function
-        let (
-            compiled,
-            _,
-            func,
-            LoadedFunctionInstantiation {
-                parameters,
-                return_,
-            },
-        ) = self
-            .loader
-            .load_function(module, function_name, &type_arguments, data_store)?;
-
-        script_signature::verify_module_function_signature_by_name(
-            compiled.as_ref(),
-            function_name,
-            additional_signature_checks,
-        )?;
+        // load the function, tracing what it reads from storage
+        let loaded = match tracer {
+            Some(tracer) => {
+                let tracing_store = TracingDataStore::new(data_store, &self.loader);
+                let loaded = self.loader.load_function(
+                    module,
+                    function_name,
+                    &type_arguments,
+                    &tracing_store,
+                );
+                tracing_store
+                    .into_loads()
+                    .into_iter()
+                    .for_each(|event| tracer.notify(event));
+                loaded
+            }
+            None => self
+                .loader
+                .load_function(module, function_name, &type_arguments, data_store),
+        };
+        let result = loaded.and_then(
+            |(
+                compiled,
+                _,
+                func,
+                LoadedFunctionInstantiation {
+                    parameters,
+                    return_,
+                },
+            )| {
+                script_signature::verify_module_function_signature_by_name(
+                    compiled.as_ref(),
+                    function_name,
+                    additional_signature_checks,
+                )?;
 
-        // execute the function
-        self.execute_function_impl(
-            func,
-            type_arguments,
-            parameters,
-            return_,
-            serialized_args,
-            data_store,
-            gas_meter,
-            extensions,
-        )
+                // execute the function
+                self.execute_function_impl(
+                    func,
+                    type_arguments,
+                    parameters,
+                    return_,
+                    serialized_args,
+                    data_store,
+                    gas_meter,
+                    extensions,
+                    tracer,
+                )
+            },
+        );
+        if let (Some(tracer), Err(err)) = (tracer, &result) {
+            tracer.error(err);
+        }
+        result
     }
 
     pub(crate) fn loader(&self) -> &Loader {
@@ -501,6 +537,7 @@ impl VMRuntime {
             data_store,
             gas_meter,
             extensions,
+            &mut None,
             bypass_declared_entry_check,
         )
     }
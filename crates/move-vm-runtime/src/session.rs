@@ -4,7 +4,7 @@
 
 use crate::{
     data_cache::TransactionDataCache, native_extensions::NativeContextExtensions,
-    runtime::VMRuntime,
+    runtime::VMRuntime, tracing::VMTracer,
 };
 use move_binary_format::{
     errors::*,
@@ -19,6 +19,7 @@ use move_core_types::{
     resolver::MoveResolver,
     runtime_value::MoveTypeLayout,
 };
+use move_trace_format::tracer::Tracer;
 use move_vm_types::{
     data_store::DataStore,
     gas::GasMeter,
@@ -30,6 +31,7 @@ pub struct Session<'r, 'l, S> {
     pub(crate) runtime: &'l VMRuntime,
     pub(crate) data_cache: TransactionDataCache<'l, S>,
     pub(crate) native_extensions: NativeContextExtensions<'r>,
+    pub(crate) tracer: Option<VMTracer>,
 }
 
 /// Serialized return values from function/script execution
@@ -86,6 +88,7 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
             &mut self.data_cache,
             gas_meter,
             &mut self.native_extensions,
+            &mut self.tracer,
             bypass_declared_entry_check,
         )
     }
@@ -118,6 +121,7 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
             &mut self.data_cache,
             gas_meter,
             &mut self.native_extensions,
+            &mut self.tracer,
             bypass_declared_entry_check,
         )
     }
@@ -182,6 +186,12 @@ impl<'r, 'l, S: MoveResolver> Session<'r, 'l, S> {
         self.runtime.loader().vm_config()
     }
 
+    /// Report the execution of the functions of this session to `tracer`, in place of the tracer
+    /// configured by `VMConfig::tracer_config`, if any.
+    pub fn set_tracer(&mut self, tracer: Box<dyn Tracer>) {
+        self.tracer = Some(VMTracer::new(tracer));
+    }
+
     /// Same like `finish`, but also extracts the native context extensions from the session.
     pub fn finish_with_extensions(
         self,
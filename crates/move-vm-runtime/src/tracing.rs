@@ -7,55 +7,44 @@ use crate::debug::DebugContext;
 
 #[cfg(any(debug_assertions, feature = "debugging"))]
 use ::{
-    move_binary_format::file_format::Bytecode,
     move_vm_types::values::Locals,
     once_cell::sync::Lazy,
-    std::{
-        env,
-        fs::{File, OpenOptions},
-        io::Write,
-        process,
-        sync::Mutex,
-        thread,
-    },
+    std::{env, sync::Mutex},
 };
 
 #[cfg(any(debug_assertions, feature = "debugging"))]
-use crate::{
-    interpreter::Interpreter,
-    loader::{Function, Loader},
-};
+use crate::interpreter::Interpreter;
 
-#[cfg(any(debug_assertions, feature = "debugging"))]
-const MOVE_VM_TRACING_ENV_VAR_NAME: &str = "MOVE_VM_TRACE";
+use crate::loader::{Function, Loader};
+use move_binary_format::{
+    errors::{PartialVMError, PartialVMResult, VMError, VMResult},
+    file_format::Bytecode,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_algebra::{InternalGas, NumBytes},
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
+    vm_status::StatusCode,
+};
+use move_trace_format::{
+    format::{Effect, TraceEvent},
+    tracer::Tracer,
+};
+use move_vm_types::{
+    data_store::DataStore,
+    loaded_data::runtime_types::Type,
+    values::{GlobalValue, Value},
+};
+use std::cell::RefCell;
 
 #[cfg(any(debug_assertions, feature = "debugging"))]
 const MOVE_VM_STEPPING_ENV_VAR_NAME: &str = "MOVE_VM_STEP";
 
-#[cfg(any(debug_assertions, feature = "debugging"))]
-static FILE_PATH: Lazy<String> = Lazy::new(|| {
-    env::var(MOVE_VM_TRACING_ENV_VAR_NAME).unwrap_or_else(|_| "move_vm_trace.trace".to_string())
-});
-
-#[cfg(any(debug_assertions, feature = "debugging"))]
-static TRACING_ENABLED: Lazy<bool> = Lazy::new(|| env::var(MOVE_VM_TRACING_ENV_VAR_NAME).is_ok());
-
 #[cfg(any(debug_assertions, feature = "debugging"))]
 static DEBUGGING_ENABLED: Lazy<bool> =
     Lazy::new(|| env::var(MOVE_VM_STEPPING_ENV_VAR_NAME).is_ok());
 
-#[cfg(any(debug_assertions, feature = "debugging"))]
-static LOGGING_FILE: Lazy<Mutex<File>> = Lazy::new(|| {
-    Mutex::new(
-        OpenOptions::new()
-            .write(true)
-            .create(true)
-            .append(true)
-            .open(&*FILE_PATH)
-            .unwrap(),
-    )
-});
-
 #[cfg(any(debug_assertions, feature = "debugging"))]
 static DEBUG_CONTEXT: Lazy<Mutex<DebugContext>> = Lazy::new(|| Mutex::new(DebugContext::new()));
 
@@ -69,19 +58,6 @@ pub(crate) fn trace(
     loader: &Loader,
     interp: &Interpreter,
 ) {
-    if *TRACING_ENABLED {
-        let f = &mut *LOGGING_FILE.lock().unwrap();
-        writeln!(
-            f,
-            "{}-{:?},{},{},{:?}",
-            process::id(),
-            thread::current().id(),
-            function_desc.pretty_string(),
-            pc,
-            instr,
-        )
-        .unwrap();
-    }
     if *DEBUGGING_ENABLED {
         DEBUG_CONTEXT
             .lock()
@@ -105,3 +81,172 @@ macro_rules! trace {
         )
     };
 }
+
+/// Reports the execution of a session to its `Tracer`, as the events of `move_trace_format`.
+pub(crate) struct VMTracer {
+    tracer: Box<dyn Tracer>,
+    next_frame_id: u64,
+    /// The ids of the open frames, innermost last.
+    frames: Vec<u64>,
+}
+
+impl VMTracer {
+    pub(crate) fn new(tracer: Box<dyn Tracer>) -> Self {
+        Self {
+            tracer,
+            next_frame_id: 0,
+            frames: vec![],
+        }
+    }
+
+    pub(crate) fn open_frame<'v>(
+        &mut self,
+        function: &Function,
+        ty_args: &[Type],
+        args: impl IntoIterator<Item = &'v Value>,
+        loader: &Loader,
+        gas_left: InternalGas,
+    ) -> PartialVMResult<()> {
+        let type_args = ty_args
+            .iter()
+            .map(|ty| loader.type_to_type_tag(ty))
+            .collect::<PartialVMResult<Vec<_>>>()?;
+        let frame_id = self.next_frame_id;
+        self.next_frame_id += 1;
+        self.frames.push(frame_id);
+        self.tracer.notify(&TraceEvent::OpenFrame {
+            frame_id,
+            module: function.module_id().clone(),
+            function_name: function.name().to_string(),
+            function_index: function.index().0,
+            type_args,
+            args: args.into_iter().map(Value::as_trace_value).collect(),
+            is_native: function.is_native(),
+            gas_left: gas_left.into(),
+        });
+        Ok(())
+    }
+
+    pub(crate) fn close_frame<'v>(
+        &mut self,
+        return_values: impl IntoIterator<Item = &'v Value>,
+        gas_left: InternalGas,
+    ) {
+        if let Some(frame_id) = self.frames.pop() {
+            self.tracer.notify(&TraceEvent::CloseFrame {
+                frame_id,
+                return_values: return_values
+                    .into_iter()
+                    .map(Value::as_trace_value)
+                    .collect(),
+                gas_left: gas_left.into(),
+            });
+        }
+    }
+
+    pub(crate) fn instruction(
+        &mut self,
+        pc: u16,
+        instruction: &Bytecode,
+        gas_left: InternalGas,
+        gas_charged: InternalGas,
+        effects: Vec<Effect>,
+    ) {
+        if let Some(frame_id) = self.frames.last() {
+            self.tracer.notify(&TraceEvent::Instruction {
+                frame_id: *frame_id,
+                pc,
+                instruction: format!("{:?}", instruction),
+                gas_left: gas_left.into(),
+                gas_charged: gas_charged.into(),
+                effects,
+            });
+        }
+    }
+
+    pub(crate) fn notify(&mut self, event: TraceEvent) {
+        self.tracer.notify(&event)
+    }
+
+    /// Ends the call with an error, leaving its frames open.
+    pub(crate) fn error(&mut self, err: &VMError) {
+        self.frames.clear();
+        self.tracer.notify(&TraceEvent::Error {
+            status: format!("{:?}", err.major_status()),
+            sub_status: err.sub_status(),
+        });
+    }
+}
+
+/// A `DataStore` recording what is read from storage through it, as trace events.
+pub(crate) struct TracingDataStore<'d, D> {
+    data_store: &'d mut D,
+    loader: &'d Loader,
+    loads: RefCell<Vec<TraceEvent>>,
+}
+
+impl<'d, D: DataStore> TracingDataStore<'d, D> {
+    pub(crate) fn new(data_store: &'d mut D, loader: &'d Loader) -> Self {
+        Self {
+            data_store,
+            loader,
+            loads: RefCell::new(vec![]),
+        }
+    }
+
+    pub(crate) fn into_loads(self) -> Vec<TraceEvent> {
+        self.loads.into_inner()
+    }
+}
+
+impl<D: DataStore> DataStore for TracingDataStore<'_, D> {
+    fn load_resource(
+        &mut self,
+        addr: AccountAddress,
+        ty: &Type,
+    ) -> PartialVMResult<(&mut GlobalValue, Option<Option<NumBytes>>)> {
+        let resource = match self.loader.type_to_type_tag(ty)? {
+            TypeTag::Struct(resource) => *resource,
+            _ => return Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)),
+        };
+        let (value, load_res) = self.data_store.load_resource(addr, ty)?;
+        // Resources already in the cache were not read from storage
+        if let Some(size) = load_res {
+            self.loads.borrow_mut().push(TraceEvent::LoadResource {
+                address: addr,
+                resource,
+                size: size.map(u64::from),
+            });
+        }
+        Ok((value, load_res))
+    }
+
+    fn link_context(&self) -> AccountAddress {
+        self.data_store.link_context()
+    }
+
+    fn relocate(&self, module_id: &ModuleId) -> PartialVMResult<ModuleId> {
+        self.data_store.relocate(module_id)
+    }
+
+    fn defining_module(
+        &self,
+        module_id: &ModuleId,
+        struct_: &IdentStr,
+    ) -> PartialVMResult<ModuleId> {
+        self.data_store.defining_module(module_id, struct_)
+    }
+
+    fn load_module(&self, module_id: &ModuleId) -> VMResult<Vec<u8>> {
+        let bytes = self.data_store.load_module(module_id)?;
+        self.loads.borrow_mut().push(TraceEvent::LoadModule {
+            module: module_id.clone(),
+            size: bytes.len() as u64,
+        });
+        Ok(bytes)
+    }
+
+    fn publish_module(&mut self, module_id: &ModuleId, blob: Vec<u8>) -> VMResult<()> {
+        self.data_store.publish_module(module_id, blob)
+    }
+}
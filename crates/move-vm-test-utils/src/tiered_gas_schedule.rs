@@ -578,6 +578,31 @@ impl<'b> GasMeter for GasStatus<'b> {
         self.charge(1, num_fields, 1, 0, STRUCT_SIZE.into())
     }
 
+    fn charge_pack_variant(
+        &mut self,
+        _is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        // Same as packing a struct
+        let num_fields = args.len() as u64;
+        self.charge(1, 1, num_fields, STRUCT_SIZE.into(), 0)
+    }
+
+    fn charge_unpack_variant(
+        &mut self,
+        _is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        // Same as unpacking a struct
+        let num_fields = args.len() as u64;
+        self.charge(1, num_fields, 1, 0, STRUCT_SIZE.into())
+    }
+
+    fn charge_variant_switch(&mut self, _val: impl ValueView) -> PartialVMResult<()> {
+        // We pop the reference to the enum value
+        self.charge(1, 0, 1, 0, REFERENCE_SIZE.into())
+    }
+
     fn charge_read_ref(&mut self, ref_val: impl ValueView) -> PartialVMResult<()> {
         // We read the the reference so we are decreasing the size of the stack by the size of the
         // reference, and adding to it the size of the value that has been read from that
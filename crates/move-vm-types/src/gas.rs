@@ -117,6 +117,22 @@ pub trait GasMeter {
         args: impl ExactSizeIterator<Item = impl ValueView>,
     ) -> PartialVMResult<()>;
 
+    fn charge_pack_variant(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()>;
+
+    /// Charge for unpacking a variant, either by value or by reference. In the latter case `args`
+    /// are the references to the fields.
+    fn charge_unpack_variant(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()>;
+
+    fn charge_variant_switch(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
     fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()>;
 
     fn charge_write_ref(
@@ -265,6 +281,26 @@ impl GasMeter for UnmeteredGasMeter {
         Ok(())
     }
 
+    fn charge_pack_variant(
+        &mut self,
+        _is_generic: bool,
+        _args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
+    fn charge_unpack_variant(
+        &mut self,
+        _is_generic: bool,
+        _args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
+    fn charge_variant_switch(&mut self, _val: impl ValueView) -> PartialVMResult<()> {
+        Ok(())
+    }
+
     fn charge_read_ref(&mut self, _val: impl ValueView) -> PartialVMResult<()> {
         Ok(())
     }
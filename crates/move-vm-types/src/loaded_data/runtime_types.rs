@@ -130,6 +130,9 @@ impl DepthFormula {
 pub struct StructType {
     pub fields: Vec<Type>,
     pub field_names: Vec<Identifier>,
+    /// The variants of the type if it is an enum, in which case `fields` and `field_names` are
+    /// empty and `struct_def` indexes the module's enum definitions instead.
+    pub variants: Option<Vec<VariantType>>,
     pub abilities: AbilitySet,
     pub type_parameters: Vec<StructTypeParameter>,
     pub name: Identifier,
@@ -139,10 +142,30 @@ pub struct StructType {
     pub depth: Option<DepthFormula>,
 }
 
+#[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct VariantType {
+    pub name: Identifier,
+    pub fields: Vec<Type>,
+    pub field_names: Vec<Identifier>,
+}
+
 impl StructType {
     pub fn type_param_constraints(&self) -> impl ExactSizeIterator<Item = &AbilitySet> {
         self.type_parameters.iter().map(|param| &param.constraints)
     }
+
+    pub fn is_enum(&self) -> bool {
+        self.variants.is_some()
+    }
+
+    /// All field types of the datatype, across every variant for an enum.
+    pub fn all_fields(&self) -> impl Iterator<Item = &Type> {
+        self.fields.iter().chain(
+            self.variants
+                .iter()
+                .flat_map(|variants| variants.iter().flat_map(|v| v.fields.iter())),
+        )
+    }
 }
 
 #[derive(Debug, Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
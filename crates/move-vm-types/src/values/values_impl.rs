@@ -8,16 +8,17 @@ use crate::{
 };
 use move_binary_format::{
     errors::*,
-    file_format::{Constant, SignatureToken},
+    file_format::{Constant, SignatureToken, VariantTag},
 };
 use move_core_types::{
     account_address::AccountAddress,
     effects::Op,
     gas_algebra::AbstractMemorySize,
-    runtime_value::{MoveStructLayout, MoveTypeLayout},
+    runtime_value::{MoveEnumLayout, MoveStructLayout, MoveTypeLayout},
     u256,
     vm_status::{sub_status::NFE_VECTOR_ERROR_BASE, StatusCode},
 };
+use move_trace_format::format::TraceValue;
 use std::{
     cell::RefCell,
     fmt::{self, Debug, Display},
@@ -56,7 +57,7 @@ enum ValueImpl {
 }
 
 /// A container is a collection of values. It is used to represent data structures like a
-/// Move vector or struct.
+/// Move vector, struct or enum variant.
 ///
 /// There is one general container that can be used to store an array of any values, same
 /// type or not, and a few specialized flavors to offer compact memory layout for small
@@ -69,6 +70,7 @@ enum Container {
     Locals(Rc<RefCell<Vec<ValueImpl>>>),
     Vec(Rc<RefCell<Vec<ValueImpl>>>),
     Struct(Rc<RefCell<Vec<ValueImpl>>>),
+    Variant(Rc<RefCell<(VariantTag, Vec<ValueImpl>)>>),
     VecU8(Rc<RefCell<Vec<u8>>>),
     VecU64(Rc<RefCell<Vec<u64>>>),
     VecU128(Rc<RefCell<Vec<u128>>>),
@@ -152,6 +154,13 @@ pub struct Struct {
     fields: Vec<ValueImpl>,
 }
 
+/// A value of a Move enum: the tag of the variant it holds and the fields of that variant.
+#[derive(Debug)]
+pub struct Variant {
+    tag: VariantTag,
+    fields: Vec<ValueImpl>,
+}
+
 // A vector. This is an alias for a Container for now but we may change
 // it once Containers are restructured.
 // It's used from vector native functions to get a vector and operate on that.
@@ -163,6 +172,11 @@ pub struct Vector(Container);
 #[derive(Debug)]
 pub struct StructRef(ContainerRef);
 
+/// A reference to a Move enum value that allows you to inspect its tag and borrow the fields
+/// of the variant it holds.
+#[derive(Debug)]
+pub struct VariantRef(ContainerRef);
+
 /// A generic Move reference that offers two functionalities: read_ref & write_ref.
 #[derive(Debug)]
 pub struct Reference(ReferenceImpl);
@@ -220,6 +234,7 @@ impl Container {
     fn len(&self) -> usize {
         match self {
             Self::Locals(r) | Self::Struct(r) | Self::Vec(r) => r.borrow().len(),
+            Self::Variant(r) => r.borrow().1.len(),
             Self::VecU8(r) => r.borrow().len(),
             Self::VecU16(r) => r.borrow().len(),
             Self::VecU32(r) => r.borrow().len(),
@@ -234,6 +249,7 @@ impl Container {
     fn rc_count(&self) -> usize {
         match self {
             Self::Locals(r) | Self::Struct(r) | Self::Vec(r) => Rc::strong_count(r),
+            Self::Variant(r) => Rc::strong_count(r),
             Self::VecU8(r) => Rc::strong_count(r),
             Self::VecU16(r) => Rc::strong_count(r),
             Self::VecU32(r) => Rc::strong_count(r),
@@ -376,6 +392,14 @@ impl Container {
         Ok(match self {
             Self::Vec(r) => Self::Vec(copy_rc_ref_vec_val(r)?),
             Self::Struct(r) => Self::Struct(copy_rc_ref_vec_val(r)?),
+            Self::Variant(r) => {
+                let r = r.borrow();
+                let fields =
+                    r.1.iter()
+                        .map(|v| v.copy_value())
+                        .collect::<PartialVMResult<_>>()?;
+                Self::Variant(Rc::new(RefCell::new((r.0, fields))))
+            }
 
             Self::VecU8(r) => Self::VecU8(Rc::new(RefCell::new(r.borrow().clone()))),
             Self::VecU16(r) => Self::VecU16(Rc::new(RefCell::new(r.borrow().clone()))),
@@ -399,6 +423,7 @@ impl Container {
         match self {
             Self::Vec(r) => Self::Vec(Rc::clone(r)),
             Self::Struct(r) => Self::Struct(Rc::clone(r)),
+            Self::Variant(r) => Self::Variant(Rc::clone(r)),
             Self::VecU8(r) => Self::VecU8(Rc::clone(r)),
             Self::VecU16(r) => Self::VecU16(Rc::clone(r)),
             Self::VecU32(r) => Self::VecU32(Rc::clone(r)),
@@ -514,6 +539,20 @@ impl Container {
                 }
                 true
             }
+            (Variant(l), Variant(r)) => {
+                let l = &l.borrow();
+                let r = &r.borrow();
+
+                if l.0 != r.0 || l.1.len() != r.1.len() {
+                    return Ok(false);
+                }
+                for (v1, v2) in l.1.iter().zip(r.1.iter()) {
+                    if !v1.equals(v2)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
             (VecU8(l), VecU8(r)) => l.borrow().eq(&*r.borrow()),
             (VecU16(l), VecU16(r)) => l.borrow().eq(&*r.borrow()),
             (VecU32(l), VecU32(r)) => l.borrow().eq(&*r.borrow()),
@@ -526,6 +565,7 @@ impl Container {
             (Locals(_), _)
             | (Vec(_), _)
             | (Struct(_), _)
+            | (Variant(_), _)
             | (VecU8(_), _)
             | (VecU16(_), _)
             | (VecU32(_), _)
@@ -557,6 +597,14 @@ impl IndexedRef {
     fn equals(&self, other: &Self) -> PartialVMResult<bool> {
         use Container::*;
 
+        // Variant fields can be compared against elements of any other container, so compare
+        // the copied out primitive values instead of enumerating every combination below.
+        if matches!(self.container_ref.container(), Variant(_))
+            || matches!(other.container_ref.container(), Variant(_))
+        {
+            return self.copy_elem()?.equals(&other.copy_elem()?);
+        }
+
         let res = match (
             self.container_ref.container(),
             other.container_ref.container(),
@@ -640,6 +688,9 @@ impl IndexedRef {
 
             // All other combinations are illegal.
             (Vec(_), _)
+            | (Struct(_), Variant(_))
+            | (Locals(_), Variant(_))
+            | (Variant(_), _)
             | (VecU8(_), _)
             | (VecU16(_), _)
             | (VecU32(_), _)
@@ -678,10 +729,15 @@ impl ContainerRef {
 
 impl IndexedRef {
     fn read_ref(self) -> PartialVMResult<Value> {
+        Ok(Value(self.copy_elem()?))
+    }
+
+    fn copy_elem(&self) -> PartialVMResult<ValueImpl> {
         use Container::*;
 
-        let res = match self.container_ref.container() {
+        Ok(match self.container_ref.container() {
             Locals(r) | Vec(r) | Struct(r) => r.borrow()[self.idx].copy_value()?,
+            Variant(r) => r.borrow().1[self.idx].copy_value()?,
             VecU8(r) => ValueImpl::U8(r.borrow()[self.idx]),
             VecU16(r) => ValueImpl::U16(r.borrow()[self.idx]),
             VecU32(r) => ValueImpl::U32(r.borrow()[self.idx]),
@@ -690,9 +746,7 @@ impl IndexedRef {
             VecU256(r) => ValueImpl::U256(r.borrow()[self.idx]),
             VecBool(r) => ValueImpl::Bool(r.borrow()[self.idx]),
             VecAddress(r) => ValueImpl::Address(r.borrow()[self.idx]),
-        };
-
-        Ok(Value(res))
+        })
     }
 }
 
@@ -748,6 +802,7 @@ impl ContainerRef {
 
                 match self.container() {
                     Container::Struct(r) => assign!(r, Struct),
+                    Container::Variant(r) => assign!(r, Variant),
                     Container::Vec(r) => assign!(r, Vec),
                     Container::VecU8(r) => assign!(r, VecU8),
                     Container::VecU16(r) => assign!(r, VecU16),
@@ -803,6 +858,10 @@ impl IndexedRef {
                 let mut v = r.borrow_mut();
                 v[self.idx] = x.0;
             }
+            (Container::Variant(r), _) => {
+                let mut v = r.borrow_mut();
+                v.1[self.idx] = x.0;
+            }
             (Container::VecU8(r), ValueImpl::U8(x)) => r.borrow_mut()[self.idx] = *x,
             (Container::VecU16(r), ValueImpl::U16(x)) => r.borrow_mut()[self.idx] = *x,
             (Container::VecU32(r), ValueImpl::U32(x)) => r.borrow_mut()[self.idx] = *x,
@@ -871,27 +930,29 @@ impl ContainerRef {
             );
         }
 
+        let borrow_generic = |v: &[ValueImpl]| match &v[idx] {
+            // TODO: check for the impossible combinations.
+            ValueImpl::Container(container) => {
+                let r = match self {
+                    Self::Local(_) => Self::Local(container.copy_by_ref()),
+                    Self::Global { status, .. } => Self::Global {
+                        status: Rc::clone(status),
+                        container: container.copy_by_ref(),
+                    },
+                };
+                ValueImpl::ContainerRef(r)
+            }
+            _ => ValueImpl::IndexedRef(IndexedRef {
+                idx,
+                container_ref: self.copy_value(),
+            }),
+        };
+
         let res = match self.container() {
             Container::Locals(r) | Container::Vec(r) | Container::Struct(r) => {
-                let v = r.borrow();
-                match &v[idx] {
-                    // TODO: check for the impossible combinations.
-                    ValueImpl::Container(container) => {
-                        let r = match self {
-                            Self::Local(_) => Self::Local(container.copy_by_ref()),
-                            Self::Global { status, .. } => Self::Global {
-                                status: Rc::clone(status),
-                                container: container.copy_by_ref(),
-                            },
-                        };
-                        ValueImpl::ContainerRef(r)
-                    }
-                    _ => ValueImpl::IndexedRef(IndexedRef {
-                        idx,
-                        container_ref: self.copy_value(),
-                    }),
-                }
+                borrow_generic(&r.borrow())
             }
+            Container::Variant(r) => borrow_generic(&r.borrow().1),
 
             Container::VecU8(_)
             | Container::VecU16(_)
@@ -916,6 +977,29 @@ impl StructRef {
     }
 }
 
+impl VariantRef {
+    pub fn get_tag(&self) -> PartialVMResult<VariantTag> {
+        match self.0.container() {
+            Container::Variant(r) => Ok(r.borrow().0),
+            c => Err(
+                PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message(format!("expected a variant container, got {:?}", c)),
+            ),
+        }
+    }
+
+    pub fn check_tag(&self, expected_tag: VariantTag) -> PartialVMResult<()> {
+        check_variant_tag(self.get_tag()?, expected_tag)
+    }
+
+    /// Borrow every field of the variant, in declaration order.
+    pub fn unpack_variant(&self) -> PartialVMResult<Vec<Value>> {
+        (0..self.0.container().len())
+            .map(|idx| Ok(Value(self.0.borrow_elem(idx)?)))
+            .collect()
+    }
+}
+
 impl Locals {
     pub fn borrow_loc(&self, idx: usize) -> PartialVMResult<Value> {
         // TODO: this is very similar to SharedContainer::borrow_elem. Find a way to
@@ -1135,6 +1219,12 @@ impl Value {
         ))))
     }
 
+    pub fn variant(v: Variant) -> Self {
+        Self(ValueImpl::Container(Container::Variant(Rc::new(
+            RefCell::new((v.tag, v.fields)),
+        ))))
+    }
+
     // TODO: consider whether we want to replace these with fn vector(v: Vec<Value>).
     pub fn vector_u8(it: impl IntoIterator<Item = u8>) -> Self {
         Self(ValueImpl::Container(Container::VecU8(Rc::new(
@@ -1288,6 +1378,25 @@ impl VMValueCast<StructRef> for Value {
     }
 }
 
+impl VMValueCast<Variant> for Value {
+    fn cast(self) -> PartialVMResult<Variant> {
+        match self.0 {
+            ValueImpl::Container(Container::Variant(r)) => {
+                let (tag, fields) = take_unique_ownership(r)?;
+                Ok(Variant { tag, fields })
+            }
+            v => Err(PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                .with_message(format!("cannot cast {:?} to enum", v,))),
+        }
+    }
+}
+
+impl VMValueCast<VariantRef> for Value {
+    fn cast(self) -> PartialVMResult<VariantRef> {
+        Ok(VariantRef(VMValueCast::cast(self)?))
+    }
+}
+
 impl VMValueCast<Vec<u8>> for Value {
     fn cast(self) -> PartialVMResult<Vec<u8>> {
         match self.0 {
@@ -2011,7 +2120,7 @@ impl VectorRef {
             Container::VecBool(r) => r.borrow().len(),
             Container::VecAddress(r) => r.borrow().len(),
             Container::Vec(r) => r.borrow().len(),
-            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+            Container::Locals(_) | Container::Struct(_) | Container::Variant(_) => unreachable!(),
         };
         Ok(Value::u64(len as u64))
     }
@@ -2030,7 +2139,7 @@ impl VectorRef {
             Container::VecBool(r) => r.borrow().len(),
             Container::VecAddress(r) => r.borrow().len(),
             Container::Vec(r) => r.borrow().len(),
-            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+            Container::Locals(_) | Container::Struct(_) | Container::Variant(_) => unreachable!(),
         };
         if size >= (capacity as usize) {
             return Err(PartialVMError::new(StatusCode::VECTOR_OPERATION_ERROR)
@@ -2048,7 +2157,7 @@ impl VectorRef {
             Container::VecBool(r) => r.borrow_mut().push(e.value_as()?),
             Container::VecAddress(r) => r.borrow_mut().push(e.value_as()?),
             Container::Vec(r) => r.borrow_mut().push(e.0),
-            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+            Container::Locals(_) | Container::Struct(_) | Container::Variant(_) => unreachable!(),
         }
 
         self.0.mark_dirty();
@@ -2122,7 +2231,7 @@ impl VectorRef {
                 Some(x) => Value(x),
                 None => err_pop_empty_vec!(),
             },
-            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+            Container::Locals(_) | Container::Struct(_) | Container::Variant(_) => unreachable!(),
         };
 
         self.0.mark_dirty();
@@ -2154,7 +2263,7 @@ impl VectorRef {
             Container::VecBool(r) => swap!(r),
             Container::VecAddress(r) => swap!(r),
             Container::Vec(r) => swap!(r),
-            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+            Container::Locals(_) | Container::Struct(_) | Container::Variant(_) => unreachable!(),
         }
 
         self.0.mark_dirty();
@@ -2271,7 +2380,7 @@ impl Vector {
                 .map(Value::address)
                 .collect(),
             Container::Vec(r) => take_unique_ownership(r)?.into_iter().map(Value).collect(),
-            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+            Container::Locals(_) | Container::Struct(_) | Container::Variant(_) => unreachable!(),
         };
         if expected_num as usize == elements.len() {
             Ok(elements)
@@ -2326,6 +2435,7 @@ impl Container {
             Self::Locals(r) | Self::Vec(r) | Self::Struct(r) => {
                 Struct::legacy_size_impl(&r.borrow())
             }
+            Self::Variant(r) => Struct::legacy_size_impl(&r.borrow().1),
             Self::VecU8(r) => {
                 AbstractMemorySize::new((r.borrow().len() * std::mem::size_of::<u8>()) as u64)
             }
@@ -2438,6 +2548,43 @@ impl Struct {
     }
 }
 
+/***************************************************************************************
+ *
+ * Variant Operations
+ *
+ *   Public APIs for Variant.
+ *
+ **************************************************************************************/
+fn check_variant_tag(tag: VariantTag, expected_tag: VariantTag) -> PartialVMResult<()> {
+    if tag != expected_tag {
+        Err(PartialVMError::new(StatusCode::VARIANT_TAG_MISMATCH)
+            .with_message(format!("expected variant {}, got {}", expected_tag, tag)))
+    } else {
+        Ok(())
+    }
+}
+
+impl Variant {
+    pub fn pack<I: IntoIterator<Item = Value>>(tag: VariantTag, vals: I) -> Self {
+        Self {
+            tag,
+            fields: vals.into_iter().map(|v| v.0).collect(),
+        }
+    }
+
+    pub fn tag(&self) -> VariantTag {
+        self.tag
+    }
+
+    pub fn check_tag(&self, expected_tag: VariantTag) -> PartialVMResult<()> {
+        check_variant_tag(self.tag, expected_tag)
+    }
+
+    pub fn unpack(self) -> PartialVMResult<impl Iterator<Item = Value>> {
+        Ok(self.fields.into_iter().map(Value))
+    }
+}
+
 /***************************************************************************************
  *
  * Global Value Operations
@@ -2601,6 +2748,109 @@ impl GlobalValue {
     }
 }
 
+/***************************************************************************************
+*
+* Trace
+*
+*   Untyped snapshots of VM Values, recorded in execution traces. References are
+*   recorded with the value they point to.
+*
+**************************************************************************************/
+
+impl ValueImpl {
+    fn as_trace_value(&self) -> TraceValue {
+        match self {
+            Self::Invalid => TraceValue::Invalid,
+
+            Self::U8(x) => TraceValue::U8(*x),
+            Self::U16(x) => TraceValue::U16(*x),
+            Self::U32(x) => TraceValue::U32(*x),
+            Self::U64(x) => TraceValue::U64(*x),
+            Self::U128(x) => TraceValue::U128(x.to_string()),
+            Self::U256(x) => TraceValue::U256(x.to_string()),
+            Self::Bool(x) => TraceValue::Bool(*x),
+            Self::Address(addr) => TraceValue::Address(*addr),
+
+            Self::Container(c) => c.as_trace_value(),
+
+            Self::ContainerRef(r) => {
+                TraceValue::Reference(Box::new(r.container().as_trace_value()))
+            }
+            Self::IndexedRef(r) => TraceValue::Reference(Box::new(
+                r.container_ref.container().elem_as_trace_value(r.idx),
+            )),
+        }
+    }
+}
+
+impl Container {
+    fn as_trace_value(&self) -> TraceValue {
+        fn vector<T: Copy>(r: &Rc<RefCell<Vec<T>>>, f: impl Fn(T) -> TraceValue) -> TraceValue {
+            TraceValue::Vector(r.borrow().iter().map(|x| f(*x)).collect())
+        }
+
+        match self {
+            Self::Locals(r) | Self::Vec(r) => {
+                TraceValue::Vector(r.borrow().iter().map(ValueImpl::as_trace_value).collect())
+            }
+            Self::Struct(r) => {
+                TraceValue::Struct(r.borrow().iter().map(ValueImpl::as_trace_value).collect())
+            }
+            Self::Variant(r) => {
+                let r = r.borrow();
+                TraceValue::Variant {
+                    tag: r.0,
+                    fields: r.1.iter().map(ValueImpl::as_trace_value).collect(),
+                }
+            }
+            Self::VecU8(r) => vector(r, TraceValue::U8),
+            Self::VecU16(r) => vector(r, TraceValue::U16),
+            Self::VecU32(r) => vector(r, TraceValue::U32),
+            Self::VecU64(r) => vector(r, TraceValue::U64),
+            Self::VecU128(r) => vector(r, |x| TraceValue::U128(x.to_string())),
+            Self::VecU256(r) => vector(r, |x| TraceValue::U256(x.to_string())),
+            Self::VecBool(r) => vector(r, TraceValue::Bool),
+            Self::VecAddress(r) => vector(r, TraceValue::Address),
+        }
+    }
+
+    fn elem_as_trace_value(&self, idx: usize) -> TraceValue {
+        fn elem<T: Copy>(
+            r: &Rc<RefCell<Vec<T>>>,
+            idx: usize,
+            f: impl Fn(T) -> TraceValue,
+        ) -> TraceValue {
+            r.borrow().get(idx).map_or(TraceValue::Invalid, |x| f(*x))
+        }
+
+        match self {
+            Self::Locals(r) | Self::Vec(r) | Self::Struct(r) => r
+                .borrow()
+                .get(idx)
+                .map_or(TraceValue::Invalid, ValueImpl::as_trace_value),
+            Self::Variant(r) => r
+                .borrow()
+                .1
+                .get(idx)
+                .map_or(TraceValue::Invalid, ValueImpl::as_trace_value),
+            Self::VecU8(r) => elem(r, idx, TraceValue::U8),
+            Self::VecU16(r) => elem(r, idx, TraceValue::U16),
+            Self::VecU32(r) => elem(r, idx, TraceValue::U32),
+            Self::VecU64(r) => elem(r, idx, TraceValue::U64),
+            Self::VecU128(r) => elem(r, idx, |x| TraceValue::U128(x.to_string())),
+            Self::VecU256(r) => elem(r, idx, |x| TraceValue::U256(x.to_string())),
+            Self::VecBool(r) => elem(r, idx, TraceValue::Bool),
+            Self::VecAddress(r) => elem(r, idx, TraceValue::Address),
+        }
+    }
+}
+
+impl Value {
+    pub fn as_trace_value(&self) -> TraceValue {
+        self.0.as_trace_value()
+    }
+}
+
 /***************************************************************************************
 *
 * Display
@@ -2656,6 +2906,7 @@ impl Container {
             Locals(r) => r.as_ptr() as usize,
             Vec(r) => r.as_ptr() as usize,
             Struct(r) => r.as_ptr() as usize,
+            Variant(r) => r.as_ptr() as usize,
             VecU8(r) => r.as_ptr() as usize,
             VecU16(r) => r.as_ptr() as usize,
             VecU32(r) => r.as_ptr() as usize,
@@ -2702,6 +2953,11 @@ impl Display for Container {
             Self::Locals(r) | Self::Vec(r) | Self::Struct(r) => {
                 display_list_of_items(r.borrow().iter(), f)
             }
+            Self::Variant(r) => {
+                let r = r.borrow();
+                write!(f, "#{} ", r.0)?;
+                display_list_of_items(r.1.iter(), f)
+            }
             Self::VecU8(r) => display_list_of_items(r.borrow().iter(), f),
             Self::VecU16(r) => display_list_of_items(r.borrow().iter(), f),
             Self::VecU32(r) => display_list_of_items(r.borrow().iter(), f),
@@ -2833,6 +3089,12 @@ pub mod debug {
                 print_list(buf, "{ ", r.borrow().iter(), print_value_impl, " }")
             }
 
+            Container::Variant(r) => {
+                let r = r.borrow();
+                debug_write!(buf, "#{} ", r.0)?;
+                print_list(buf, "{ ", r.1.iter(), print_value_impl, " }")
+            }
+
             Container::VecU8(r) => print_list(buf, "[", r.borrow().iter(), print_u8, "]"),
             Container::VecU16(r) => print_list(buf, "[", r.borrow().iter(), print_u16, "]"),
             Container::VecU32(r) => print_list(buf, "[", r.borrow().iter(), print_u32, "]"),
@@ -2874,6 +3136,7 @@ pub mod debug {
             Container::Locals(r) | Container::Vec(r) | Container::Struct(r) => {
                 print_slice_elem(buf, &r.borrow(), idx, print_value_impl)
             }
+            Container::Variant(r) => print_slice_elem(buf, &r.borrow().1, idx, print_value_impl),
 
             Container::VecU8(r) => print_slice_elem(buf, &r.borrow(), idx, print_u8),
             Container::VecU16(r) => print_slice_elem(buf, &r.borrow(), idx, print_u16),
@@ -2929,8 +3192,8 @@ pub mod debug {
  *
  **************************************************************************************/
 use serde::{
-    de::Error as DeError,
-    ser::{Error as SerError, SerializeSeq, SerializeTuple},
+    de::{Error as DeError, VariantAccess},
+    ser::{Error as SerError, SerializeSeq, SerializeTuple, SerializeTupleVariant},
     Deserialize,
 };
 
@@ -2993,6 +3256,14 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveTypeLayout, ValueIm
                 .serialize(serializer)
             }
 
+            (MoveTypeLayout::Enum(enum_layout), ValueImpl::Container(Container::Variant(r))) => {
+                (AnnotatedValue {
+                    layout: enum_layout,
+                    val: &*r.borrow(),
+                })
+                .serialize(serializer)
+            }
+
             (MoveTypeLayout::Vector(layout), ValueImpl::Container(c)) => {
                 let layout = &**layout;
                 match (layout, c) {
@@ -3073,6 +3344,35 @@ impl<'a, 'b> serde::Serialize for AnnotatedValue<'a, 'b, MoveStructLayout, Vec<V
     }
 }
 
+impl<'a, 'b> serde::Serialize
+    for AnnotatedValue<'a, 'b, MoveEnumLayout, (VariantTag, Vec<ValueImpl>)>
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (tag, values) = &self.val;
+        let tag_idx = *tag as usize;
+        let Some(fields) = self.layout.0.get(tag_idx) else {
+            return Err(invariant_violation::<S>(format!(
+                "cannot serialize variant value {:?} as {:?} -- unknown variant tag",
+                self.val, self.layout
+            )));
+        };
+        if fields.len() != values.len() {
+            return Err(invariant_violation::<S>(format!(
+                "cannot serialize variant value {:?} as {:?} -- number of fields mismatch",
+                self.val, self.layout
+            )));
+        }
+        let mut t = serializer.serialize_tuple_variant("", tag_idx as u32, "", values.len())?;
+        for (field_layout, val) in fields.iter().zip(values.iter()) {
+            t.serialize_field(&AnnotatedValue {
+                layout: field_layout,
+                val,
+            })?;
+        }
+        t.end()
+    }
+}
+
 #[derive(Clone)]
 struct SeedWrapper<L> {
     layout: L,
@@ -3105,6 +3405,13 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveTypeLayout> {
                 .deserialize(deserializer)?,
             )),
 
+            L::Enum(enum_layout) => Ok(Value::variant(
+                SeedWrapper {
+                    layout: enum_layout,
+                }
+                .deserialize(deserializer)?,
+            )),
+
             L::Vector(layout) => {
                 let container = match &**layout {
                     L::U8 => {
@@ -3157,6 +3464,17 @@ impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveStructLayout> {
     }
 }
 
+impl<'d> serde::de::DeserializeSeed<'d> for SeedWrapper<&MoveEnumLayout> {
+    type Value = Variant;
+
+    fn deserialize<D: serde::de::Deserializer<'d>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_enum("", &[], EnumFieldVisitor(&self.layout.0))
+    }
+}
+
 struct VectorElementVisitor<'a>(SeedWrapper<&'a MoveTypeLayout>);
 
 impl<'d, 'a> serde::de::Visitor<'d> for VectorElementVisitor<'a> {
@@ -3205,6 +3523,28 @@ impl<'d, 'a> serde::de::Visitor<'d> for StructFieldVisitor<'a> {
     }
 }
 
+struct EnumFieldVisitor<'a>(&'a [Vec<MoveTypeLayout>]);
+
+impl<'d, 'a> serde::de::Visitor<'d> for EnumFieldVisitor<'a> {
+    type Value = Variant;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("Enum")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::EnumAccess<'d>,
+    {
+        let (tag, rest) = data.variant::<u32>()?;
+        let Some(field_layouts) = self.0.get(tag as usize) else {
+            return Err(A::Error::invalid_length(tag as usize, &self));
+        };
+        let fields = rest.tuple_variant(field_layouts.len(), StructFieldVisitor(field_layouts))?;
+        Ok(Variant::pack(tag as VariantTag, fields))
+    }
+}
+
 /***************************************************************************************
 *
 * Constants
@@ -3282,6 +3622,14 @@ impl Container {
                     }
                 }
             }
+            Variant(r) => {
+                let r = r.borrow();
+                if visitor.visit_variant(depth, r.0, r.1.len()) {
+                    for val in r.1.iter() {
+                        val.visit_impl(visitor, depth + 1);
+                    }
+                }
+            }
             VecU8(r) => visitor.visit_vec_u8(depth, &r.borrow()),
             VecU16(r) => visitor.visit_vec_u16(depth, &r.borrow()),
             VecU32(r) => visitor.visit_vec_u32(depth, &r.borrow()),
@@ -3298,6 +3646,7 @@ impl Container {
 
         match self {
             Locals(r) | Vec(r) | Struct(r) => r.borrow()[idx].visit_impl(visitor, depth + 1),
+            Variant(r) => r.borrow().1[idx].visit_impl(visitor, depth + 1),
             VecU8(vals) => visitor.visit_u8(depth + 1, vals.borrow()[idx]),
             VecU16(vals) => visitor.visit_u16(depth + 1, vals.borrow()[idx]),
             VecU32(vals) => visitor.visit_u32(depth + 1, vals.borrow()[idx]),
@@ -3386,6 +3735,16 @@ impl ValueView for Struct {
     }
 }
 
+impl ValueView for Variant {
+    fn visit(&self, visitor: &mut impl ValueVisitor) {
+        if visitor.visit_variant(0, self.tag, self.fields.len()) {
+            for val in self.fields.iter() {
+                val.visit_impl(visitor, 1);
+            }
+        }
+    }
+}
+
 impl ValueView for Vector {
     fn visit(&self, visitor: &mut impl ValueVisitor) {
         self.0.visit_impl(visitor, 0)
@@ -3424,6 +3783,12 @@ impl ValueView for VectorRef {
     }
 }
 
+impl ValueView for VariantRef {
+    fn visit(&self, visitor: &mut impl ValueVisitor) {
+        self.0.visit_impl(visitor, 0)
+    }
+}
+
 impl ValueView for StructRef {
     fn visit(&self, visitor: &mut impl ValueVisitor) {
         self.0.visit_impl(visitor, 0)
@@ -3445,6 +3810,13 @@ impl Struct {
     }
 }
 
+impl Variant {
+    #[allow(clippy::needless_lifetimes)]
+    pub fn field_views<'a>(&'a self) -> impl ExactSizeIterator<Item = impl ValueView + 'a> {
+        self.fields.iter()
+    }
+}
+
 impl Vector {
     #[allow(clippy::needless_lifetimes)]
     pub fn elem_views<'a>(&'a self) -> impl ExactSizeIterator<Item = impl ValueView + 'a> {
@@ -3612,6 +3984,24 @@ pub mod prop {
                 .collect::<Vec<_>>()
                 .prop_map(move |vals| Value::struct_(Struct::pack(vals)))
                 .boxed(),
+
+            L::Enum(enum_layout) => {
+                let variants = enum_layout
+                    .0
+                    .iter()
+                    .enumerate()
+                    .map(|(tag, fields)| {
+                        let tag = tag as VariantTag;
+                        fields
+                            .iter()
+                            .map(value_strategy_with_layout)
+                            .collect::<Vec<_>>()
+                            .prop_map(move |vals| Value::variant(Variant::pack(tag, vals)))
+                            .boxed()
+                    })
+                    .collect::<Vec<_>>();
+                proptest::strategy::Union::new(variants).boxed()
+            }
         }
     }
 
@@ -3633,8 +4023,10 @@ pub mod prop {
         leaf.prop_recursive(8, 32, 2, |inner| {
             prop_oneof![
                 1 => inner.clone().prop_map(|layout| L::Vector(Box::new(layout))),
-                1 => vec(inner, 0..1).prop_map(|f_layouts| {
+                1 => vec(inner.clone(), 0..1).prop_map(|f_layouts| {
                      L::Struct(MoveStructLayout::new(f_layouts))}),
+                1 => vec(vec(inner, 0..1), 1..3).prop_map(|v_layouts| {
+                     L::Enum(MoveEnumLayout(v_layouts))}),
             ]
         })
     }
@@ -3647,7 +4039,7 @@ pub mod prop {
     }
 }
 
-use move_core_types::runtime_value::{MoveStruct, MoveValue};
+use move_core_types::runtime_value::{MoveStruct, MoveValue, MoveVariant};
 
 impl ValueImpl {
     pub fn as_move_value(&self, layout: &MoveTypeLayout) -> MoveValue {
@@ -3671,6 +4063,16 @@ impl ValueImpl {
                 MoveValue::Struct(MoveStruct::new(fields))
             }
 
+            (L::Enum(enum_layout), ValueImpl::Container(Container::Variant(r))) => {
+                let (tag, values) = &*r.borrow();
+                let field_layouts = &enum_layout.0[*tag as usize];
+                let mut fields = vec![];
+                for (v, field_layout) in values.iter().zip(field_layouts.iter()) {
+                    fields.push(v.as_move_value(field_layout));
+                }
+                MoveValue::Variant(MoveVariant::new(*tag, fields))
+            }
+
             (L::Vector(inner_layout), ValueImpl::Container(c)) => MoveValue::Vector(match c {
                 Container::VecU8(r) => r.borrow().iter().map(|u| MoveValue::U8(*u)).collect(),
                 Container::VecU16(r) => r.borrow().iter().map(|u| MoveValue::U16(*u)).collect(),
@@ -3690,6 +4092,9 @@ impl ValueImpl {
                 Container::Struct(_) => {
                     panic!("got struct container when converting vec")
                 }
+                Container::Variant(_) => {
+                    panic!("got variant container when converting vec")
+                }
                 Container::Locals(_) => panic!("got locals container when converting vec"),
             }),
 
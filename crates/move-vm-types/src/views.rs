@@ -231,6 +231,13 @@ pub trait ValueVisitor {
     fn visit_address(&mut self, depth: usize, val: AccountAddress);
 
     fn visit_struct(&mut self, depth: usize, len: usize) -> bool;
+
+    /// Visits an enum value holding variant `tag` with `len` fields. Defaults to treating the
+    /// variant like a struct with the same fields.
+    fn visit_variant(&mut self, depth: usize, _tag: u16, len: usize) -> bool {
+        self.visit_struct(depth, len)
+    }
+
     fn visit_vec(&mut self, depth: usize, len: usize) -> bool;
 
     fn visit_ref(&mut self, depth: usize, is_global: bool) -> bool;
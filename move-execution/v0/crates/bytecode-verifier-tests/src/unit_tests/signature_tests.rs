@@ -113,6 +113,7 @@ fn no_verify_locals_good() {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![Ret],
+                    jump_tables: vec![],
                 }),
             },
             FunctionDefinition {
@@ -123,9 +124,14 @@ fn no_verify_locals_good() {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(1),
                     code: vec![Ret],
+                    jump_tables: vec![],
                 }),
             },
         ],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
     assert!(verify_module_unmetered(&compiled_module_good).is_ok());
 }
@@ -207,8 +213,13 @@ fn big_signature_test() {
             code: Some(CodeUnit {
                 locals: SignatureIndex(0),
                 code,
+                jump_tables: vec![],
             }),
         }],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     };
 
     // save module and verify that it can ser/de
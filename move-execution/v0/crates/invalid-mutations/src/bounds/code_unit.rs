@@ -8,7 +8,8 @@ use move_binary_format::{
         Bytecode, CodeOffset, CompiledModule, ConstantPoolIndex, FieldHandleIndex,
         FieldInstantiationIndex, FunctionDefinitionIndex, FunctionHandleIndex,
         FunctionInstantiationIndex, LocalIndex, SignatureIndex, StructDefInstantiationIndex,
-        StructDefinitionIndex, TableIndex,
+        StructDefinitionIndex, TableIndex, VariantHandleIndex, VariantInstantiationHandleIndex,
+        VariantJumpTableIndex,
     },
     internals::ModuleIndex,
     IndexKind,
@@ -179,6 +180,7 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
         let code = func_def.code.as_mut().unwrap();
         let locals_len = self.module.signatures[func_handle.parameters.into_index()].len()
             + self.module.signatures[code.locals.into_index()].len();
+        let jump_tables_len = code.jump_tables.len();
         let code = &mut code.code;
         let code_len = code.len();
 
@@ -196,6 +198,8 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
         let function_inst_len = self.module.function_instantiations.len();
         let field_inst_len = self.module.field_instantiations.len();
         let signature_pool_len = self.module.signatures.len();
+        let variant_handles_len = self.module.variant_handles.len();
+        let variant_inst_len = self.module.variant_instantiation_handles.len();
 
         mutations
             .iter()
@@ -294,6 +298,78 @@ impl<'a> ApplyCodeUnitBoundsContext<'a> {
                         StructDefInstantiationIndex,
                         UnpackGeneric
                     ),
+                    PackVariant(_) => struct_bytecode!(
+                        variant_handles_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantHandleIndex,
+                        PackVariant
+                    ),
+                    PackVariantGeneric(_) => struct_bytecode!(
+                        variant_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantInstantiationHandleIndex,
+                        PackVariantGeneric
+                    ),
+                    UnpackVariant(_) => struct_bytecode!(
+                        variant_handles_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantHandleIndex,
+                        UnpackVariant
+                    ),
+                    UnpackVariantImmRef(_) => struct_bytecode!(
+                        variant_handles_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantHandleIndex,
+                        UnpackVariantImmRef
+                    ),
+                    UnpackVariantMutRef(_) => struct_bytecode!(
+                        variant_handles_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantHandleIndex,
+                        UnpackVariantMutRef
+                    ),
+                    UnpackVariantGeneric(_) => struct_bytecode!(
+                        variant_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantInstantiationHandleIndex,
+                        UnpackVariantGeneric
+                    ),
+                    UnpackVariantGenericImmRef(_) => struct_bytecode!(
+                        variant_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantInstantiationHandleIndex,
+                        UnpackVariantGenericImmRef
+                    ),
+                    UnpackVariantGenericMutRef(_) => struct_bytecode!(
+                        variant_inst_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantInstantiationHandleIndex,
+                        UnpackVariantGenericMutRef
+                    ),
+                    VariantSwitch(_) => struct_bytecode!(
+                        jump_tables_len,
+                        current_fdef,
+                        bytecode_idx,
+                        offset,
+                        VariantJumpTableIndex,
+                        VariantSwitch
+                    ),
                     BrTrue(_) => {
                         code_bytecode!(code_len, current_fdef, bytecode_idx, offset, BrTrue)
                     }
@@ -438,6 +514,15 @@ fn is_interesting(bytecode: &Bytecode) -> bool {
         | PackGeneric(_)
         | Unpack(_)
         | UnpackGeneric(_)
+        | PackVariant(_)
+        | PackVariantGeneric(_)
+        | UnpackVariant(_)
+        | UnpackVariantImmRef(_)
+        | UnpackVariantMutRef(_)
+        | UnpackVariantGeneric(_)
+        | UnpackVariantGenericImmRef(_)
+        | UnpackVariantGenericMutRef(_)
+        | VariantSwitch(_)
         | BrTrue(_)
         | BrFalse(_)
         | Branch(_)
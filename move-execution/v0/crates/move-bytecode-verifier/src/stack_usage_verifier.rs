@@ -117,6 +117,19 @@ impl<'a> StackUsageVerifier<'a> {
     /// of pushes it does
     fn instruction_effect(&self, instruction: &Bytecode) -> PartialVMResult<(u64, u64)> {
         Ok(match instruction {
+            // Enums are only supported from bytecode version 7 onwards
+            Bytecode::PackVariant(_)
+            | Bytecode::PackVariantGeneric(_)
+            | Bytecode::UnpackVariant(_)
+            | Bytecode::UnpackVariantImmRef(_)
+            | Bytecode::UnpackVariantMutRef(_)
+            | Bytecode::UnpackVariantGeneric(_)
+            | Bytecode::UnpackVariantGenericImmRef(_)
+            | Bytecode::UnpackVariantGenericMutRef(_)
+            | Bytecode::VariantSwitch(_) => {
+                return Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)
+                    .with_message("enum bytecodes are not supported".to_string()));
+            }
             // Instructions that pop, but don't push
             Bytecode::Pop
             | Bytecode::BrTrue(_)
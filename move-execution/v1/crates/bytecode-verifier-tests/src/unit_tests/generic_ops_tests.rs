@@ -138,6 +138,7 @@ fn make_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             // fun g_fn<T>() { return; }
@@ -149,6 +150,7 @@ fn make_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![Bytecode::Ret],
+                    jump_tables: vec![],
                 }),
             },
             // fun test_fn() { ... } - tests will fill up the code
@@ -160,6 +162,7 @@ fn make_module() -> CompiledModule {
                 code: Some(CodeUnit {
                     locals: SignatureIndex(0),
                     code: vec![],
+                    jump_tables: vec![],
                 }),
             },
         ],
@@ -180,6 +183,10 @@ fn make_module() -> CompiledModule {
         struct_def_instantiations: vec![],
         function_instantiations: vec![],
         field_instantiations: vec![],
+        enum_defs: vec![],
+        enum_def_instantiations: vec![],
+        variant_handles: vec![],
+        variant_instantiation_handles: vec![],
     }
 }
 
@@ -193,6 +200,7 @@ fn generic_call_to_non_generic_func() {
             Bytecode::CallGeneric(FunctionInstantiationIndex(0)),
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module.function_instantiations.push(FunctionInstantiation {
         handle: FunctionHandleIndex(0),
@@ -214,6 +222,7 @@ fn non_generic_call_to_generic_func() {
     module.function_defs[2].code = Some(CodeUnit {
         locals: SignatureIndex(0),
         code: vec![Bytecode::Call(FunctionHandleIndex(1)), Bytecode::Ret],
+        jump_tables: vec![],
     });
     let err = InstructionConsistency::verify_module(&module)
         .expect_err("Call to generic function must fail");
@@ -235,6 +244,7 @@ fn generic_pack_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -263,6 +273,7 @@ fn non_generic_pack_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     let err = InstructionConsistency::verify_module(&module)
         .expect_err("Pack to generic struct must fail");
@@ -285,6 +296,7 @@ fn generic_unpack_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -314,6 +326,7 @@ fn non_generic_unpack_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -343,6 +356,7 @@ fn generic_mut_borrow_field_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module.field_instantiations.push(FieldInstantiation {
         handle: FieldHandleIndex(0),
@@ -374,6 +388,7 @@ fn non_generic_mut_borrow_field_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
@@ -407,6 +422,7 @@ fn generic_borrow_field_on_non_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module.field_instantiations.push(FieldInstantiation {
         handle: FieldHandleIndex(0),
@@ -438,6 +454,7 @@ fn non_generic_borrow_field_on_generic_struct() {
             Bytecode::Pop,
             Bytecode::Ret,
         ],
+        jump_tables: vec![],
     });
     module
         .struct_def_instantiations
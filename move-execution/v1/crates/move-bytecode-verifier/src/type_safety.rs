@@ -463,6 +463,19 @@ fn verify_instr(
     meter: &mut (impl Meter + ?Sized),
 ) -> PartialVMResult<()> {
     match bytecode {
+        // Enums are only supported from bytecode version 7 onwards
+        Bytecode::PackVariant(_)
+        | Bytecode::PackVariantGeneric(_)
+        | Bytecode::UnpackVariant(_)
+        | Bytecode::UnpackVariantImmRef(_)
+        | Bytecode::UnpackVariantMutRef(_)
+        | Bytecode::UnpackVariantGeneric(_)
+        | Bytecode::UnpackVariantGenericImmRef(_)
+        | Bytecode::UnpackVariantGenericMutRef(_)
+        | Bytecode::VariantSwitch(_) => {
+            return Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)
+                .with_message("enum bytecodes are not supported".to_string()));
+        }
         Bytecode::Pop => {
             let operand = safe_unwrap_err!(verifier.stack.pop());
             let abilities = verifier
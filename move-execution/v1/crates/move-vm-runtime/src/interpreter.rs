@@ -1186,6 +1186,17 @@ impl Frame {
             | Bytecode::MoveToGenericDeprecated(_) => {
                 unreachable!("Global bytecodes deprecated")
             }
+            Bytecode::PackVariant(_)
+            | Bytecode::PackVariantGeneric(_)
+            | Bytecode::UnpackVariant(_)
+            | Bytecode::UnpackVariantImmRef(_)
+            | Bytecode::UnpackVariantMutRef(_)
+            | Bytecode::UnpackVariantGeneric(_)
+            | Bytecode::UnpackVariantGenericImmRef(_)
+            | Bytecode::UnpackVariantGenericMutRef(_)
+            | Bytecode::VariantSwitch(_) => {
+                unreachable!("Enum bytecodes are not supported")
+            }
             Bytecode::FreezeRef => {
                 gas_meter.charge_simple_instr(S::FreezeRef)?;
                 // FreezeRef should just be a null op as we don't distinguish between mut
@@ -244,6 +244,7 @@ impl ModuleCache {
                 StructType {
                     fields: vec![],
                     field_names,
+                    variants: None,
                     abilities: struct_handle.abilities,
                     type_parameters: struct_handle.type_parameters.clone(),
                     name: name.to_owned(),
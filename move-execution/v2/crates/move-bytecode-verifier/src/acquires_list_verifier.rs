@@ -87,6 +87,17 @@ impl<'a> AcquiresVerifier<'a> {
         offset: CodeOffset,
     ) -> PartialVMResult<()> {
         match instruction {
+            // Enums are only supported from bytecode version 7 onwards
+            Bytecode::PackVariant(_)
+            | Bytecode::PackVariantGeneric(_)
+            | Bytecode::UnpackVariant(_)
+            | Bytecode::UnpackVariantImmRef(_)
+            | Bytecode::UnpackVariantMutRef(_)
+            | Bytecode::UnpackVariantGeneric(_)
+            | Bytecode::UnpackVariantGenericImmRef(_)
+            | Bytecode::UnpackVariantGenericMutRef(_)
+            | Bytecode::VariantSwitch(_) => Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)
+                .with_message("enum bytecodes are not supported".to_string())),
             Bytecode::Call(idx) => self.call_acquire(*idx, offset),
             Bytecode::CallGeneric(idx) => {
                 let fi = self.module.function_instantiation_at(*idx);
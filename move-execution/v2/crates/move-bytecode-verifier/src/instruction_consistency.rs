@@ -48,6 +48,19 @@ impl<'a> InstructionConsistency<'a> {
             use Bytecode::*;
 
             match instr {
+                // Enums are only supported from bytecode version 7 onwards
+                Bytecode::PackVariant(_)
+                | Bytecode::PackVariantGeneric(_)
+                | Bytecode::UnpackVariant(_)
+                | Bytecode::UnpackVariantImmRef(_)
+                | Bytecode::UnpackVariantMutRef(_)
+                | Bytecode::UnpackVariantGeneric(_)
+                | Bytecode::UnpackVariantGenericImmRef(_)
+                | Bytecode::UnpackVariantGenericMutRef(_)
+                | Bytecode::VariantSwitch(_) => {
+                    return Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)
+                        .with_message("enum bytecodes are not supported".to_string()));
+                }
                 MutBorrowField(field_handle_index) => {
                     self.check_field_op(offset, *field_handle_index, /* generic */ false)?;
                 }
@@ -38,6 +38,19 @@ fn execute_inner(
 ) -> PartialVMResult<()> {
     meter.add(Scope::Function, STEP_BASE_COST)?;
     match bytecode {
+        // Enums are only supported from bytecode version 7 onwards
+        Bytecode::PackVariant(_)
+        | Bytecode::PackVariantGeneric(_)
+        | Bytecode::UnpackVariant(_)
+        | Bytecode::UnpackVariantImmRef(_)
+        | Bytecode::UnpackVariantMutRef(_)
+        | Bytecode::UnpackVariantGeneric(_)
+        | Bytecode::UnpackVariantGenericImmRef(_)
+        | Bytecode::UnpackVariantGenericMutRef(_)
+        | Bytecode::VariantSwitch(_) => {
+            return Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)
+                .with_message("enum bytecodes are not supported".to_string()));
+        }
         Bytecode::StLoc(idx) => match state.local_state(*idx) {
             LocalState::MaybeAvailable | LocalState::Available
                 if !state.local_abilities(*idx).has_drop() =>
@@ -127,6 +127,19 @@ impl<'a> SignatureChecker<'a> {
         use Bytecode::*;
         for (offset, instr) in code.code.iter().enumerate() {
             let result = match instr {
+                // Enums are only supported from bytecode version 7 onwards
+                Bytecode::PackVariant(_)
+                | Bytecode::PackVariantGeneric(_)
+                | Bytecode::UnpackVariant(_)
+                | Bytecode::UnpackVariantImmRef(_)
+                | Bytecode::UnpackVariantMutRef(_)
+                | Bytecode::UnpackVariantGeneric(_)
+                | Bytecode::UnpackVariantGenericImmRef(_)
+                | Bytecode::UnpackVariantGenericMutRef(_)
+                | Bytecode::VariantSwitch(_) => {
+                    return Err(PartialVMError::new(StatusCode::UNKNOWN_OPCODE)
+                        .with_message("enum bytecodes are not supported".to_string()));
+                }
                 CallGeneric(idx) => {
                     let func_inst = self.resolver.function_instantiation_at(*idx);
                     let func_handle = self.resolver.function_handle_at(func_inst.handle);